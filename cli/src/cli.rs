@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use version::VersionType;
 
 #[derive(Parser)]
@@ -80,61 +80,257 @@ pub enum Commands {
     },
 
     /// Execute HTTP requests across multiple environments and compare responses
-    HttpDiff {
-        /// Comma-separated list of environments to test (defaults to all configured environments)
-        #[clap(short, long)]
-        environments: Option<String>,
+    HttpDiff(Box<HttpDiffOptions>),
+}
 
-        /// Comma-separated list of route names to test (defaults to all configured routes)
-        #[clap(short, long)]
-        routes: Option<String>,
+/// Options of `fnc http-diff`, boxed in [`Commands`] to keep the enum small
+#[derive(Args, Clone, Debug)]
+pub struct HttpDiffOptions {
+    #[clap(subcommand)]
+    pub command: Option<HttpDiffCommand>,
+
+    /// Comma-separated list of environments to test (defaults to all configured environments)
+    #[clap(short, long)]
+    pub environments: Option<String>,
+
+    /// Comma-separated list of route names to test (defaults to all configured routes)
+    #[clap(short, long)]
+    pub routes: Option<String>,
+
+    /// Run only the routes and environments of a profile defined in the configuration
+    #[clap(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Include headers in response comparison (disabled by default)
+    #[clap(short = 'H', long, default_value_t = false)]
+    pub include_headers: bool,
+
+    /// Include error analysis in output (disabled by default)
+    #[clap(short = 'E', long, default_value_t = false)]
+    pub include_errors: bool,
+
+    /// Diff view style for response comparison
+    #[clap(short = 'd', long, value_enum, default_value_t = DiffViewType::Unified)]
+    pub diff_view: DiffViewType,
+
+    /// Configuration file path (defaults to http-diff.toml)
+    #[clap(short, long, default_value = "http-diff.toml")]
+    pub config: String,
+
+    /// CSV file with test user data (defaults to users.csv)
+    #[clap(long, default_value = "users.csv")]
+    pub users_file: String,
+
+    /// Generate configuration files if they don't exist
+    #[clap(long, default_value_t = false)]
+    pub init: bool,
+
+    /// Enable verbose output with additional information
+    #[clap(short, long, default_value_t = false)]
+    pub verbose: bool,
+
+    /// Save curl commands to file for debugging
+    #[clap(long, default_value = "curl_commands.txt")]
+    pub output_file: String,
+
+    /// Generate executive summary report (format auto-detected from file extension)
+    #[clap(long)]
+    pub report: Option<String>,
+
+    /// Force CLI output instead of TUI (when stdout is a TTY)
+    #[clap(long, default_value_t = false)]
+    pub no_tui: bool,
+
+    /// Force TUI mode even when output is redirected
+    #[clap(long, default_value_t = false)]
+    pub force_tui: bool,
+
+    /// Exit with a non-zero code when these differences are found (overrides [global] fail_on)
+    #[clap(long, value_enum)]
+    pub fail_on: Option<FailOnType>,
+
+    /// Exit with a non-zero code when mixed-status responses exceed this count
+    #[clap(long)]
+    pub max_mixed_responses: Option<usize>,
+
+    /// Exit with a non-zero code when any execution error occurs
+    #[clap(long, default_value_t = false)]
+    pub fail_on_errors: bool,
 
-        /// Include headers in response comparison (disabled by default)
-        #[clap(short = 'H', long, default_value_t = false)]
-        include_headers: bool,
+    /// Record every response under this name in .http-diff/cache/recordings
+    #[clap(long, value_name = "NAME")]
+    pub record: Option<String>,
 
-        /// Include error analysis in output (disabled by default)
-        #[clap(short = 'E', long, default_value_t = false)]
-        include_errors: bool,
+    /// Serve an environment of a recording as the virtual environment ENV@RECORDING (repeatable)
+    #[clap(long, value_name = "RECORDING:ENV")]
+    pub replay: Vec<String>,
 
-        /// Diff view style for response comparison
-        #[clap(short = 'd', long, value_enum, default_value_t = DiffViewType::Unified)]
-        diff_view: DiffViewType,
+    /// Save the normalized responses of this run as a named baseline
+    #[clap(long, value_name = "NAME")]
+    pub save_baseline: Option<String>,
 
-        /// Configuration file path (defaults to http-diff.toml)
+    /// Compare this run to a saved baseline, reporting new, fixed and still-present differences
+    #[clap(long, value_name = "NAME")]
+    pub against_baseline: Option<String>,
+
+    /// Print every request that would be sent, per environment, without sending any
+    #[clap(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// Write the dry-run plan as JSON to FILE instead of printing it (implies --dry-run)
+    #[clap(long, value_name = "FILE")]
+    pub dry_run_json: Option<String>,
+}
+
+#[cfg(test)]
+impl Cli {
+    /// Options of a parsed `http-diff` command
+    pub fn http_diff_options(self) -> HttpDiffOptions {
+        match self.command {
+            Commands::HttpDiff(options) => *options,
+            _ => panic!("Expected HttpDiff command"),
+        }
+    }
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum HttpDiffCommand {
+    /// Generate routes and a users.csv skeleton from an API description
+    Import {
+        #[clap(subcommand)]
+        source: ImportSource,
+    },
+
+    /// Convert the configuration for use in other tools
+    Export {
+        #[clap(subcommand)]
+        target: ExportTarget,
+    },
+
+    /// Check the configuration and users CSV for problems without sending any request
+    Check {
+        /// Configuration file to check
         #[clap(short, long, default_value = "http-diff.toml")]
         config: String,
 
-        /// CSV file with test user data (defaults to users.csv)
+        /// CSV file providing the values of placeholders
         #[clap(long, default_value = "users.csv")]
         users_file: String,
+    },
+
+    /// Work with configuration files
+    Config {
+        #[clap(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ConfigAction {
+    /// Rewrite a configuration file as TOML, YAML or JSON, chosen by the output extension
+    Convert {
+        /// Configuration file to convert
+        input: String,
+
+        /// File to write (.toml, .yaml, .yml or .json)
+        output: String,
 
-        /// Generate configuration files if they don't exist
+        /// Overwrite an existing file
         #[clap(long, default_value_t = false)]
-        init: bool,
+        force: bool,
+    },
+}
 
-        /// Enable verbose output with additional information
-        #[clap(short, long, default_value_t = false)]
-        verbose: bool,
+#[derive(Subcommand, Clone, Debug)]
+pub enum ImportSource {
+    /// Import routes from an OpenAPI 3 document (JSON or YAML)
+    #[clap(name = "openapi")]
+    OpenApi {
+        /// Path of the OpenAPI document
+        spec: String,
+
+        #[clap(flatten)]
+        output: ImportOutput,
+    },
 
-        /// Save curl commands to file for debugging
-        #[clap(long, default_value = "curl_commands.txt")]
-        output_file: String,
+    /// Import requests captured in a HAR file exported from a browser
+    Har {
+        /// Path of the HAR file
+        file: String,
 
-        /// Generate executive summary report (format auto-detected from file extension)
+        /// Keep IDs found in paths instead of moving them into users.csv
         #[clap(long)]
-        report: Option<String>,
+        keep_ids: bool,
 
-        /// Force CLI output instead of TUI (when stdout is a TTY)
-        #[clap(long, default_value_t = false)]
-        no_tui: bool,
+        #[clap(flatten)]
+        output: ImportOutput,
+    },
+
+    /// Import requests from curl commands, one per line, continued with a
+    /// trailing `\` or chained with `;` and `&&`
+    Curl {
+        /// Path of the file holding the curl commands
+        file: String,
+
+        /// Keep IDs found in paths instead of moving them into users.csv
+        #[clap(long)]
+        keep_ids: bool,
 
-        /// Force TUI mode even when output is redirected
+        #[clap(flatten)]
+        output: ImportOutput,
+    },
+
+    /// Import a Postman v2.1 collection and its environments
+    Postman {
+        /// Path of the collection file
+        collection: String,
+
+        /// Postman environment file to import as an environment (repeatable)
+        #[clap(short, long = "environment", value_name = "FILE")]
+        environments: Vec<String>,
+
+        #[clap(flatten)]
+        output: ImportOutput,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ExportTarget {
+    /// Export routes as a Postman v2.1 collection and environments as Postman environments
+    Postman {
+        /// Configuration file to export
+        #[clap(short, long, default_value = "http-diff.toml")]
+        config: String,
+
+        /// CSV file whose first row becomes the collection variables
+        #[clap(long, default_value = "users.csv")]
+        users_file: String,
+
+        /// Collection file to write; environment files are written next to it
+        #[clap(short, long, default_value = "http-diff.postman_collection.json")]
+        output: String,
+
+        /// Overwrite existing files
         #[clap(long, default_value_t = false)]
-        force_tui: bool,
+        force: bool,
     },
 }
 
+#[derive(Args, Clone, Debug)]
+pub struct ImportOutput {
+    /// Configuration file to write
+    #[clap(short, long, default_value = "http-diff.toml")]
+    pub config: String,
+
+    /// CSV file to write with one column per discovered parameter
+    #[clap(long, default_value = "users.csv")]
+    pub users_file: String,
+
+    /// Overwrite existing files
+    #[clap(long, default_value_t = false)]
+    pub force: bool,
+}
+
 #[derive(Subcommand, Clone, Debug)]
 pub enum FixType {
     /// Fix package versions in a JavaScript monorepo
@@ -167,6 +363,18 @@ pub enum DeployType {
     Release,
 }
 
+#[derive(ValueEnum, Clone, Debug, PartialEq)]
+pub enum FailOnType {
+    /// Differences never cause a non-zero exit code
+    Never,
+
+    /// Any status, header or body difference fails the run
+    AnyDifference,
+
+    /// Only status code differences fail the run
+    StatusDifference,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum DiffViewType {
     /// Traditional unified diff (up/down view) - default
@@ -40,6 +40,12 @@ pub enum CliError {
     #[error("{0}")]
     Other(String),
 
+    #[error("{0}")]
+    Config(String),
+
+    #[error("{message}")]
+    Failed { code: i32, message: String },
+
     #[error("{0}: {1}")]
     WithContext(String, Box<CliError>),
 }
@@ -63,9 +69,21 @@ impl CliError {
             Self::AnyhowError(err) => format!("Error: {err}"),
             Self::DialoguerError(err) => format!("UI interaction error: {err}"),
             Self::Other(msg) => msg.clone(),
+            Self::Config(msg) => msg.clone(),
+            Self::Failed { message, .. } => message.clone(),
             Self::WithContext(ctx, err) => format!("{ctx}: {}", err.user_message()),
         }
     }
+
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(_) => http_diff::execution::policy::EXIT_CONFIG_ERROR,
+            Self::Failed { code, .. } => *code,
+            Self::WithContext(_, err) => err.exit_code(),
+            _ => 1,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, CliError>;
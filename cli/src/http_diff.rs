@@ -2,13 +2,20 @@ use crate::error::{CliError, Result};
 
 use crate::progress::ProgressTracker as CliProgressTracker;
 use crate::ui;
+use colored::Colorize;
 use dialoguer::{Confirm, theme::ColorfulTheme};
 use http_diff::{
-    CliRenderer, ConditionEvaluatorImpl, DefaultHttpClient, DefaultResponseComparator, DefaultTestRunner, OutputRenderer,
-    ProgressTracker as HttpProgressTracker, TestRunner,
-    config::{HttpDiffConfig, ensure_config_files_exist, load_user_data},
+    CliRenderer, ConditionEvaluatorImpl, DefaultHttpClient, DefaultResponseComparator, FailOn,
+    FailurePolicy, OutputRenderer, ProgressTracker as HttpProgressTracker, TestRunner,
+    TestRunnerImpl,
+    baseline::{Baseline, BaselineComparator, BaselineReport},
+    config::{HttpDiffConfig, UserData, ensure_config_files_exist, load_user_data},
     curl::CurlGenerator,
+    execution::{DryRunPlan, DryRunPlanner},
+    output_manager::OutputManager,
+    recording::{Recording, ReplayClient, ResponseRecorder, virtual_environment_name},
     renderers::{ReportMetadata, ReportRendererFactory},
+    secrets::SecretStore,
 };
 use indicatif::{ProgressBar, ProgressStyle};
 use std::fs;
@@ -20,6 +27,7 @@ use tokio::runtime::Runtime;
 pub struct HttpDiffArgs {
     pub environments: Option<String>,
     pub routes: Option<String>,
+    pub profile: Option<String>,
     pub include_headers: bool,
     pub include_errors: bool,
     pub diff_view: crate::cli::DiffViewType,
@@ -31,11 +39,26 @@ pub struct HttpDiffArgs {
     pub report_file: Option<String>,
     pub no_tui: bool,
     pub force_tui: bool,
+    pub fail_on: Option<crate::cli::FailOnType>,
+    pub max_mixed_responses: Option<usize>,
+    pub fail_on_errors: bool,
+    pub record: Option<String>,
+    pub replay: Vec<String>,
+    pub save_baseline: Option<String>,
+    pub against_baseline: Option<String>,
+    pub dry_run: bool,
+    pub dry_run_json: Option<String>,
 }
 
 pub fn execute(args: HttpDiffArgs) -> Result<()> {
-    // Determine whether to use TUI or CLI based on arguments and environment
-    let use_tui = should_use_tui(&args);
+    // Determine whether to use TUI or CLI based on arguments and environment.
+    // The interactive workflow only runs live requests of the whole configuration,
+    // so recording, replay, baselines, profiles and dry runs use the CLI.
+    let use_tui = should_use_tui(&args)
+        && !records_responses(&args)
+        && args.replay.is_empty()
+        && args.profile.is_none()
+        && !is_dry_run(&args);
 
     if use_tui {
         // Launch TUI immediately - it will handle the complete workflow
@@ -94,7 +117,7 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
                 return Ok(());
             }
         } else {
-            return Err(CliError::Other(
+            return Err(CliError::Config(
                 "Configuration files are required to run HTTP diff tests".to_string(),
             ));
         }
@@ -102,19 +125,42 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
 
     // Load configuration
     ui::status_message("Loading configuration...");
-    let config = HttpDiffConfig::load_from_file(config_path)
-        .map_err(|e| CliError::Other(format!("Failed to load configuration: {}", e)))?;
+    let mut config = HttpDiffConfig::load_from_file(config_path)
+        .map_err(|e| CliError::Config(format!("Failed to load configuration: {}", e)))?;
+    if let Some(profile) = &args.profile {
+        config
+            .apply_profile(profile)
+            .map_err(|e| CliError::Config(e.to_string()))?;
+    }
+    let replays = add_replay_environments(&args.replay, &mut config)?;
+
+    // Every component of the run resolves and redacts secrets with the same store
+    let secrets = http_diff::secrets::install(SecretStore::from_config(&config));
+
+    // Load the baseline before running so a missing baseline fails fast
+    let baseline = match &args.against_baseline {
+        Some(name) => {
+            let output_manager = OutputManager::current_dir().map_err(|e| {
+                CliError::Other(format!("Failed to initialize output manager: {}", e))
+            })?;
+            Some(
+                Baseline::load(&output_manager, name)
+                    .map_err(|e| CliError::Config(e.to_string()))?,
+            )
+        }
+        None => None,
+    };
 
     // Validate that we have environments and routes
     if config.environments.is_empty() {
-        return Err(CliError::Other(
+        return Err(CliError::Config(
             "No environments configured. Please add environments to your configuration file"
                 .to_string(),
         ));
     }
 
     if config.routes.is_empty() {
-        return Err(CliError::Other(
+        return Err(CliError::Config(
             "No routes configured. Please add routes to your configuration file".to_string(),
         ));
     }
@@ -125,6 +171,9 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
         config.routes.len()
     ));
 
+    // CLI flags take precedence over the [global] failure policy settings
+    let failure_policy = build_failure_policy(&args, &config);
+
     // Parse environment list
     let env_list = args.environments.as_ref().map(|env_str| {
         env_str
@@ -145,7 +194,7 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
     if let Some(ref envs) = env_list {
         for env in envs {
             if !config.environments.contains_key(env) {
-                return Err(CliError::Other(format!(
+                return Err(CliError::Config(format!(
                     "Environment '{}' not found in configuration. Available environments: {}",
                     env,
                     config
@@ -164,7 +213,7 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
         let available_routes: Vec<String> = config.routes.iter().map(|r| r.name.clone()).collect();
         for route in routes {
             if !available_routes.contains(route) {
-                return Err(CliError::Other(format!(
+                return Err(CliError::Config(format!(
                     "Route '{}' not found in configuration. Available routes: {}",
                     route,
                     available_routes.join(", ")
@@ -176,7 +225,7 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
     // Load user data
     ui::status_message("Loading user test data...");
     let user_data = load_user_data(users_path)
-        .map_err(|e| CliError::Other(format!("Failed to load user data: {}", e)))?;
+        .map_err(|e| CliError::Config(format!("Failed to load user data: {}", e)))?;
 
     if user_data.is_empty() {
         ui::warning_message("No user data found. Tests will run without parameter substitution.");
@@ -184,6 +233,10 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
         ui::success_message(&format!("Loaded {} user records", user_data.len()));
     }
 
+    if is_dry_run(&args) {
+        return dry_run(&args, &config, secrets, &user_data, env_list, route_list);
+    }
+
     // Setup progress tracking
     let env_count = env_list
         .as_ref()
@@ -213,17 +266,33 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
 
     // Create test runner with custom comparator settings
     let client = DefaultHttpClient::new(config.clone())
-        .map_err(|e| CliError::Other(format!("Failed to create HTTP client: {}", e)))?;
-
-    let mut comparator =
-        DefaultResponseComparator::new().with_diff_view_style(diff_view_style.clone());
+        .map_err(|e| CliError::Other(format!("Failed to create HTTP client: {}", e)))?
+        .with_secrets(secrets.clone());
+    let client = replays
+        .into_iter()
+        .fold(ReplayClient::new(client), |client, replay| {
+            client.with_replay(replay.name, replay.recording, &replay.environment)
+        });
+
+    let mut comparator = DefaultResponseComparator::new()
+        .with_diff_view_style(diff_view_style.clone())
+        .with_config(&config);
     if args.include_headers {
         comparator = comparator.with_headers_comparison();
     }
 
     let condition_evaluator = ConditionEvaluatorImpl::new();
-    let runner = DefaultTestRunner::new(config.clone(), client, comparator, condition_evaluator)
-        .map_err(|e| CliError::Other(format!("Failed to initialize test runner: {}", e)))?;
+    let mut runner =
+        TestRunnerImpl::new(config.clone(), client, comparator, condition_evaluator)
+            .map_err(|e| CliError::Other(format!("Failed to initialize test runner: {}", e)))?;
+    let recorder = records_responses(&args).then(|| {
+        Arc::new(ResponseRecorder::new(
+            args.record.clone().unwrap_or_else(|| "current".to_string()),
+        ))
+    });
+    if let Some(recorder) = &recorder {
+        runner = runner.with_recorder(recorder.clone());
+    }
     progress.complete_step();
 
     // Execute HTTP diff tests with visual progress bar
@@ -287,6 +356,12 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
             route_list,
             Some(Box::new(move |p: &HttpProgressTracker| {
                 pb_clone.set_position(p.completed_requests as u64);
+                if p.throttled_requests > 0 {
+                    pb_clone.set_message(format!(
+                        "Executing HTTP requests... (⏳ {} throttled)",
+                        p.throttled_requests
+                    ));
+                }
             })),
         )
         .await
@@ -294,6 +369,14 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
 
     pb.finish_with_message("✅ All HTTP requests completed!");
 
+    if execution_result.progress.throttled_requests > 0 {
+        ui::info_message(&format!(
+            "Rate limits delayed {} request(s) for {:.1}s in total",
+            execution_result.progress.throttled_requests,
+            execution_result.progress.throttle_wait().as_secs_f64()
+        ));
+    }
+
     progress.complete_step();
 
     // Analyze and display results
@@ -325,13 +408,39 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
                 execution_errors.len()
             ));
         }
+
+        let cookie_jars = execution_result
+            .chain_metadata
+            .as_ref()
+            .map(|metadata| metadata.cookie_jars.as_slice())
+            .unwrap_or_default();
+        for jar in cookie_jars {
+            let cookies = jar
+                .cookies
+                .iter()
+                .map(|cookie| {
+                    format!(
+                        "{}={} ({}{})",
+                        cookie.name,
+                        secrets.mask_header("Cookie", &cookie.value),
+                        cookie.domain,
+                        cookie.path
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui::info_message(&format!(
+                "Cookie jar for user {} on {}: {}",
+                jar.user_data_index, jar.environment, cookies
+            ));
+        }
     }
 
     progress.complete_step();
 
     // Generate output files
     progress.start_step();
-    let _curl_generator = CurlGenerator::new(config.clone());
+    let _curl_generator = CurlGenerator::new(config.clone()).with_secrets(secrets.clone());
 
     // Generate curl commands file
     let mut curl_commands = Vec::new();
@@ -370,6 +479,24 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
         "Curl commands saved to {}",
         resolved_output_path.display()
     ));
+
+    let recording = recorder.map(|recorder| recorder.recording());
+    if let (Some(recording), true) = (&recording, args.record.is_some()) {
+        let path = recording
+            .save(&output_manager)
+            .map_err(|e| CliError::Other(format!("Failed to save recording: {}", e)))?;
+        ui::success_message(&format!(
+            "Recorded {} responses to {}",
+            recording.responses.len(),
+            path.display()
+        ));
+    }
+    if let (Some(recording), Some(name)) = (&recording, &args.save_baseline) {
+        let path = Baseline::from_recording(name.clone(), recording)
+            .save(&output_manager)
+            .map_err(|e| CliError::Other(format!("Failed to save baseline: {}", e)))?;
+        ui::success_message(&format!("Baseline '{}' saved to {}", name, path.display()));
+    }
     progress.complete_step();
 
     progress.complete();
@@ -442,7 +569,7 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
         } else {
             CliRenderer::without_errors().with_diff_style(diff_view_style)
         };
-        println!("{}", renderer.render(&execution_result));
+        println!("{}", secrets.redact(&renderer.render(&execution_result)));
 
         // Show next steps if there are differences
         if different_count > 0 {
@@ -467,9 +594,284 @@ async fn execute_async(args: HttpDiffArgs) -> Result<()> {
         }
     }
 
+    let baseline_report = match (&baseline, &recording) {
+        (Some(baseline), Some(recording)) => {
+            let mut comparator = BaselineComparator::new(&config);
+            if args.include_headers {
+                comparator = comparator.with_headers_comparison();
+            }
+            let report = comparator.compare(baseline, recording);
+            render_baseline_report(&report);
+            Some(report)
+        }
+        _ => None,
+    };
+
+    // Apply the failure policy so CI pipelines can gate on the outcome;
+    // differences the baseline did not have count like those of the run
+    let verdict =
+        failure_policy.evaluate_with_baseline(&execution_result, baseline_report.as_ref());
+    if !verdict.is_passed() {
+        return Err(CliError::Failed {
+            code: verdict.exit_code(),
+            message: format!("Failure policy violated: {}", verdict.reasons().join("; ")),
+        });
+    }
+
     Ok(())
 }
 
+/// Whether only the requests of the run are shown, with `--dry-run` or `--dry-run-json`
+fn is_dry_run(args: &HttpDiffArgs) -> bool {
+    args.dry_run || args.dry_run_json.is_some()
+}
+
+/// Print or write the requests a run would send, without sending any
+fn dry_run(
+    args: &HttpDiffArgs,
+    config: &HttpDiffConfig,
+    secrets: Arc<SecretStore>,
+    user_data: &[UserData],
+    environments: Option<Vec<String>>,
+    routes: Option<Vec<String>>,
+) -> Result<()> {
+    let plan = DryRunPlanner::new(config, ConditionEvaluatorImpl::new())
+        .with_secrets(secrets)
+        .plan(user_data, environments, routes)
+        .map_err(|e| CliError::Config(format!("Failed to plan the requests: {}", e)))?;
+
+    match &args.dry_run_json {
+        Some(path) => {
+            let json = serde_json::to_string_pretty(&plan)
+                .map_err(|e| CliError::Other(format!("Failed to serialize the plan: {}", e)))?;
+            fs::write(path, json + "\n")
+                .map_err(|e| CliError::Other(format!("Failed to write {}: {}", path, e)))?;
+            ui::success_message(&format!("Dry-run plan written to {}", path));
+        }
+        None => render_dry_run(&plan),
+    }
+
+    ui::info_message(&format!(
+        "{} requests would be sent, {} skipped by route conditions",
+        plan.request_count(),
+        plan.skipped_count()
+    ));
+    match plan.error_count() {
+        0 => Ok(()),
+        errors => Err(CliError::Config(format!(
+            "{} {} cannot be built",
+            errors,
+            if errors == 1 { "request" } else { "requests" }
+        ))),
+    }
+}
+
+/// Print the requests of a dry run, grouped by environment
+fn render_dry_run(plan: &DryRunPlan) {
+    for environment in &plan.environments {
+        ui::section_header(&format!("Environment '{}'", environment.environment));
+        for request in &environment.requests {
+            let user = request
+                .user_data
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!(
+                "  [batch {}] {}, user {} ({})",
+                request.batch,
+                request.route.bold(),
+                request.user_index + 1,
+                user
+            );
+            if let Some(reason) = &request.skipped {
+                println!("    {}", format!("skipped: {}", reason).dimmed());
+                continue;
+            }
+            if let Some(error) = &request.error {
+                println!("    {}", format!("cannot be built: {}", error).red());
+                continue;
+            }
+            if let Some(url) = &request.url {
+                println!("    {} {}", request.method, url);
+            }
+            for (name, value) in &request.headers {
+                println!("    {}: {}", name, value);
+            }
+            if let Some(body) = &request.body {
+                for line in body.lines() {
+                    println!("    | {}", line);
+                }
+            }
+            if !request.unresolved.is_empty() {
+                let names = request
+                    .unresolved
+                    .iter()
+                    .map(|name| format!("{{{}}}", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!(
+                    "    {}",
+                    format!("unresolved until upstream routes run: {}", names).yellow()
+                );
+            }
+        }
+    }
+}
+
+/// Whether the run keeps its responses for `--record` or baselines
+fn records_responses(args: &HttpDiffArgs) -> bool {
+    args.record.is_some() || args.save_baseline.is_some() || args.against_baseline.is_some()
+}
+
+/// Print how the run compares to a baseline
+fn render_baseline_report(report: &BaselineReport) {
+    ui::section_header(&format!("Baseline '{}'", report.baseline));
+    ui::info_message(&format!(
+        "Compared {} responses to the baseline ({} without a baseline response)",
+        report.compared, report.unmatched
+    ));
+
+    if report.changes.is_empty() {
+        ui::success_message("No response changed since the baseline");
+    } else {
+        ui::warning_message(&format!(
+            "{} responses changed since the baseline",
+            report.changes.len()
+        ));
+        for change in &report.changes {
+            let user = change
+                .user_data
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let differences = change
+                .differences
+                .iter()
+                .map(|difference| difference.category.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui::list_item(&format!(
+                "{} [{}] ({}): {}",
+                change.route, change.environment, user, differences
+            ));
+        }
+    }
+
+    for (title, differences) in [
+        ("New differences", &report.new),
+        ("Fixed differences", &report.fixed),
+        ("Still-present differences", &report.still_present),
+    ] {
+        ui::info_message(&format!("{}: {}", title, differences.len()));
+        for difference in differences {
+            ui::list_item(&difference.to_string());
+        }
+    }
+}
+
+/// A recorded environment requested with `--replay RECORDING:ENV`
+struct ReplaySpec {
+    name: String,
+    recording: Arc<Recording>,
+    environment: String,
+}
+
+/// Load the recordings requested with `--replay` and add their virtual environments
+///
+/// A virtual environment is named `ENV@RECORDING` and copies the configuration of `ENV`,
+/// so route URLs and headers resolve as they did when the responses were recorded.
+fn add_replay_environments(
+    specs: &[String],
+    config: &mut HttpDiffConfig,
+) -> Result<Vec<ReplaySpec>> {
+    if specs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let output_manager = OutputManager::current_dir()
+        .map_err(|e| CliError::Other(format!("Failed to initialize output manager: {}", e)))?;
+    let mut recordings: std::collections::HashMap<String, Arc<Recording>> =
+        std::collections::HashMap::new();
+    let mut replays = Vec::new();
+
+    for spec in specs {
+        let (recording_name, environment) = spec.split_once(':').ok_or_else(|| {
+            CliError::Config(format!(
+                "Invalid --replay '{}': expected RECORDING:ENV",
+                spec
+            ))
+        })?;
+
+        let recording = match recordings.get(recording_name) {
+            Some(recording) => recording.clone(),
+            None => {
+                let recording = Arc::new(
+                    Recording::load(&output_manager, recording_name)
+                        .map_err(|e| CliError::Config(e.to_string()))?,
+                );
+                recordings.insert(recording_name.to_string(), recording.clone());
+                recording
+            }
+        };
+
+        if !recording.environments().iter().any(|e| e == environment) {
+            return Err(CliError::Config(format!(
+                "Recording '{}' has no responses for environment '{}'. Recorded environments: {}",
+                recording_name,
+                environment,
+                recording.environments().join(", ")
+            )));
+        }
+
+        let mut virtual_env = config
+            .environments
+            .get(environment)
+            .cloned()
+            .ok_or_else(|| {
+                CliError::Config(format!(
+                    "Cannot replay environment '{}': it is not in the configuration",
+                    environment
+                ))
+            })?;
+        // Replayed requests never reach the network
+        virtual_env.rate_limit = None;
+        virtual_env.auth = None;
+
+        let name = virtual_environment_name(environment, recording_name);
+        config.environments.insert(name.clone(), virtual_env);
+        replays.push(ReplaySpec {
+            name,
+            recording,
+            environment: environment.to_string(),
+        });
+    }
+
+    Ok(replays)
+}
+
+/// Build the failure policy from the [global] config section and CLI overrides
+fn build_failure_policy(args: &HttpDiffArgs, config: &HttpDiffConfig) -> FailurePolicy {
+    let mut policy = FailurePolicy::from_global(config.global.as_ref());
+
+    if let Some(fail_on) = &args.fail_on {
+        policy = policy.with_fail_on(match fail_on {
+            crate::cli::FailOnType::Never => FailOn::Never,
+            crate::cli::FailOnType::AnyDifference => FailOn::AnyDifference,
+            crate::cli::FailOnType::StatusDifference => FailOn::StatusDifference,
+        });
+    }
+    if let Some(max_mixed) = args.max_mixed_responses {
+        policy = policy.with_max_mixed_responses(max_mixed);
+    }
+    if args.fail_on_errors {
+        policy = policy.with_fail_on_errors(true);
+    }
+
+    policy
+}
+
 /// Determine whether to use TUI or CLI based on arguments and environment
 fn should_use_tui(args: &HttpDiffArgs) -> bool {
     // If explicitly forced to use TUI, use it
@@ -604,6 +1006,7 @@ path = "/api/test"
         let result = execute_async(HttpDiffArgs {
             environments: Some("invalid_env".to_string()),
             routes: None,
+            profile: None,
             include_headers: false,
             include_errors: false,
             diff_view: crate::cli::DiffViewType::Unified,
@@ -615,39 +1018,179 @@ path = "/api/test"
             report_file: None,
             no_tui: false,
             force_tui: false,
+            fail_on: None,
+            max_mixed_responses: None,
+            fail_on_errors: false,
+            record: None,
+            replay: Vec::new(),
+            save_baseline: None,
+            against_baseline: None,
+            dry_run: false,
+            dry_run_json: None,
         })
         .await;
 
-        assert!(result.is_err());
+        let err = result.unwrap_err();
         assert!(
-            result
-                .unwrap_err()
-                .to_string()
+            err.to_string()
                 .contains("Environment 'invalid_env' not found")
         );
+        assert_eq!(
+            err.exit_code(),
+            http_diff::execution::policy::EXIT_CONFIG_ERROR
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_writes_plan_without_sending() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("http-diff.toml");
+        let users_path = temp_dir.path().join("users.csv");
+        let plan_path = temp_dir.path().join("plan.json");
+
+        // The hosts do not resolve, so any request sent would fail
+        fs::write(
+            &config_path,
+            r#"
+[environments.test]
+base_url = "https://api-test.invalid"
+
+[[routes]]
+name = "login"
+method = "POST"
+path = "/login/{userId}"
+
+[[routes.extract]]
+name = "token"
+type = "json_path"
+source = "$.token"
+
+[[routes]]
+name = "orders"
+method = "GET"
+path = "/orders"
+depends_on = ["login"]
+params = { session = "{token}" }
+"#,
+        )
+        .unwrap();
+        fs::write(&users_path, "userId\n123\n").unwrap();
+
+        execute_async(HttpDiffArgs {
+            environments: None,
+            routes: None,
+            profile: None,
+            include_headers: false,
+            include_errors: false,
+            diff_view: crate::cli::DiffViewType::Unified,
+            config_path: config_path.to_string_lossy().to_string(),
+            users_file: users_path.to_string_lossy().to_string(),
+            init: false,
+            verbose: false,
+            output_file: "curl_commands.txt".to_string(),
+            report_file: None,
+            no_tui: true,
+            force_tui: false,
+            fail_on: None,
+            max_mixed_responses: None,
+            fail_on_errors: false,
+            record: None,
+            replay: Vec::new(),
+            save_baseline: None,
+            against_baseline: None,
+            dry_run: false,
+            dry_run_json: Some(plan_path.to_string_lossy().to_string()),
+        })
+        .await
+        .unwrap();
+
+        let plan: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&plan_path).unwrap()).unwrap();
+        let requests = &plan["environments"][0]["requests"];
+        assert_eq!(requests[0]["url"], "https://api-test.invalid/login/123");
+        assert_eq!(requests[1]["batch"], 1);
+        assert_eq!(
+            requests[1]["url"],
+            "https://api-test.invalid/orders?session={token}"
+        );
+        assert_eq!(requests[1]["unresolved"], serde_json::json!(["token"]));
+    }
+
+    #[test]
+    fn test_failure_policy_cli_overrides_config() {
+        let config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.test]
+base_url = "https://api-test.example.com"
+
+[global]
+fail_on = "any_difference"
+max_mixed_responses = 5
+
+[[routes]]
+name = "test-route"
+method = "GET"
+path = "/api/test"
+"#,
+        )
+        .unwrap();
+
+        let mut args = HttpDiffArgs {
+            environments: None,
+            routes: None,
+            profile: None,
+            include_headers: false,
+            include_errors: false,
+            diff_view: crate::cli::DiffViewType::Unified,
+            config_path: "http-diff.toml".to_string(),
+            users_file: "users.csv".to_string(),
+            init: false,
+            verbose: false,
+            output_file: "curl_commands.txt".to_string(),
+            report_file: None,
+            no_tui: true,
+            force_tui: false,
+            fail_on: None,
+            max_mixed_responses: None,
+            fail_on_errors: false,
+            record: None,
+            replay: Vec::new(),
+            save_baseline: None,
+            against_baseline: None,
+            dry_run: false,
+            dry_run_json: None,
+        };
+
+        let policy = build_failure_policy(&args, &config);
+        assert_eq!(policy.fail_on, FailOn::AnyDifference);
+        assert_eq!(policy.max_mixed_responses, Some(5));
+        assert!(!policy.fail_on_errors);
+
+        args.fail_on = Some(crate::cli::FailOnType::StatusDifference);
+        args.fail_on_errors = true;
+        let policy = build_failure_policy(&args, &config);
+        assert_eq!(policy.fail_on, FailOn::StatusDifference);
+        assert_eq!(policy.max_mixed_responses, Some(5));
+        assert!(policy.fail_on_errors);
     }
 
     #[test]
     fn test_cli_argument_parsing() {
-        use crate::cli::{Cli, Commands};
+        use crate::cli::{Cli, HttpDiffOptions};
         use clap::Parser;
 
         // Test basic http-diff command
         let cli = Cli::try_parse_from(["fnc", "http-diff"]).unwrap();
 
-        if let Commands::HttpDiff {
+        let HttpDiffOptions {
             environments,
             routes,
             include_headers,
             ..
-        } = cli.command
-        {
-            assert_eq!(environments, None);
-            assert_eq!(routes, None);
-            assert!(!include_headers);
-        } else {
-            panic!("Expected HttpDiff command");
-        }
+        } = cli.http_diff_options();
+        assert_eq!(environments, None);
+        assert_eq!(routes, None);
+        assert!(!include_headers);
 
         // Test with all flags
         let cli = Cli::try_parse_from([
@@ -655,6 +1198,8 @@ path = "/api/test"
             "http-diff",
             "--environments",
             "test,prod",
+            "--profile",
+            "smoke",
             "--include-headers",
             "--config",
             "custom.toml",
@@ -664,12 +1209,29 @@ path = "/api/test"
             "--verbose",
             "--output-file",
             "output.txt",
+            "--fail-on",
+            "status-difference",
+            "--max-mixed-responses",
+            "2",
+            "--fail-on-errors",
+            "--record",
+            "nightly",
+            "--replay",
+            "last-week:prod",
+            "--save-baseline",
+            "v1.5",
+            "--against-baseline",
+            "v1.4",
+            "--dry-run-json",
+            "plan.json",
         ])
         .unwrap();
 
-        if let Commands::HttpDiff {
+        let HttpDiffOptions {
+            command: _,
             environments,
             routes: _,
+            profile,
             include_headers,
             include_errors: _,
             diff_view: _,
@@ -681,17 +1243,32 @@ path = "/api/test"
             report: _,
             no_tui: _,
             force_tui: _,
-        } = cli.command
-        {
-            assert_eq!(environments, Some("test,prod".to_string()));
-            assert!(include_headers);
-            assert_eq!(config, "custom.toml");
-            assert_eq!(users_file, "custom.csv");
-            assert!(init);
-            assert!(verbose);
-            assert_eq!(output_file, "output.txt");
-        } else {
-            panic!("Expected HttpDiff command");
-        }
+            fail_on,
+            max_mixed_responses,
+            fail_on_errors,
+            record,
+            replay,
+            save_baseline,
+            against_baseline,
+            dry_run,
+            dry_run_json,
+        } = cli.http_diff_options();
+        assert_eq!(environments, Some("test,prod".to_string()));
+        assert_eq!(profile, Some("smoke".to_string()));
+        assert!(include_headers);
+        assert_eq!(config, "custom.toml");
+        assert_eq!(users_file, "custom.csv");
+        assert!(init);
+        assert!(verbose);
+        assert_eq!(output_file, "output.txt");
+        assert_eq!(fail_on, Some(crate::cli::FailOnType::StatusDifference));
+        assert_eq!(max_mixed_responses, Some(2));
+        assert!(fail_on_errors);
+        assert_eq!(record, Some("nightly".to_string()));
+        assert_eq!(replay, vec!["last-week:prod".to_string()]);
+        assert_eq!(save_baseline, Some("v1.5".to_string()));
+        assert_eq!(against_baseline, Some("v1.4".to_string()));
+        assert!(!dry_run);
+        assert_eq!(dry_run_json, Some("plan.json".to_string()));
     }
 }
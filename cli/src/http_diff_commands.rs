@@ -0,0 +1,549 @@
+use crate::cli::{ConfigAction, ExportTarget, HttpDiffCommand, ImportOutput, ImportSource};
+use crate::error::{CliError, Result};
+use crate::ui;
+use dialoguer::{Confirm, theme::ColorfulTheme};
+use http_diff::config::{
+    ConfigFormat, DefaultConfigLoader, check_config, load_user_data, without_nulls,
+};
+use http_diff::import::{
+    CapturedTraffic, CurlImporter, HarImporter, ImportedConfig, OpenApiImporter, PostmanExporter,
+    PostmanImporter,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn execute(command: HttpDiffCommand) -> Result<()> {
+    match command {
+        HttpDiffCommand::Import { source } => import(source),
+        HttpDiffCommand::Export { target } => export(target),
+        HttpDiffCommand::Check { config, users_file } => check(&config, &users_file),
+        HttpDiffCommand::Config { action } => config(action),
+    }
+}
+
+fn import(source: ImportSource) -> Result<()> {
+    let (imported, output) = match source {
+        ImportSource::OpenApi { spec, output } => {
+            ui::status_message(&format!("Importing OpenAPI document {}...", spec));
+            let imported = OpenApiImporter::from_file(&spec)
+                .and_then(|importer| importer.import())
+                .map_err(|e| CliError::Config(e.to_string()))?;
+            (imported, output)
+        }
+        ImportSource::Har {
+            file,
+            keep_ids,
+            output,
+        } => {
+            ui::status_message(&format!("Importing HAR file {}...", file));
+            let traffic =
+                HarImporter::from_file(&file).map_err(|e| CliError::Config(e.to_string()))?;
+            (import_traffic(&traffic, keep_ids)?, output)
+        }
+        ImportSource::Curl {
+            file,
+            keep_ids,
+            output,
+        } => {
+            ui::status_message(&format!("Importing curl commands from {}...", file));
+            let traffic =
+                CurlImporter::from_file(&file).map_err(|e| CliError::Config(e.to_string()))?;
+            (import_traffic(&traffic, keep_ids)?, output)
+        }
+        ImportSource::Postman {
+            collection,
+            environments,
+            output,
+        } => {
+            ui::status_message(&format!("Importing Postman collection {}...", collection));
+            let imported = PostmanImporter::from_file(&collection)
+                .and_then(|importer| {
+                    environments.iter().try_fold(importer, |importer, file| {
+                        importer.with_environment_file(file)
+                    })
+                })
+                .and_then(|importer| importer.import())
+                .map_err(|e| CliError::Config(e.to_string()))?;
+            (imported, output)
+        }
+    };
+
+    write_import(&imported, &output)?;
+    for warning in &imported.warnings {
+        ui::warning_message(warning);
+    }
+    Ok(())
+}
+
+/// Generate routes from captured requests, offering to move the IDs found
+/// in paths into users.csv unless `--keep-ids` is given
+fn import_traffic(traffic: &CapturedTraffic, keep_ids: bool) -> Result<ImportedConfig> {
+    let detected = traffic.detected_parameters();
+    let parameterize = if keep_ids || detected.is_empty() {
+        false
+    } else if atty::is(atty::Stream::Stdin) {
+        let names: Vec<&str> = detected.iter().map(|p| p.name.as_str()).collect();
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Move {} detected path IDs ({}) into users.csv?",
+                detected.len(),
+                names.join(", ")
+            ))
+            .default(true)
+            .interact()
+            .map_err(|e| CliError::Other(format!("Failed to get user confirmation: {}", e)))?
+    } else {
+        true
+    };
+
+    traffic
+        .import(parameterize)
+        .map_err(|e| CliError::Config(e.to_string()))
+}
+
+/// Write the generated configuration and users.csv skeleton, refusing to
+/// overwrite existing files unless `--force` is given
+fn write_import(imported: &ImportedConfig, output: &ImportOutput) -> Result<()> {
+    let config_path = Path::new(&output.config);
+    let users_path = Path::new(&output.users_file);
+    let write_users = !imported.parameters.is_empty();
+
+    if !output.force {
+        refuse_overwrite(
+            [Some(config_path), write_users.then_some(users_path)]
+                .into_iter()
+                .flatten(),
+        )?;
+    }
+
+    let config = imported
+        .to_toml()
+        .map_err(|e| CliError::Other(e.to_string()))?;
+    fs::write(config_path, config).map_err(|e| {
+        CliError::Other(format!("Failed to write {}: {}", config_path.display(), e))
+    })?;
+    ui::success_message(&format!(
+        "Wrote {} routes and {} environments to {}",
+        imported.config.routes.len(),
+        imported.config.environments.len(),
+        config_path.display()
+    ));
+
+    if write_users {
+        let users = imported
+            .users_csv()
+            .map_err(|e| CliError::Other(e.to_string()))?;
+        fs::write(users_path, users).map_err(|e| {
+            CliError::Other(format!("Failed to write {}: {}", users_path.display(), e))
+        })?;
+        ui::success_message(&format!(
+            "Wrote {} parameters to {}; add one row per test user",
+            imported.parameters.len(),
+            users_path.display()
+        ));
+    } else {
+        ui::info_message("No route takes parameters; no users.csv was written");
+    }
+
+    Ok(())
+}
+
+fn refuse_overwrite<'a, I: IntoIterator<Item = &'a Path>>(paths: I) -> Result<()> {
+    for path in paths {
+        if path.exists() {
+            return Err(CliError::Config(format!(
+                "{} already exists; use --force to overwrite it",
+                path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn export(target: ExportTarget) -> Result<()> {
+    let ExportTarget::Postman {
+        config,
+        users_file,
+        output,
+        force,
+    } = target;
+
+    let config_path = Path::new(&config);
+    // `${VAR}` references are exported as Postman variables, not as their values
+    let loaded = DefaultConfigLoader::load_without_interpolation(config_path)
+        .map_err(|e| CliError::Config(format!("Failed to load {}: {}", config, e)))?;
+    let secrets =
+        http_diff::secrets::install(http_diff::secrets::SecretStore::from_config(&loaded));
+    // The first user becomes the collection variables, so placeholders have a value in Postman
+    let variables: BTreeMap<String, String> = if Path::new(&users_file).exists() {
+        load_user_data(&users_file)
+            .map_err(|e| CliError::Config(format!("Failed to load {}: {}", users_file, e)))?
+            .into_iter()
+            .next()
+            .map(|user| user.data.into_iter().collect())
+            .unwrap_or_default()
+    } else {
+        BTreeMap::new()
+    };
+    let name = config_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("http-diff");
+
+    let export = PostmanExporter::new(&loaded)
+        .name(name)
+        .variables(variables)
+        .export()
+        .map_err(|e| CliError::Other(e.to_string()))?;
+
+    let collection_path = PathBuf::from(&output);
+    let directory = collection_path.parent().unwrap_or(Path::new(""));
+    let mut files = vec![(collection_path.clone(), export.collection)];
+    for (environment, document) in export.environments {
+        let path = directory.join(format!("{}.postman_environment.json", environment));
+        files.push((path, document));
+    }
+    if !force {
+        refuse_overwrite(files.iter().map(|(path, _)| path.as_path()))?;
+    }
+
+    for (path, document) in &files {
+        let content =
+            serde_json::to_string_pretty(document).map_err(|e| CliError::Other(e.to_string()))?;
+        fs::write(path, secrets.redact(&content))
+            .map_err(|e| CliError::Other(format!("Failed to write {}: {}", path.display(), e)))?;
+    }
+    ui::success_message(&format!(
+        "Wrote {} routes to {} and {} environments next to it",
+        loaded.routes.len(),
+        collection_path.display(),
+        files.len() - 1
+    ));
+    for warning in &export.warnings {
+        ui::warning_message(warning);
+    }
+    Ok(())
+}
+
+fn check(config: &str, users_file: &str) -> Result<()> {
+    ui::status_message(&format!("Checking {} and {}...", config, users_file));
+    let findings = check_config(config, users_file);
+    if findings.is_empty() {
+        ui::success_message("No problems found");
+        return Ok(());
+    }
+
+    for finding in &findings {
+        ui::error_message(&finding.to_string());
+    }
+    Err(CliError::Config(format!(
+        "Found {} {} in {}",
+        findings.len(),
+        if findings.len() == 1 {
+            "problem"
+        } else {
+            "problems"
+        },
+        config
+    )))
+}
+
+fn config(action: ConfigAction) -> Result<()> {
+    let ConfigAction::Convert {
+        input,
+        output,
+        force,
+    } = action;
+
+    let input_path = Path::new(&input);
+    let output_path = Path::new(&output);
+    let from = ConfigFormat::from_path(input_path);
+    let to = ConfigFormat::from_extension(output_path).ok_or_else(|| {
+        CliError::Config(format!(
+            "Cannot tell the format of {}; use a .toml, .yaml, .yml or .json extension",
+            output
+        ))
+    })?;
+    if !force {
+        refuse_overwrite([output_path])?;
+    }
+
+    let content = fs::read_to_string(input_path)
+        .map_err(|e| CliError::Config(format!("Failed to read {}: {}", input, e)))?;
+    let mut settings = from
+        .parse(input_path, &content)
+        .map_err(|e| CliError::Config(e.to_string()))?;
+    let converted = from
+        .convert(&content, to)
+        .map_err(|e| CliError::Config(format!("Failed to convert {}: {}", input, e)))?;
+    if !converted.dropped_nulls.is_empty() {
+        ui::warning_message(&format!(
+            "TOML has no null; these values are left out of {}: {}",
+            output,
+            converted.dropped_nulls.join(", ")
+        ));
+        settings = without_nulls(&settings);
+    }
+    // Reading the result back guarantees it loads with the same settings
+    let round_trip = to
+        .parse(output_path, &converted.content)
+        .map_err(|e| CliError::Other(e.to_string()))?;
+    if round_trip != settings {
+        return Err(CliError::Config(format!(
+            "{} holds values that cannot be written as {}",
+            input,
+            to.name()
+        )));
+    }
+
+    fs::write(output_path, converted.content)
+        .map_err(|e| CliError::Other(format!("Failed to write {}: {}", output, e)))?;
+    ui::success_message(&format!(
+        "Converted {} ({}) to {} ({})",
+        input,
+        from.name(),
+        output,
+        to.name()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, HttpDiffOptions};
+    use clap::Parser;
+
+    #[test]
+    fn test_import_openapi_parsing() {
+        let cli = Cli::try_parse_from([
+            "fnc",
+            "http-diff",
+            "import",
+            "openapi",
+            "spec.yaml",
+            "--config",
+            "imported.toml",
+            "--force",
+        ])
+        .unwrap();
+
+        let HttpDiffOptions {
+            command:
+                Some(HttpDiffCommand::Import {
+                    source: ImportSource::OpenApi { spec, output },
+                }),
+            ..
+        } = cli.http_diff_options()
+        else {
+            panic!("Expected http-diff import openapi command");
+        };
+        assert_eq!(spec, "spec.yaml");
+        assert_eq!(output.config, "imported.toml");
+        assert_eq!(output.users_file, "users.csv");
+        assert!(output.force);
+    }
+
+    #[test]
+    fn test_import_har_and_curl_parsing() {
+        let cli =
+            Cli::try_parse_from(["fnc", "http-diff", "import", "har", "capture.har"]).unwrap();
+        let HttpDiffOptions {
+            command:
+                Some(HttpDiffCommand::Import {
+                    source: ImportSource::Har { file, keep_ids, .. },
+                }),
+            ..
+        } = cli.http_diff_options()
+        else {
+            panic!("Expected http-diff import har command");
+        };
+        assert_eq!(file, "capture.har");
+        assert!(!keep_ids);
+
+        let cli = Cli::try_parse_from([
+            "fnc",
+            "http-diff",
+            "import",
+            "curl",
+            "requests.sh",
+            "--keep-ids",
+        ])
+        .unwrap();
+        let HttpDiffOptions {
+            command:
+                Some(HttpDiffCommand::Import {
+                    source: ImportSource::Curl { file, keep_ids, .. },
+                }),
+            ..
+        } = cli.http_diff_options()
+        else {
+            panic!("Expected http-diff import curl command");
+        };
+        assert_eq!(file, "requests.sh");
+        assert!(keep_ids);
+    }
+
+    #[test]
+    fn test_export_postman_writes_collection_and_environments() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("shop.toml");
+        fs::write(
+            &config,
+            r#"
+[environments.prod]
+base_url = "https://shop.example.com"
+headers = { "Authorization" = "Bearer ${HTTP_DIFF_EXPORT_TEST_TOKEN}" }
+
+[environments.staging]
+base_url = "https://staging.example.com"
+
+[[routes]]
+name = "get_order"
+method = "GET"
+path = "/orders/{orderId}"
+group = "Orders"
+"#,
+        )
+        .unwrap();
+        let users = dir.path().join("users.csv");
+        fs::write(&users, "orderId\n17\n").unwrap();
+        let output = dir.path().join("shop.postman_collection.json");
+        let target = || ExportTarget::Postman {
+            config: config.display().to_string(),
+            users_file: users.display().to_string(),
+            output: output.display().to_string(),
+            force: false,
+        };
+
+        // SAFETY: no other test reads this variable
+        unsafe { std::env::set_var("HTTP_DIFF_EXPORT_TEST_TOKEN", "tok-exported") };
+        export(target()).unwrap();
+        let collection: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(collection["info"]["name"], "shop");
+        assert_eq!(collection["item"][0]["name"], "Orders");
+        assert_eq!(collection["variable"][0]["value"], "17");
+        let prod = fs::read_to_string(dir.path().join("prod.postman_environment.json")).unwrap();
+        assert!(prod.contains("Bearer {{HTTP_DIFF_EXPORT_TEST_TOKEN}}"));
+        assert!(!prod.contains("tok-exported"));
+        assert!(dir.path().join("staging.postman_environment.json").exists());
+
+        assert!(export(target()).is_err());
+    }
+
+    #[test]
+    fn test_check_fails_on_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("http-diff.toml");
+        let users = dir.path().join("users.csv");
+        fs::write(
+            &config,
+            r#"
+[environments.prod]
+base_url = "https://shop.example.com"
+
+[[routes]]
+name = "get_order"
+method = "GET"
+path = "/orders/{orderId}"
+"#,
+        )
+        .unwrap();
+        fs::write(&users, "orderId\n17\n").unwrap();
+        let (config, users) = (config.display().to_string(), users.display().to_string());
+
+        let cli = Cli::try_parse_from(["fnc", "http-diff", "check", "-c", &config]).unwrap();
+        assert!(matches!(
+            cli.http_diff_options().command,
+            Some(HttpDiffCommand::Check { .. })
+        ));
+
+        check(&config, &users).unwrap();
+        fs::write(&users, "userId\n17\n").unwrap();
+        let err = check(&config, &users).unwrap_err();
+        assert!(err.to_string().contains("Found 1 problem in"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_convert_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml = dir.path().join("http-diff.toml");
+        fs::write(
+            &toml,
+            r#"
+[environments.prod]
+base_url = "https://${PROD_HOST:-shop.example.com}"
+
+[[routes]]
+name = "get_order"
+method = "GET"
+path = "/orders/{orderId}"
+"#,
+        )
+        .unwrap();
+        let yaml = dir.path().join("http-diff.yaml");
+        let json = dir.path().join("http-diff.json");
+        let convert = |input: &Path, output: &Path| ConfigAction::Convert {
+            input: input.display().to_string(),
+            output: output.display().to_string(),
+            force: false,
+        };
+
+        let cli =
+            Cli::try_parse_from(["fnc", "http-diff", "config", "convert", "a.toml", "a.yaml"])
+                .unwrap();
+        assert!(matches!(
+            cli.http_diff_options().command,
+            Some(HttpDiffCommand::Config {
+                action: ConfigAction::Convert { .. }
+            })
+        ));
+
+        config(convert(&toml, &yaml)).unwrap();
+        config(convert(&yaml, &json)).unwrap();
+        let loaded = http_diff::HttpDiffConfig::load_from_file(&json).unwrap();
+        assert_eq!(
+            loaded.environments["prod"].base_url,
+            "https://shop.example.com"
+        );
+        assert_eq!(loaded.routes[0].path, "/orders/{orderId}");
+
+        assert!(config(convert(&toml, &yaml)).is_err());
+        assert!(config(convert(&toml, &dir.path().join("http-diff.ini"))).is_err());
+    }
+
+    #[test]
+    fn test_import_refuses_to_overwrite_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = dir.path().join("spec.json");
+        fs::write(
+            &spec,
+            r#"{"openapi": "3.0.0", "paths": {"/users/{id}": {"get": {"operationId": "getUser"}}}}"#,
+        )
+        .unwrap();
+        let mut output = ImportOutput {
+            config: dir.path().join("http-diff.toml").display().to_string(),
+            users_file: dir.path().join("users.csv").display().to_string(),
+            force: false,
+        };
+        let source = |output: &ImportOutput| ImportSource::OpenApi {
+            spec: spec.display().to_string(),
+            output: output.clone(),
+        };
+
+        import(source(&output)).unwrap();
+        assert!(
+            fs::read_to_string(&output.users_file)
+                .unwrap()
+                .starts_with("id\n")
+        );
+        let config = http_diff::HttpDiffConfig::load_from_file(&output.config).unwrap();
+        assert_eq!(config.routes[0].name, "getUser");
+
+        assert!(import(source(&output)).is_err());
+        output.force = true;
+        import(source(&output)).unwrap();
+    }
+}
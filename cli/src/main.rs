@@ -3,6 +3,7 @@ mod cli;
 mod deploy;
 mod error;
 mod http_diff;
+mod http_diff_commands;
 mod package_version;
 mod progress;
 mod sync_versions;
@@ -10,7 +11,7 @@ mod ui;
 mod upgrade;
 
 use clap::Parser;
-use cli::{Cli, Commands, FixType};
+use cli::{Cli, Commands, FixType, HttpDiffOptions};
 use colored::Colorize;
 use std::process;
 
@@ -37,39 +38,66 @@ fn main() {
             verbose,
         } => sync_versions::execute(source, targets, discover, max_depth, verbose),
         Commands::Upgrade { force, verbose } => upgrade::execute(force, verbose),
-        Commands::HttpDiff {
-            environments,
-            routes,
-            include_headers,
-            include_errors,
-            diff_view,
-            config,
-            users_file,
-            init,
-            verbose,
-            output_file,
-            report,
-            no_tui,
-            force_tui,
-        } => http_diff::execute(http_diff::HttpDiffArgs {
-            environments,
-            routes,
-            include_headers,
-            include_errors,
-            diff_view,
-            config_path: config,
-            users_file,
-            init,
-            verbose,
-            output_file,
-            report_file: report,
-            no_tui,
-            force_tui,
-        }),
+        Commands::HttpDiff(options) => match *options {
+            HttpDiffOptions {
+                command: Some(command),
+                ..
+            } => http_diff_commands::execute(command),
+            HttpDiffOptions {
+                command: None,
+                environments,
+                routes,
+                profile,
+                include_headers,
+                include_errors,
+                diff_view,
+                config,
+                users_file,
+                init,
+                verbose,
+                output_file,
+                report,
+                no_tui,
+                force_tui,
+                fail_on,
+                max_mixed_responses,
+                fail_on_errors,
+                record,
+                replay,
+                save_baseline,
+                against_baseline,
+                dry_run,
+                dry_run_json,
+            } => http_diff::execute(http_diff::HttpDiffArgs {
+                environments,
+                routes,
+                profile,
+                include_headers,
+                include_errors,
+                diff_view,
+                config_path: config,
+                users_file,
+                init,
+                verbose,
+                output_file,
+                report_file: report,
+                no_tui,
+                force_tui,
+                fail_on,
+                max_mixed_responses,
+                fail_on_errors,
+                record,
+                replay,
+                save_baseline,
+                against_baseline,
+                dry_run,
+                dry_run_json,
+            }),
+        },
     };
 
     if let Err(err) = result {
         eprintln!("{} {}", "Error:".bold().red(), err.user_message());
-        process::exit(1);
+        process::exit(err.exit_code());
     }
 }
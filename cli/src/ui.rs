@@ -36,3 +36,8 @@ pub fn info_message(message: &str) {
 pub fn step_message(step_number: usize, message: &str) {
     println!("  {}. {}", format!("{step_number}").cyan(), message);
 }
+
+/// Print an item of a list under the previous message
+pub fn list_item(message: &str) {
+    println!("  {} {}", "-".cyan(), message);
+}
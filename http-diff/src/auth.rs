@@ -0,0 +1,419 @@
+//! Authentication providers for environments
+//!
+//! Credentials configured in `[environments.<name>.auth]` are attached to every
+//! request sent to that environment. Tokens obtained from an OAuth2 token
+//! endpoint or a login route are cached until shortly before they expire, and
+//! are renewed when the server rejects them with 401 Unauthorized.
+
+use crate::config::ApiKeyLocation;
+use crate::error::{HttpDiffError, Result};
+use crate::secrets::SecretStore;
+use base64::Engine;
+use reqwest::header::{HeaderName, HeaderValue, ACCEPT, AUTHORIZATION};
+use reqwest::{Client, Request};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Tokens are renewed this long before they expire
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Scheme written before tokens when none is configured
+pub const DEFAULT_TOKEN_SCHEME: &str = "Bearer";
+
+/// Credential attached to a request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credential {
+    Header { name: String, value: String },
+    Query { name: String, value: String },
+}
+
+impl Credential {
+    /// HTTP basic authentication
+    pub fn basic(username: &str, password: &str) -> Self {
+        let encoded =
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+        Self::Header {
+            name: AUTHORIZATION.to_string(),
+            value: format!("Basic {}", encoded),
+        }
+    }
+
+    /// Access token sent in `header` (defaults to Authorization) after `scheme`
+    /// (defaults to Bearer, empty for none)
+    pub fn token(header: Option<&str>, scheme: Option<&str>, token: &str) -> Self {
+        let scheme = scheme.unwrap_or(DEFAULT_TOKEN_SCHEME);
+        Self::Header {
+            name: header.unwrap_or(AUTHORIZATION.as_str()).to_string(),
+            value: if scheme.is_empty() {
+                token.to_string()
+            } else {
+                format!("{} {}", scheme, token)
+            },
+        }
+    }
+
+    /// Static API key
+    pub fn api_key(location: ApiKeyLocation, name: &str, value: &str) -> Self {
+        let (name, value) = (name.to_string(), value.to_string());
+        match location {
+            ApiKeyLocation::Header => Self::Header { name, value },
+            ApiKeyLocation::Query => Self::Query { name, value },
+        }
+    }
+
+    /// Attach the credential unless the request already carries it
+    ///
+    /// Headers and query parameters set explicitly on a route win over the
+    /// environment's credentials.
+    pub fn apply(&self, request: &mut Request) -> Result<()> {
+        match self {
+            Self::Header { name, value } => {
+                let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                    HttpDiffError::invalid_config(format!("Invalid auth header name '{}'", name))
+                })?;
+                if !request.headers().contains_key(&name) {
+                    let value = HeaderValue::from_str(value).map_err(|_| {
+                        HttpDiffError::invalid_config(format!(
+                            "Invalid value for auth header '{}'",
+                            name
+                        ))
+                    })?;
+                    request.headers_mut().insert(name, value);
+                }
+            }
+            Self::Query { name, value } => {
+                let url = request.url_mut();
+                if !url.query_pairs().any(|(key, _)| key == name.as_str()) {
+                    url.query_pairs_mut().append_pair(name, value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// OAuth2 grant used to obtain a token
+#[derive(Debug, Clone, PartialEq)]
+pub enum OAuth2Grant {
+    ClientCredentials,
+    Password { username: String, password: String },
+}
+
+/// Token request to an OAuth2 token endpoint, with placeholders resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuth2TokenRequest {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+    pub grant: OAuth2Grant,
+}
+
+impl OAuth2TokenRequest {
+    /// Key under which tokens issued for this request are cached
+    pub fn cache_key(&self, environment: &str) -> String {
+        let username = match &self.grant {
+            OAuth2Grant::ClientCredentials => "",
+            OAuth2Grant::Password { username, .. } => username,
+        };
+        format!(
+            "oauth2:{}:{}:{}:{}",
+            environment, self.token_url, self.client_id, username
+        )
+    }
+
+    /// Obtain a token, using `refresh_token` first when available
+    ///
+    /// A rejected refresh token falls back to the configured grant.
+    pub async fn send(
+        &self,
+        client: &Client,
+        environment: &str,
+        refresh_token: Option<String>,
+    ) -> Result<TokenResponse> {
+        if let Some(refresh_token) = refresh_token {
+            if let Ok(token) = self.post(client, environment, Some(&refresh_token)).await {
+                return Ok(token);
+            }
+        }
+        self.post(client, environment, None).await
+    }
+
+    async fn post(
+        &self,
+        client: &Client,
+        environment: &str,
+        refresh_token: Option<&str>,
+    ) -> Result<TokenResponse> {
+        let failed = |message: String| {
+            HttpDiffError::authentication_failed(
+                environment.to_string(),
+                crate::secrets::redact(&message),
+            )
+        };
+
+        let response = client
+            .post(&self.token_url)
+            .header(ACCEPT, "application/json")
+            .form(&self.form(refresh_token))
+            .send()
+            .await
+            .map_err(|e| failed(format!("token request to {} failed: {}", self.token_url, e)))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| failed(format!("failed to read token response: {}", e)))?;
+        if !status.is_success() {
+            return Err(failed(format!(
+                "token endpoint {} returned {}: {}",
+                self.token_url,
+                status,
+                body.trim()
+            )));
+        }
+
+        serde_json::from_str(&body).map_err(|e| failed(format!("invalid token response: {}", e)))
+    }
+
+    fn form<'a>(&'a self, refresh_token: Option<&'a str>) -> Vec<(&'static str, &'a str)> {
+        let mut form = match (refresh_token, &self.grant) {
+            (Some(refresh_token), _) => vec![
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+            ],
+            (None, OAuth2Grant::ClientCredentials) => vec![("grant_type", "client_credentials")],
+            (None, OAuth2Grant::Password { username, password }) => vec![
+                ("grant_type", "password"),
+                ("username", username.as_str()),
+                ("password", password.as_str()),
+            ],
+        };
+        form.push(("client_id", &self.client_id));
+        if let Some(client_secret) = &self.client_secret {
+            form.push(("client_secret", client_secret));
+        }
+        if let Some(scope) = &self.scope {
+            form.push(("scope", scope));
+        }
+        form
+    }
+}
+
+/// Token returned by a token endpoint or login route
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    /// Lifetime in seconds (the token is kept until rejected when unset)
+    pub expires_in: Option<u64>,
+    pub refresh_token: Option<String>,
+}
+
+impl TokenResponse {
+    /// Create a token without expiry or refresh token
+    pub fn new<S: Into<String>>(access_token: S) -> Self {
+        Self {
+            access_token: access_token.into(),
+            expires_in: None,
+            refresh_token: None,
+        }
+    }
+}
+
+/// Access token attached to a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuedToken {
+    /// Cache key the token was stored under
+    pub key: String,
+    pub access_token: String,
+}
+
+#[derive(Debug)]
+struct CachedToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Option<Instant>,
+    rejected: bool,
+}
+
+impl CachedToken {
+    fn is_usable(&self, now: Instant) -> bool {
+        !self.rejected && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// Tokens shared by all requests of a run
+#[derive(Debug)]
+pub struct TokenCache {
+    tokens: tokio::sync::Mutex<HashMap<String, CachedToken>>,
+    /// Store the fetched tokens are registered with for redaction
+    secrets: Arc<SecretStore>,
+}
+
+impl TokenCache {
+    /// Create an empty cache registering fetched tokens with `secrets`
+    pub fn new(secrets: Arc<SecretStore>) -> Self {
+        Self {
+            tokens: tokio::sync::Mutex::new(HashMap::new()),
+            secrets,
+        }
+    }
+
+    /// Return the token cached under `key`, fetching a new one when it is
+    /// missing, expired or was rejected
+    ///
+    /// `fetch` receives the refresh token of the previous token, if any. The
+    /// cache stays locked while fetching so concurrent requests share a single
+    /// token instead of each requesting their own.
+    pub async fn get_or_fetch<F, Fut>(&self, key: &str, fetch: F) -> Result<IssuedToken>
+    where
+        F: FnOnce(Option<String>) -> Fut,
+        Fut: Future<Output = Result<TokenResponse>>,
+    {
+        let mut tokens = self.tokens.lock().await;
+        let refresh_token = match tokens.get(key) {
+            Some(cached) if cached.is_usable(Instant::now()) => {
+                return Ok(IssuedToken {
+                    key: key.to_string(),
+                    access_token: cached.access_token.clone(),
+                });
+            }
+            Some(cached) => cached.refresh_token.clone(),
+            None => None,
+        };
+
+        let response = fetch(refresh_token).await?;
+        self.secrets.register(&response.access_token);
+
+        let expires_at = response.expires_in.map(|seconds| {
+            Instant::now() + Duration::from_secs(seconds).saturating_sub(EXPIRY_MARGIN)
+        });
+        tokens.insert(
+            key.to_string(),
+            CachedToken {
+                access_token: response.access_token.clone(),
+                refresh_token: response.refresh_token,
+                expires_at,
+                rejected: false,
+            },
+        );
+
+        Ok(IssuedToken {
+            key: key.to_string(),
+            access_token: response.access_token,
+        })
+    }
+
+    /// Mark a token rejected by the server so the next request renews it
+    ///
+    /// Does nothing when another request already replaced the token.
+    pub async fn invalidate(&self, token: &IssuedToken) {
+        if let Some(cached) = self.tokens.lock().await.get_mut(&token.key) {
+            if cached.access_token == token.access_token {
+                cached.rejected = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str) -> Request {
+        Client::new().get(url).build().unwrap()
+    }
+
+    #[test]
+    fn test_credentials_do_not_override_route_values() {
+        let mut req = request("https://api.example.com/users?api_key=route");
+        Credential::api_key(ApiKeyLocation::Query, "api_key", "env")
+            .apply(&mut req)
+            .unwrap();
+        Credential::api_key(ApiKeyLocation::Query, "tenant", "a b")
+            .apply(&mut req)
+            .unwrap();
+        assert_eq!(req.url().query(), Some("api_key=route&tenant=a+b"));
+
+        Credential::basic("user", "pass").apply(&mut req).unwrap();
+        Credential::token(None, None, "ignored")
+            .apply(&mut req)
+            .unwrap();
+        assert_eq!(req.headers()["authorization"], "Basic dXNlcjpwYXNz");
+
+        Credential::token(Some("X-Session"), Some(""), "abc")
+            .apply(&mut req)
+            .unwrap();
+        assert_eq!(req.headers()["x-session"], "abc");
+    }
+
+    #[test]
+    fn test_oauth2_form() {
+        let token_request = OAuth2TokenRequest {
+            token_url: "https://auth.example.com/token".to_string(),
+            client_id: "client".to_string(),
+            client_secret: None,
+            scope: Some("read".to_string()),
+            grant: OAuth2Grant::Password {
+                username: "alice".to_string(),
+                password: "secret".to_string(),
+            },
+        };
+
+        assert_eq!(
+            token_request.form(None),
+            vec![
+                ("grant_type", "password"),
+                ("username", "alice"),
+                ("password", "secret"),
+                ("client_id", "client"),
+                ("scope", "read"),
+            ]
+        );
+        assert_eq!(
+            token_request.form(Some("refresh"))[..2],
+            [
+                ("grant_type", "refresh_token"),
+                ("refresh_token", "refresh")
+            ]
+        );
+        assert_eq!(
+            token_request.cache_key("prod"),
+            "oauth2:prod:https://auth.example.com/token:client:alice"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_cache_renews_rejected_tokens() {
+        let secrets = Arc::new(SecretStore::new());
+        let cache = TokenCache::new(secrets.clone());
+        let fetch = |token: &'static str| {
+            move |refresh_token: Option<String>| async move {
+                Ok(TokenResponse {
+                    access_token: format!("{}{}", token, refresh_token.unwrap_or_default()),
+                    expires_in: Some(3600),
+                    refresh_token: Some("+refreshed".to_string()),
+                })
+            }
+        };
+
+        let first = cache.get_or_fetch("key", fetch("first")).await.unwrap();
+        assert_eq!(first.access_token, "first");
+        assert_eq!(secrets.redact("token first"), "token ***");
+        let cached = cache.get_or_fetch("key", fetch("second")).await.unwrap();
+        assert_eq!(cached, first);
+
+        cache.invalidate(&first).await;
+        let renewed = cache.get_or_fetch("key", fetch("third")).await.unwrap();
+        assert_eq!(renewed.access_token, "third+refreshed");
+
+        // A stale rejection does not discard the renewed token
+        cache.invalidate(&first).await;
+        let cached = cache.get_or_fetch("key", fetch("fourth")).await.unwrap();
+        assert_eq!(cached, renewed);
+    }
+}
@@ -0,0 +1,549 @@
+//! Baseline snapshots and regression comparison between runs
+//!
+//! A baseline stores the normalized responses of a run under a name in the
+//! cache directory of the [`OutputManager`]. A later run is compared to it in
+//! two ways:
+//!
+//! - every response is diffed against the baseline response of the same route,
+//!   user data row and environment, showing what changed since the snapshot;
+//!   each of these differences is new, so a run of a single environment is
+//!   checked against the baseline too
+//! - when both runs cover several environments, the differences between
+//!   environments are also classified as new, fixed or still present compared
+//!   to the differences between the same environments in the baseline
+
+use crate::comparison::analyzer::DifferenceAnalyzer;
+use crate::comparison::content::ContentNormalizer;
+use crate::comparison::rules::RouteComparisonRules;
+use crate::comparison::DEFAULT_IGNORED_HEADERS;
+use crate::config::HttpDiffConfig;
+use crate::error::{HttpDiffError, Result};
+use crate::output_manager::{OutputCategory, OutputManager};
+use crate::recording::Recording;
+use crate::types::{Difference, DifferenceCategory, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// Subdirectory of the cache directory holding baselines
+pub const BASELINES_DIR: &str = "baselines";
+
+/// A normalized response stored in a baseline
+///
+/// Volatile headers are dropped and the body is normalized the way responses
+/// are normalized for comparison, so baseline files diff cleanly between saves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineResponse {
+    pub route: String,
+    pub environment: String,
+    /// The user data row the request was made with
+    pub user_data: BTreeMap<String, String>,
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+impl BaselineResponse {
+    fn to_http_response(&self) -> HttpResponse {
+        HttpResponse::new(
+            self.status,
+            self.headers.clone().into_iter().collect(),
+            self.body.clone(),
+            String::new(),
+            String::new(),
+        )
+    }
+}
+
+/// A named snapshot of normalized responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub responses: Vec<BaselineResponse>,
+}
+
+impl Baseline {
+    /// Create a baseline from the responses of a recorded run
+    ///
+    /// Resolved secrets are redacted, so a run compared to a saved baseline
+    /// is compared with the same redacted text the baseline file holds.
+    pub fn from_recording<S: Into<String>>(name: S, recording: &Recording) -> Self {
+        let normalizer = ContentNormalizer::new(true);
+        let secrets = crate::secrets::active();
+        let responses = recording
+            .responses
+            .iter()
+            .map(|recorded| BaselineResponse {
+                route: recorded.route.clone(),
+                environment: recorded.environment.clone(),
+                user_data: recorded
+                    .user_data
+                    .iter()
+                    .map(|(key, value)| (key.clone(), secrets.redact(value)))
+                    .collect(),
+                status: recorded.response.status,
+                headers: recorded
+                    .response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.to_lowercase(), secrets.redact(value)))
+                    .filter(|(name, _)| !DEFAULT_IGNORED_HEADERS.contains(&name.as_str()))
+                    .collect(),
+                body: secrets.redact(&normalizer.normalize(&recorded.response.body, None)),
+            })
+            .collect();
+
+        Self {
+            name: name.into(),
+            created_at: chrono::Utc::now(),
+            responses,
+        }
+    }
+
+    /// Path of the baseline file for `name`
+    pub fn path(output_manager: &OutputManager, name: &str) -> Result<PathBuf> {
+        if name.is_empty()
+            || name.starts_with('.')
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Invalid baseline name '{}': use letters, digits, '-', '_' and '.'",
+                name
+            )));
+        }
+        Ok(output_manager
+            .category_path(OutputCategory::Cache)
+            .join(BASELINES_DIR)
+            .join(format!("{}.json", name)))
+    }
+
+    /// Load a baseline by name
+    pub fn load(output_manager: &OutputManager, name: &str) -> Result<Self> {
+        let path = Self::path(output_manager, name)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            HttpDiffError::invalid_config(format!(
+                "Cannot read baseline '{}' at {}: {}",
+                name,
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            HttpDiffError::invalid_config(format!("Baseline '{}' is not valid: {}", name, e))
+        })
+    }
+
+    /// Save the baseline, returning the path written
+    ///
+    /// Resolved secrets are redacted from the stored responses.
+    pub fn save(&self, output_manager: &OutputManager) -> Result<PathBuf> {
+        let path = Self::path(output_manager, &self.name)?;
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| HttpDiffError::general(format!("Failed to serialize baseline: {}", e)))?;
+        output_manager.write_file_atomic(&path, content)?;
+        Ok(path)
+    }
+
+    /// Environments with responses in the baseline, sorted by name
+    pub fn environments(&self) -> Vec<String> {
+        let environments: BTreeSet<&String> =
+            self.responses.iter().map(|r| &r.environment).collect();
+        environments.into_iter().cloned().collect()
+    }
+
+    /// Find the baseline response of a route, environment and user data row
+    ///
+    /// A baseline saved from a single environment serves as the reference for
+    /// every environment, so any environment can be compared to it.
+    pub fn find(
+        &self,
+        route: &str,
+        environment: &str,
+        user_data: &BTreeMap<String, String>,
+    ) -> Option<&BaselineResponse> {
+        let environments = self.environments();
+        let environment = match environments.as_slice() {
+            [only] => only.as_str(),
+            _ => environment,
+        };
+        self.responses
+            .iter()
+            .find(|r| r.route == route && r.environment == environment && &r.user_data == user_data)
+    }
+}
+
+/// A response that changed since the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineChange {
+    pub route: String,
+    pub environment: String,
+    pub user_data: BTreeMap<String, String>,
+    pub differences: Vec<Difference>,
+}
+
+/// A difference between two environments, identified across runs
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DifferenceKey {
+    pub route: String,
+    pub user_data: BTreeMap<String, String>,
+    /// The compared environments, e.g. `prod ↔ staging`
+    pub environments: String,
+    /// What differs, e.g. `Status Code` or `Response Body $.plan`
+    pub difference: String,
+}
+
+impl std::fmt::Display for DifferenceKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let user = self
+            .user_data
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "{} [{}] ({}): {}",
+            self.route, self.environments, user, self.difference
+        )
+    }
+}
+
+/// Result of comparing a run to a baseline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineReport {
+    pub baseline: String,
+    /// Number of responses compared to a baseline response
+    pub compared: usize,
+    /// Responses without a baseline response to compare to
+    pub unmatched: usize,
+    /// Responses that changed since the baseline
+    pub changes: Vec<BaselineChange>,
+    /// Differences from the baseline response of each environment, and
+    /// differences between environments that the baseline did not have
+    pub new: Vec<DifferenceKey>,
+    /// Differences between environments in the baseline that are gone
+    pub fixed: Vec<DifferenceKey>,
+    /// Differences between environments found in both runs
+    pub still_present: Vec<DifferenceKey>,
+}
+
+impl BaselineReport {
+    /// Check whether the run matches the baseline
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty() && self.new.is_empty()
+    }
+}
+
+/// Compares runs to baselines using the [`DifferenceAnalyzer`]
+pub struct BaselineComparator {
+    analyzer: DifferenceAnalyzer,
+    compare_headers: bool,
+    rules: RouteComparisonRules,
+}
+
+impl BaselineComparator {
+    /// Create a comparator applying the comparison rules of the configuration
+    pub fn new(config: &HttpDiffConfig) -> Self {
+        Self {
+            analyzer: DifferenceAnalyzer::new(
+                DEFAULT_IGNORED_HEADERS
+                    .iter()
+                    .map(|h| h.to_string())
+                    .collect(),
+                true,
+                crate::types::DEFAULT_LARGE_RESPONSE_THRESHOLD,
+            ),
+            compare_headers: false,
+            rules: RouteComparisonRules::from_config(config),
+        }
+    }
+
+    /// Enable headers comparison (disabled by default)
+    pub fn with_headers_comparison(mut self) -> Self {
+        self.compare_headers = true;
+        self
+    }
+
+    /// Compare the responses of a run to a baseline
+    pub fn compare(&self, baseline: &Baseline, recording: &Recording) -> BaselineReport {
+        let current = Baseline::from_recording(recording.name.clone(), recording);
+        let mut report = BaselineReport {
+            baseline: baseline.name.clone(),
+            ..BaselineReport::default()
+        };
+
+        for response in &current.responses {
+            let Some(expected) =
+                baseline.find(&response.route, &response.environment, &response.user_data)
+            else {
+                report.unmatched += 1;
+                continue;
+            };
+            report.compared += 1;
+
+            let differences = self.differences(
+                &response.route,
+                expected,
+                response,
+                &format!("baseline {}", baseline.name),
+                &response.environment,
+            );
+            if !differences.is_empty() {
+                let environments = format!("baseline {} ↔ {}", baseline.name, response.environment);
+                report
+                    .new
+                    .extend(differences.iter().flat_map(difference_descriptions).map(
+                        |difference| DifferenceKey {
+                            route: response.route.clone(),
+                            user_data: response.user_data.clone(),
+                            environments: environments.clone(),
+                            difference,
+                        },
+                    ));
+                report.changes.push(BaselineChange {
+                    route: response.route.clone(),
+                    environment: response.environment.clone(),
+                    user_data: response.user_data.clone(),
+                    differences,
+                });
+            }
+        }
+
+        // Differences between environments are compared for pairs present in both runs
+        let before = self.environment_differences(baseline);
+        let after = self.environment_differences(&current);
+        for (pair, keys) in &after {
+            if let Some(previous) = before.get(pair) {
+                report.new.extend(keys.difference(previous).cloned());
+                report.fixed.extend(previous.difference(keys).cloned());
+                report
+                    .still_present
+                    .extend(keys.intersection(previous).cloned());
+            }
+        }
+        report.new.sort();
+
+        report
+    }
+
+    fn differences(
+        &self,
+        route: &str,
+        response1: &BaselineResponse,
+        response2: &BaselineResponse,
+        env1: &str,
+        env2: &str,
+    ) -> Vec<Difference> {
+        self.analyzer
+            .analyze_responses_with_rules(
+                &response1.to_http_response(),
+                &response2.to_http_response(),
+                env1,
+                env2,
+                self.compare_headers,
+                self.rules.for_route(route),
+            )
+            .0
+    }
+
+    /// Differences between every pair of environments, grouped by route, user and pair
+    fn environment_differences(
+        &self,
+        baseline: &Baseline,
+    ) -> BTreeMap<DifferenceKey, BTreeSet<DifferenceKey>> {
+        let mut groups: BTreeMap<(&str, &BTreeMap<String, String>), Vec<&BaselineResponse>> =
+            BTreeMap::new();
+        for response in &baseline.responses {
+            groups
+                .entry((response.route.as_str(), &response.user_data))
+                .or_default()
+                .push(response);
+        }
+
+        let mut differences = BTreeMap::new();
+        for ((route, user_data), mut responses) in groups {
+            responses.sort_by(|a, b| a.environment.cmp(&b.environment));
+            for (i, response1) in responses.iter().enumerate() {
+                for response2 in &responses[i + 1..] {
+                    let pair = DifferenceKey {
+                        route: route.to_string(),
+                        user_data: user_data.clone(),
+                        environments: format!(
+                            "{} ↔ {}",
+                            response1.environment, response2.environment
+                        ),
+                        difference: String::new(),
+                    };
+                    let keys = self
+                        .differences(
+                            route,
+                            response1,
+                            response2,
+                            &response1.environment,
+                            &response2.environment,
+                        )
+                        .iter()
+                        .flat_map(difference_descriptions)
+                        .map(|difference| DifferenceKey {
+                            difference,
+                            ..pair.clone()
+                        })
+                        .collect();
+                    differences.insert(pair, keys);
+                }
+            }
+        }
+        differences
+    }
+}
+
+/// Stable descriptions of what a difference covers, independent of the values involved
+fn difference_descriptions(difference: &Difference) -> Vec<String> {
+    let category = difference.category.name();
+    match difference.category {
+        DifferenceCategory::Headers => difference
+            .header_diff
+            .iter()
+            .flatten()
+            .map(|header| format!("{} {}", category, header.name))
+            .collect(),
+        DifferenceCategory::Body => match &difference.json_changes {
+            Some(changes) if !changes.is_empty() => changes
+                .iter()
+                .map(|change| format!("{} {}", category, change.path))
+                .collect(),
+            _ => vec![category.to_string()],
+        },
+        DifferenceCategory::Status | DifferenceCategory::Latency => vec![category.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UserData;
+    use crate::recording::ResponseRecorder;
+    use std::collections::HashMap;
+
+    fn record(recorder: &ResponseRecorder, env: &str, status: u16, body: &str) {
+        let user = UserData::new(HashMap::from([("userId".to_string(), "1".to_string())]));
+        let response = HttpResponse::new(
+            status,
+            HashMap::from([("Date".to_string(), "today".to_string())]),
+            body.to_string(),
+            String::new(),
+            String::new(),
+        );
+        recorder.record("users", 0, env, &user, &response);
+    }
+
+    fn config() -> HttpDiffConfig {
+        toml::from_str(
+            r#"
+[environments.prod]
+base_url = "https://prod.example.com"
+
+[environments.staging]
+base_url = "https://staging.example.com"
+
+[[routes]]
+name = "users"
+method = "GET"
+path = "/users/{userId}"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_baseline_normalizes_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_manager = OutputManager::new(dir.path());
+
+        let recorder = ResponseRecorder::new("run");
+        record(&recorder, "prod", 200, r#"{"b": 1,   "a": 2}"#);
+        let baseline = Baseline::from_recording("v1.4", &recorder.recording());
+        assert!(baseline.responses[0].headers.is_empty());
+
+        let path = baseline.save(&output_manager).unwrap();
+        assert!(path.ends_with(".http-diff/cache/baselines/v1.4.json"));
+        let loaded = Baseline::load(&output_manager, "v1.4").unwrap();
+        assert_eq!(loaded.responses, baseline.responses);
+
+        // A single-environment baseline is the reference for any environment
+        assert!(loaded
+            .find("users", "staging", &baseline.responses[0].user_data)
+            .is_some());
+        assert!(Baseline::path(&output_manager, "../v1").is_err());
+    }
+
+    #[test]
+    fn test_compare_reports_changes_and_classifies_differences() {
+        let before = ResponseRecorder::new("before");
+        record(&before, "prod", 200, r#"{"plan": "basic", "name": "Ann"}"#);
+        record(&before, "staging", 200, r#"{"plan": "pro", "name": "Ann"}"#);
+        let baseline = Baseline::from_recording("v1.4", &before.recording());
+
+        let after = ResponseRecorder::new("after");
+        record(&after, "prod", 200, r#"{"plan": "basic", "name": "Ann"}"#);
+        record(
+            &after,
+            "staging",
+            500,
+            r#"{"plan": "basic", "name": "Bob"}"#,
+        );
+
+        let report = BaselineComparator::new(&config()).compare(&baseline, &after.recording());
+        assert_eq!(report.compared, 2);
+        assert_eq!(report.unmatched, 0);
+        assert!(!report.is_unchanged());
+
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].environment, "staging");
+
+        let describe = |keys: &[DifferenceKey], environments: &str| -> Vec<String> {
+            keys.iter()
+                .filter(|k| k.environments == environments)
+                .map(|k| k.difference.clone())
+                .collect()
+        };
+        assert_eq!(
+            describe(&report.new, "baseline v1.4 ↔ staging"),
+            vec![
+                "Response Body $.name",
+                "Response Body $.plan",
+                "Status Code"
+            ]
+        );
+        assert!(describe(&report.new, "baseline v1.4 ↔ prod").is_empty());
+        assert_eq!(
+            describe(&report.new, "prod ↔ staging"),
+            vec!["Response Body $.name", "Status Code"]
+        );
+        assert_eq!(
+            describe(&report.fixed, "prod ↔ staging"),
+            vec!["Response Body $.plan"]
+        );
+        assert!(report.still_present.is_empty());
+    }
+
+    #[test]
+    fn test_compare_single_environment_run() {
+        let before = ResponseRecorder::new("before");
+        record(&before, "prod", 200, r#"{"plan": "basic"}"#);
+        let baseline = Baseline::from_recording("v1.4", &before.recording());
+
+        let after = ResponseRecorder::new("after");
+        record(&after, "prod", 503, r#"{"plan": "basic"}"#);
+
+        let report = BaselineComparator::new(&config()).compare(&baseline, &after.recording());
+        assert_eq!(report.compared, 1);
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.new.len(), 1);
+        assert_eq!(report.new[0].environments, "baseline v1.4 ↔ prod");
+        assert_eq!(report.new[0].difference, "Status Code");
+    }
+}
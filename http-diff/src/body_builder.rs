@@ -0,0 +1,487 @@
+//! Request body construction
+//!
+//! A route's body is declared in one of several ways: an inline `body` string,
+//! a `body_file`, a `json` table, `form` fields or `multipart` parts. This
+//! module resolves the declaration for a user data row, substituting
+//! placeholders in every text value, so the HTTP client and the curl
+//! generators send and reproduce exactly the same body.
+//!
+//! Files are read relative to the configuration file that declares the
+//! route, and placeholders in them are substituted like in inline values;
+//! binary files are sent unchanged.
+
+use crate::config::{MultipartPart, Route, UserData};
+use crate::error::{HttpDiffError, Result};
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use reqwest::Request;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Content type of URL-encoded form bodies
+pub const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Content type of JSON bodies built from a `json` table
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// A request body with placeholders substituted
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestBody {
+    /// Text from `body` or `body_file`, sent as-is
+    Text(String),
+    /// JSON serialized from a `json` table
+    Json(String),
+    /// URL-encoded form fields
+    Form(Vec<(String, String)>),
+    /// Parts of a multipart form
+    Multipart(Vec<ResolvedPart>),
+}
+
+/// A multipart part with placeholders substituted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedPart {
+    pub name: String,
+    pub content: PartContent,
+    pub content_type: Option<String>,
+}
+
+/// Contents of a multipart part
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartContent {
+    Text(String),
+    File {
+        /// Path of the file, resolved against the route's configuration file
+        path: String,
+        filename: String,
+        /// File contents, with placeholders substituted when the file is text
+        contents: Vec<u8>,
+        /// Whether substitution changed the contents, so that the file on
+        /// disk no longer matches what is sent
+        substituted: bool,
+    },
+}
+
+impl RequestBody {
+    /// The `Content-Type` header to send given the one configured in headers
+    ///
+    /// Returns `None` for multipart bodies, whose content type carries a
+    /// boundary chosen by the client.
+    pub fn content_type(&self, configured: Option<&str>) -> Option<String> {
+        match self {
+            RequestBody::Text(_) => configured.map(str::to_string),
+            RequestBody::Json(_) => Some(configured.unwrap_or(JSON_CONTENT_TYPE).to_string()),
+            RequestBody::Form(_) => Some(
+                configured
+                    .filter(|ct| ct.starts_with(FORM_CONTENT_TYPE))
+                    .unwrap_or(FORM_CONTENT_TYPE)
+                    .to_string(),
+            ),
+            RequestBody::Multipart(_) => None,
+        }
+    }
+
+    /// Replace the `Content-Type` of resolved headers with the one this body is sent as
+    ///
+    /// The configured header name is kept. Multipart bodies drop the header, as
+    /// their content type is set together with the boundary when sending.
+    pub fn apply_content_type(&self, headers: &mut HashMap<String, String>) {
+        let configured: Vec<String> = headers
+            .keys()
+            .filter(|name| name.eq_ignore_ascii_case(CONTENT_TYPE.as_str()))
+            .cloned()
+            .collect();
+        let configured = configured
+            .into_iter()
+            .filter_map(|name| headers.remove_entry(&name))
+            .min();
+        if let Some(content_type) =
+            self.content_type(configured.as_ref().map(|(_, value)| value.as_str()))
+        {
+            let name = configured.map_or_else(|| "Content-Type".to_string(), |(name, _)| name);
+            headers.insert(name, content_type);
+        }
+    }
+
+    /// Set this body on a request
+    ///
+    /// The request's headers are expected to have gone through
+    /// [`RequestBody::apply_content_type`]; multipart bodies set their content
+    /// type with the boundary here.
+    pub fn apply(self, request: &mut Request) -> Result<()> {
+        let body = match self {
+            RequestBody::Text(text) | RequestBody::Json(text) => reqwest::Body::from(text),
+            RequestBody::Form(fields) => reqwest::Body::from(encode_form(&fields)),
+            RequestBody::Multipart(parts) => {
+                let form = multipart_form(parts)?;
+                let content_type = format!("multipart/form-data; boundary={}", form.boundary());
+                set_content_type(request, &content_type)?;
+                reqwest::Body::wrap_stream(form.into_stream())
+            }
+        };
+        *request.body_mut() = Some(body);
+        Ok(())
+    }
+
+    /// Curl options reproducing this body, as option and unescaped argument pairs
+    pub fn curl_options(&self) -> Vec<(&'static str, String)> {
+        match self {
+            RequestBody::Text(text) | RequestBody::Json(text) => vec![("-d", text.clone())],
+            RequestBody::Form(fields) => fields
+                .iter()
+                .map(|(name, value)| ("--data-urlencode", format!("{}={}", name, value)))
+                .collect(),
+            RequestBody::Multipart(parts) => parts
+                .iter()
+                .map(|part| match &part.content {
+                    // --form-string keeps values starting with '@' or '<' literal
+                    PartContent::Text(value) => {
+                        ("--form-string", format!("{}={}", part.name, value))
+                    }
+                    PartContent::File {
+                        path,
+                        filename,
+                        contents,
+                        substituted,
+                    } => {
+                        let mut argument = if *substituted {
+                            // Sent inline, as curl would upload the file unsubstituted
+                            let text = String::from_utf8_lossy(contents)
+                                .replace('\\', "\\\\")
+                                .replace('"', "\\\"");
+                            format!("{}=\"{}\";filename={}", part.name, text, filename)
+                        } else {
+                            format!("{}=@{};filename={}", part.name, path, filename)
+                        };
+                        if let Some(content_type) = &part.content_type {
+                            argument.push_str(";type=");
+                            argument.push_str(content_type);
+                        }
+                        ("-F", argument)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Resolves the body declared by a route for a user data row
+pub struct BodyBuilder<'a> {
+    route: &'a Route,
+    user_data: &'a UserData,
+}
+
+impl<'a> BodyBuilder<'a> {
+    /// Create a new body builder
+    pub fn new(route: &'a Route, user_data: &'a UserData) -> Self {
+        Self { route, user_data }
+    }
+
+    /// Build the body, or `None` when the route declares no body
+    pub fn build(&self) -> Result<Option<RequestBody>> {
+        let route = self.route;
+        if let Some(body) = &route.body {
+            return Ok(Some(RequestBody::Text(self.substitute(body)?)));
+        }
+        if let Some(path) = &route.body_file {
+            let path = route.resolve_path(&self.substitute(path)?);
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                HttpDiffError::invalid_config(format!(
+                    "Cannot read body file '{}' of route '{}': {}",
+                    path.display(),
+                    route.name,
+                    e
+                ))
+            })?;
+            return Ok(Some(RequestBody::Text(self.substitute(&content)?)));
+        }
+        if let Some(json) = &route.json {
+            let json = self.substitute_json(json)?;
+            return Ok(Some(RequestBody::Json(json.to_string())));
+        }
+        if let Some(form) = &route.form {
+            let fields = form
+                .iter()
+                .map(|(name, value)| Ok((name.clone(), self.substitute(value)?)))
+                .collect::<Result<_>>()?;
+            return Ok(Some(RequestBody::Form(fields)));
+        }
+        if let Some(parts) = &route.multipart {
+            let parts = parts
+                .iter()
+                .map(|part| self.resolve_part(part))
+                .collect::<Result<_>>()?;
+            return Ok(Some(RequestBody::Multipart(parts)));
+        }
+        Ok(None)
+    }
+
+    fn substitute(&self, text: &str) -> Result<String> {
+        self.user_data.substitute_placeholders(text, false, false)
+    }
+
+    /// Substitute placeholders in every string of a JSON document, keys included
+    fn substitute_json(&self, value: &Value) -> Result<Value> {
+        Ok(match value {
+            Value::String(text) => Value::String(self.substitute(text)?),
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.substitute_json(item))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, item)| Ok((self.substitute(key)?, self.substitute_json(item)?)))
+                    .collect::<Result<_>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+
+    fn resolve_part(&self, part: &MultipartPart) -> Result<ResolvedPart> {
+        let content = match (&part.value, &part.file) {
+            (Some(value), None) => PartContent::Text(self.substitute(value)?),
+            (None, Some(file)) => {
+                let path = self.route.resolve_path(&self.substitute(file)?);
+                let filename = match &part.filename {
+                    Some(filename) => self.substitute(filename)?,
+                    None => path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.display().to_string()),
+                };
+                let bytes = std::fs::read(&path).map_err(|e| {
+                    HttpDiffError::invalid_config(format!(
+                        "Cannot read multipart file '{}' of route '{}': {}",
+                        path.display(),
+                        self.route.name,
+                        e
+                    ))
+                })?;
+                let (contents, substituted) = match std::str::from_utf8(&bytes) {
+                    Ok(text) => {
+                        let text = self.substitute(text)?;
+                        let substituted = text.as_bytes() != bytes.as_slice();
+                        (text.into_bytes(), substituted)
+                    }
+                    Err(_) => (bytes, false),
+                };
+                PartContent::File {
+                    path: path.display().to_string(),
+                    filename,
+                    contents,
+                    substituted,
+                }
+            }
+            _ => {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "Multipart part '{}' of route '{}' must set exactly one of 'value' and 'file'",
+                    part.name, self.route.name
+                )))
+            }
+        };
+        Ok(ResolvedPart {
+            name: self.substitute(&part.name)?,
+            content,
+            content_type: part
+                .content_type
+                .as_deref()
+                .map(|content_type| self.substitute(content_type))
+                .transpose()?,
+        })
+    }
+}
+
+fn encode_form(fields: &[(String, String)]) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(fields)
+        .finish()
+}
+
+fn multipart_form(parts: Vec<ResolvedPart>) -> Result<reqwest::multipart::Form> {
+    let mut form = reqwest::multipart::Form::new();
+    for part in parts {
+        let mut body = match part.content {
+            PartContent::Text(value) => reqwest::multipart::Part::text(value),
+            PartContent::File {
+                filename, contents, ..
+            } => reqwest::multipart::Part::bytes(contents).file_name(filename),
+        };
+        if let Some(content_type) = &part.content_type {
+            body = body.mime_str(content_type).map_err(|_| {
+                HttpDiffError::invalid_config(format!(
+                    "Invalid content type '{}' for multipart part '{}'",
+                    content_type, part.name
+                ))
+            })?;
+        }
+        form = form.part(part.name, body);
+    }
+    Ok(form)
+}
+
+fn set_content_type(request: &mut Request, content_type: &str) -> Result<()> {
+    let value = HeaderValue::from_str(content_type).map_err(|_| {
+        HttpDiffError::invalid_config(format!("Invalid content type '{}'", content_type))
+    })?;
+    request.headers_mut().insert(CONTENT_TYPE, value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn route(toml_route: &str) -> Route {
+        toml::from_str(&format!(
+            "name = \"upload\"\nmethod = \"POST\"\npath = \"/upload\"\n{}",
+            toml_route
+        ))
+        .unwrap()
+    }
+
+    fn user() -> UserData {
+        UserData::new(HashMap::from([
+            ("userId".to_string(), "42".to_string()),
+            ("key".to_string(), "owner".to_string()),
+        ]))
+    }
+
+    #[test]
+    fn test_json_table_is_substituted_and_serialized() {
+        let route = route(
+            "json = { id = \"{userId}\", count = 2, tags = [\"u-{userId}\"], nested = { \"{key}\" = true } }",
+        );
+        let body = BodyBuilder::new(&route, &user()).build().unwrap().unwrap();
+
+        let RequestBody::Json(json) = &body else {
+            panic!("expected a JSON body");
+        };
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"id": "42", "count": 2, "tags": ["u-42"], "nested": {"owner": true}})
+        );
+        assert_eq!(body.content_type(None).as_deref(), Some(JSON_CONTENT_TYPE));
+        assert_eq!(
+            body.content_type(Some("application/vnd.api+json"))
+                .as_deref(),
+            Some("application/vnd.api+json")
+        );
+    }
+
+    #[test]
+    fn test_body_file_is_read_relative_to_the_config_and_substituted() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("fixtures")).unwrap();
+        std::fs::write(
+            dir.path().join("fixtures/order.json"),
+            r#"{"user": "{userId}"}"#,
+        )
+        .unwrap();
+
+        let mut route = route("body_file = 'fixtures/order.json'");
+        route.base_dir = Some(dir.path().to_path_buf());
+        let body = BodyBuilder::new(&route, &user()).build().unwrap();
+        assert_eq!(
+            body,
+            Some(RequestBody::Text(r#"{"user": "42"}"#.to_string()))
+        );
+
+        let missing = self::route("body_file = 'missing/order.json'");
+        let err = BodyBuilder::new(&missing, &user()).build().unwrap_err();
+        assert!(err.to_string().contains("missing/order.json"));
+    }
+
+    #[test]
+    fn test_form_fields_override_json_content_type() {
+        let route = route("form = { user = \"{userId}\", note = \"a&b c\" }");
+        let body = BodyBuilder::new(&route, &user()).build().unwrap().unwrap();
+
+        assert_eq!(
+            body.content_type(Some("application/json")).as_deref(),
+            Some(FORM_CONTENT_TYPE)
+        );
+        let mut headers = HashMap::from([
+            ("content-type".to_string(), "application/json".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+        ]);
+        body.apply_content_type(&mut headers);
+        assert_eq!(headers["content-type"], FORM_CONTENT_TYPE);
+        assert_eq!(headers.len(), 2);
+        assert_eq!(
+            body.curl_options(),
+            vec![
+                ("--data-urlencode", "note=a&b c".to_string()),
+                ("--data-urlencode", "user=42".to_string()),
+            ]
+        );
+        let RequestBody::Form(fields) = body else {
+            panic!("expected a form body");
+        };
+        assert_eq!(encode_form(&fields), "note=a%26b+c&user=42");
+    }
+
+    #[test]
+    fn test_multipart_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("fixtures")).unwrap();
+        let image = [0x89, b'P', b'N', b'G', 0xff, 0x00];
+        std::fs::write(dir.path().join("fixtures/42.png"), image).unwrap();
+        std::fs::write(dir.path().join("fixtures/note.txt"), "for {key}").unwrap();
+
+        let mut route = route(
+            r#"
+[[multipart]]
+name = "owner"
+value = "@{userId}"
+
+[[multipart]]
+name = "avatar"
+file = "fixtures/{userId}.png"
+content_type = "image/png"
+
+[[multipart]]
+name = "note"
+file = "fixtures/note.txt"
+"#,
+        );
+        route.base_dir = Some(dir.path().to_path_buf());
+        let body = BodyBuilder::new(&route, &user()).build().unwrap().unwrap();
+
+        assert_eq!(body.content_type(Some("application/json")), None);
+        let avatar = dir.path().join("fixtures/42.png");
+        assert_eq!(
+            body.curl_options(),
+            vec![
+                ("--form-string", "owner=@42".to_string()),
+                (
+                    "-F",
+                    format!(
+                        "avatar=@{};filename=42.png;type=image/png",
+                        avatar.display()
+                    )
+                ),
+                // Text files are substituted, so curl sends them inline
+                ("-F", "note=\"for owner\";filename=note.txt".to_string()),
+            ]
+        );
+        let RequestBody::Multipart(parts) = &body else {
+            panic!("expected a multipart body");
+        };
+        assert!(matches!(
+            &parts[1].content,
+            PartContent::File { contents, substituted: false, .. } if contents == &image
+        ));
+
+        let missing = self::route("[[multipart]]\nname = \"a\"\nfile = \"missing.png\"");
+        let err = BodyBuilder::new(&missing, &user()).build().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot read multipart file 'missing.png'"));
+
+        let invalid = self::route("[[multipart]]\nname = \"empty\"");
+        assert!(BodyBuilder::new(&invalid, &user()).build().is_err());
+    }
+}
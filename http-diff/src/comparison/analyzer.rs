@@ -1,4 +1,7 @@
 use crate::comparison::content::{ContentNormalizer, HeaderNormalizer};
+use crate::comparison::json_diff::JsonDiffer;
+use crate::comparison::rules::ComparisonRules;
+use crate::config::LatencyThreshold;
 /// Response difference analysis and categorization
 use crate::types::{Difference, DifferenceCategory, HttpResponse};
 use std::collections::HashMap;
@@ -24,6 +27,7 @@ pub struct BodyDiff {
 pub struct DifferenceAnalyzer {
     content_normalizer: ContentNormalizer,
     header_normalizer: HeaderNormalizer,
+    json_differ: JsonDiffer,
     large_response_threshold: usize,
 }
 
@@ -37,6 +41,7 @@ impl DifferenceAnalyzer {
         Self {
             content_normalizer: ContentNormalizer::new(ignore_whitespace),
             header_normalizer: HeaderNormalizer::new(ignore_headers),
+            json_differ: JsonDiffer::new(),
             large_response_threshold,
         }
     }
@@ -50,6 +55,30 @@ impl DifferenceAnalyzer {
         env2: &str,
         compare_headers: bool,
     ) -> Vec<Difference> {
+        self.analyze_responses_with_rules(
+            response1,
+            response2,
+            env1,
+            env2,
+            compare_headers,
+            &ComparisonRules::default(),
+        )
+        .0
+    }
+
+    /// Analyze differences between two responses applying per-route comparison rules
+    ///
+    /// Returns the differences together with the ignore rules that excluded at least
+    /// one body field in either response.
+    pub fn analyze_responses_with_rules(
+        &self,
+        response1: &HttpResponse,
+        response2: &HttpResponse,
+        env1: &str,
+        env2: &str,
+        compare_headers: bool,
+        rules: &ComparisonRules,
+    ) -> (Vec<Difference>, Vec<String>) {
         let mut differences = Vec::new();
 
         // Compare status codes
@@ -63,6 +92,7 @@ impl DifferenceAnalyzer {
                 diff_output: None,
                 header_diff: None,
                 body_diff: None,
+                json_changes: None,
             });
         }
 
@@ -78,14 +108,31 @@ impl DifferenceAnalyzer {
         }
 
         // Compare bodies
-        if let Some(body_diff) = self.analyze_bodies(&response1.body, &response2.body) {
-            differences.push(Difference::with_body_diff(
-                "Body differences detected".to_string(),
-                body_diff,
-            ));
+        let (body_diff, ignored_fields) =
+            self.analyze_bodies(&response1.body, &response2.body, rules);
+        if let Some(body_diff) = body_diff {
+            // Structural changes are only available when both bodies are JSON
+            let json_changes = self
+                .json_differ
+                .diff_str(&body_diff.normalized_body1, &body_diff.normalized_body2);
+            let difference =
+                Difference::with_body_diff("Body differences detected".to_string(), body_diff);
+            differences.push(match json_changes {
+                Some(changes) => difference.with_json_changes(changes),
+                None => difference,
+            });
+        }
+
+        // Compare latency only when a regression threshold is configured
+        if let Some(threshold) = &rules.latency_threshold {
+            if let Some(difference) =
+                self.analyze_latency(response1, response2, env1, env2, threshold)
+            {
+                differences.push(difference);
+            }
         }
 
-        differences
+        (differences, ignored_fields)
     }
 
     /// Analyze header differences and return raw data
@@ -140,24 +187,79 @@ impl DifferenceAnalyzer {
         }
     }
 
-    /// Analyze body differences and return raw data
-    fn analyze_bodies(&self, body1: &str, body2: &str) -> Option<BodyDiff> {
-        let normalized_body1 = self.content_normalizer.normalize(body1, None);
-        let normalized_body2 = self.content_normalizer.normalize(body2, None);
-
-        if normalized_body1 == normalized_body2 {
-            return None;
+    /// Analyze body differences applying the comparison rules, returning raw data
+    /// along with the ignore rules that matched
+    fn analyze_bodies(
+        &self,
+        body1: &str,
+        body2: &str,
+        rules: &ComparisonRules,
+    ) -> (Option<BodyDiff>, Vec<String>) {
+        let (normalized1, normalized2) =
+            self.content_normalizer.normalize_pair(body1, body2, rules);
+
+        // Report matched rules in configuration order
+        let ignored_fields: Vec<String> = rules
+            .ignore_fields
+            .iter()
+            .filter(|rule| {
+                normalized1.ignored_fields.contains(rule)
+                    || normalized2.ignored_fields.contains(rule)
+            })
+            .cloned()
+            .collect();
+
+        if normalized1.text == normalized2.text {
+            return (None, ignored_fields);
         }
 
         let total_size = body1.len() + body2.len();
         let is_large_response = total_size > self.large_response_threshold;
 
-        Some(BodyDiff {
-            normalized_body1,
-            normalized_body2,
+        let body_diff = BodyDiff {
+            normalized_body1: normalized1.text,
+            normalized_body2: normalized2.text,
             is_large_response,
             total_size,
-        })
+        };
+
+        (Some(body_diff), ignored_fields)
+    }
+
+    /// Report a latency difference when the slower response exceeds the threshold
+    ///
+    /// Responses without timing data (e.g. synthetic or replayed ones) are skipped.
+    fn analyze_latency(
+        &self,
+        response1: &HttpResponse,
+        response2: &HttpResponse,
+        env1: &str,
+        env2: &str,
+        threshold: &LatencyThreshold,
+    ) -> Option<Difference> {
+        let total1 = response1.timing.as_ref()?.total_ms;
+        let total2 = response2.timing.as_ref()?.total_ms;
+        let (fast, slow) = (total1.min(total2), total1.max(total2));
+        let delta = slow - fast;
+        let ratio = slow as f64 / fast.max(1) as f64;
+
+        if delta == 0
+            || threshold.ratio.is_some_and(|min_ratio| ratio < min_ratio)
+            || threshold
+                .min_delta_ms
+                .is_some_and(|min_delta| delta < min_delta)
+        {
+            return None;
+        }
+
+        let slower_env = if total2 > total1 { env2 } else { env1 };
+        Some(Difference::new(
+            DifferenceCategory::Latency,
+            format!(
+                "Latency differs between {} and {}: {}ms vs {}ms ({} is {:.1}x slower)",
+                env1, env2, total1, total2, slower_env, ratio
+            ),
+        ))
     }
 
     /// Check if responses have identical content
@@ -204,6 +306,8 @@ mod tests {
             body: body.to_string(),
             url: "https://example.com/api/test".to_string(),
             curl_command: "curl 'https://example.com/api/test'".to_string(),
+            timing: None,
+            attempts: 1,
         }
     }
 
@@ -262,6 +366,15 @@ mod tests {
         assert!(!body_diff.is_large_response);
         assert!(body_diff.normalized_body1.contains("test"));
         assert!(body_diff.normalized_body2.contains("prod"));
+
+        // JSON bodies also carry structural changes
+        let changes = differences[0].json_changes.as_ref().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].pointer, "/data");
+        assert_eq!(
+            changes[0].summary(),
+            "`$.data` changed from `test` to `prod`"
+        );
     }
 
     #[test]
@@ -317,6 +430,126 @@ mod tests {
 
         assert!(body_diff.is_large_response);
         assert_eq!(body_diff.total_size, 120);
+        // Plain text bodies have no structural changes
+        assert!(differences[0].json_changes.is_none());
+    }
+
+    #[test]
+    fn test_ignored_body_fields() {
+        let analyzer =
+            DifferenceAnalyzer::new(vec![], true, crate::types::DEFAULT_LARGE_RESPONSE_THRESHOLD);
+        let rules = ComparisonRules::new().with_ignore_fields([
+            "$.requestId",
+            "$.items[*].generatedAt",
+            "$.unused",
+        ]);
+
+        let response1 = create_test_response(
+            200,
+            r#"{"requestId":"r-1","items":[{"name":"a","generatedAt":"t1"}]}"#,
+        );
+        let response2 = create_test_response(
+            200,
+            r#"{"requestId":"r-2","items":[{"name":"a","generatedAt":"t2"}]}"#,
+        );
+
+        let (differences, ignored_fields) = analyzer
+            .analyze_responses_with_rules(&response1, &response2, "test", "prod", false, &rules);
+
+        assert!(differences.is_empty());
+        assert_eq!(
+            ignored_fields,
+            vec!["$.requestId", "$.items[*].generatedAt"]
+        );
+
+        // Without rules the generated values make the bodies differ
+        assert_eq!(
+            analyzer
+                .analyze_responses(&response1, &response2, "test", "prod", false)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_compare_rules_suppress_noise() {
+        let analyzer =
+            DifferenceAnalyzer::new(vec![], true, crate::types::DEFAULT_LARGE_RESPONSE_THRESHOLD);
+        let compare: crate::config::CompareRules = toml::from_str(
+            r#"
+numeric_tolerance = { absolute = 0.01 }
+case_insensitive = ["$.currency"]
+unordered_arrays = ["$.tags"]
+"#,
+        )
+        .unwrap();
+        let rules = ComparisonRules::new().with_compare_rules(&compare);
+
+        let response1 = create_test_response(
+            200,
+            r#"{"price": 19.99, "currency": "usd", "tags": ["a", "b"], "stock": 3}"#,
+        );
+        let response2 = create_test_response(
+            200,
+            r#"{"price": 19.994, "currency": "USD", "tags": ["b", "a"], "stock": 4}"#,
+        );
+
+        let (differences, _) = analyzer
+            .analyze_responses_with_rules(&response1, &response2, "test", "prod", false, &rules);
+
+        // Only the real change survives in both the text and the structural diff
+        assert_eq!(differences.len(), 1);
+        let changes = differences[0].json_changes.as_ref().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].pointer, "/stock");
+    }
+
+    #[test]
+    fn test_latency_regression_threshold() {
+        use crate::types::ResponseTiming;
+
+        let analyzer = DifferenceAnalyzer::new(vec![], true, 50_000);
+        let timed = |total_ms| {
+            create_test_response(200, r#"{"status": "ok"}"#).with_timing(ResponseTiming {
+                total_ms,
+                ..Default::default()
+            })
+        };
+        let threshold = LatencyThreshold {
+            ratio: Some(2.0),
+            min_delta_ms: Some(100),
+        };
+        let rules = ComparisonRules::new().with_latency_threshold(threshold);
+
+        let (differences, _) = analyzer.analyze_responses_with_rules(
+            &timed(500),
+            &timed(100),
+            "staging",
+            "prod",
+            false,
+            &rules,
+        );
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].category, DifferenceCategory::Latency);
+        assert_eq!(
+            differences[0].description,
+            "Latency differs between staging and prod: 500ms vs 100ms (staging is 5.0x slower)"
+        );
+
+        // Below the minimum delta, a large ratio is still noise
+        let (differences, _) = analyzer.analyze_responses_with_rules(
+            &timed(30),
+            &timed(10),
+            "staging",
+            "prod",
+            false,
+            &rules,
+        );
+        assert!(differences.is_empty());
+
+        // Without a threshold latency is never compared
+        let differences = analyzer.analyze_responses(&timed(500), &timed(100), "a", "b", false);
+        assert!(differences.is_empty());
     }
 
     #[test]
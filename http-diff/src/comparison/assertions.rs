@@ -0,0 +1,489 @@
+//! Route assertions evaluated against each environment's response
+//!
+//! Cross-environment diffing cannot flag environments that are broken in the
+//! same way; assertions check every response against fixed expectations from
+//! the route's `assert` block instead.
+
+use crate::config::{
+    ExpectedStatus, HttpDiffConfig, JsonAssertion, Route, RouteAssertions, SchemaSource,
+};
+use crate::types::{AssertionResult, HttpResponse};
+use jsonpath_rust::{JsonPathFinder, JsonPathInst};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Evaluates the `assert` blocks of all routes of a configuration
+#[derive(Default)]
+pub struct AssertionEvaluator {
+    routes: HashMap<String, CompiledAssertions>,
+}
+
+/// Route assertions with regexes and schema compiled once up front
+struct CompiledAssertions {
+    assertions: RouteAssertions,
+    /// Compiled `matches` pattern per JSON assertion
+    matchers: Vec<Option<Result<Regex, String>>>,
+    schema: Option<Result<jsonschema::Validator, String>>,
+}
+
+impl AssertionEvaluator {
+    /// Compile the assertions declared on the routes of a configuration
+    ///
+    /// Schema files are resolved relative to the route's configuration file. Invalid
+    /// patterns and schemas are reported as failing assertions at evaluation time;
+    /// config validation reports them earlier.
+    pub fn from_config(config: &HttpDiffConfig) -> Self {
+        let routes = config
+            .routes
+            .iter()
+            .filter_map(|route| {
+                let assertions = route.assertions.as_ref()?;
+                Some((
+                    route.name.clone(),
+                    CompiledAssertions::compile(route, assertions),
+                ))
+            })
+            .collect();
+
+        Self { routes }
+    }
+
+    /// Check whether any route declares assertions
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Evaluate a route's assertions against the responses of each environment
+    ///
+    /// Results are ordered by environment name, then by declaration order.
+    pub fn evaluate(
+        &self,
+        route_name: &str,
+        responses: &HashMap<String, HttpResponse>,
+    ) -> Vec<AssertionResult> {
+        let Some(compiled) = self.routes.get(route_name) else {
+            return Vec::new();
+        };
+
+        let mut environments: Vec<&String> = responses.keys().collect();
+        environments.sort();
+
+        environments
+            .into_iter()
+            .filter(|env| {
+                compiled
+                    .assertions
+                    .environments
+                    .as_ref()
+                    .is_none_or(|envs| envs.contains(env))
+            })
+            .flat_map(|env| compiled.evaluate(env, &responses[env]))
+            .collect()
+    }
+}
+
+impl CompiledAssertions {
+    fn compile(route: &Route, assertions: &RouteAssertions) -> Self {
+        let matchers = assertions
+            .json
+            .iter()
+            .flatten()
+            .map(|check| {
+                check
+                    .matches
+                    .as_ref()
+                    .map(|pattern| Regex::new(pattern).map_err(|e| e.to_string()))
+            })
+            .collect();
+
+        let schema = assertions
+            .schema
+            .as_ref()
+            .map(|source| load_schema(route, source).and_then(|schema| compile_schema(&schema)));
+
+        Self {
+            assertions: assertions.clone(),
+            matchers,
+            schema,
+        }
+    }
+
+    fn evaluate(&self, env: &str, response: &HttpResponse) -> Vec<AssertionResult> {
+        let mut results = Vec::new();
+
+        if let Some(expected) = &self.assertions.status {
+            let assertion = match expected {
+                ExpectedStatus::Code(code) => format!("status is {}", code),
+                ExpectedStatus::AnyOf(codes) => format!("status in {:?}", codes),
+            };
+            results.push(if expected.accepts(response.status) {
+                AssertionResult::passed(env, assertion)
+            } else {
+                AssertionResult::failed(env, assertion, format!("got {}", response.status))
+            });
+        }
+
+        for header in self.assertions.headers.iter().flatten() {
+            let assertion = format!("header '{}' present", header);
+            let present = response
+                .headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case(header));
+            results.push(if present {
+                AssertionResult::passed(env, assertion)
+            } else {
+                AssertionResult::failed(env, assertion, "header missing".to_string())
+            });
+        }
+
+        if let Some(max_latency) = self.assertions.max_latency_ms {
+            // Responses without timing (e.g. synthetic ones) cannot be checked
+            if let Some(timing) = &response.timing {
+                let assertion = format!("latency <= {}ms", max_latency);
+                results.push(if timing.total_ms <= max_latency {
+                    AssertionResult::passed(env, assertion)
+                } else {
+                    AssertionResult::failed(env, assertion, format!("took {}ms", timing.total_ms))
+                });
+            }
+        }
+
+        let needs_body =
+            self.assertions.json.as_ref().is_some_and(|c| !c.is_empty()) || self.schema.is_some();
+        if !needs_body {
+            return results;
+        }
+        let body = serde_json::from_str::<Value>(&response.body).map_err(|e| e.to_string());
+
+        for (check, matcher) in self.assertions.json.iter().flatten().zip(&self.matchers) {
+            let assertion = describe_json_assertion(check);
+            let outcome = match &body {
+                Ok(body) => evaluate_json_assertion(check, matcher.as_ref(), body),
+                Err(e) => Err(format!("body is not valid JSON: {}", e)),
+            };
+            results.push(match outcome {
+                Ok(()) => AssertionResult::passed(env, assertion),
+                Err(message) => AssertionResult::failed(env, assertion, message),
+            });
+        }
+
+        if let Some(schema) = &self.schema {
+            let assertion = "body matches JSON Schema".to_string();
+            let outcome = match (schema, &body) {
+                (Err(e), _) => Err(e.clone()),
+                (_, Err(e)) => Err(format!("body is not valid JSON: {}", e)),
+                (Ok(validator), Ok(body)) => validate_schema(validator, body),
+            };
+            results.push(match outcome {
+                Ok(()) => AssertionResult::passed(env, assertion),
+                Err(message) => AssertionResult::failed(env, assertion, message),
+            });
+        }
+
+        results
+    }
+}
+
+/// Load a JSON Schema of a route from a file or an inline definition
+///
+/// Files are resolved relative to the configuration file that defines the route.
+pub fn load_schema(route: &Route, source: &SchemaSource) -> std::result::Result<Value, String> {
+    match source {
+        SchemaSource::Inline(schema) => Ok(schema.clone()),
+        SchemaSource::File(path) => {
+            let resolved = route.resolve_path(path);
+            let content = std::fs::read_to_string(&resolved)
+                .map_err(|e| format!("cannot read schema file '{}': {}", resolved.display(), e))?;
+            serde_json::from_str(&content).map_err(|e| {
+                format!(
+                    "schema file '{}' is not valid JSON: {}",
+                    resolved.display(),
+                    e
+                )
+            })
+        }
+    }
+}
+
+/// Compile a JSON Schema into a validator
+pub fn compile_schema(schema: &Value) -> std::result::Result<jsonschema::Validator, String> {
+    jsonschema::validator_for(schema).map_err(|e| format!("invalid JSON Schema: {}", e))
+}
+
+fn validate_schema(
+    validator: &jsonschema::Validator,
+    body: &Value,
+) -> std::result::Result<(), String> {
+    let errors: Vec<String> = validator
+        .iter_errors(body)
+        .take(3)
+        .map(|error| {
+            let location = error.instance_path().to_string();
+            if location.is_empty() {
+                error.to_string()
+            } else {
+                format!("{}: {}", location, error)
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn describe_json_assertion(check: &JsonAssertion) -> String {
+    if let Some(expected) = &check.equals {
+        format!("{} equals {}", check.path, expected)
+    } else if let Some(pattern) = &check.matches {
+        format!("{} matches /{}/", check.path, pattern)
+    } else if check.exists == Some(false) {
+        format!("{} does not exist", check.path)
+    } else {
+        format!("{} exists", check.path)
+    }
+}
+
+fn evaluate_json_assertion(
+    check: &JsonAssertion,
+    matcher: Option<&std::result::Result<Regex, String>>,
+    body: &Value,
+) -> std::result::Result<(), String> {
+    let path = JsonPathInst::from_str(&check.path)
+        .map_err(|e| format!("invalid JSONPath '{}': {}", check.path, e))?;
+    let found = match JsonPathFinder::new(Box::new(body.clone()), Box::new(path)).find() {
+        Value::Array(values) => values,
+        _ => Vec::new(),
+    };
+
+    if check.exists == Some(false) {
+        return match found.first() {
+            None => Ok(()),
+            Some(value) => Err(format!("found {}", value)),
+        };
+    }
+    if found.is_empty() {
+        return Err("no value at path".to_string());
+    }
+
+    // Operators must hold for every matched value
+    if let Some(expected) = &check.equals {
+        if let Some(value) = found.iter().find(|value| !json_equals(value, expected)) {
+            return Err(format!("found {}", value));
+        }
+    }
+    if let Some(matcher) = matcher {
+        let regex = matcher
+            .as_ref()
+            .map_err(|e| format!("invalid pattern: {}", e))?;
+        for value in &found {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            if !regex.is_match(&text) {
+                return Err(format!("found {}", value));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare JSON values, treating integers and floats with the same value as equal
+fn json_equals(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResponseTiming;
+
+    fn evaluate(assert_toml: &str, response: HttpResponse) -> Vec<AssertionResult> {
+        let config: HttpDiffConfig = toml::from_str(&format!(
+            r#"
+[environments.prod]
+base_url = "https://prod.example.com"
+
+[[routes]]
+name = "user"
+method = "GET"
+path = "/user"
+
+[routes.assert]
+{}
+"#,
+            assert_toml
+        ))
+        .unwrap();
+
+        let mut responses = HashMap::new();
+        responses.insert("prod".to_string(), response);
+        AssertionEvaluator::from_config(&config).evaluate("user", &responses)
+    }
+
+    fn response(status: u16, body: &str) -> HttpResponse {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        HttpResponse::new(
+            status,
+            headers,
+            body.to_string(),
+            "https://prod.example.com/user".to_string(),
+            "curl".to_string(),
+        )
+    }
+
+    fn failures(results: &[AssertionResult]) -> Vec<(String, String)> {
+        results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| (r.assertion.clone(), r.message.clone().unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_status_headers_and_latency() {
+        let results = evaluate(
+            r#"
+status = [200, 201]
+headers = ["content-type", "x-request-id"]
+max_latency_ms = 100
+"#,
+            response(500, "{}").with_timing(ResponseTiming {
+                total_ms: 250,
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            failures(&results),
+            vec![
+                ("status in [200, 201]".to_string(), "got 500".to_string()),
+                (
+                    "header 'x-request-id' present".to_string(),
+                    "header missing".to_string()
+                ),
+                ("latency <= 100ms".to_string(), "took 250ms".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_assertions() {
+        let results = evaluate(
+            r#"
+json = [
+    { path = "$.plan", equals = "premium" },
+    { path = "$.items[*].id", matches = '^\d+$' },
+    { path = "$.price", equals = 10 },
+    { path = "$.deletedAt", exists = false },
+    { path = "$.owner" },
+]
+"#,
+            response(
+                200,
+                r#"{"plan": "basic", "items": [{"id": 1}, {"id": "x2"}], "price": 10.0}"#,
+            ),
+        );
+
+        assert_eq!(
+            failures(&results),
+            vec![
+                (
+                    r#"$.plan equals "premium""#.to_string(),
+                    r#"found "basic""#.to_string()
+                ),
+                (
+                    r"$.items[*].id matches /^\d+$/".to_string(),
+                    r#"found "x2""#.to_string()
+                ),
+                ("$.owner exists".to_string(), "no value at path".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_schema() {
+        let assert_toml = r#"
+[routes.assert.schema]
+type = "object"
+required = ["id"]
+properties = { id = { type = "integer" } }
+"#;
+
+        let results = evaluate(assert_toml, response(200, r#"{"id": 7}"#));
+        assert!(results.iter().all(|r| r.passed));
+
+        let results = evaluate(assert_toml, response(200, r#"{"id": "7"}"#));
+        let failed = failures(&results);
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].1.starts_with("/id: "));
+
+        let results = evaluate(assert_toml, response(200, "not json"));
+        assert!(failures(&results)[0]
+            .1
+            .starts_with("body is not valid JSON"));
+    }
+
+    #[test]
+    fn test_schema_file_is_resolved_relative_to_the_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join("api");
+        std::fs::create_dir_all(config_dir.join("schemas")).unwrap();
+        std::fs::write(
+            config_dir.join("schemas/user.json"),
+            r#"{"type": "object", "required": ["id"]}"#,
+        )
+        .unwrap();
+        let config_path = config_dir.join("http-diff.toml");
+        std::fs::write(
+            &config_path,
+            r#"
+[environments.prod]
+base_url = "https://prod.example.com"
+
+[[routes]]
+name = "user"
+method = "GET"
+path = "/user"
+
+[routes.assert]
+schema = "schemas/user.json"
+"#,
+        )
+        .unwrap();
+
+        // The working directory is not the configuration directory
+        let config = HttpDiffConfig::load_from_file(&config_path).unwrap();
+        let evaluator = AssertionEvaluator::from_config(&config);
+        let evaluate = |body: &str| {
+            let responses = HashMap::from([("prod".to_string(), response(200, body))]);
+            failures(&evaluator.evaluate("user", &responses))
+        };
+
+        assert!(evaluate(r#"{"id": 7}"#).is_empty());
+        assert_eq!(evaluate("{}")[0].0, "body matches JSON Schema");
+    }
+
+    #[test]
+    fn test_environment_filter() {
+        let results = evaluate(
+            r#"
+status = 200
+environments = ["staging"]
+"#,
+            response(500, "{}"),
+        );
+        assert!(results.is_empty());
+    }
+}
@@ -1,5 +1,19 @@
 /// Content normalization and type detection for HTTP response comparison
+use crate::comparison::rules::{ComparisonRules, MaskRule};
+use crate::config::NumericTolerance;
+use jsonpath_rust::{JsonPathFinder, JsonPathInst};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Normalized content together with the ignore rules that matched it
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedContent {
+    /// Normalized text ready for diffing
+    pub text: String,
+    /// Ignore rules (JSONPath expressions) that removed at least one field
+    pub ignored_fields: Vec<String>,
+}
 
 /// Content normalizer with type-aware processing
 pub struct ContentNormalizer {
@@ -29,6 +43,132 @@ impl ContentNormalizer {
         }
     }
 
+    /// Normalize content after stripping JSON fields matched by the given JSONPath expressions
+    ///
+    /// Non-JSON content is normalized as usual and reports no ignored fields.
+    pub fn normalize_ignoring(
+        &self,
+        text: &str,
+        content_type: Option<&str>,
+        ignore_fields: &[String],
+    ) -> NormalizedContent {
+        if ignore_fields.is_empty() {
+            return NormalizedContent {
+                text: self.normalize(text, content_type),
+                ignored_fields: Vec::new(),
+            };
+        }
+
+        let Ok(mut json_value) = serde_json::from_str::<Value>(text.trim()) else {
+            return NormalizedContent {
+                text: self.normalize(text, content_type),
+                ignored_fields: Vec::new(),
+            };
+        };
+
+        let ignored_fields = Self::strip_json_fields(&mut json_value, ignore_fields);
+        self.render_json(&json_value, text, ignored_fields)
+    }
+
+    /// Normalize a pair of bodies applying ignore and value-normalization rules
+    ///
+    /// Value rules need both documents: numeric tolerance copies the first
+    /// body's number into the second when the two are within tolerance, so the
+    /// remaining text and structural diffs only show real changes. Non-JSON
+    /// content is normalized as usual.
+    pub fn normalize_pair(
+        &self,
+        text1: &str,
+        text2: &str,
+        rules: &ComparisonRules,
+    ) -> (NormalizedContent, NormalizedContent) {
+        if !rules.has_value_rules() {
+            return (
+                self.normalize_ignoring(text1, None, &rules.ignore_fields),
+                self.normalize_ignoring(text2, None, &rules.ignore_fields),
+            );
+        }
+
+        let parsed = (
+            serde_json::from_str::<Value>(text1.trim()),
+            serde_json::from_str::<Value>(text2.trim()),
+        );
+        let (Ok(mut value1), Ok(mut value2)) = parsed else {
+            return (
+                self.normalize_ignoring(text1, None, &rules.ignore_fields),
+                self.normalize_ignoring(text2, None, &rules.ignore_fields),
+            );
+        };
+
+        let ignored1 = Self::strip_json_fields(&mut value1, &rules.ignore_fields);
+        let ignored2 = Self::strip_json_fields(&mut value2, &rules.ignore_fields);
+
+        for value in [&mut value1, &mut value2] {
+            apply_masks(value, &rules.masks);
+            for path in find_all_paths(value, &rules.case_insensitive) {
+                if let Some(target) = value_at_mut(value, &path) {
+                    lowercase_strings(target);
+                }
+            }
+            sort_unordered_arrays(value, &rules.unordered_arrays);
+        }
+
+        if let Some(tolerance) = &rules.numeric_tolerance {
+            let scope = tolerance
+                .paths
+                .as_ref()
+                .map(|paths| find_all_paths(&value1, paths));
+            let mut path = Vec::new();
+            reconcile_numbers(&value1, &mut value2, &mut path, tolerance, scope.as_deref());
+        }
+
+        (
+            self.render_json(&value1, text1, ignored1),
+            self.render_json(&value2, text2, ignored2),
+        )
+    }
+
+    fn render_json(
+        &self,
+        value: &Value,
+        original: &str,
+        ignored_fields: Vec<String>,
+    ) -> NormalizedContent {
+        let rendered = if self.ignore_whitespace {
+            serde_json::to_string_pretty(value)
+        } else {
+            serde_json::to_string(value)
+        };
+
+        NormalizedContent {
+            text: rendered.unwrap_or_else(|_| original.to_string()),
+            ignored_fields,
+        }
+    }
+
+    /// Remove all values matched by the JSONPath expressions, returning the expressions that matched
+    fn strip_json_fields(json_value: &mut Value, ignore_fields: &[String]) -> Vec<String> {
+        let mut matched_rules = Vec::new();
+        let mut paths: Vec<Vec<PathSegment>> = Vec::new();
+
+        for rule in ignore_fields {
+            let found = find_paths(json_value, rule);
+            if !found.is_empty() {
+                matched_rules.push(rule.clone());
+                paths.extend(found);
+            }
+        }
+
+        // Remove deepest and highest-indexed values first so remaining paths stay valid
+        paths.sort();
+        paths.dedup();
+        for path in paths.iter().rev() {
+            remove_path(json_value, path);
+        }
+
+        matched_rules
+    }
+
     /// Detect content type from text content
     pub fn detect_content_type(&self, text: &str) -> &str {
         let trimmed = text.trim();
@@ -91,6 +231,204 @@ impl ContentNormalizer {
     }
 }
 
+/// A single step in a concrete JSON path
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse a concrete path as produced by `JsonPathFinder::find_as_path`,
+/// e.g. `$.['items'][0].['id']`
+fn parse_found_path(path: &str) -> Option<Vec<PathSegment>> {
+    let mut rest = path.strip_prefix('$')?;
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix(".['") {
+            let end = after.find("']")?;
+            segments.push(PathSegment::Key(after[..end].to_string()));
+            rest = &after[end + 2..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            segments.push(PathSegment::Index(after[..end].parse().ok()?));
+            rest = &after[end + 1..];
+        } else {
+            return None;
+        }
+    }
+
+    Some(segments)
+}
+
+/// Find the concrete, non-root paths matched by a JSONPath expression
+fn find_paths(value: &Value, expression: &str) -> Vec<Vec<PathSegment>> {
+    // Invalid expressions are reported by config validation; skip them here
+    let Ok(path) = JsonPathInst::from_str(expression) else {
+        return Vec::new();
+    };
+
+    let finder = JsonPathFinder::new(Box::new(value.clone()), Box::new(path));
+    match finder.find_as_path() {
+        Value::Array(found) => found
+            .iter()
+            .filter_map(|p| p.as_str().and_then(parse_found_path))
+            .filter(|segments| !segments.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Find the concrete paths matched by any of the JSONPath expressions
+fn find_all_paths(value: &Value, expressions: &[String]) -> Vec<Vec<PathSegment>> {
+    let mut paths: Vec<Vec<PathSegment>> = expressions
+        .iter()
+        .flat_map(|expression| find_paths(value, expression))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+fn value_at_mut<'a>(value: &'a mut Value, path: &[PathSegment]) -> Option<&'a mut Value> {
+    path.iter()
+        .try_fold(value, |current, segment| match (segment, current) {
+            (PathSegment::Key(key), Value::Object(map)) => map.get_mut(key),
+            (PathSegment::Index(index), Value::Array(items)) => items.get_mut(*index),
+            _ => None,
+        })
+}
+
+/// Apply a function to every string value under `value`
+fn for_each_string(value: &mut Value, f: &mut dyn FnMut(&mut String)) {
+    match value {
+        Value::String(s) => f(s),
+        Value::Array(items) => items.iter_mut().for_each(|item| for_each_string(item, f)),
+        Value::Object(map) => map.values_mut().for_each(|item| for_each_string(item, f)),
+        _ => {}
+    }
+}
+
+fn lowercase_strings(value: &mut Value) {
+    for_each_string(value, &mut |s| *s = s.to_lowercase());
+}
+
+/// Replace regex matches in string values, limited to the mask's paths when set
+fn apply_masks(value: &mut Value, masks: &[MaskRule]) {
+    for mask in masks {
+        let mut replace = |s: &mut String| {
+            if mask.regex.is_match(s) {
+                *s = mask
+                    .regex
+                    .replace_all(s, mask.replacement.as_str())
+                    .into_owned();
+            }
+        };
+
+        if mask.paths.is_empty() {
+            for_each_string(value, &mut replace);
+        } else {
+            for path in find_all_paths(value, &mask.paths) {
+                if let Some(target) = value_at_mut(value, &path) {
+                    for_each_string(target, &mut replace);
+                }
+            }
+        }
+    }
+}
+
+/// Sort matched arrays into a canonical order, innermost arrays first
+fn sort_unordered_arrays(value: &mut Value, expressions: &[String]) {
+    let mut paths = find_all_paths(value, expressions);
+    paths.sort_by_key(|path| std::cmp::Reverse(path.len()));
+
+    for path in paths {
+        if let Some(Value::Array(items)) = value_at_mut(value, &path) {
+            items.sort_by_cached_key(|item| item.to_string());
+        }
+    }
+}
+
+/// Check whether two numbers are equal within the tolerance
+fn within_tolerance(a: f64, b: f64, tolerance: &NumericTolerance) -> bool {
+    let delta = (a - b).abs();
+    tolerance.absolute.is_some_and(|abs| delta <= abs)
+        || tolerance
+            .relative
+            .is_some_and(|rel| delta <= rel * a.abs().max(b.abs()))
+}
+
+/// Walk both documents in parallel and copy numbers from the first document
+/// into the second where they are equal within tolerance
+///
+/// When `scope` is set, only numbers at or below one of the scoped paths are reconciled.
+fn reconcile_numbers(
+    value1: &Value,
+    value2: &mut Value,
+    path: &mut Vec<PathSegment>,
+    tolerance: &NumericTolerance,
+    scope: Option<&[Vec<PathSegment>]>,
+) {
+    match (value1, value2) {
+        (Value::Number(n1), value2 @ Value::Number(_)) => {
+            let in_scope = scope.is_none_or(|paths| paths.iter().any(|p| path.starts_with(p)));
+            let (Some(a), Some(b)) = (n1.as_f64(), value2.as_f64()) else {
+                return;
+            };
+            if in_scope && a != b && within_tolerance(a, b, tolerance) {
+                *value2 = value1.clone();
+            }
+        }
+        (Value::Object(map1), Value::Object(map2)) => {
+            for (key, child1) in map1 {
+                if let Some(child2) = map2.get_mut(key) {
+                    path.push(PathSegment::Key(key.clone()));
+                    reconcile_numbers(child1, child2, path, tolerance, scope);
+                    path.pop();
+                }
+            }
+        }
+        (Value::Array(items1), Value::Array(items2)) => {
+            for (index, (child1, child2)) in items1.iter().zip(items2.iter_mut()).enumerate() {
+                path.push(PathSegment::Index(index));
+                reconcile_numbers(child1, child2, path, tolerance, scope);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Remove the value at the given path, ignoring paths that no longer exist
+fn remove_path(value: &mut Value, path: &[PathSegment]) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+
+    let mut current = value;
+    for segment in parents {
+        let next = match (segment, current) {
+            (PathSegment::Key(key), Value::Object(map)) => map.get_mut(key),
+            (PathSegment::Index(index), Value::Array(items)) => items.get_mut(*index),
+            _ => None,
+        };
+        match next {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+
+    match (last, current) {
+        (PathSegment::Key(key), Value::Object(map)) => {
+            map.remove(key);
+        }
+        (PathSegment::Index(index), Value::Array(items)) if *index < items.len() => {
+            items.remove(*index);
+        }
+        _ => {}
+    }
+}
+
 /// Header normalizer for case-insensitive comparison
 pub struct HeaderNormalizer {
     ignore_headers: Vec<String>,
@@ -198,6 +536,165 @@ mod tests {
         assert_eq!(normalized2, text2);
     }
 
+    #[test]
+    fn test_normalize_ignoring_json_fields() {
+        let normalizer = ContentNormalizer::new(true);
+        let ignore_fields = vec![
+            "$.meta.generatedAt".to_string(),
+            "$.items[*].id".to_string(),
+            "$.missing".to_string(),
+        ];
+
+        let body1 = r#"{"meta":{"generatedAt":"2024-01-01","page":1},"items":[{"id":"a1","name":"x"},{"id":"a2","name":"y"}]}"#;
+        let body2 = r#"{"meta":{"generatedAt":"2024-02-02","page":1},"items":[{"id":"b1","name":"x"},{"id":"b2","name":"y"}]}"#;
+
+        let normalized1 = normalizer.normalize_ignoring(body1, None, &ignore_fields);
+        let normalized2 = normalizer.normalize_ignoring(body2, None, &ignore_fields);
+
+        assert_eq!(normalized1.text, normalized2.text);
+        assert!(!normalized1.text.contains("generatedAt"));
+        assert!(!normalized1.text.contains("\"id\""));
+        assert!(normalized1.text.contains("\"page\""));
+        assert_eq!(
+            normalized1.ignored_fields,
+            vec![
+                "$.meta.generatedAt".to_string(),
+                "$.items[*].id".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_ignoring_array_elements_and_non_json() {
+        let normalizer = ContentNormalizer::new(true);
+
+        let ignore_fields = vec!["$.tags[0]".to_string(), "$.tags[2]".to_string()];
+        let normalized =
+            normalizer.normalize_ignoring(r#"{"tags":["a","b","c","d"]}"#, None, &ignore_fields);
+        let value: Value = serde_json::from_str(&normalized.text).unwrap();
+        assert_eq!(value["tags"], serde_json::json!(["b", "d"]));
+
+        let normalized = normalizer.normalize_ignoring("plain text", None, &ignore_fields);
+        assert_eq!(normalized.text, "plain text");
+        assert!(normalized.ignored_fields.is_empty());
+    }
+
+    #[test]
+    fn test_parse_found_path() {
+        assert_eq!(
+            parse_found_path("$.['items'][10].['id']"),
+            Some(vec![
+                PathSegment::Key("items".to_string()),
+                PathSegment::Index(10),
+                PathSegment::Key("id".to_string()),
+            ])
+        );
+        assert_eq!(parse_found_path("$"), Some(vec![]));
+        assert_eq!(parse_found_path("items"), None);
+    }
+
+    fn compare_rules(toml_rules: &str) -> ComparisonRules {
+        let compare: crate::config::CompareRules = toml::from_str(toml_rules).unwrap();
+        ComparisonRules::new().with_compare_rules(&compare)
+    }
+
+    fn normalized_pair_equal(rules: &ComparisonRules, body1: &str, body2: &str) -> bool {
+        let (normalized1, normalized2) =
+            ContentNormalizer::new(true).normalize_pair(body1, body2, rules);
+        normalized1.text == normalized2.text
+    }
+
+    #[test]
+    fn test_numeric_tolerance() {
+        let rules = compare_rules("numeric_tolerance = { absolute = 0.01 }");
+        assert!(normalized_pair_equal(
+            &rules,
+            r#"{"price": 9.99}"#,
+            r#"{"price": 9.991}"#
+        ));
+        assert!(!normalized_pair_equal(
+            &rules,
+            r#"{"price": 9.99}"#,
+            r#"{"price": 10.5}"#
+        ));
+
+        let rules = compare_rules("numeric_tolerance = { relative = 0.01 }");
+        assert!(normalized_pair_equal(
+            &rules,
+            r#"[1000, 5]"#,
+            r#"[1009, 5]"#
+        ));
+        assert!(!normalized_pair_equal(
+            &rules,
+            r#"[1000, 5]"#,
+            r#"[1000, 5.1]"#
+        ));
+
+        // Scoped tolerance leaves other numbers exact
+        let rules =
+            compare_rules(r#"numeric_tolerance = { absolute = 1, paths = ["$.items[*].price"] }"#);
+        assert!(normalized_pair_equal(
+            &rules,
+            r#"{"items": [{"price": 10, "qty": 1}]}"#,
+            r#"{"items": [{"price": 10.5, "qty": 1}]}"#
+        ));
+        assert!(!normalized_pair_equal(
+            &rules,
+            r#"{"items": [{"price": 10, "qty": 1}]}"#,
+            r#"{"items": [{"price": 10, "qty": 1.5}]}"#
+        ));
+    }
+
+    #[test]
+    fn test_case_insensitive_paths() {
+        let rules = compare_rules(r#"case_insensitive = ["$.currency", "$.tags"]"#);
+        assert!(normalized_pair_equal(
+            &rules,
+            r#"{"currency": "usd", "tags": ["A", "b"]}"#,
+            r#"{"currency": "USD", "tags": ["a", "B"]}"#
+        ));
+        assert!(!normalized_pair_equal(
+            &rules,
+            r#"{"currency": "usd", "name": "x"}"#,
+            r#"{"currency": "USD", "name": "X"}"#
+        ));
+    }
+
+    #[test]
+    fn test_unordered_arrays() {
+        let rules = compare_rules(r#"unordered_arrays = ["$.tags", "$.items"]"#);
+        assert!(normalized_pair_equal(
+            &rules,
+            r#"{"tags": ["a", "b", "c"], "items": [{"id": 1}, {"id": 2}]}"#,
+            r#"{"tags": ["c", "a", "b"], "items": [{"id": 2}, {"id": 1}]}"#
+        ));
+        assert!(!normalized_pair_equal(
+            &rules,
+            r#"{"tags": ["a", "b"], "order": [1, 2]}"#,
+            r#"{"tags": ["b", "a"], "order": [2, 1]}"#
+        ));
+    }
+
+    #[test]
+    fn test_regex_masks() {
+        let rules = compare_rules(
+            r#"
+mask = [
+    { pattern = '\d{4}-\d{2}-\d{2}T[0-9:.]+Z', replacement = "<timestamp>" },
+    { pattern = "^[0-9a-f-]{36}$", paths = ["$.requestId"] },
+]
+"#,
+        );
+        let (normalized1, normalized2) = ContentNormalizer::new(true).normalize_pair(
+            r#"{"at": "created 2024-01-01T10:00:00Z", "requestId": "0b6f8c4e-1d2a-4e4b-9a55-2f1f0e6b7c11"}"#,
+            r#"{"at": "created 2024-02-03T11:30:00.5Z", "requestId": "7d1a2b3c-4e5f-4a6b-8c7d-9e0f1a2b3c4d"}"#,
+            &rules,
+        );
+        assert_eq!(normalized1.text, normalized2.text);
+        assert!(normalized1.text.contains("created <timestamp>"));
+        assert!(normalized1.text.contains(r#""requestId": "***""#));
+    }
+
     #[test]
     fn test_header_normalization() {
         let ignore_headers = vec!["date".to_string(), "x-request-id".to_string()];
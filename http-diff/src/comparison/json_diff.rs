@@ -0,0 +1,501 @@
+//! Structural JSON diff producing path-addressed changes
+//!
+//! Complements the line-based text diff with a semantic list of changes keyed
+//! by JSON pointer (RFC 6901), so reviewers see "`$.user.plan` changed from
+//! `basic` to `premium`" instead of a wall of lines.
+//!
+//! Arrays are matched element-wise: arrays of objects sharing a unique identity
+//! key (`id`, `uuid`, ...) are matched by that key, other arrays are aligned on
+//! their longest common subsequence with the remaining elements paired by position.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Default maximum number of changes collected for a single body comparison
+pub const DEFAULT_MAX_JSON_CHANGES: usize = 1000;
+
+/// Object keys used to match array elements by identity, in order of preference
+const IDENTITY_KEYS: &[&str] = &["id", "_id", "uuid", "key", "name"];
+
+/// Upper bound on the LCS table size before arrays are compared by position only
+const MAX_LCS_CELLS: usize = 1_000_000;
+
+/// Kind of a structural change between two JSON documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonChangeKind {
+    /// Value only present in the second document
+    Added,
+    /// Value only present in the first document
+    Removed,
+    /// Value of the same JSON type differs
+    Changed,
+    /// Value changed its JSON type (e.g. number to string)
+    TypeChanged,
+}
+
+impl JsonChangeKind {
+    /// Get a short symbol for text output
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            JsonChangeKind::Added => "+",
+            JsonChangeKind::Removed => "-",
+            JsonChangeKind::Changed => "~",
+            JsonChangeKind::TypeChanged => "!",
+        }
+    }
+}
+
+/// A single structural change addressed by JSON pointer
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JsonChange {
+    /// JSON pointer of the changed value (RFC 6901, empty for the root)
+    pub pointer: String,
+    /// The same location as a JSONPath expression (e.g. `$.items[0].id`)
+    pub path: String,
+    pub kind: JsonChangeKind,
+    /// Value in the first environment
+    pub old_value: Option<Value>,
+    /// Value in the second environment
+    pub new_value: Option<Value>,
+}
+
+impl JsonChange {
+    /// Human-readable one-line summary of the change
+    pub fn summary(&self) -> String {
+        let old = self
+            .old_value
+            .as_ref()
+            .map(format_value)
+            .unwrap_or_default();
+        let new = self
+            .new_value
+            .as_ref()
+            .map(format_value)
+            .unwrap_or_default();
+
+        match self.kind {
+            JsonChangeKind::Added => format!("`{}` added with `{}`", self.path, new),
+            JsonChangeKind::Removed => format!("`{}` removed (was `{}`)", self.path, old),
+            JsonChangeKind::Changed => {
+                format!("`{}` changed from `{}` to `{}`", self.path, old, new)
+            }
+            JsonChangeKind::TypeChanged => format!(
+                "`{}` changed type from {} `{}` to {} `{}`",
+                self.path,
+                self.old_value.as_ref().map(type_name).unwrap_or("null"),
+                old,
+                self.new_value.as_ref().map(type_name).unwrap_or("null"),
+                new
+            ),
+        }
+    }
+}
+
+impl fmt::Display for JsonChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+/// Semantic JSON differ
+#[derive(Debug, Clone)]
+pub struct JsonDiffer {
+    max_changes: usize,
+}
+
+impl JsonDiffer {
+    /// Create a differ with the default change limit
+    pub fn new() -> Self {
+        Self {
+            max_changes: DEFAULT_MAX_JSON_CHANGES,
+        }
+    }
+
+    /// Limit the number of changes collected per comparison
+    pub fn with_max_changes(mut self, max_changes: usize) -> Self {
+        self.max_changes = max_changes;
+        self
+    }
+
+    /// Diff two JSON texts, returning `None` when either side is not valid JSON
+    pub fn diff_str(&self, text1: &str, text2: &str) -> Option<Vec<JsonChange>> {
+        let value1: Value = serde_json::from_str(text1).ok()?;
+        let value2: Value = serde_json::from_str(text2).ok()?;
+        Some(self.diff(&value1, &value2))
+    }
+
+    /// Diff two JSON values
+    pub fn diff(&self, value1: &Value, value2: &Value) -> Vec<JsonChange> {
+        let mut changes = Vec::new();
+        let mut path = Vec::new();
+        self.diff_values(value1, value2, &mut path, &mut changes);
+        changes
+    }
+
+    fn diff_values(
+        &self,
+        value1: &Value,
+        value2: &Value,
+        path: &mut Vec<Segment>,
+        changes: &mut Vec<JsonChange>,
+    ) {
+        if changes.len() >= self.max_changes || value1 == value2 {
+            return;
+        }
+
+        match (value1, value2) {
+            (Value::Object(map1), Value::Object(map2)) => {
+                for (key, child1) in map1 {
+                    path.push(Segment::Key(key.clone()));
+                    match map2.get(key) {
+                        Some(child2) => self.diff_values(child1, child2, path, changes),
+                        None => {
+                            self.push(changes, path, JsonChangeKind::Removed, Some(child1), None)
+                        }
+                    }
+                    path.pop();
+                }
+                for (key, child2) in map2 {
+                    if !map1.contains_key(key) {
+                        path.push(Segment::Key(key.clone()));
+                        self.push(changes, path, JsonChangeKind::Added, None, Some(child2));
+                        path.pop();
+                    }
+                }
+            }
+            (Value::Array(items1), Value::Array(items2)) => {
+                self.diff_arrays(items1, items2, path, changes)
+            }
+            _ if type_name(value1) != type_name(value2) => self.push(
+                changes,
+                path,
+                JsonChangeKind::TypeChanged,
+                Some(value1),
+                Some(value2),
+            ),
+            _ => self.push(
+                changes,
+                path,
+                JsonChangeKind::Changed,
+                Some(value1),
+                Some(value2),
+            ),
+        }
+    }
+
+    /// Diff array elements; matched and added elements are addressed by their
+    /// index in the second array, removed elements by their index in the first
+    fn diff_arrays(
+        &self,
+        items1: &[Value],
+        items2: &[Value],
+        path: &mut Vec<Segment>,
+        changes: &mut Vec<JsonChange>,
+    ) {
+        let steps = match identity_key(items1, items2) {
+            Some(key) => align_by_key(items1, items2, key),
+            None => align_by_lcs(items1, items2),
+        };
+
+        for step in steps {
+            match step {
+                Alignment::Pair(i, j) => {
+                    path.push(Segment::Index(j));
+                    self.diff_values(&items1[i], &items2[j], path, changes);
+                    path.pop();
+                }
+                Alignment::Removed(i) => {
+                    path.push(Segment::Index(i));
+                    self.push(
+                        changes,
+                        path,
+                        JsonChangeKind::Removed,
+                        Some(&items1[i]),
+                        None,
+                    );
+                    path.pop();
+                }
+                Alignment::Added(j) => {
+                    path.push(Segment::Index(j));
+                    self.push(changes, path, JsonChangeKind::Added, None, Some(&items2[j]));
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    fn push(
+        &self,
+        changes: &mut Vec<JsonChange>,
+        path: &[Segment],
+        kind: JsonChangeKind,
+        old_value: Option<&Value>,
+        new_value: Option<&Value>,
+    ) {
+        if changes.len() >= self.max_changes {
+            return;
+        }
+
+        changes.push(JsonChange {
+            pointer: to_pointer(path),
+            path: to_json_path(path),
+            kind,
+            old_value: old_value.cloned(),
+            new_value: new_value.cloned(),
+        });
+    }
+}
+
+impl Default for JsonDiffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single step of a JSON path
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// How an array element of one side relates to the other side
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Alignment {
+    Pair(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// Find an identity key present with a unique scalar value on every element of both arrays
+fn identity_key(items1: &[Value], items2: &[Value]) -> Option<&'static str> {
+    if items1.is_empty() || items2.is_empty() {
+        return None;
+    }
+
+    IDENTITY_KEYS.iter().copied().find(|key| {
+        [items1, items2].iter().all(|items| {
+            let mut seen = Vec::with_capacity(items.len());
+            items.iter().all(|item| match item.get(*key) {
+                Some(id) if !id.is_object() && !id.is_array() && !seen.contains(&id) => {
+                    seen.push(id);
+                    true
+                }
+                _ => false,
+            })
+        })
+    })
+}
+
+/// Match elements by identity key, keeping the order of the second array
+fn align_by_key(items1: &[Value], items2: &[Value], key: &str) -> Vec<Alignment> {
+    let index1: HashMap<String, usize> = items1
+        .iter()
+        .enumerate()
+        .map(|(i, item)| (item[key].to_string(), i))
+        .collect();
+    let ids2: Vec<String> = items2.iter().map(|item| item[key].to_string()).collect();
+
+    let mut steps: Vec<Alignment> = (0..items1.len())
+        .filter(|i| !ids2.contains(&items1[*i][key].to_string()))
+        .map(Alignment::Removed)
+        .collect();
+    steps.extend(ids2.iter().enumerate().map(|(j, id)| match index1.get(id) {
+        Some(&i) => Alignment::Pair(i, j),
+        None => Alignment::Added(j),
+    }));
+    steps
+}
+
+/// Align elements on their longest common subsequence, pairing the unmatched
+/// elements between anchors by position
+fn align_by_lcs(items1: &[Value], items2: &[Value]) -> Vec<Alignment> {
+    let (n, m) = (items1.len(), items2.len());
+    if n.saturating_mul(m) > MAX_LCS_CELLS {
+        return align_gap(0..n, 0..m);
+    }
+
+    // lengths[i][j] = LCS length of items1[i..] and items2[j..]
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if items1[i] == items2[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let (mut gap_i, mut gap_j) = (0, 0);
+    while i < n && j < m {
+        if items1[i] == items2[j] {
+            steps.extend(align_gap(gap_i..i, gap_j..j));
+            steps.push(Alignment::Pair(i, j));
+            i += 1;
+            j += 1;
+            gap_i = i;
+            gap_j = j;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    steps.extend(align_gap(gap_i..n, gap_j..m));
+    steps
+}
+
+/// Pair unmatched elements by position, reporting the surplus as removed or added
+fn align_gap(range1: std::ops::Range<usize>, range2: std::ops::Range<usize>) -> Vec<Alignment> {
+    let paired = range1.len().min(range2.len());
+    let mut steps: Vec<Alignment> = range1
+        .clone()
+        .zip(range2.clone())
+        .map(|(i, j)| Alignment::Pair(i, j))
+        .collect();
+    steps.extend(range1.skip(paired).map(Alignment::Removed));
+    steps.extend(range2.skip(paired).map(Alignment::Added));
+    steps
+}
+
+fn to_pointer(path: &[Segment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+            Segment::Index(index) => format!("/{}", index),
+        })
+        .collect()
+}
+
+fn to_json_path(path: &[Segment]) -> String {
+    let mut json_path = String::from("$");
+    for segment in path {
+        match segment {
+            Segment::Key(key) if is_identifier(key) => {
+                json_path.push('.');
+                json_path.push_str(key);
+            }
+            Segment::Key(key) => {
+                json_path.push_str(&format!("['{}']", key.replace('\'', "\\'")));
+            }
+            Segment::Index(index) => json_path.push_str(&format!("[{}]", index)),
+        }
+    }
+    json_path
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Format a value for summaries, showing strings without quotes
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn diff(value1: Value, value2: Value) -> Vec<JsonChange> {
+        JsonDiffer::new().diff(&value1, &value2)
+    }
+
+    #[test]
+    fn test_object_changes() {
+        let changes = diff(
+            json!({"user": {"plan": "basic", "age": 30, "legacy": true}}),
+            json!({"user": {"plan": "premium", "age": "30", "email": "a@b.c"}}),
+        );
+
+        let summaries: Vec<String> = changes.iter().map(JsonChange::summary).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "`$.user.age` changed type from number `30` to string `30`",
+                "`$.user.legacy` removed (was `true`)",
+                "`$.user.plan` changed from `basic` to `premium`",
+                "`$.user.email` added with `a@b.c`",
+            ]
+        );
+        assert_eq!(changes[0].pointer, "/user/age");
+        assert_eq!(changes[0].kind, JsonChangeKind::TypeChanged);
+    }
+
+    #[test]
+    fn test_array_insertion_is_not_reported_as_shift() {
+        let changes = diff(
+            json!({"tags": ["a", "b", "c"]}),
+            json!({"tags": ["a", "x", "b", "c"]}),
+        );
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, JsonChangeKind::Added);
+        assert_eq!(changes[0].pointer, "/tags/1");
+        assert_eq!(changes[0].new_value, Some(json!("x")));
+    }
+
+    #[test]
+    fn test_arrays_of_objects_matched_by_id() {
+        let changes = diff(
+            json!([{"id": 1, "qty": 1}, {"id": 2, "qty": 5}, {"id": 3, "qty": 7}]),
+            json!([{"id": 2, "qty": 6}, {"id": 3, "qty": 7}, {"id": 4, "qty": 1}]),
+        );
+
+        let kinds: Vec<(JsonChangeKind, &str)> = changes
+            .iter()
+            .map(|c| (c.kind, c.pointer.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (JsonChangeKind::Removed, "/0"),
+                (JsonChangeKind::Changed, "/0/qty"),
+                (JsonChangeKind::Added, "/2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paths_escape_special_keys() {
+        let changes = diff(json!({"a/b": {"x y": 1}}), json!({"a/b": {"x y": 2}}));
+
+        assert_eq!(changes[0].pointer, "/a~1b/x y");
+        assert_eq!(changes[0].path, "$['a/b']['x y']");
+    }
+
+    #[test]
+    fn test_change_limit_and_invalid_json() {
+        let differ = JsonDiffer::new().with_max_changes(2);
+        let changes = differ.diff(
+            &json!({"a": 1, "b": 1, "c": 1}),
+            &json!({"a": 2, "b": 2, "c": 2}),
+        );
+        assert_eq!(changes.len(), 2);
+
+        assert!(differ.diff_str("not json", "{}").is_none());
+        assert_eq!(differ.diff_str("{}", "{}"), Some(vec![]));
+    }
+}
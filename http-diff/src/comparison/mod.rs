@@ -1,35 +1,45 @@
 pub mod analyzer;
+pub mod assertions;
 /// Response comparison module with pure business logic
 pub mod content;
+pub mod json_diff;
 pub mod response_validator;
+pub mod rules;
 
 use crate::error::Result;
 use crate::traits::ResponseComparator as ResponseComparatorTrait;
 use crate::types::{ComparisonResult, DiffViewStyle, HttpResponse};
 use crate::utils::environment_utils::{EnvironmentOrderResolver, EnvironmentValidator};
 use analyzer::DifferenceAnalyzer;
+use rules::RouteComparisonRules;
 use std::collections::HashMap;
 
 // Re-export for easier access
+pub use assertions::AssertionEvaluator;
+pub use json_diff::{JsonChange, JsonChangeKind, JsonDiffer};
 pub use response_validator::ResponseValidatorImpl;
+pub use rules::ComparisonRules;
+
+/// Headers that change on every request and are ignored by default
+pub const DEFAULT_IGNORED_HEADERS: &[&str] = &["date", "server", "x-request-id", "x-correlation-id"];
 
 /// Response comparator with configurable comparison strategies - pure business logic only
 pub struct ResponseComparator {
     analyzer: DifferenceAnalyzer,
     compare_headers: bool,
     diff_view_style: DiffViewStyle,
+    rules: RouteComparisonRules,
+    assertions: AssertionEvaluator,
 }
 
 impl ResponseComparator {
     /// Create a new response comparator with default settings
     /// By default, compares only HTTP status and response body (headers comparison disabled)
     pub fn new() -> Self {
-        let ignore_headers = vec![
-            "date".to_string(),
-            "server".to_string(),
-            "x-request-id".to_string(),
-            "x-correlation-id".to_string(),
-        ];
+        let ignore_headers = DEFAULT_IGNORED_HEADERS
+            .iter()
+            .map(|header| header.to_string())
+            .collect();
 
         Self {
             analyzer: DifferenceAnalyzer::new(
@@ -39,6 +49,8 @@ impl ResponseComparator {
             ),
             compare_headers: false,
             diff_view_style: DiffViewStyle::Unified,
+            rules: RouteComparisonRules::default(),
+            assertions: AssertionEvaluator::default(),
         }
     }
 
@@ -52,6 +64,8 @@ impl ResponseComparator {
             ),
             compare_headers: false,
             diff_view_style: DiffViewStyle::Unified,
+            rules: RouteComparisonRules::default(),
+            assertions: AssertionEvaluator::default(),
         }
     }
 
@@ -70,6 +84,8 @@ impl ResponseComparator {
             ),
             compare_headers,
             diff_view_style: DiffViewStyle::Unified,
+            rules: RouteComparisonRules::default(),
+            assertions: AssertionEvaluator::default(),
         }
     }
 
@@ -91,6 +107,19 @@ impl ResponseComparator {
         self
     }
 
+    /// Apply the comparison rules and route assertions declared in the configuration
+    pub fn with_config(mut self, config: &crate::config::HttpDiffConfig) -> Self {
+        self.rules = RouteComparisonRules::from_config(config);
+        self.assertions = AssertionEvaluator::from_config(config);
+        self
+    }
+
+    /// Set comparison rules explicitly
+    pub fn with_rules(mut self, rules: RouteComparisonRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
     /// Compare responses from multiple environments with optional base environment
     pub fn compare_responses_with_base(
         &self,
@@ -111,6 +140,9 @@ impl ResponseComparator {
         // Validate minimum environment count
         EnvironmentValidator::validate_minimum_environments(&environments)?;
 
+        // Assertions are checked per environment, independent of diffing
+        let assertions = self.assertions.evaluate(&route_name, &responses);
+
         if environments.len() < 2 {
             // Need at least 2 environments for comparison
             return Ok(ComparisonResult {
@@ -123,6 +155,8 @@ impl ResponseComparator {
                 has_errors: false,
                 error_bodies: None,
                 base_environment,
+                ignored_fields: Vec::new(),
+                assertions,
             });
         }
 
@@ -130,19 +164,27 @@ impl ResponseComparator {
         // If base_environment is specified, it will be first due to resolver ordering
         let base_env = &environments[0];
         let base_response = &responses[base_env];
+        let rules = self.rules.for_route(&route_name);
+        let mut ignored_fields: Vec<String> = Vec::new();
 
         for env in environments.iter().skip(1) {
             let target_response = &responses[env];
 
-            let pair_differences = self.analyzer.analyze_responses(
+            let (pair_differences, pair_ignored) = self.analyzer.analyze_responses_with_rules(
                 base_response,
                 target_response,
                 base_env,
                 env,
                 self.compare_headers,
+                rules,
             );
 
             differences.extend(pair_differences);
+            for field in pair_ignored {
+                if !ignored_fields.contains(&field) {
+                    ignored_fields.push(field);
+                }
+            }
         }
 
         let is_identical = differences.is_empty();
@@ -167,6 +209,8 @@ impl ResponseComparator {
             has_errors,
             error_bodies,
             base_environment: Some(base_env.clone()),
+            ignored_fields,
+            assertions,
         })
     }
 
@@ -233,6 +277,8 @@ mod tests {
             body: body.to_string(),
             url: "https://example.com/api/test".to_string(),
             curl_command: "curl 'https://example.com/api/test'".to_string(),
+            timing: None,
+            attempts: 1,
         }
     }
 
@@ -365,6 +411,35 @@ mod tests {
         assert_eq!(error_bodies.get("prod"), Some(&"not found".to_string()));
     }
 
+    #[test]
+    fn test_ignore_rules_from_config() {
+        let config = crate::config::HttpDiffConfig::builder()
+            .environment("test", "https://test.example.com", None)
+            .environment("prod", "https://prod.example.com", None)
+            .get_route("users", "/users")
+            .configure_global(|global| global.ignore_field("$.generatedAt"))
+            .build()
+            .unwrap();
+        let comparator = ResponseComparator::new().with_config(&config);
+
+        let mut responses = HashMap::new();
+        responses.insert(
+            "test".to_string(),
+            create_test_response(200, r#"{"status": "ok", "generatedAt": "1"}"#),
+        );
+        responses.insert(
+            "prod".to_string(),
+            create_test_response(200, r#"{"status": "ok", "generatedAt": "2"}"#),
+        );
+
+        let result = comparator
+            .compare_responses("users".to_string(), HashMap::new(), responses)
+            .unwrap();
+
+        assert!(result.is_identical);
+        assert_eq!(result.ignored_fields, vec!["$.generatedAt".to_string()]);
+    }
+
     #[test]
     fn test_invalid_response_count() {
         let comparator = ResponseComparator::new();
@@ -0,0 +1,299 @@
+//! Per-route comparison rules resolved from configuration
+//!
+//! Rules are declared globally in `[global]` and per route in `[[routes]]`;
+//! route rules extend the global ones. Value-normalization rules from a route's
+//! `[routes.compare]` table apply to that route only.
+
+use crate::config::{CompareRules, HttpDiffConfig, LatencyThreshold, NumericTolerance, Route};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Replacement used by masks that do not declare one
+pub const DEFAULT_MASK_REPLACEMENT: &str = "***";
+
+/// Comparison rules applied when diffing the responses of a single route
+#[derive(Debug, Clone, Default)]
+pub struct ComparisonRules {
+    /// JSONPath expressions for body fields excluded from comparison
+    pub ignore_fields: Vec<String>,
+    /// Tolerance within which differing JSON numbers are considered equal
+    pub numeric_tolerance: Option<NumericTolerance>,
+    /// JSONPath expressions whose string values are compared case-insensitively
+    pub case_insensitive: Vec<String>,
+    /// JSONPath expressions of arrays compared without regard to element order
+    pub unordered_arrays: Vec<String>,
+    /// Compiled regex masks applied to string values
+    pub masks: Vec<MaskRule>,
+    /// Threshold above which latency differences are reported (disabled when unset)
+    pub latency_threshold: Option<LatencyThreshold>,
+}
+
+/// A compiled regex mask
+#[derive(Debug, Clone)]
+pub struct MaskRule {
+    pub regex: Regex,
+    pub replacement: String,
+    /// JSONPath expressions limiting the mask (empty applies to all strings)
+    pub paths: Vec<String>,
+}
+
+impl ComparisonRules {
+    /// Create empty comparison rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add JSONPath expressions for body fields to ignore
+    pub fn with_ignore_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for field in fields {
+            let field = field.into();
+            if !self.ignore_fields.contains(&field) {
+                self.ignore_fields.push(field);
+            }
+        }
+        self
+    }
+
+    /// Set the threshold above which latency differences are reported
+    pub fn with_latency_threshold(mut self, threshold: LatencyThreshold) -> Self {
+        self.latency_threshold = Some(threshold);
+        self
+    }
+
+    /// Apply value-normalization rules, replacing any previously set ones
+    ///
+    /// Masks with invalid patterns are skipped; config validation reports them.
+    /// A latency threshold on the route overrides the inherited one.
+    pub fn with_compare_rules(mut self, compare: &CompareRules) -> Self {
+        if let Some(threshold) = &compare.latency_threshold {
+            self.latency_threshold = Some(threshold.clone());
+        }
+        self.numeric_tolerance = compare.numeric_tolerance.clone();
+        self.case_insensitive = compare.case_insensitive.clone().unwrap_or_default();
+        self.unordered_arrays = compare.unordered_arrays.clone().unwrap_or_default();
+        self.masks = compare
+            .mask
+            .iter()
+            .flatten()
+            .filter_map(|mask| {
+                Some(MaskRule {
+                    regex: Regex::new(&mask.pattern).ok()?,
+                    replacement: mask
+                        .replacement
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_MASK_REPLACEMENT.to_string()),
+                    paths: mask.paths.clone().unwrap_or_default(),
+                })
+            })
+            .collect();
+        self
+    }
+
+    /// Resolve the global comparison rules from configuration
+    pub fn from_global(config: &HttpDiffConfig) -> Self {
+        let global = config.global.as_ref();
+        let rules = Self::new().with_ignore_fields(
+            global
+                .and_then(|g| g.ignore_fields.clone())
+                .unwrap_or_default(),
+        );
+        match global.and_then(|g| g.latency_threshold.clone()) {
+            Some(threshold) => rules.with_latency_threshold(threshold),
+            None => rules,
+        }
+    }
+
+    /// Extend these rules with the rules declared on a route
+    pub fn merged_with_route(&self, route: &Route) -> Self {
+        let rules = self
+            .clone()
+            .with_ignore_fields(route.ignore_fields.clone().unwrap_or_default());
+        match &route.compare {
+            Some(compare) => rules.with_compare_rules(compare),
+            None => rules,
+        }
+    }
+
+    /// Check whether any value-normalization rule is configured
+    pub fn has_value_rules(&self) -> bool {
+        self.numeric_tolerance.is_some()
+            || !self.case_insensitive.is_empty()
+            || !self.unordered_arrays.is_empty()
+            || !self.masks.is_empty()
+    }
+
+    /// Check whether any rule is configured
+    pub fn is_empty(&self) -> bool {
+        self.ignore_fields.is_empty() && !self.has_value_rules() && self.latency_threshold.is_none()
+    }
+}
+
+/// Comparison rules for every route of a configuration
+#[derive(Debug, Clone, Default)]
+pub struct RouteComparisonRules {
+    global: ComparisonRules,
+    routes: HashMap<String, ComparisonRules>,
+}
+
+impl RouteComparisonRules {
+    /// Resolve global and per-route comparison rules from configuration
+    pub fn from_config(config: &HttpDiffConfig) -> Self {
+        let global = ComparisonRules::from_global(config);
+        let routes = config
+            .routes
+            .iter()
+            .map(|route| (route.name.clone(), global.merged_with_route(route)))
+            .collect();
+
+        Self { global, routes }
+    }
+
+    /// Replace the global rules applied to routes without their own rules
+    pub fn with_global(mut self, global: ComparisonRules) -> Self {
+        self.global = global;
+        self
+    }
+
+    /// Get the rules for a route, falling back to the global rules
+    pub fn for_route(&self, route_name: &str) -> &ComparisonRules {
+        self.routes.get(route_name).unwrap_or(&self.global)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_rules_extend_global_rules() {
+        let config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.dev]
+base_url = "https://dev.example.com"
+
+[global]
+ignore_fields = ["$.meta.generatedAt"]
+
+[[routes]]
+name = "users"
+method = "GET"
+path = "/users"
+ignore_fields = ["$.items[*].id", "$.meta.generatedAt"]
+
+[[routes]]
+name = "health"
+method = "GET"
+path = "/health"
+"#,
+        )
+        .unwrap();
+
+        let rules = RouteComparisonRules::from_config(&config);
+
+        assert_eq!(
+            rules.for_route("users").ignore_fields,
+            vec!["$.meta.generatedAt", "$.items[*].id"]
+        );
+        assert_eq!(
+            rules.for_route("health").ignore_fields,
+            vec!["$.meta.generatedAt"]
+        );
+        assert_eq!(
+            rules.for_route("unknown").ignore_fields,
+            vec!["$.meta.generatedAt"]
+        );
+    }
+
+    #[test]
+    fn test_route_compare_rules() {
+        let config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.dev]
+base_url = "https://dev.example.com"
+
+[[routes]]
+name = "prices"
+method = "GET"
+path = "/prices"
+
+[routes.compare]
+numeric_tolerance = { absolute = 0.01 }
+case_insensitive = ["$.currency"]
+unordered_arrays = ["$.tags"]
+mask = [
+    { pattern = "\\d{4}-\\d{2}-\\d{2}" },
+    { pattern = "[", replacement = "x" },
+]
+
+[[routes]]
+name = "health"
+method = "GET"
+path = "/health"
+"#,
+        )
+        .unwrap();
+
+        let rules = RouteComparisonRules::from_config(&config);
+        let prices = rules.for_route("prices");
+
+        assert_eq!(
+            prices.numeric_tolerance.as_ref().unwrap().absolute,
+            Some(0.01)
+        );
+        assert_eq!(prices.case_insensitive, vec!["$.currency"]);
+        assert_eq!(prices.unordered_arrays, vec!["$.tags"]);
+        // The invalid pattern is skipped
+        assert_eq!(prices.masks.len(), 1);
+        assert_eq!(prices.masks[0].replacement, DEFAULT_MASK_REPLACEMENT);
+        assert!(prices.has_value_rules());
+
+        assert!(rules.for_route("health").is_empty());
+    }
+
+    #[test]
+    fn test_route_latency_threshold_overrides_global() {
+        let config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.dev]
+base_url = "https://dev.example.com"
+
+[global]
+latency_threshold = { ratio = 2.0, min_delta_ms = 100 }
+
+[[routes]]
+name = "search"
+method = "GET"
+path = "/search"
+
+[routes.compare]
+latency_threshold = { ratio = 3.0 }
+
+[[routes]]
+name = "health"
+method = "GET"
+path = "/health"
+"#,
+        )
+        .unwrap();
+
+        let rules = RouteComparisonRules::from_config(&config);
+
+        let search = rules
+            .for_route("search")
+            .latency_threshold
+            .as_ref()
+            .unwrap();
+        assert_eq!(search.ratio, Some(3.0));
+        assert_eq!(search.min_delta_ms, None);
+        let health = rules
+            .for_route("health")
+            .latency_threshold
+            .as_ref()
+            .unwrap();
+        assert_eq!(health.ratio, Some(2.0));
+    }
+}
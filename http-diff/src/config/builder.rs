@@ -1,4 +1,6 @@
-use crate::config::types::{Environment, GlobalConfig, HttpDiffConfig, Route};
+use crate::config::types::{
+    AuthConfig, Environment, GlobalConfig, HttpDiffConfig, RateLimit, Route,
+};
 use crate::error::Result;
 use std::collections::HashMap;
 
@@ -30,14 +32,35 @@ impl HttpDiffConfigBuilder {
         self.environments.insert(
             name.into(),
             Environment {
+                extends: None,
                 base_url: base_url.into(),
                 headers,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
         self
     }
 
+    /// Throttle requests to a previously added environment
+    #[must_use]
+    pub fn rate_limit<S: Into<String>>(mut self, name: S, rate_limit: RateLimit) -> Self {
+        if let Some(env) = self.environments.get_mut(&name.into()) {
+            env.rate_limit = Some(rate_limit);
+        }
+        self
+    }
+
+    /// Authenticate requests to a previously added environment
+    #[must_use]
+    pub fn auth<S: Into<String>>(mut self, name: S, auth: AuthConfig) -> Self {
+        if let Some(env) = self.environments.get_mut(&name.into()) {
+            env.auth = Some(auth);
+        }
+        self
+    }
+
     /// Set global configuration directly
     pub fn global_config(mut self, global: GlobalConfig) -> Self {
         self.global = Some(global);
@@ -114,6 +137,16 @@ impl HttpDiffConfigBuilder {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         });
         self
     }
@@ -136,6 +169,7 @@ impl HttpDiffConfigBuilder {
     /// Returns an error if the configuration is invalid (e.g., no environments or routes defined)
     pub fn build(self) -> Result<HttpDiffConfig> {
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments: self.environments,
             global: self.global,
             routes: self.routes,
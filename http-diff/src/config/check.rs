@@ -0,0 +1,481 @@
+//! Static checks of a configuration
+//!
+//! [`check_config`] loads a configuration, runs every validation of
+//! [`ConfigValidatorImpl`] and checks the placeholders of the routes against
+//! the columns of the users CSV and the extraction rules; environment headers
+//! and the contents of `body_file` are checked the same way. All problems are
+//! reported, each with the file and line it comes from, and no request is
+//! sent.
+
+use crate::config::loader::{ConfigSources, DefaultConfigLoader, SourceLocation};
+use crate::config::types::Route;
+use crate::config::validator::{ConfigSubject, ConfigValidatorImpl};
+use crate::error::HttpDiffError;
+use crate::template::{map_placeholders, Expression};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// A problem found by [`check_config`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckFinding {
+    /// File the problem is in
+    pub path: PathBuf,
+    /// Line of the problem, when it can be located
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl CheckFinding {
+    fn new<P: Into<PathBuf>, S: Into<String>>(path: P, line: Option<usize>, message: S) -> Self {
+        Self {
+            path: path.into(),
+            line,
+            message: message.into(),
+        }
+    }
+
+    fn at(location: &SourceLocation, message: String) -> Self {
+        Self::new(location.path.clone(), Some(location.line), message)
+    }
+}
+
+impl std::fmt::Display for CheckFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Check a configuration and the users CSV filling its placeholders
+///
+/// Returns every problem found, ordered by file and line; an empty list means
+/// the configuration is ready to run.
+pub fn check_config<P: AsRef<Path>, Q: AsRef<Path>>(
+    config_path: P,
+    users_path: Q,
+) -> Vec<CheckFinding> {
+    let config_path = config_path.as_ref();
+    let users_path = users_path.as_ref();
+
+    let (config, sources) = match DefaultConfigLoader::load_with_sources(config_path) {
+        Ok(loaded) => loaded,
+        Err(HttpDiffError::ConfigAt {
+            path,
+            line,
+            message,
+        }) => return vec![CheckFinding::new(path, Some(line), message)],
+        Err(e) => return vec![CheckFinding::new(config_path, None, message_of(e))],
+    };
+    let mut locator = Locator::new(&sources);
+    let mut findings = Vec::new();
+
+    for problem in ConfigValidatorImpl::new().find_problems(&config, config_path) {
+        let location = match &problem.subject {
+            ConfigSubject::Route(name) => sources.routes.get(name),
+            ConfigSubject::Environment(name) => sources.environments.get(name),
+            ConfigSubject::Global => sources.global.as_ref(),
+            ConfigSubject::Configuration => None,
+        };
+        let message = message_of(problem.error);
+        findings.push(match location {
+            Some(location) => CheckFinding::at(location, message),
+            None => CheckFinding::new(config_path, None, message),
+        });
+    }
+
+    let (columns, csv_findings) = read_columns(users_path);
+    findings.extend(csv_findings);
+    let users_file = users_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| users_path.display().to_string());
+
+    let extracted: BTreeSet<&str> = config
+        .routes
+        .iter()
+        .flat_map(|route| route.extract.iter().flatten())
+        .map(|rule| rule.name.as_str())
+        .collect();
+    let provided = |name: &str| columns.contains(name) || extracted.contains(name);
+    let mut environments: Vec<_> = config.environments.iter().collect();
+    environments.sort_by_key(|(name, _)| name.as_str());
+    for (name, environment) in environments {
+        let Some(origin) = sources.environments.get(name) else {
+            continue;
+        };
+        // Headers inherited through `extends` are checked on the parent
+        let inherited = environment
+            .extends
+            .as_ref()
+            .and_then(|parent| config.environments.get(parent))
+            .and_then(|parent| parent.headers.as_ref());
+        let headers =
+            environment.headers.iter().flatten().filter(|(key, value)| {
+                inherited.and_then(|headers| headers.get(*key)) != Some(*value)
+            });
+        let texts = headers.map(|(_, value)| value.as_str());
+        for placeholder in missing_in(texts, provided) {
+            findings.push(CheckFinding::at(
+                &locator.find(origin, &format!("{{{}}}", placeholder)),
+                format!(
+                    "placeholder {{{}}} of environment '{}' is not provided by a column of {} or an extraction rule",
+                    placeholder, name, users_file
+                ),
+            ));
+        }
+    }
+    for route in &config.routes {
+        let Some(origin) = sources.routes.get(&route.name) else {
+            continue;
+        };
+        for placeholder in missing_variables(route, provided) {
+            findings.push(CheckFinding::at(
+                &locator.find(origin, &format!("{{{}}}", placeholder)),
+                format!(
+                    "placeholder {{{}}} of route '{}' is not provided by a column of {} or an extraction rule",
+                    placeholder, route.name, users_file
+                ),
+            ));
+        }
+    }
+
+    findings.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+    findings
+}
+
+/// Message of an error without the generic prefix of configuration errors
+fn message_of(error: HttpDiffError) -> String {
+    match error {
+        HttpDiffError::InvalidConfig { message } => message,
+        error => error.to_string(),
+    }
+}
+
+/// Column names of the users CSV, with the problems found reading it
+fn read_columns(path: &Path) -> (BTreeSet<String>, Vec<CheckFinding>) {
+    if !path.exists() {
+        return (
+            BTreeSet::new(),
+            vec![CheckFinding::new(
+                path,
+                None,
+                "users file not found; run `fnc http-diff --init` to create it",
+            )],
+        );
+    }
+
+    let finding = |e: csv::Error| {
+        let line = e.position().map(|position| position.line() as usize);
+        CheckFinding::new(path, line, e.to_string())
+    };
+    let mut reader = match csv::Reader::from_path(path) {
+        Ok(reader) => reader,
+        Err(e) => return (BTreeSet::new(), vec![finding(e)]),
+    };
+    let columns = match reader.headers() {
+        Ok(headers) => headers.iter().map(str::to_string).collect(),
+        Err(e) => return (BTreeSet::new(), vec![finding(e)]),
+    };
+    let findings = reader
+        .records()
+        .filter_map(|record| record.err())
+        .map(finding)
+        .collect();
+    (columns, findings)
+}
+
+/// Placeholders of a route using a variable nothing provides
+///
+/// Each variable is reported once, with the first placeholder using it.
+fn missing_variables<F>(route: &Route, provided: F) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut texts: Vec<&str> = vec![route.path.as_str()];
+    let maps = [
+        route.headers.as_ref(),
+        route.params.as_ref(),
+        route.base_urls.as_ref(),
+    ];
+    texts.extend(
+        maps.into_iter()
+            .flatten()
+            .flat_map(|map| map.values())
+            .map(String::as_str),
+    );
+    texts.extend(route.form.iter().flatten().map(|(_, value)| value.as_str()));
+    texts.extend(route.body.as_deref());
+    texts.extend(route.body_file.as_deref());
+    // A templated path names no single file to read
+    let body_file = route
+        .body_file
+        .as_deref()
+        .filter(|path| !path.contains('{'))
+        .and_then(|path| std::fs::read_to_string(route.resolve_path(path)).ok());
+    texts.extend(body_file.as_deref());
+    for part in route.multipart.iter().flatten() {
+        texts.extend(part.value.as_deref());
+        texts.extend(part.file.as_deref());
+    }
+    let mut json_strings = Vec::new();
+    if let Some(json) = &route.json {
+        collect_strings(json, &mut json_strings);
+    }
+    texts.extend(json_strings);
+    missing_in(texts, provided)
+}
+
+/// Placeholders of `texts` using a variable nothing provides, each variable once
+fn missing_in<'a, I, F>(texts: I, provided: F) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: Fn(&str) -> bool,
+{
+    let mut missing = Vec::new();
+    let mut reported: Vec<String> = Vec::new();
+    for text in texts {
+        map_placeholders(text, |source, expression| {
+            let mut names = Vec::new();
+            required_variables(expression, &mut names);
+            for name in names {
+                if !provided(&name) && !reported.contains(&name) {
+                    missing.push(source.to_string());
+                    reported.push(name);
+                }
+            }
+            None
+        });
+    }
+    missing
+}
+
+/// Variables an expression cannot be evaluated without
+fn required_variables(expression: &Expression, names: &mut Vec<String>) {
+    match expression {
+        Expression::Variable(name) => names.push(name.clone()),
+        Expression::Call { args, .. } => {
+            for arg in args {
+                required_variables(arg, names);
+            }
+        }
+        // A `??` default stands in for missing operands
+        Expression::Default(_)
+        | Expression::Literal(_)
+        | Expression::EnvVar(_)
+        | Expression::Secret(_) => {}
+    }
+}
+
+fn collect_strings<'a>(value: &'a serde_json::Value, strings: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::String(text) => strings.push(text),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_strings(item, strings);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for (key, item) in fields {
+                strings.push(key);
+                collect_strings(item, strings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Finds the line of a text within the definition of an environment or route
+struct Locator {
+    /// Lines starting a definition, per file, bounding the search
+    starts: HashMap<PathBuf, BTreeSet<usize>>,
+    files: HashMap<PathBuf, Vec<String>>,
+}
+
+impl Locator {
+    fn new(sources: &ConfigSources) -> Self {
+        let mut starts: HashMap<PathBuf, BTreeSet<usize>> = HashMap::new();
+        for location in sources
+            .routes
+            .values()
+            .chain(sources.environments.values())
+            .chain(sources.profiles.values())
+            .chain(sources.global.iter())
+        {
+            starts
+                .entry(location.path.clone())
+                .or_default()
+                .insert(location.line);
+        }
+        Self {
+            starts,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Line containing `needle` from `origin` to the next definition, or `origin` itself
+    fn find(&mut self, origin: &SourceLocation, needle: &str) -> SourceLocation {
+        let lines = self.files.entry(origin.path.clone()).or_insert_with(|| {
+            std::fs::read_to_string(&origin.path)
+                .map(|content| content.lines().map(str::to_string).collect())
+                .unwrap_or_default()
+        });
+        let end = self
+            .starts
+            .get(&origin.path)
+            .and_then(|starts| starts.range(origin.line + 1..).next().copied())
+            .unwrap_or(lines.len() + 1);
+
+        let line = (origin.line..end)
+            .find(|line| {
+                lines
+                    .get(line - 1)
+                    .is_some_and(|text| text.contains(needle))
+            })
+            .unwrap_or(origin.line);
+        SourceLocation {
+            path: origin.path.clone(),
+            line,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_check_reports_every_problem_with_its_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("http-diff.toml");
+        fs::write(
+            &config,
+            r#"[environments.staging]
+base_url = "https://staging.example.com"
+
+[[routes]]
+name = "get_user"
+method = "GET"
+path = "/users/{userId}"
+depends_on = ["login"]
+
+[[routes]]
+name = "update_user"
+method = "PATCHED"
+path = "/users/{userId}"
+headers = { "Content-Type" = "application/json", "X-Org" = "{orgId ?? 'none'}" }
+body = '{"name": {name}, "team": "{teamId}",}'
+
+[[routes.extract]]
+name = "etag"
+type = "regex"
+source = "etag: ("
+"#,
+        )
+        .unwrap();
+        let users = dir.path().join("users.csv");
+        fs::write(
+            &users,
+            "userId,name
+1,ann
+2
+",
+        )
+        .unwrap();
+
+        let findings: Vec<String> = check_config(&config, &users)
+            .iter()
+            .map(|finding| {
+                let path = finding.path.file_name().unwrap().to_string_lossy();
+                format!("{}:{:?}: {}", path, finding.line, finding.message)
+            })
+            .collect();
+        let expected = [
+            "http-diff.toml:Some(5): Route 'get_user' depends on non-existent route 'login'",
+            "http-diff.toml:Some(11): Invalid HTTP method 'PATCHED'",
+            "http-diff.toml:Some(11): Invalid regex 'etag: (' in extraction rule 'etag'",
+            "http-diff.toml:Some(11): body of route 'update_user' is sent as JSON but is not valid JSON",
+            "http-diff.toml:Some(15): placeholder {teamId} of route 'update_user' is not provided by a column of users.csv",
+            "users.csv:Some(3): CSV error",
+        ];
+        assert_eq!(findings.len(), expected.len(), "{:#?}", findings);
+        for (finding, expected) in findings.iter().zip(expected) {
+            assert!(finding.starts_with(expected), "{} !~ {}", finding, expected);
+        }
+    }
+
+    #[test]
+    fn test_check_scans_environment_headers_and_body_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("fixtures")).unwrap();
+        fs::write(
+            dir.path().join("fixtures/order.json"),
+            r#"{"user": "{userId}", "sku": "{sku}"}"#,
+        )
+        .unwrap();
+        let config = dir.path().join("http-diff.toml");
+        fs::write(
+            &config,
+            r#"[environments.staging]
+base_url = "https://staging.example.com"
+headers = { "X-User" = "{userId}", "X-Tenant" = "{tenantId}" }
+
+[environments.prod]
+extends = "staging"
+base_url = "https://prod.example.com"
+
+[[routes]]
+name = "create_order"
+method = "POST"
+path = "/orders"
+body_file = "fixtures/order.json"
+"#,
+        )
+        .unwrap();
+        let users = dir.path().join("users.csv");
+        fs::write(&users, "userId\n1\n").unwrap();
+
+        let findings: Vec<String> = check_config(&config, &users)
+            .iter()
+            .map(|finding| format!("{:?}: {}", finding.line, finding.message))
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                "Some(3): placeholder {tenantId} of environment 'staging' is not provided by a column of users.csv or an extraction rule",
+                "Some(10): placeholder {sku} of route 'create_order' is not provided by a column of users.csv or an extraction rule",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_reports_load_errors_and_missing_users_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("http-diff.toml");
+        fs::write(
+            &config,
+            "[environments.prod]\nextends = \"qa\"\nbase_url = \"https://x\"\n",
+        )
+        .unwrap();
+        let findings = check_config(&config, dir.path().join("users.csv"));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, Some(1));
+        assert_eq!(
+            findings[0].message,
+            "environment 'prod' extends unknown environment 'qa'"
+        );
+
+        fs::write(
+            &config,
+            "[environments.prod]\nbase_url = \"https://x\"\n\n[[routes]]\nname = \"health\"\nmethod = \"GET\"\npath = \"/health\"\n",
+        )
+        .unwrap();
+        let findings = check_config(&config, dir.path().join("users.csv"));
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0]
+            .to_string()
+            .ends_with("users.csv: users file not found; run `fnc http-diff --init` to create it"));
+    }
+}
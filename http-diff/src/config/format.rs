@@ -0,0 +1,350 @@
+//! Configuration file formats
+//!
+//! A configuration may be written in TOML, YAML or JSON; the format is chosen
+//! by the file extension and the settings are the same in all three. Included
+//! files may use a different format than the file including them.
+
+use crate::config::types::{GlobalConfig, Profile, Route};
+use crate::error::{HttpDiffError, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Settings of a configuration file, before files are merged and typed
+///
+/// JSON values rather than TOML ones, since YAML and JSON files may hold `null`.
+pub type Settings = serde_json::Map<String, serde_json::Value>;
+
+/// Format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+/// Sections of one file, checked with line numbers before files are merged
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct FileSections {
+    #[serde(default)]
+    routes: Vec<Route>,
+    global: Option<GlobalConfig>,
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+impl ConfigFormat {
+    /// Format of a file by its extension, or `None` when the extension is not known
+    pub fn from_extension<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Format of a file by its extension, TOML when the extension is not known
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        Self::from_extension(path).unwrap_or(Self::Toml)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Toml => "TOML",
+            Self::Yaml => "YAML",
+            Self::Json => "JSON",
+        }
+    }
+
+    /// Parse the content of a configuration file, checking the types of its sections
+    ///
+    /// `null` values of YAML and JSON files are kept: settings holding `null`
+    /// are treated as absent, and `null` in request bodies is sent as written.
+    pub fn parse(&self, path: &Path, content: &str) -> Result<Settings> {
+        let parse_error = |e: &dyn std::fmt::Display| {
+            HttpDiffError::invalid_config(format!(
+                "Failed to parse {} in {}: {}",
+                self.name(),
+                path.display(),
+                e
+            ))
+        };
+        let section_error = |e: &dyn std::fmt::Display| {
+            HttpDiffError::invalid_config(format!(
+                "Invalid configuration in {}: {}",
+                path.display(),
+                e
+            ))
+        };
+
+        let value: serde_json::Value = match self {
+            Self::Toml => {
+                let value = toml::from_str(content).map_err(|e| parse_error(&e))?;
+                toml::from_str::<FileSections>(content).map_err(|e| section_error(&e))?;
+                value
+            }
+            Self::Yaml => {
+                let value = serde_yaml::from_str(content).map_err(|e| parse_error(&e))?;
+                // An empty YAML document is an empty configuration
+                if !serde_json::Value::is_null(&value) {
+                    serde_yaml::from_str::<FileSections>(content).map_err(|e| section_error(&e))?;
+                }
+                value
+            }
+            Self::Json => {
+                let value = serde_json::from_str(content).map_err(|e| parse_error(&e))?;
+                serde_json::from_str::<FileSections>(content).map_err(|e| section_error(&e))?;
+                value
+            }
+        };
+        match value {
+            serde_json::Value::Object(settings) => Ok(settings),
+            serde_json::Value::Null => Ok(Settings::new()),
+            _ => Err(parse_error(&"the configuration must be a mapping")),
+        }
+    }
+
+    /// Rewrite a configuration file in another format
+    ///
+    /// Settings are kept as written: includes, `extends` and `${VAR}`
+    /// references are not resolved, so the converted file loads the same way.
+    /// TOML has no `null`, so `null` values are left out of TOML files and
+    /// listed in [`Converted::dropped_nulls`].
+    pub fn convert(&self, content: &str, to: ConfigFormat) -> Result<Converted> {
+        let mut value = self.read(content).map_err(|e| {
+            HttpDiffError::invalid_config(format!("Failed to parse {}: {}", self.name(), e))
+        })?;
+        let mut dropped_nulls = Vec::new();
+        if to == Self::Toml {
+            remove_nulls(&mut value, "", &mut dropped_nulls);
+        }
+        let content = to.write(&value).map_err(|e| {
+            HttpDiffError::invalid_config(format!("Failed to write {}: {}", to.name(), e))
+        })?;
+        Ok(Converted {
+            content,
+            dropped_nulls,
+        })
+    }
+
+    /// Read a document into a value keeping the order of its keys
+    fn read(&self, content: &str) -> std::result::Result<serde_yaml::Value, String> {
+        let value: serde_yaml::Value = match self {
+            Self::Toml => toml::from_str(content).map_err(|e| e.to_string())?,
+            Self::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string())?,
+            Self::Json => serde_json::from_str(content).map_err(|e| e.to_string())?,
+        };
+        // An empty YAML document is an empty configuration
+        if value.is_null() {
+            return Ok(serde_yaml::Value::Mapping(Default::default()));
+        }
+        Ok(value)
+    }
+
+    fn write(&self, value: &serde_yaml::Value) -> std::result::Result<String, String> {
+        match self {
+            Self::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+            Self::Json => serde_json::to_string_pretty(value)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// A configuration rewritten by [`ConfigFormat::convert`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Converted {
+    pub content: String,
+    /// Paths of the `null` values left out of a TOML file, such as `routes[0].json.manager`
+    pub dropped_nulls: Vec<String>,
+}
+
+/// Settings without their `null` values, as they read back from a TOML file
+pub fn without_nulls(settings: &Settings) -> Settings {
+    fn strip(value: &serde_json::Value) -> Option<serde_json::Value> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Array(items) => Some(serde_json::Value::Array(
+                items.iter().filter_map(strip).collect(),
+            )),
+            serde_json::Value::Object(fields) => Some(serde_json::Value::Object(
+                fields
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), strip(value)?)))
+                    .collect(),
+            )),
+            value => Some(value.clone()),
+        }
+    }
+    settings
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), strip(value)?)))
+        .collect()
+}
+
+/// Drop `null` entries, which TOML cannot represent, recording their paths
+fn remove_nulls(value: &mut serde_yaml::Value, path: &str, dropped: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter_mut() {
+                let path = child_path(path, key);
+                if value.is_null() {
+                    dropped.push(path);
+                } else {
+                    remove_nulls(value, &path, dropped);
+                }
+            }
+            mapping.retain(|_, value| !value.is_null());
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for (index, value) in items.iter_mut().enumerate() {
+                let path = format!("{}[{}]", path, index);
+                if value.is_null() {
+                    dropped.push(path);
+                } else {
+                    remove_nulls(value, &path, dropped);
+                }
+            }
+            items.retain(|value| !value.is_null());
+        }
+        _ => {}
+    }
+}
+
+fn child_path(path: &str, key: &serde_yaml::Value) -> String {
+    let key = match key {
+        serde_yaml::Value::String(key) => key.clone(),
+        key => serde_yaml::to_string(key)
+            .map(|key| key.trim_end().to_string())
+            .unwrap_or_default(),
+    };
+    if path.is_empty() {
+        key
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"include = ["routes/*.toml"]
+
+[environments.staging]
+base_url = "https://${STAGING_HOST:-staging.example.com}"
+is_base = true
+
+[environments.prod]
+extends = "staging"
+base_url = "https://api.example.com"
+
+[[routes]]
+name = "get_user"
+method = "GET"
+path = "/users/{userId}"
+
+[routes.headers]
+Accept = "application/json"
+"#;
+
+    #[test]
+    fn test_convert_round_trips_between_formats() {
+        let yaml = ConfigFormat::Toml
+            .convert(TOML, ConfigFormat::Yaml)
+            .unwrap()
+            .content;
+        assert!(yaml.contains("- name: get_user\n  method: GET"), "{}", yaml);
+        let json = ConfigFormat::Yaml
+            .convert(&yaml, ConfigFormat::Json)
+            .unwrap()
+            .content;
+        assert!(json.contains(r#""extends": "staging""#), "{}", json);
+        let toml = ConfigFormat::Json
+            .convert(&json, ConfigFormat::Toml)
+            .unwrap()
+            .content;
+
+        let path = Path::new("http-diff.toml");
+        let original = ConfigFormat::Toml.parse(path, TOML).unwrap();
+        assert_eq!(ConfigFormat::Toml.parse(path, &toml).unwrap(), original);
+        assert_eq!(
+            ConfigFormat::Yaml
+                .parse(Path::new("http-diff.yaml"), &yaml)
+                .unwrap(),
+            original
+        );
+        assert_eq!(
+            ConfigFormat::Json
+                .parse(Path::new("http-diff.json"), &json)
+                .unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_format_and_location() {
+        assert_eq!(
+            ConfigFormat::from_path("config/http-diff.YML"),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(ConfigFormat::from_path("http-diff"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_extension("http-diff.ini"), None);
+
+        let error = ConfigFormat::Yaml
+            .parse(
+                Path::new("http-diff.yaml"),
+                "routes:\n  - name: get_user\n    method: GET\n",
+            )
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("http-diff.yaml"), "{}", error);
+        assert!(error.contains("missing field `path`"), "{}", error);
+        assert!(error.contains("line 2"), "{}", error);
+
+        let table = ConfigFormat::Json
+            .parse(
+                Path::new("http-diff.json"),
+                r#"{"global": {"timeout_seconds": null}, "routes": []}"#,
+            )
+            .unwrap();
+        assert_eq!(table["global"]["timeout_seconds"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_nulls_are_kept_except_in_toml() {
+        let yaml = "routes:\n  - name: create_user\n    method: POST\n    path: /users\n    json:\n      manager: null\n      items: [1, null, 2]\n";
+        let settings = ConfigFormat::Yaml
+            .parse(Path::new("http-diff.yaml"), yaml)
+            .unwrap();
+        assert_eq!(
+            settings["routes"][0]["json"],
+            serde_json::json!({"manager": null, "items": [1, null, 2]})
+        );
+
+        let json = ConfigFormat::Yaml
+            .convert(yaml, ConfigFormat::Json)
+            .unwrap();
+        assert!(json.dropped_nulls.is_empty());
+        let from_json = ConfigFormat::Json
+            .parse(Path::new("http-diff.json"), &json.content)
+            .unwrap();
+        assert_eq!(from_json, settings);
+
+        let toml = ConfigFormat::Yaml
+            .convert(yaml, ConfigFormat::Toml)
+            .unwrap();
+        assert_eq!(
+            toml.dropped_nulls,
+            vec!["routes[0].json.manager", "routes[0].json.items[1]"]
+        );
+        let from_toml = ConfigFormat::Toml
+            .parse(Path::new("http-diff.toml"), &toml.content)
+            .unwrap();
+        assert_eq!(from_toml, without_nulls(&settings));
+    }
+}
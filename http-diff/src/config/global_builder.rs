@@ -3,7 +3,7 @@
 //! This module provides a dedicated builder for global HTTP configurations
 //! that can be accessed through the main configuration builder.
 
-use crate::config::types::GlobalConfig;
+use crate::config::types::{FailOn, GlobalConfig, LatencyThreshold, RetryConfig, SecretsConfig};
 use std::collections::HashMap;
 
 /// Builder for global HTTP configuration with fluent API
@@ -62,6 +62,63 @@ impl GlobalConfigBuilder {
         self
     }
 
+    /// Set which differences cause the run to fail
+    pub fn fail_on(mut self, fail_on: FailOn) -> Self {
+        self.config.fail_on = Some(fail_on);
+        self
+    }
+
+    /// Set the maximum number of mixed-status responses before the run fails
+    pub fn max_mixed_responses(mut self, max_mixed: usize) -> Self {
+        self.config.max_mixed_responses = Some(max_mixed);
+        self
+    }
+
+    /// Set whether execution errors cause the run to fail
+    pub fn fail_on_errors(mut self, fail: bool) -> Self {
+        self.config.fail_on_errors = Some(fail);
+        self
+    }
+
+    /// Set whether failing route assertions fail the run
+    pub fn fail_on_assertions(mut self, fail: bool) -> Self {
+        self.config.fail_on_assertions = Some(fail);
+        self
+    }
+
+    /// Add a JSONPath expression for a body field excluded from comparison
+    pub fn ignore_field<S: Into<String>>(mut self, path: S) -> Self {
+        self.config
+            .ignore_fields
+            .get_or_insert_with(Vec::new)
+            .push(path.into());
+        self
+    }
+
+    /// Set the latency difference that is reported as a regression
+    pub fn latency_threshold(mut self, threshold: LatencyThreshold) -> Self {
+        self.config.latency_threshold = Some(threshold);
+        self
+    }
+
+    /// Set the retry policy for flaky requests
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.config.retry = Some(retry);
+        self
+    }
+
+    /// Set where `{secret:NAME}` values come from
+    pub fn secrets(mut self, secrets: SecretsConfig) -> Self {
+        self.config.secrets = Some(secrets);
+        self
+    }
+
+    /// Set whether cookies are kept per user and environment across requests
+    pub fn cookie_jar(mut self, enabled: bool) -> Self {
+        self.config.cookie_jar = Some(enabled);
+        self
+    }
+
     /// Build the global configuration
     pub fn build(self) -> GlobalConfig {
         self.config
@@ -102,6 +159,15 @@ mod tests {
             max_concurrent_requests: Some(20),
             headers: None,
             params: None,
+            fail_on: None,
+            max_mixed_responses: None,
+            fail_on_errors: None,
+            fail_on_assertions: None,
+            ignore_fields: None,
+            latency_threshold: None,
+            retry: None,
+            secrets: None,
+            cookie_jar: None,
         };
 
         let config = GlobalConfigBuilder::from_config(existing)
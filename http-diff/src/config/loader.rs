@@ -1,7 +1,26 @@
-use crate::config::types::{HttpDiffConfig, UserData};
+//! Configuration loading
+//!
+//! Configuration files are TOML, YAML or JSON, by extension (see
+//! [`ConfigFormat`]). Besides a single `http-diff.toml`, a configuration may be split across
+//! files and reuse settings:
+//!
+//! - `include = ["routes/*.toml"]` loads more files, relative to the including
+//!   one; their environments, routes and profiles are added to it, and their
+//!   other settings fill the ones it does not set
+//! - `extends = "staging"` on an environment inherits the settings of another
+//! - `${VAR}` and `${VAR:-default}` in string values are replaced by process
+//!   environment variables when the file is loaded; `$${` writes a literal `${`.
+//!   [`DefaultConfigLoader::load_without_interpolation`] keeps them as
+//!   `{$VAR}` placeholders instead, for configurations that are shared
+//!
+//! Errors point at the file and line the faulty setting comes from.
+
+use crate::config::format::{ConfigFormat, Settings};
+use crate::config::types::{Environment, GlobalConfig, HttpDiffConfig, Profile, Route, UserData};
 use crate::error::{HttpDiffError, Result};
+use serde_json::Value;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Configuration loader trait
 pub trait ConfigLoader {
@@ -12,32 +31,550 @@ pub trait ConfigLoader {
 pub struct DefaultConfigLoader;
 
 impl ConfigLoader for DefaultConfigLoader {
-    /// Load configuration from http-diff.toml file with validation and enhanced error context
+    /// Load configuration from http-diff.toml, .yaml, .yml or .json file, chosen by extension
     fn load_from_file<P: AsRef<Path>>(path: P) -> Result<HttpDiffConfig> {
-        let path_ref = path.as_ref();
+        Self::load_with_sources(path).map(|(config, _)| config)
+    }
+}
+
+impl DefaultConfigLoader {
+    /// Load configuration along with the files and lines its parts are defined at
+    pub fn load_with_sources<P: AsRef<Path>>(path: P) -> Result<(HttpDiffConfig, ConfigSources)> {
+        load_with_format(path.as_ref(), ConfigFormat::from_path(&path))
+    }
 
-        // Check if file exists and provide helpful error message
-        if !path_ref.exists() {
+    /// Load configuration keeping `${VAR}` references instead of their values
+    ///
+    /// References become `{$VAR}` placeholders, and `{$VAR ?? "default"}` when
+    /// they have a default, so exporting the configuration does not write out
+    /// the values of environment variables.
+    pub fn load_without_interpolation<P: AsRef<Path>>(path: P) -> Result<HttpDiffConfig> {
+        let path = path.as_ref();
+        if !path.exists() {
             return Err(HttpDiffError::ConfigNotFound {
-                path: path_ref.to_path_buf(),
+                path: path.to_path_buf(),
             });
         }
 
-        let content = std::fs::read_to_string(path_ref).map_err(HttpDiffError::Io)?;
+        let mut document = Document {
+            keep_env_references: true,
+            ..Document::default()
+        };
+        document.load(path, ConfigFormat::from_path(path), &mut Vec::new())?;
+        document.into_config().map(|(config, _)| config)
+    }
+}
+
+/// Loads TOML configuration files
+pub struct TomlConfigLoader;
+
+impl ConfigLoader for TomlConfigLoader {
+    fn load_from_file<P: AsRef<Path>>(path: P) -> Result<HttpDiffConfig> {
+        load_with_format(path.as_ref(), ConfigFormat::Toml).map(|(config, _)| config)
+    }
+}
+
+/// Loads YAML configuration files
+pub struct YamlConfigLoader;
+
+impl ConfigLoader for YamlConfigLoader {
+    fn load_from_file<P: AsRef<Path>>(path: P) -> Result<HttpDiffConfig> {
+        load_with_format(path.as_ref(), ConfigFormat::Yaml).map(|(config, _)| config)
+    }
+}
+
+/// Loads JSON configuration files
+pub struct JsonConfigLoader;
+
+impl ConfigLoader for JsonConfigLoader {
+    fn load_from_file<P: AsRef<Path>>(path: P) -> Result<HttpDiffConfig> {
+        load_with_format(path.as_ref(), ConfigFormat::Json).map(|(config, _)| config)
+    }
+}
+
+/// Load configuration with validation and enhanced error context
+fn load_with_format(path: &Path, format: ConfigFormat) -> Result<(HttpDiffConfig, ConfigSources)> {
+    // Check if file exists and provide helpful error message
+    if !path.exists() {
+        return Err(HttpDiffError::ConfigNotFound {
+            path: path.to_path_buf(),
+        });
+    }
+
+    let mut document = Document::default();
+    document.load(path, format, &mut Vec::new())?;
+    document.into_config()
+}
+
+/// File and line a setting is defined at
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// Where the parts of a loaded configuration are defined
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    pub environments: HashMap<String, SourceLocation>,
+    pub routes: HashMap<String, SourceLocation>,
+    pub profiles: HashMap<String, SourceLocation>,
+    /// `[global]` table of the file whose settings take precedence
+    pub global: Option<SourceLocation>,
+}
+
+impl SourceLocation {
+    fn error<S: Into<String>>(&self, message: S) -> HttpDiffError {
+        HttpDiffError::config_at(self.path.clone(), self.line, message)
+    }
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)
+    }
+}
+
+/// Settings gathered from a configuration file and the files it includes
+#[derive(Default)]
+struct Document {
+    /// Settings other than environments, routes and profiles
+    settings: Settings,
+    environments: Vec<(String, Settings, SourceLocation)>,
+    routes: Vec<(Value, SourceLocation)>,
+    profiles: Vec<(String, Value, SourceLocation)>,
+    global: Option<SourceLocation>,
+    /// Keep `${VAR}` references as placeholders instead of substituting them
+    keep_env_references: bool,
+}
+
+impl Document {
+    /// Add the settings of a file, then those of the files it includes
+    ///
+    /// Included files are read in the format of their extension. `stack`
+    /// holds the files being loaded, to detect include cycles.
+    fn load(&mut self, path: &Path, format: ConfigFormat, stack: &mut Vec<PathBuf>) -> Result<()> {
+        let content = std::fs::read_to_string(path).map_err(HttpDiffError::Io)?;
+
+        let mut table = format.parse(path, &content)?;
+        let lines = Lines::new(path, &content);
+        for (_, value) in table.iter_mut() {
+            interpolate(value, &lines, self.keep_env_references)?;
+        }
+
+        let includes = table.remove("include");
+        if let Some(environments) = table.remove("environments") {
+            let Value::Object(environments) = environments else {
+                return Err(lines
+                    .at("[environments]")
+                    .error("environments must be a table"));
+            };
+            for (name, environment) in environments {
+                let origin = lines.section("environments", &name);
+                let Value::Object(environment) = environment else {
+                    return Err(origin.error(format!("environment '{}' must be a table", name)));
+                };
+                if let Some((_, _, first)) = self.environments.iter().find(|(n, _, _)| *n == name) {
+                    return Err(origin.error(format!(
+                        "environment '{}' is already defined at {}",
+                        name, first
+                    )));
+                }
+                self.environments.push((name, environment, origin));
+            }
+        }
+        if let Some(Value::Array(routes)) = table.remove("routes") {
+            let mut cursor = 0;
+            for route in routes {
+                let name = route
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or_default();
+                let origin = lines.route(name, &mut cursor);
+                self.routes.push((route, origin));
+            }
+        }
+        if let Some(Value::Object(profiles)) = table.remove("profiles") {
+            for (name, profile) in profiles {
+                let origin = lines.section("profiles", &name);
+                if let Some((_, _, first)) = self.profiles.iter().find(|(n, _, _)| *n == name) {
+                    return Err(origin.error(format!(
+                        "profile '{}' is already defined at {}",
+                        name, first
+                    )));
+                }
+                self.profiles.push((name, profile, origin));
+            }
+        }
+        if self.global.is_none() && table.contains_key("global") {
+            self.global = Some(lines.key("global"));
+        }
+        // Settings of the including file take precedence over included ones
+        merge_missing(&mut self.settings, table);
+
+        stack.push(path.canonicalize().map_err(HttpDiffError::Io)?);
+        for include in include_patterns(includes, &lines)? {
+            for included in expand_include(path, &include, &lines)? {
+                let canonical = included.canonicalize().map_err(HttpDiffError::Io)?;
+                if stack.contains(&canonical) {
+                    let chain: Vec<String> = stack
+                        .iter()
+                        .chain(std::iter::once(&canonical))
+                        .map(|p| p.display().to_string())
+                        .collect();
+                    return Err(lines
+                        .at("include")
+                        .error(format!("include cycle: {}", chain.join(" -> "))));
+                }
+                self.load(&included, ConfigFormat::from_path(&included), stack)?;
+            }
+        }
+        stack.pop();
+        Ok(())
+    }
+
+    fn into_config(self) -> Result<(HttpDiffConfig, ConfigSources)> {
+        let mut sources = ConfigSources {
+            global: self.global.clone(),
+            ..Default::default()
+        };
+        let mut environments = HashMap::new();
+        for (name, _, origin) in &self.environments {
+            let table = resolve_environment(&self.environments, name, &mut Vec::new())?;
+            let environment: Environment = serde_json::from_value(Value::Object(table))
+                .map_err(|e| origin.error(format!("environment '{}': {}", name, e)))?;
+            environments.insert(name.clone(), environment);
+            sources.environments.insert(name.clone(), origin.clone());
+        }
+
+        let mut routes: Vec<Route> = Vec::with_capacity(self.routes.len());
+        let mut route_origins: Vec<&SourceLocation> = Vec::with_capacity(self.routes.len());
+        for (value, origin) in &self.routes {
+            let mut route: Route = serde_json::from_value(value.clone())
+                .map_err(|e| origin.error(format!("route: {}", e)))?;
+            if let Some(index) = routes.iter().position(|r| r.name == route.name) {
+                return Err(origin.error(format!(
+                    "route '{}' is already defined at {}",
+                    route.name, route_origins[index]
+                )));
+            }
+            route.base_dir = origin.path.parent().map(Path::to_path_buf);
+            sources.routes.insert(route.name.clone(), origin.clone());
+            routes.push(route);
+            route_origins.push(origin);
+        }
+
+        let mut profiles = HashMap::new();
+        for (name, value, origin) in self.profiles {
+            let profile: Profile = serde_json::from_value(value)
+                .map_err(|e| origin.error(format!("profile '{}': {}", name, e)))?;
+            profiles.insert(name.clone(), profile);
+            sources.profiles.insert(name, origin);
+        }
+
+        let global = match self.settings.get("global") {
+            Some(global) => Some(
+                serde_json::from_value::<GlobalConfig>(global.clone()).map_err(|e| {
+                    let message = format!("invalid [global] settings: {}", e);
+                    match &self.global {
+                        Some(origin) => origin.error(message),
+                        None => HttpDiffError::invalid_config(message),
+                    }
+                })?,
+            ),
+            None => None,
+        };
+
+        let config = HttpDiffConfig {
+            environments,
+            global,
+            routes,
+            profiles,
+        };
+        Ok((config, sources))
+    }
+}
+
+/// Settings of an environment with those it inherits through `extends`
+fn resolve_environment(
+    environments: &[(String, Settings, SourceLocation)],
+    name: &str,
+    chain: &mut Vec<String>,
+) -> Result<Settings> {
+    let (_, table, origin) = environments
+        .iter()
+        .find(|(n, _, _)| n == name)
+        .expect("resolved environments are defined");
+    let Some(parent) = table.get("extends") else {
+        return Ok(table.clone());
+    };
+    let Some(parent) = parent.as_str() else {
+        return Err(origin.error(format!("environment '{}': extends must be a string", name)));
+    };
+
+    chain.push(name.to_string());
+    if chain.iter().any(|n| n == parent) {
+        chain.push(parent.to_string());
+        return Err(origin.error(format!(
+            "environment inheritance cycle: {}",
+            chain.join(" -> ")
+        )));
+    }
+    if !environments.iter().any(|(n, _, _)| n == parent) {
+        return Err(origin.error(format!(
+            "environment '{}' extends unknown environment '{}'",
+            name, parent
+        )));
+    }
+
+    let mut resolved = table.clone();
+    let mut inherited = resolve_environment(environments, parent, chain)?;
+    inherited.remove("is_base");
+    merge_missing(&mut resolved, inherited);
+    Ok(resolved)
+}
+
+/// Add the entries of `other` that `table` lacks, merging nested tables
+fn merge_missing(table: &mut Settings, other: Settings) {
+    for (key, value) in other {
+        match (table.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(value)) => {
+                merge_missing(existing, value)
+            }
+            (Some(_), _) => {}
+            (None, value) => {
+                table.insert(key, value);
+            }
+        }
+    }
+}
+
+fn include_patterns(includes: Option<Value>, lines: &Lines) -> Result<Vec<String>> {
+    match includes {
+        None => Ok(Vec::new()),
+        Some(Value::String(pattern)) => Ok(vec![pattern]),
+        Some(Value::Array(patterns)) => patterns
+            .into_iter()
+            .map(|pattern| match pattern {
+                Value::String(pattern) => Ok(pattern),
+                _ => Err(lines.at("include").error("include entries must be strings")),
+            })
+            .collect(),
+        Some(_) => Err(lines
+            .at("include")
+            .error("include must be a path or a list of paths")),
+    }
+}
+
+/// Files matching an include pattern, relative to the including file, in name order
+fn expand_include(from: &Path, pattern: &str, lines: &Lines) -> Result<Vec<PathBuf>> {
+    let base = from.parent().unwrap_or(Path::new(""));
+    let full = base.join(pattern);
+    let full = full.to_string_lossy();
+
+    if !pattern.contains(['*', '?', '[']) {
+        let path = PathBuf::from(full.as_ref());
+        if !path.is_file() {
+            return Err(lines
+                .at(pattern)
+                .error(format!("included file {} not found", path.display())));
+        }
+        return Ok(vec![path]);
+    }
+
+    let entries = glob::glob(&full).map_err(|e| {
+        lines
+            .at(pattern)
+            .error(format!("invalid include pattern '{}': {}", pattern, e))
+    })?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Replace `${VAR}` references in the strings of a value
+///
+/// With `keep`, references become `{$VAR}` placeholders instead of values.
+fn interpolate(value: &mut Value, lines: &Lines, keep: bool) -> Result<()> {
+    match value {
+        Value::String(text) if text.contains('$') => {
+            *text = interpolate_text(text, keep)
+                .map_err(|(reference, message)| lines.at(&reference).error(message))?;
+        }
+        Value::Array(items) => {
+            for item in items {
+                interpolate(item, lines, keep)?;
+            }
+        }
+        Value::Object(table) => {
+            for (_, item) in table.iter_mut() {
+                interpolate(item, lines, keep)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Substitute process environment variables in a string
+///
+/// With `keep`, references are rewritten as placeholders instead. Errors carry
+/// the reference that failed, to locate it, and a message.
+fn interpolate_text(text: &str, keep: bool) -> std::result::Result<String, (String, String)> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start..];
+        if let Some(escaped) = after.strip_prefix("$${") {
+            result.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let Some(reference) = after.strip_prefix("${") else {
+            result.push('$');
+            rest = &after[1..];
+            continue;
+        };
+        let Some(end) = reference.find('}') else {
+            return Err((
+                after.to_string(),
+                format!("unterminated variable reference '{}'", after),
+            ));
+        };
+        let (name, default) = match reference[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&reference[..end], None),
+        };
+        if keep {
+            match default {
+                Some(default) => result.push_str(&format!("{{${} ?? {:?}}}", name, default)),
+                None => result.push_str(&format!("{{${}}}", name)),
+            }
+            rest = &reference[end + 1..];
+            continue;
+        }
+        match (std::env::var(name), default) {
+            (Ok(value), _) => result.push_str(&value),
+            (Err(_), Some(default)) => result.push_str(default),
+            (Err(_), None) => {
+                return Err((
+                    format!("${{{}", name),
+                    format!("environment variable '{}' is not set", name),
+                ))
+            }
+        }
+        rest = &reference[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Locates settings in the text of a configuration file
+struct Lines<'a> {
+    path: &'a Path,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(path: &'a Path, content: &'a str) -> Self {
+        Self {
+            path,
+            lines: content.lines().collect(),
+        }
+    }
+
+    fn origin(&self, index: Option<usize>) -> SourceLocation {
+        SourceLocation {
+            path: self.path.to_path_buf(),
+            line: index.map_or(1, |index| index + 1),
+        }
+    }
+
+    /// First line containing `needle`
+    fn at(&self, needle: &str) -> SourceLocation {
+        self.origin(self.lines.iter().position(|line| line.contains(needle)))
+    }
+
+    /// Line defining a top-level table, such as `[global]` or `global:`
+    fn key(&self, name: &str) -> SourceLocation {
+        let header = format!("[{}", name);
+        self.origin(self.lines.iter().position(|line| {
+            line.starts_with(&header) || key_value(line).is_some_and(|(key, _)| key == name)
+        }))
+    }
 
-        // Parse TOML with enhanced error context
-        let config: HttpDiffConfig = toml::from_str(&content).map_err(|e| {
-            HttpDiffError::invalid_config(format!(
-                "Failed to parse TOML in {}: {}",
-                path_ref.display(),
-                e
-            ))
-        })?;
+    /// Line defining an entry of a table, such as `[environments.prod]` or `prod:`
+    fn section(&self, table: &str, name: &str) -> SourceLocation {
+        let headers = [
+            format!("[{}.{}]", table, name),
+            format!("[{}.\"{}\"]", table, name),
+            format!("[{}.{}.", table, name),
+        ];
+        // YAML and JSON entries are keys nested under the table key
+        let start = self
+            .lines
+            .iter()
+            .position(|line| key_value(line).is_some_and(|(key, _)| key == table))
+            .unwrap_or(0);
+        let index = self
+            .lines
+            .iter()
+            .position(|line| {
+                headers
+                    .iter()
+                    .any(|header| line.trim_start().starts_with(header.as_str()))
+            })
+            .or_else(|| {
+                self.lines[start..]
+                    .iter()
+                    .position(|line| key_value(line).is_some_and(|(key, _)| key == name))
+                    .map(|offset| start + offset)
+            });
+        self.origin(index)
+    }
 
-        Ok(config)
+    /// Line naming the next route, searching from `cursor`
+    fn route(&self, name: &str, cursor: &mut usize) -> SourceLocation {
+        let found = self.lines[*cursor..]
+            .iter()
+            .position(|line| key_value(line) == Some(("name", name)));
+        match found {
+            Some(offset) => {
+                *cursor += offset + 1;
+                self.origin(Some(*cursor - 1))
+            }
+            None => self.origin(None),
+        }
     }
 }
 
+/// First key and value of a line such as `name = "x"`, `- name: x` or `{ "name": "x", ...`
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start_matches(|c: char| c.is_whitespace() || c == '-' || c == '{');
+    let (key, rest) = quoted(line).or_else(|| {
+        let end = line.find([':', '=', ' '])?;
+        Some((&line[..end], &line[end..]))
+    })?;
+    let rest = rest.trim_start().strip_prefix([':', '='])?.trim();
+    let value = match quoted(rest) {
+        Some((value, _)) => value,
+        None => rest[..rest.find([',', '}']).unwrap_or(rest.len())].trim_end(),
+    };
+    Some((key, value))
+}
+
+/// Content of a quoted string at the start of `text`, and the text after it
+fn quoted(text: &str) -> Option<(&str, &str)> {
+    let quote = text.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let end = text[1..].find(quote)? + 1;
+    Some((&text[1..end], &text[end + 1..]))
+}
+
 /// Load user data from CSV file
 pub fn load_user_data<P: AsRef<Path>>(path: P) -> Result<Vec<UserData>> {
     let mut reader = csv::Reader::from_path(path)?;
@@ -72,3 +609,297 @@ impl HttpDiffConfig {
         DefaultConfigLoader::load_from_file(path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_includes_and_environment_inheritance() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("HTTP_DIFF_LOADER_TEST_HOST", "prod.example.com");
+        let main = write(
+            dir.path(),
+            "http-diff.toml",
+            r#"
+include = ["routes/*.toml"]
+
+[global]
+timeout_seconds = 5
+
+[environments.staging]
+base_url = "https://staging.example.com"
+is_base = true
+headers = { "X-Client" = "http-diff", "X-Tier" = "staging" }
+
+[environments.prod]
+extends = "staging"
+base_url = "https://${HTTP_DIFF_LOADER_TEST_HOST}"
+headers = { "X-Tier" = "prod" }
+
+[environments.canary]
+extends = "prod"
+base_url = "https://${HTTP_DIFF_LOADER_UNSET_HOST:-canary.example.com}/$${raw}"
+"#,
+        );
+        write(
+            dir.path(),
+            "routes/orders.toml",
+            r#"
+[global]
+timeout_seconds = 60
+follow_redirects = false
+
+[[routes]]
+name = "get_order"
+method = "GET"
+path = "/orders/{orderId}"
+
+[profiles.smoke]
+routes = ["get_*"]
+"#,
+        );
+        write(
+            dir.path(),
+            "routes/users.toml",
+            "[[routes]]\nname = \"get_user\"\nmethod = \"GET\"\npath = \"/users/{userId}\"\n",
+        );
+
+        let config = DefaultConfigLoader::load_from_file(&main).unwrap();
+        let names: Vec<_> = config.routes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["get_order", "get_user"]);
+        // Files routes refer to are read next to the file declaring them
+        assert_eq!(
+            config.routes[0].resolve_path("fixtures/order.json"),
+            dir.path().join("routes/fixtures/order.json")
+        );
+        let global = config.global.as_ref().unwrap();
+        assert_eq!(global.timeout_seconds, Some(5));
+        assert_eq!(global.follow_redirects, Some(false));
+        assert!(config.profiles.contains_key("smoke"));
+
+        let prod = &config.environments["prod"];
+        assert_eq!(prod.base_url, "https://prod.example.com");
+        assert!(!prod.is_base);
+        assert_eq!(prod.extends.as_deref(), Some("staging"));
+        let headers = prod.headers.as_ref().unwrap();
+        assert_eq!(headers["X-Tier"], "prod");
+        assert_eq!(headers["X-Client"], "http-diff");
+
+        let canary = &config.environments["canary"];
+        assert_eq!(canary.base_url, "https://canary.example.com/${raw}");
+        assert_eq!(canary.headers.as_ref().unwrap()["X-Tier"], "prod");
+    }
+
+    #[test]
+    fn test_load_without_interpolation_keeps_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write(
+            dir.path(),
+            "http-diff.toml",
+            r#"
+[environments.prod]
+base_url = "https://${HTTP_DIFF_LOADER_UNSET_HOST:-api.example.com}/$${raw}"
+headers = { "Authorization" = "Bearer ${HTTP_DIFF_LOADER_UNSET_TOKEN}" }
+
+[[routes]]
+name = "r"
+method = "GET"
+path = "/"
+"#,
+        );
+
+        // Unset variables are not an error, as nothing is substituted
+        let config = DefaultConfigLoader::load_without_interpolation(&path).unwrap();
+        let prod = &config.environments["prod"];
+        assert_eq!(
+            prod.base_url,
+            r#"https://{$HTTP_DIFF_LOADER_UNSET_HOST ?? "api.example.com"}/${raw}"#
+        );
+        assert_eq!(
+            prod.headers.as_ref().unwrap()["Authorization"],
+            "Bearer {$HTTP_DIFF_LOADER_UNSET_TOKEN}"
+        );
+    }
+
+    #[test]
+    fn test_errors_point_at_file_and_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let error = |content: &str| {
+            let path = write(dir.path(), "http-diff.toml", content);
+            DefaultConfigLoader::load_from_file(&path)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let message = error(
+            "[environments.a]\nbase_url = \"http://a\"\n\n[environments.b]\nextends = \"missing\"\n\n[[routes]]\nname = \"r\"\nmethod = \"GET\"\npath = \"/\"\n",
+        );
+        assert!(
+            message.contains(
+                "http-diff.toml:4: environment 'b' extends unknown environment 'missing'"
+            ),
+            "{}",
+            message
+        );
+
+        let message = error(
+            "[environments.a]\nbase_url = \"${HTTP_DIFF_LOADER_UNSET_VAR}\"\n\n[[routes]]\nname = \"r\"\nmethod = \"GET\"\npath = \"/\"\n",
+        );
+        assert!(
+            message.contains(
+                "http-diff.toml:2: environment variable 'HTTP_DIFF_LOADER_UNSET_VAR' is not set"
+            ),
+            "{}",
+            message
+        );
+
+        write(
+            dir.path(),
+            "more.toml",
+            "include = \"http-diff.toml\"\n\n[[routes]]\nname = \"r\"\nmethod = \"GET\"\npath = \"/other\"\n",
+        );
+        let message = error(
+            "include = \"more.toml\"\n\n[environments.a]\nbase_url = \"http://a\"\n\n[[routes]]\nname = \"r\"\nmethod = \"GET\"\npath = \"/\"\n",
+        );
+        assert!(
+            message.contains("more.toml:1: include cycle"),
+            "{}",
+            message
+        );
+
+        // Each file's [global] is checked when read; the merged one is checked again
+        let mut document = Document::default();
+        document.settings.insert(
+            "global".to_string(),
+            serde_json::json!({"timeout_seconds": "soon"}),
+        );
+        document.global = Some(SourceLocation {
+            path: dir.path().join("http-diff.toml"),
+            line: 4,
+        });
+        let message = document.into_config().unwrap_err().to_string();
+        assert!(
+            message.contains("http-diff.toml:4: invalid [global] settings"),
+            "{}",
+            message
+        );
+
+        let message =
+            error("include = \"missing.toml\"\n[environments.a]\nbase_url = \"http://a\"\n");
+        assert!(
+            message.contains("http-diff.toml:1: included file"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_duplicate_routes_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "extra.toml",
+            "\n[[routes]]\nname = \"health\"\nmethod = \"GET\"\npath = \"/v2/health\"\n",
+        );
+        let main = write(
+            dir.path(),
+            "http-diff.toml",
+            "include = [\"extra.toml\"]\n\n[environments.a]\nbase_url = \"http://a\"\n\n[[routes]]\nname = \"health\"\nmethod = \"GET\"\npath = \"/health\"\n",
+        );
+
+        let message = DefaultConfigLoader::load_from_file(&main)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("extra.toml:3: route 'health' is already defined at"),
+            "{}",
+            message
+        );
+        assert!(message.contains("http-diff.toml:7"), "{}", message);
+    }
+
+    #[test]
+    fn test_yaml_and_json_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "routes.json",
+            r#"{
+  "routes": [
+    {
+      "name": "get_user",
+      "method": "GET",
+      "path": "/users/{userId}",
+      "headers": null,
+      "json": { "manager": null, "items": [1, null] }
+    }
+  ]
+}
+"#,
+        );
+        let main = write(
+            dir.path(),
+            "http-diff.yaml",
+            r#"include: routes.json
+environments:
+  staging:
+    base_url: https://staging.example.com
+    is_base: true
+  prod:
+    extends: staging
+    base_url: https://prod.example.com
+"#,
+        );
+
+        let config = DefaultConfigLoader::load_from_file(&main).unwrap();
+        assert_eq!(config.routes[0].name, "get_user");
+        assert!(config.routes[0].headers.is_none());
+        assert_eq!(
+            config.routes[0].json,
+            Some(serde_json::json!({"manager": null, "items": [1, null]}))
+        );
+        assert!(!config.environments["prod"].is_base);
+        let message = TomlConfigLoader::load_from_file(&main)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Failed to parse TOML"), "{}", message);
+
+        let main = write(
+            dir.path(),
+            "http-diff.yml",
+            "environments:\n  staging:\n    base_url: https://staging.example.com\n  prod:\n    extends: qa\n    base_url: https://prod.example.com\n",
+        );
+        let message = YamlConfigLoader::load_from_file(&main)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message
+                .contains("http-diff.yml:4: environment 'prod' extends unknown environment 'qa'"),
+            "{}",
+            message
+        );
+
+        let main = write(
+            dir.path(),
+            "http-diff.json",
+            "{\n  \"environments\": {\n    \"a\": { \"base_url\": \"http://a\" }\n  },\n  \"routes\": [\n    { \"name\": \"r\", \"method\": \"GET\", \"path\": \"/\" },\n    { \"name\": \"r\", \"method\": \"GET\", \"path\": \"/other\" }\n  ]\n}\n",
+        );
+        let message = JsonConfigLoader::load_from_file(&main)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("http-diff.json:7: route 'r' is already defined at"),
+            "{}",
+            message
+        );
+    }
+}
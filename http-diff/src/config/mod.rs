@@ -1,4 +1,6 @@
 pub mod builder;
+pub mod check;
+pub mod format;
 pub mod global_builder;
 pub mod loader;
 pub mod templates;
@@ -6,10 +8,20 @@ pub mod types;
 pub mod validator;
 
 pub use builder::HttpDiffConfigBuilder;
+pub use check::{check_config, CheckFinding};
 pub use global_builder::GlobalConfigBuilder;
-pub use loader::{load_user_data, ConfigLoader};
+pub use format::{without_nulls, ConfigFormat, Converted, Settings};
+pub use loader::{
+    load_user_data, ConfigLoader, ConfigSources, DefaultConfigLoader, JsonConfigLoader,
+    SourceLocation, TomlConfigLoader, YamlConfigLoader,
+};
 pub use templates::{
     ensure_config_files_exist, generate_default_config_template, generate_default_users_csv,
 };
-pub use types::{Environment, GlobalConfig, HttpDiffConfig, Route, UserData, ValueExtractionRule, ExtractorType};
-pub use validator::ConfigValidatorImpl;
+pub use types::{
+    ApiKeyLocation, AuthConfig, CompareRules, Environment, ExpectedStatus, ExtractorType, FailOn,
+    GlobalConfig, HttpDiffConfig, JsonAssertion, LatencyThreshold, MultipartPart,
+    NumericTolerance, Profile, RateLimit, RetryConfig, Route, RouteAssertions, SchemaSource, SecretsConfig,
+    UserData, ValueExtractionRule, ValueMask,
+};
+pub use validator::{ConfigProblem, ConfigSubject, ConfigValidatorImpl};
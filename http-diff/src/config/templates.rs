@@ -15,6 +15,10 @@ headers."X-Environment" = "testing"
 base_url = "https://api.example.com"
 headers."X-Scope" = "prod"
 is_base = true
+# Throttle requests to this environment only (optional)
+# rate_limit = { requests_per_second = 5.0, burst = 2, max_concurrent_requests = 2 }
+# Authenticate every request to this environment (optional)
+# auth = { type = "basic", username = "{secret:PROD_USER}", password = "{secret:PROD_PASSWORD}" }
 
 [environments.staging]
 base_url = "https://api-staging.example.com"
@@ -28,6 +32,20 @@ timeout_seconds = 30
 follow_redirects = true
 # Maximum number of concurrent requests (1-100)
 max_concurrent_requests = 10
+# Exit with a non-zero code on differences: never, any_difference, status_difference
+# fail_on = "any_difference"
+# Exit with a non-zero code when more mixed-status responses than this are found
+# max_mixed_responses = 0
+# Exit with a non-zero code when any execution error occurs
+# fail_on_errors = true
+# Exit with a non-zero code when a route assertion fails (default: true)
+# fail_on_assertions = false
+# JSONPath expressions for body fields excluded from comparison
+# ignore_fields = ["$.meta.generatedAt", "$.requestId"]
+# Report a latency difference when an environment is this much slower than another
+# latency_threshold = { ratio = 2.0, min_delta_ms = 200 }
+# Keep cookies per user and environment across chained routes
+# cookie_jar = true
 
 # Global headers applied to all requests
 [global.headers]
@@ -39,6 +57,16 @@ max_concurrent_requests = 10
 [global.params]
 version = "v1"
 
+# Retry transient failures (optional, no retries when unset)
+# [global.retry]
+# max_attempts = 3
+# retry_on_status = [502, 503, 504]
+
+# Resolve {secret:NAME} placeholders and mask credentials in output (optional)
+# [global.secrets]
+# env_file = ".env"
+# masked_headers = ["Authorization", "Cookie", "X-Api-Key"]
+
 # Route definitions - define your API endpoints here
 [[routes]]
 name = "user-profile"
@@ -46,8 +74,10 @@ method = "GET"
 path = "/api/users/{userId}"
 
 # Route-specific headers (optional)
+# Placeholders accept expressions such as {uuid()}, {upper(userId)} or {siteId ?? "MLA"}
 [routes.headers]
 "Accept" = "application/json"
+# "X-Request-Id" = "{uuid()}"
 
 # Route-specific query parameters (optional)
 [routes.params]
@@ -59,6 +89,20 @@ include_metadata = "true"
 # test = "https://legacy-test.example.com"
 # prod = "https://legacy.example.com"
 
+# Value-normalization rules for comparing JSON bodies (optional)
+# [routes.compare]
+# numeric_tolerance = { absolute = 0.01, relative = 0.001 }
+# case_insensitive = ["$.status"]
+# unordered_arrays = ["$.roles"]
+# mask = [{ pattern = '\d{4}-\d{2}-\d{2}T[0-9:.]+Z', replacement = "<timestamp>" }]
+
+# Expectations checked against each environment's response (optional)
+# [routes.assert]
+# status = 200
+# headers = ["content-type"]
+# max_latency_ms = 1000
+# json = [{ path = "$.id", exists = true }]
+
 [[routes]]
 name = "site-info"
 method = "GET"
@@ -75,6 +119,8 @@ name = "create-user"
 method = "POST"
 path = "/api/users"
 body = '{"name": "Test User", "email": "test@example.com"}'
+# Alternatively: body_file = "fixtures/user.json", json = { name = "Test User" },
+# form = { name = "Test User" } or [[routes.multipart]] parts
 
 [routes.headers]
 "Content-Type" = "application/json"
@@ -1,7 +1,8 @@
 use crate::conditions::ExecutionCondition;
 use crate::error::{HttpDiffError, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Main configuration structure for HTTP diff testing
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -12,6 +13,30 @@ pub struct HttpDiffConfig {
     pub global: Option<GlobalConfig>,
     /// Route definitions
     pub routes: Vec<Route>,
+    /// Named subsets of routes and environments, selected with `--profile`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, Profile>,
+}
+
+/// Subset of the routes and environments to run
+///
+/// Routes are selected by name or group; when neither is given every route
+/// runs. The routes a selected route depends on are always kept.
+///
+/// ```toml
+/// [profiles.smoke]
+/// routes = ["login", "get_user*"]
+/// groups = ["Orders"]
+/// environments = ["staging", "prod"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Profile {
+    /// Route names; `*` matches any run of characters
+    pub routes: Option<Vec<String>>,
+    /// Route groups whose routes are selected
+    pub groups: Option<Vec<String>>,
+    /// Environments to run against
+    pub environments: Option<Vec<String>>,
 }
 
 /// Environment configuration with base URL and headers
@@ -24,6 +49,102 @@ pub struct Environment {
     /// Whether this environment should be treated as the base for comparisons
     #[serde(default)]
     pub is_base: bool,
+    /// Request throttling applied to this environment only
+    pub rate_limit: Option<RateLimit>,
+    /// Credentials attached to every request sent to this environment
+    pub auth: Option<AuthConfig>,
+    /// Environment whose settings this one inherits, overriding them field by field
+    ///
+    /// Resolved when the configuration is loaded; headers are merged, and
+    /// `is_base` is never inherited.
+    pub extends: Option<String>,
+}
+
+/// Authentication provider of an environment
+///
+/// Every string field accepts placeholders, so credentials can come from
+/// `{secret:NAME}` or from user data.
+///
+/// ```toml
+/// [environments.prod.auth]
+/// type = "oauth2_client_credentials"
+/// token_url = "https://auth.example.com/oauth/token"
+/// client_id = "http-diff"
+/// client_secret = "{secret:PROD_CLIENT_SECRET}"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthConfig {
+    /// OAuth2 client credentials grant, sent as a bearer token
+    #[serde(rename = "oauth2_client_credentials")]
+    OAuth2ClientCredentials {
+        /// Token endpoint
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        /// Space-separated scopes to request
+        scope: Option<String>,
+    },
+    /// OAuth2 resource owner password grant, sent as a bearer token
+    #[serde(rename = "oauth2_password")]
+    OAuth2Password {
+        /// Token endpoint
+        token_url: String,
+        client_id: String,
+        client_secret: Option<String>,
+        username: String,
+        password: String,
+        /// Space-separated scopes to request
+        scope: Option<String>,
+    },
+    /// HTTP basic authentication
+    Basic { username: String, password: String },
+    /// Static API key sent in a header or query parameter
+    ApiKey {
+        /// Header or query parameter name
+        name: String,
+        value: String,
+        #[serde(default)]
+        location: ApiKeyLocation,
+    },
+    /// Token obtained by sending one of the configured routes
+    LoginRoute {
+        /// Name of the route performing the login
+        route: String,
+        /// JSONPath of the token in the login response body
+        token_path: String,
+        /// Header carrying the token (defaults to Authorization)
+        header: Option<String>,
+        /// Scheme written before the token (defaults to Bearer, empty for none)
+        scheme: Option<String>,
+    },
+}
+
+/// Where an API key is sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+}
+
+/// Per-environment request throttling
+///
+/// ```toml
+/// [environments.prod.rate_limit]
+/// requests_per_second = 5.0
+/// burst = 2
+/// max_concurrent_requests = 2
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RateLimit {
+    /// Sustained request rate (unlimited when unset)
+    pub requests_per_second: Option<f64>,
+    /// Requests that may be sent back-to-back before throttling starts (defaults to 1)
+    pub burst: Option<u32>,
+    /// Maximum in-flight requests to this environment, on top of the global limit
+    pub max_concurrent_requests: Option<usize>,
 }
 
 /// Global configuration settings
@@ -39,6 +160,58 @@ pub struct GlobalConfig {
     pub headers: Option<HashMap<String, String>>,
     /// Global query parameters applied to all requests
     pub params: Option<HashMap<String, String>>,
+    /// Which differences cause the run to fail
+    pub fail_on: Option<FailOn>,
+    /// Fail when the number of mixed-status responses exceeds this threshold
+    pub max_mixed_responses: Option<usize>,
+    /// Fail when any execution error occurs
+    pub fail_on_errors: Option<bool>,
+    /// Fail when any route assertion fails (defaults to true)
+    pub fail_on_assertions: Option<bool>,
+    /// JSONPath expressions for body fields excluded from comparison on every route
+    pub ignore_fields: Option<Vec<String>>,
+    /// Report a latency difference when environments differ by more than this
+    pub latency_threshold: Option<LatencyThreshold>,
+    /// Retry policy for flaky requests (no retries when unset)
+    pub retry: Option<RetryConfig>,
+    /// Where `{secret:NAME}` values come from and which headers are masked in output
+    pub secrets: Option<SecretsConfig>,
+    /// Keep cookies set by responses per user and environment, and send them on later requests
+    pub cookie_jar: Option<bool>,
+}
+
+/// Sources for `{secret:NAME}` placeholders
+///
+/// Secrets are looked up in the process environment first, then in the
+/// dotenv file, then by running the command.
+///
+/// ```toml
+/// [global.secrets]
+/// env_file = ".env.local"
+/// command = "pass show http-diff/{name}"
+/// masked_headers = ["Authorization", "X-Session"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct SecretsConfig {
+    /// Dotenv file with `NAME=value` lines (defaults to `.env`)
+    pub env_file: Option<String>,
+    /// Shell command printing a secret on stdout; `{name}` is replaced by the secret name
+    pub command: Option<String>,
+    /// Headers whose values are always masked in output (replaces the default list)
+    pub masked_headers: Option<Vec<String>>,
+}
+
+/// Which comparison differences cause a run to fail
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailOn {
+    /// Differences never fail the run
+    #[default]
+    Never,
+    /// Any difference in status, headers or body fails the run
+    AnyDifference,
+    /// Only status code differences fail the run
+    StatusDifference,
 }
 
 /// Route definition for HTTP requests
@@ -58,6 +231,14 @@ pub struct Route {
     pub base_urls: Option<HashMap<String, String>>,
     /// Request body for POST/PUT requests
     pub body: Option<String>,
+    /// File whose contents are sent as the request body
+    pub body_file: Option<String>,
+    /// Request body declared as a TOML table and sent as JSON
+    pub json: Option<serde_json::Value>,
+    /// Fields sent as an `application/x-www-form-urlencoded` body
+    pub form: Option<BTreeMap<String, String>>,
+    /// Parts of a `multipart/form-data` body
+    pub multipart: Option<Vec<MultipartPart>>,
     /// Conditional execution rules for this route
     pub conditions: Option<Vec<ExecutionCondition>>,
     /// Value extraction rules for chaining requests
@@ -67,6 +248,201 @@ pub struct Route {
     /// Whether to wait for value extraction from dependencies before executing
     #[serde(default)]
     pub wait_for_extraction: Option<bool>,
+    /// JSONPath expressions for body fields excluded from comparison on this route
+    pub ignore_fields: Option<Vec<String>>,
+    /// Value-normalization rules applied when comparing JSON bodies of this route
+    pub compare: Option<CompareRules>,
+    /// Expectations checked against every environment's response, independent of diffing
+    #[serde(rename = "assert")]
+    pub assertions: Option<RouteAssertions>,
+    /// Retry policy for this route, overriding the global one field by field
+    pub retry: Option<RetryConfig>,
+    /// Group the route belongs to, such as the Postman folder it was imported from
+    pub group: Option<String>,
+    /// Directory of the file the route was loaded from, which relative
+    /// `body_file` and multipart `file` paths are resolved against
+    #[serde(skip)]
+    pub base_dir: Option<PathBuf>,
+}
+
+impl Route {
+    /// Path of a file the route refers to, relative to the file it was loaded from
+    ///
+    /// Without a `base_dir`, relative paths stay relative to the working directory.
+    pub fn resolve_path(&self, path: &str) -> PathBuf {
+        match &self.base_dir {
+            Some(dir) if Path::new(path).is_relative() => dir.join(path),
+            _ => PathBuf::from(path),
+        }
+    }
+}
+
+/// A part of a `multipart/form-data` request body
+///
+/// Exactly one of `value` and `file` is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MultipartPart {
+    /// Form field name
+    pub name: String,
+    /// Text value of the part
+    pub value: Option<String>,
+    /// File whose contents are uploaded as the part
+    pub file: Option<String>,
+    /// File name sent to the server (defaults to the name of `file`)
+    pub filename: Option<String>,
+    /// Content type of the part
+    pub content_type: Option<String>,
+}
+
+/// Expectations a route's responses must satisfy in each environment
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RouteAssertions {
+    /// Expected status code, or a list of accepted status codes
+    pub status: Option<ExpectedStatus>,
+    /// Headers that must be present (names are case-insensitive)
+    pub headers: Option<Vec<String>>,
+    /// JSONPath checks on the response body
+    pub json: Option<Vec<JsonAssertion>>,
+    /// Maximum response time in milliseconds
+    pub max_latency_ms: Option<u64>,
+    /// JSON Schema the body must satisfy, as a file path or an inline table
+    pub schema: Option<SchemaSource>,
+    /// Environments the assertions apply to (all environments when unset)
+    pub environments: Option<Vec<String>>,
+}
+
+/// Expected status code(s) of a response
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ExpectedStatus {
+    /// A single expected status code
+    Code(u16),
+    /// Any of the listed status codes
+    AnyOf(Vec<u16>),
+}
+
+impl ExpectedStatus {
+    /// Check whether a status code is expected
+    pub fn accepts(&self, status: u16) -> bool {
+        match self {
+            ExpectedStatus::Code(code) => *code == status,
+            ExpectedStatus::AnyOf(codes) => codes.contains(&status),
+        }
+    }
+}
+
+/// A check on the value selected by a JSONPath expression
+///
+/// With no operator set the path must match at least one value.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct JsonAssertion {
+    /// JSONPath expression selecting the value
+    pub path: String,
+    /// Expected value
+    pub equals: Option<serde_json::Value>,
+    /// Whether the path must (true) or must not (false) match a value
+    pub exists: Option<bool>,
+    /// Regular expression the value must match (non-string values are matched as JSON)
+    pub matches: Option<String>,
+}
+
+/// Location of a JSON Schema
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum SchemaSource {
+    /// Path to a JSON Schema file
+    File(String),
+    /// Schema declared inline in the configuration
+    Inline(serde_json::Value),
+}
+
+/// Value-normalization rules for comparing JSON response bodies
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct CompareRules {
+    /// Tolerance within which differing JSON numbers are considered equal
+    pub numeric_tolerance: Option<NumericTolerance>,
+    /// JSONPath expressions whose string values are compared case-insensitively
+    pub case_insensitive: Option<Vec<String>>,
+    /// JSONPath expressions of arrays compared without regard to element order
+    pub unordered_arrays: Option<Vec<String>>,
+    /// Regex masks applied to string values before comparison
+    pub mask: Option<Vec<ValueMask>>,
+    /// Latency regression threshold overriding the global one
+    pub latency_threshold: Option<LatencyThreshold>,
+}
+
+/// Tolerance for differences between JSON numbers
+///
+/// Two numbers are equal when they are within either the absolute or the
+/// relative tolerance.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct NumericTolerance {
+    /// Maximum absolute difference, e.g. `0.01`
+    pub absolute: Option<f64>,
+    /// Maximum difference relative to the larger magnitude, e.g. `0.001` for 0.1%
+    pub relative: Option<f64>,
+    /// JSONPath expressions limiting the tolerance to these values (all numbers when unset)
+    pub paths: Option<Vec<String>>,
+}
+
+/// Retry policy for requests that fail transiently
+///
+/// Unset fields fall back to the global policy, then to the defaults documented
+/// on each field.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RetryConfig {
+    /// Total number of attempts including the first one (default 3)
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry in milliseconds (default 200)
+    pub initial_backoff_ms: Option<u64>,
+    /// Upper bound for the delay between attempts in milliseconds (default 5000)
+    pub max_backoff_ms: Option<u64>,
+    /// Factor applied to the delay after each retry (default 2.0)
+    pub multiplier: Option<f64>,
+    /// Randomize each delay between zero and the computed backoff (default true)
+    pub jitter: Option<bool>,
+    /// Status codes that trigger a retry (default 502, 503, 504)
+    pub retry_on_status: Option<Vec<u16>>,
+}
+
+impl RetryConfig {
+    /// Overlay the fields set on `other` onto this configuration
+    pub fn merged_with(&self, other: &RetryConfig) -> RetryConfig {
+        RetryConfig {
+            max_attempts: other.max_attempts.or(self.max_attempts),
+            initial_backoff_ms: other.initial_backoff_ms.or(self.initial_backoff_ms),
+            max_backoff_ms: other.max_backoff_ms.or(self.max_backoff_ms),
+            multiplier: other.multiplier.or(self.multiplier),
+            jitter: other.jitter.or(self.jitter),
+            retry_on_status: other
+                .retry_on_status
+                .clone()
+                .or_else(|| self.retry_on_status.clone()),
+        }
+    }
+}
+
+/// Threshold above which a latency difference between environments is reported
+///
+/// A difference is reported when the slower environment exceeds both the ratio
+/// and the minimum delta relative to the faster one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct LatencyThreshold {
+    /// Minimum slowdown factor, e.g. `2.0` when one environment takes twice as long
+    pub ratio: Option<f64>,
+    /// Minimum absolute slowdown in milliseconds, filtering out noise on fast routes
+    pub min_delta_ms: Option<u64>,
+}
+
+/// Regex mask replacing matching parts of string values before comparison
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ValueMask {
+    /// Regular expression to replace
+    pub pattern: String,
+    /// Replacement text (defaults to `***`)
+    pub replacement: Option<String>,
+    /// JSONPath expressions limiting the mask to these values (all strings when unset)
+    pub paths: Option<Vec<String>>,
 }
 
 /// User data loaded from CSV for parameter substitution
@@ -94,6 +470,9 @@ impl UserData {
 
     /// Substitute placeholders like {userId} with actual values from CSV data
     ///
+    /// Placeholders may also hold expressions such as `{upper(userId)}` or
+    /// `{siteId ?? "MLA"}`; see [`crate::template`].
+    ///
     /// # Arguments
     /// * `text` - The text containing placeholders in {param_name} format
     /// * `url_encode` - Whether to URL encode the substituted values (true for paths, false for headers/body)
@@ -104,73 +483,31 @@ impl UserData {
         url_encode: bool,
         strict: bool,
     ) -> Result<String> {
-        // Use single-pass algorithm to avoid multiple reallocations
-        let mut result = String::with_capacity(text.len() + 50); // Pre-allocate with some extra space
-        let mut chars = text.char_indices().peekable();
-
-        while let Some((_pos, ch)) = chars.next() {
-            if ch == '{' {
-                // Found potential parameter start, collect parameter name
-                let mut param_name = String::new();
-                let mut found_end = false;
-
-                while let Some((_, next_ch)) = chars.peek() {
-                    if *next_ch == '}' {
-                        chars.next(); // consume the '}'
-                        found_end = true;
-                        break;
-                    } else if *next_ch == '{' {
-                        // Nested braces, not a valid parameter
-                        break;
-                    } else {
-                        param_name.push(chars.next().unwrap().1);
-                    }
+        crate::template::render(
+            text,
+            url_encode,
+            |name| self.data.get(name).cloned(),
+            |name| {
+                if !strict {
+                    // Non-strict mode: preserve the original placeholder
+                    return Ok(());
                 }
-
-                if found_end && is_valid_param_name(&param_name) {
-                    if let Some(value) = self.data.get(&param_name) {
-                        // Substitute the parameter
-                        if url_encode {
-                            result.push_str(&urlencoding::encode(value));
-                        } else {
-                            result.push_str(value);
-                        }
-                    } else if strict {
-                        // Strict mode: error if parameter is missing
-                        let available = self
-                            .data
-                            .keys()
-                            .map(String::as_str)
-                            .collect::<Vec<_>>()
-                            .join(", ");
-                        return Err(HttpDiffError::MissingPathParameter {
-                            param: param_name,
-                            available_params: if available.is_empty() {
-                                "none".to_string()
-                            } else {
-                                available
-                            },
-                        });
+                let available = self
+                    .data
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(HttpDiffError::MissingPathParameter {
+                    param: name.to_string(),
+                    available_params: if available.is_empty() {
+                        "none".to_string()
                     } else {
-                        // Non-strict mode: preserve the original placeholder
-                        result.push('{');
-                        result.push_str(&param_name);
-                        result.push('}');
-                    }
-                } else {
-                    // Invalid parameter format, preserve the original text
-                    result.push(ch);
-                    result.push_str(&param_name);
-                    if found_end {
-                        result.push('}');
-                    }
-                }
-            } else {
-                result.push(ch);
-            }
-        }
-
-        Ok(result)
+                        available
+                    },
+                })
+            },
+        )
     }
 
     /// Get available parameters from this UserData instance
@@ -342,6 +679,9 @@ pub struct ValueExtractionRule {
     #[serde(alias = "type")]
     pub extractor_type: ExtractorType,
     /// Source location/path for extraction (JSONPath, regex pattern, header name, etc.)
+    ///
+    /// Unused, and may be omitted, for status code and body extraction.
+    #[serde(default)]
     pub source: String,
     /// Default value to use if extraction fails
     pub default_value: Option<String>,
@@ -362,11 +702,18 @@ pub enum ExtractorType {
     Header,
     /// Extract HTTP status code
     StatusCode,
-}
-
-/// Check if a parameter name is a valid identifier (letters, numbers, underscore)
-fn is_valid_param_name(name: &str) -> bool {
-    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+    /// Extract value using an XPath expression from an XML or HTML body
+    #[serde(rename = "xpath")]
+    XPath,
+    /// Extract the value of a cookie set by the response, by cookie name
+    Cookie,
+    /// Capture the whole response body
+    Body,
+    /// Extract value using a JMESPath query from a JSON body
+    #[serde(rename = "jmespath")]
+    JmesPath,
+    /// Compute a value from a placeholder template over previously extracted values
+    Computed,
 }
 
 /// Default implementation for GlobalConfig
@@ -378,6 +725,15 @@ impl Default for GlobalConfig {
             max_concurrent_requests: Some(10),
             headers: None,
             params: None,
+            fail_on: None,
+            max_mixed_responses: None,
+            fail_on_errors: None,
+            fail_on_assertions: None,
+            ignore_fields: None,
+            latency_threshold: None,
+            retry: None,
+            secrets: None,
+            cookie_jar: None,
         }
     }
 }
@@ -426,7 +782,7 @@ impl HttpDiffConfig {
             if let Some(extractions) = &route.extract {
                 for extraction in extractions {
                     // Validate extraction rule name is a valid identifier
-                    if !is_valid_param_name(&extraction.name) {
+                    if !crate::template::is_valid_param_name(&extraction.name) {
                         return Err(HttpDiffError::invalid_config(
                             format!(
                                 "Route '{}' has invalid extraction rule name '{}'. Must be alphanumeric with underscores only.",
@@ -437,7 +793,10 @@ impl HttpDiffConfig {
 
                     // Validate source is not empty
                     if extraction.source.trim().is_empty()
-                        && !matches!(extraction.extractor_type, ExtractorType::StatusCode)
+                        && !matches!(
+                            extraction.extractor_type,
+                            ExtractorType::StatusCode | ExtractorType::Body
+                        )
                     {
                         return Err(HttpDiffError::invalid_config(
                             format!(
@@ -457,7 +816,7 @@ impl HttpDiffConfig {
     }
 
     /// Validate there are no circular dependencies in the route chain
-    fn validate_no_circular_dependencies(&self) -> Result<()> {
+    pub(crate) fn validate_no_circular_dependencies(&self) -> Result<()> {
         use std::collections::{HashMap, VecDeque};
 
         // Build dependency graph
@@ -568,6 +927,103 @@ impl HttpDiffConfig {
 
         Ok(result)
     }
+
+    /// Keep only the routes and environments selected by a profile
+    ///
+    /// Routes the selected ones depend on, directly or through a login route
+    /// of a kept environment, are kept as well.
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self.profiles.get(name).cloned().ok_or_else(|| {
+            let mut available: Vec<_> = self.profiles.keys().cloned().collect();
+            available.sort();
+            HttpDiffError::invalid_config(format!(
+                "Profile '{}' not found. Available profiles: {}",
+                name,
+                available.join(", ")
+            ))
+        })?;
+
+        if let Some(environments) = &profile.environments {
+            if let Some(unknown) = environments
+                .iter()
+                .find(|env| !self.environments.contains_key(*env))
+            {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "Profile '{}' selects unknown environment '{}'",
+                    name, unknown
+                )));
+            }
+            self.environments
+                .retain(|env, _| environments.contains(env));
+        }
+
+        if profile.routes.is_none() && profile.groups.is_none() {
+            return Ok(());
+        }
+        let mut selected: Vec<String> = self
+            .routes
+            .iter()
+            .filter(|route| {
+                let by_name = profile.routes.iter().flatten().any(|pattern| {
+                    matches_pattern(pattern, &route.name)
+                });
+                let by_group = match &route.group {
+                    Some(group) => profile.groups.iter().flatten().any(|g| g == group),
+                    None => false,
+                };
+                by_name || by_group
+            })
+            .map(|route| route.name.clone())
+            .collect();
+        if selected.is_empty() {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Profile '{}' does not select any route",
+                name
+            )));
+        }
+
+        selected.extend(self.environments.values().filter_map(|env| match &env.auth {
+            Some(AuthConfig::LoginRoute { route, .. }) => Some(route.clone()),
+            _ => None,
+        }));
+        let mut index = 0;
+        while index < selected.len() {
+            let dependencies = self
+                .routes
+                .iter()
+                .find(|route| route.name == selected[index])
+                .and_then(|route| route.depends_on.clone())
+                .unwrap_or_default();
+            for dependency in dependencies {
+                if !selected.contains(&dependency) {
+                    selected.push(dependency);
+                }
+            }
+            index += 1;
+        }
+        self.routes.retain(|route| selected.contains(&route.name));
+        Ok(())
+    }
+}
+
+/// Match a name against a pattern where `*` stands for any run of characters
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
 }
 
 #[cfg(test)]
@@ -629,6 +1085,29 @@ mod tests {
         assert_eq!(encoded_result, "/search?q=hello%20world");
     }
 
+    #[test]
+    fn test_user_data_substitute_expressions() {
+        let mut data = HashMap::new();
+        data.insert("site".to_string(), "mla".to_string());
+        let user_data = UserData::new(data);
+
+        let path = user_data
+            .substitute_placeholders("/sites/{upper(site)}/{region ?? 'north west'}", true, true)
+            .unwrap();
+        assert_eq!(path, "/sites/MLA/north%20west");
+
+        let err = user_data
+            .substitute_placeholders("/users/{lower(userId)}", true, true)
+            .unwrap_err();
+        assert!(err.to_string().contains("'userId' not found"));
+
+        // Non-strict mode keeps unresolved expressions as written
+        let body = user_data
+            .substitute_placeholders(r#"{"id": "{lower(userId)}"}"#, false, false)
+            .unwrap();
+        assert_eq!(body, r#"{"id": "{lower(userId)}"}"#);
+    }
+
     #[test]
     fn test_user_data_with_context_creation() {
         let mut data = HashMap::new();
@@ -742,4 +1221,56 @@ mod tests {
         let conflicting = user_data_with_context.get_conflicting_parameters();
         assert_eq!(conflicting, vec!["user_id"]);
     }
+
+    #[test]
+    fn test_apply_profile_keeps_dependencies() {
+        let mut config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.staging]
+base_url = "https://staging.example.com"
+auth = { type = "login_route", route = "login", token_path = "$.token" }
+
+[environments.prod]
+base_url = "https://prod.example.com"
+
+[[routes]]
+name = "login"
+method = "POST"
+path = "/login"
+
+[[routes]]
+name = "create_order"
+method = "POST"
+path = "/orders"
+
+[[routes]]
+name = "get_order"
+method = "GET"
+path = "/orders/{orderId}"
+depends_on = ["create_order"]
+
+[[routes]]
+name = "get_user"
+method = "GET"
+path = "/users/{userId}"
+group = "Users"
+
+[profiles.smoke]
+routes = ["get_o*"]
+environments = ["staging"]
+"#,
+        )
+        .unwrap();
+
+        config.apply_profile("smoke").unwrap();
+        let names: Vec<_> = config.routes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["login", "create_order", "get_order"]);
+        assert_eq!(config.environments.len(), 1);
+        assert!(config.environments.contains_key("staging"));
+
+        let error = config.apply_profile("nightly").unwrap_err().to_string();
+        assert!(error.contains("Profile 'nightly' not found"), "{}", error);
+        assert!(matches_pattern("get_*_by_id", "get_user_by_id"));
+        assert!(!matches_pattern("get_*", "list_users"));
+    }
 }
@@ -1,11 +1,35 @@
-use crate::config::types::HttpDiffConfig;
+use crate::comparison::assertions::{compile_schema, load_schema};
+use crate::config::types::{
+    AuthConfig, CompareRules, ExpectedStatus, ExtractorType, HttpDiffConfig, LatencyThreshold,
+    RateLimit, RetryConfig, Route, RouteAssertions, SecretsConfig, ValueExtractionRule,
+};
 use crate::error::{HttpDiffError, Result};
 use crate::traits::ConfigValidator;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::str::FromStr;
 
 /// Configuration validator implementation
 pub struct ConfigValidatorImpl;
 
+/// Part of a configuration a problem was found in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSubject {
+    /// The configuration as a whole
+    Configuration,
+    /// The `[global]` table
+    Global,
+    Environment(String),
+    Route(String),
+}
+
+/// A problem found while validating a configuration
+#[derive(Debug)]
+pub struct ConfigProblem {
+    pub subject: ConfigSubject,
+    pub error: HttpDiffError,
+}
+
 impl ConfigValidator for ConfigValidatorImpl {
     type Config = HttpDiffConfig;
 
@@ -21,107 +45,652 @@ impl ConfigValidatorImpl {
         Self
     }
 
-    /// Validation with enhanced error context
+    /// Validation with enhanced error context, failing with the first problem found
     pub fn validate_with_context<P: AsRef<Path>>(
         &self,
         config: &HttpDiffConfig,
         config_path: P,
     ) -> Result<()> {
+        match self.find_problems(config, config_path).into_iter().next() {
+            Some(problem) => Err(problem.error),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem of a configuration, in the order validation finds them
+    pub fn find_problems<P: AsRef<Path>>(
+        &self,
+        config: &HttpDiffConfig,
+        config_path: P,
+    ) -> Vec<ConfigProblem> {
         let config_path_str = config_path.as_ref().to_string_lossy();
+        let mut problems = Vec::new();
+        let mut report = |subject: ConfigSubject, result: Result<()>| {
+            if let Err(error) = result {
+                problems.push(ConfigProblem { subject, error });
+            }
+        };
 
         if config.environments.is_empty() {
-            return Err(HttpDiffError::invalid_config(format!(
-                "No environments configured in {}. Add at least one environment to [environments] section.",
-                config_path_str
-            )));
+            report(
+                ConfigSubject::Configuration,
+                Err(HttpDiffError::invalid_config(format!(
+                    "No environments configured in {}. Add at least one environment to [environments] section.",
+                    config_path_str
+                ))),
+            );
         }
 
         if config.routes.is_empty() {
-            return Err(HttpDiffError::invalid_config(format!(
-                "No routes configured in {}. Add at least one [[routes]] entry.",
-                config_path_str
-            )));
+            report(
+                ConfigSubject::Configuration,
+                Err(HttpDiffError::invalid_config(format!(
+                    "No routes configured in {}. Add at least one [[routes]] entry.",
+                    config_path_str
+                ))),
+            );
         }
 
         // Ensure no more than one base environment is selected
         let base_count = config.environments.values().filter(|e| e.is_base).count();
         if base_count > 1 {
-            return Err(HttpDiffError::invalid_config(format!(
-                "Multiple environments are marked as base in {}; only one is allowed",
-                config_path_str
-            )));
+            report(
+                ConfigSubject::Configuration,
+                Err(HttpDiffError::invalid_config(format!(
+                    "Multiple environments are marked as base in {}; only one is allowed",
+                    config_path_str
+                ))),
+            );
+        }
+
+        let mut env_names: Vec<&String> = config.environments.keys().collect();
+        env_names.sort();
+        for env_name in &env_names {
+            let env = &config.environments[*env_name];
+            let subject = || ConfigSubject::Environment(env_name.to_string());
+            if let Some(rate_limit) = &env.rate_limit {
+                report(subject(), Self::validate_rate_limit(rate_limit, env_name));
+            }
+            if let Some(auth) = &env.auth {
+                report(subject(), Self::validate_auth(config, auth, env_name));
+            }
         }
 
         // Validate HTTP methods and environment references
+        let mut seen_routes = HashSet::new();
+        let mut unknown_dependency = false;
         for route in &config.routes {
+            let subject = || ConfigSubject::Route(route.name.clone());
+            if !seen_routes.insert(route.name.as_str()) {
+                report(
+                    subject(),
+                    Err(HttpDiffError::invalid_config(format!(
+                        "Route name '{}' is used by more than one route",
+                        route.name
+                    ))),
+                );
+            }
+
             let valid_methods = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"];
             if !valid_methods.contains(&route.method.as_str()) {
-                return Err(HttpDiffError::invalid_config(format!(
-                    "Invalid HTTP method '{}' in route '{}'. Valid methods: {}",
-                    route.method,
-                    route.name,
-                    valid_methods.join(", ")
-                )));
+                report(
+                    subject(),
+                    Err(HttpDiffError::invalid_config(format!(
+                        "Invalid HTTP method '{}' in route '{}'. Valid methods: {}",
+                        route.method,
+                        route.name,
+                        valid_methods.join(", ")
+                    ))),
+                );
             }
 
             // Validate base URL overrides reference existing environments
             if let Some(base_urls) = &route.base_urls {
                 for env_name in base_urls.keys() {
                     if !config.environments.contains_key(env_name) {
-                        return Err(HttpDiffError::invalid_config(format!(
-                            "Route '{}' references unknown environment '{}' in base_urls. Available environments: {}",
-                            route.name,
-                            env_name,
-                            config.environments.keys().map(String::as_str).collect::<Vec<_>>().join(", ")
-                        )));
+                        report(
+                            subject(),
+                            Err(HttpDiffError::invalid_config(format!(
+                                "Route '{}' references unknown environment '{}' in base_urls. Available environments: {}",
+                                route.name,
+                                env_name,
+                                config.environments.keys().map(String::as_str).collect::<Vec<_>>().join(", ")
+                            ))),
+                        );
                     }
                 }
             }
 
+            // Validate dependencies exist
+            for dependency in route.depends_on.iter().flatten() {
+                if !config.routes.iter().any(|r| &r.name == dependency) {
+                    unknown_dependency = true;
+                    report(
+                        subject(),
+                        Err(HttpDiffError::invalid_config(format!(
+                            "Route '{}' depends on non-existent route '{}'",
+                            route.name, dependency
+                        ))),
+                    );
+                }
+            }
+
             // Validate execution conditions if present
             if let Some(conditions) = &route.conditions {
                 for condition in conditions {
-                    condition.validate()?;
+                    report(subject(), condition.validate());
                 }
             }
+
+            let location = format!("route '{}'", route.name);
+            for rule in route.extract.iter().flatten() {
+                report(subject(), Self::validate_extraction(rule, &location));
+            }
+            report(subject(), Self::validate_body(route, &location));
+            report(
+                subject(),
+                Self::validate_json_body(config, route, &location),
+            );
+            report(
+                subject(),
+                Self::validate_json_paths(
+                    route.ignore_fields.as_deref(),
+                    "ignore_fields",
+                    &location,
+                ),
+            );
+            if let Some(compare) = &route.compare {
+                report(subject(), Self::validate_compare_rules(compare, &location));
+            }
+            if let Some(assertions) = &route.assertions {
+                report(
+                    subject(),
+                    Self::validate_assertions(config, route, assertions, &location),
+                );
+            }
+            if let Some(retry) = &route.retry {
+                report(subject(), Self::validate_retry(retry, &location));
+            }
         }
 
         // Validate URLs in environments
-        for (env_name, env) in &config.environments {
+        for env_name in &env_names {
+            let env = &config.environments[*env_name];
             if url::Url::parse(&env.base_url).is_err() {
-                return Err(HttpDiffError::invalid_config(format!(
-                    "Invalid base_url '{}' in environment '{}'. Must be a valid URL.",
-                    env.base_url, env_name
-                )));
+                report(
+                    ConfigSubject::Environment(env_name.to_string()),
+                    Err(HttpDiffError::invalid_config(format!(
+                        "Invalid base_url '{}' in environment '{}'. Must be a valid URL.",
+                        env.base_url, env_name
+                    ))),
+                );
             }
         }
 
         // Validate timeout if specified
         if let Some(global) = &config.global {
+            let subject = || ConfigSubject::Global;
             if let Some(timeout) = global.timeout_seconds {
                 if timeout == 0 || timeout > 300 {
-                    return Err(HttpDiffError::invalid_config(
-                        "timeout_seconds must be between 1 and 300 seconds".to_string(),
-                    ));
+                    report(
+                        subject(),
+                        Err(HttpDiffError::invalid_config(
+                            "timeout_seconds must be between 1 and 300 seconds".to_string(),
+                        )),
+                    );
                 }
             }
 
             // Validate max_concurrent_requests if specified
             if let Some(max_concurrent) = global.max_concurrent_requests {
                 if max_concurrent == 0 || max_concurrent > 100 {
-                    return Err(HttpDiffError::invalid_config(
-                        "max_concurrent_requests must be between 1 and 100".to_string(),
-                    ));
+                    report(
+                        subject(),
+                        Err(HttpDiffError::invalid_config(
+                            "max_concurrent_requests must be between 1 and 100".to_string(),
+                        )),
+                    );
+                }
+            }
+
+            report(
+                subject(),
+                Self::validate_json_paths(
+                    global.ignore_fields.as_deref(),
+                    "ignore_fields",
+                    "[global]",
+                ),
+            );
+            if let Some(threshold) = &global.latency_threshold {
+                report(
+                    subject(),
+                    Self::validate_latency_threshold(threshold, "[global]"),
+                );
+            }
+            if let Some(retry) = &global.retry {
+                report(subject(), Self::validate_retry(retry, "[global]"));
+            }
+            if let Some(secrets) = &global.secrets {
+                report(subject(), Self::validate_secrets(secrets));
+            }
+        }
+
+        // Unknown dependencies would be mistaken for cycles
+        if !unknown_dependency {
+            report(
+                ConfigSubject::Configuration,
+                config.validate_no_circular_dependencies(),
+            );
+        }
+
+        problems
+    }
+
+    /// Validate an extraction rule, including the syntax of its expression
+    fn validate_extraction(rule: &ValueExtractionRule, location: &str) -> Result<()> {
+        if !crate::template::is_valid_param_name(&rule.name) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Invalid extraction rule name '{}' in {}. Must be alphanumeric with underscores only.",
+                rule.name, location
+            )));
+        }
+        let source = rule.source.as_str();
+        if source.trim().is_empty()
+            && !matches!(
+                rule.extractor_type,
+                ExtractorType::StatusCode | ExtractorType::Body
+            )
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Extraction rule '{}' of {} has empty source",
+                rule.name, location
+            )));
+        }
+
+        let invalid = |kind: &str, reason: String| {
+            Err(HttpDiffError::invalid_config(format!(
+                "Invalid {} '{}' in extraction rule '{}' of {}: {}",
+                kind, source, rule.name, location, reason
+            )))
+        };
+        match rule.extractor_type {
+            ExtractorType::JsonPath => {
+                if let Err(e) = jsonpath_rust::JsonPathInst::from_str(source) {
+                    return invalid("JSONPath", e.to_string());
+                }
+            }
+            ExtractorType::Regex => {
+                if let Err(e) = regex::Regex::new(source) {
+                    return invalid("regex", e.to_string());
+                }
+            }
+            ExtractorType::JmesPath => {
+                if let Err(e) = jmespath::compile(source) {
+                    return invalid("JMESPath expression", e.to_string());
+                }
+            }
+            ExtractorType::XPath => match sxd_xpath::Factory::new().build(source) {
+                Ok(Some(_)) => {}
+                Ok(None) => return invalid("XPath expression", "expression is empty".to_string()),
+                Err(e) => return invalid("XPath expression", e.to_string()),
+            },
+            ExtractorType::Header
+            | ExtractorType::StatusCode
+            | ExtractorType::Cookie
+            | ExtractorType::Body
+            | ExtractorType::Computed => {}
+        }
+        Ok(())
+    }
+
+    /// Validate that a body sent as JSON is valid JSON
+    ///
+    /// Placeholders stand for values, quoted or not, so `{"id": {userId}}` is
+    /// accepted. Files named with placeholders are only checked once resolved.
+    fn validate_json_body(config: &HttpDiffConfig, route: &Route, location: &str) -> Result<()> {
+        let content_types = |headers: Option<&HashMap<String, String>>| -> Vec<String> {
+            headers
+                .into_iter()
+                .flatten()
+                .filter(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                .map(|(_, value)| value.to_ascii_lowercase())
+                .collect()
+        };
+        // A route's own Content-Type wins over those of environments and [global]
+        let mut declared = content_types(route.headers.as_ref());
+        if declared.is_empty() {
+            declared.extend(content_types(
+                config.global.as_ref().and_then(|g| g.headers.as_ref()),
+            ));
+            for env in config.environments.values() {
+                declared.extend(content_types(env.headers.as_ref()));
+            }
+        }
+        if !declared.iter().any(|value| value.contains("json")) {
+            return Ok(());
+        }
+
+        let (field, body) = match (&route.body, &route.body_file) {
+            (Some(body), _) => ("body", body.clone()),
+            (None, Some(path)) if !path.contains('{') => {
+                match std::fs::read_to_string(route.resolve_path(path)) {
+                    Ok(content) => ("body_file", content),
+                    Err(_) => return Ok(()),
                 }
             }
+            _ => return Ok(()),
+        };
+        let sample = crate::template::map_placeholders(&body, |_, _| Some("0".to_string()));
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(&sample) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "{} of {} is sent as JSON but is not valid JSON: {}",
+                field, location, e
+            )));
         }
+        Ok(())
+    }
 
-        // Validate chain configuration
-        config.validate_chain_config()?;
+    /// Validate that a list of rules holds well-formed JSONPath expressions
+    fn validate_json_paths(paths: Option<&[String]>, field: &str, location: &str) -> Result<()> {
+        for path in paths.unwrap_or_default() {
+            if let Err(e) = jsonpath_rust::JsonPathInst::from_str(path) {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "Invalid JSONPath '{}' in {} of {}: {}",
+                    path, field, location, e
+                )));
+            }
+        }
+        Ok(())
+    }
 
+    /// Validate the value-normalization rules of a route
+    fn validate_compare_rules(compare: &CompareRules, location: &str) -> Result<()> {
+        if let Some(tolerance) = &compare.numeric_tolerance {
+            if tolerance.absolute.is_none() && tolerance.relative.is_none() {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "numeric_tolerance of {} must set 'absolute' or 'relative'",
+                    location
+                )));
+            }
+            for (name, value) in [("absolute", tolerance.absolute), ("relative", tolerance.relative)] {
+                if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                    return Err(HttpDiffError::invalid_config(format!(
+                        "numeric_tolerance.{} of {} must be a non-negative number",
+                        name, location
+                    )));
+                }
+            }
+            Self::validate_json_paths(tolerance.paths.as_deref(), "numeric_tolerance.paths", location)?;
+        }
+
+        Self::validate_json_paths(compare.case_insensitive.as_deref(), "case_insensitive", location)?;
+        Self::validate_json_paths(compare.unordered_arrays.as_deref(), "unordered_arrays", location)?;
+
+        for mask in compare.mask.iter().flatten() {
+            if let Err(e) = regex::Regex::new(&mask.pattern) {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "Invalid mask pattern '{}' in {}: {}",
+                    mask.pattern, location, e
+                )));
+            }
+            Self::validate_json_paths(mask.paths.as_deref(), "mask.paths", location)?;
+        }
+
+        if let Some(threshold) = &compare.latency_threshold {
+            Self::validate_latency_threshold(threshold, location)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validate a retry policy
+    fn validate_retry(retry: &RetryConfig, location: &str) -> Result<()> {
+        if retry.max_attempts.is_some_and(|n| n == 0 || n > 10) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "retry.max_attempts of {} must be between 1 and 10",
+                location
+            )));
+        }
+        if retry.multiplier.is_some_and(|m| !m.is_finite() || m < 1.0) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "retry.multiplier of {} must be at least 1.0",
+                location
+            )));
+        }
+        if let (Some(initial), Some(max)) = (retry.initial_backoff_ms, retry.max_backoff_ms) {
+            if initial > max {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "retry.initial_backoff_ms of {} must not exceed max_backoff_ms",
+                    location
+                )));
+            }
+        }
+        if let Some(code) = retry
+            .retry_on_status
+            .iter()
+            .flatten()
+            .find(|code| !(100..=599).contains(*code))
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Invalid status {} in retry.retry_on_status of {}",
+                code, location
+            )));
+        }
         Ok(())
     }
 
+    /// Validate that a route declares at most one body and that its files exist
+    ///
+    /// Relative paths are looked up next to the file the route was loaded
+    /// from; paths containing placeholders are only checked once resolved.
+    fn validate_body(route: &Route, location: &str) -> Result<()> {
+        let declared: Vec<&str> = [
+            ("body", route.body.is_some()),
+            ("body_file", route.body_file.is_some()),
+            ("json", route.json.is_some()),
+            ("form", route.form.is_some()),
+            ("multipart", route.multipart.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(field, set)| set.then_some(field))
+        .collect();
+        if declared.len() > 1 {
+            return Err(HttpDiffError::invalid_config(format!(
+                "{} declares more than one body: {}",
+                location,
+                declared.join(", ")
+            )));
+        }
+
+        let check_file = |path: &str, field: &str| {
+            let resolved = route.resolve_path(path);
+            if path.contains('{') || resolved.is_file() {
+                Ok(())
+            } else {
+                Err(HttpDiffError::invalid_config(format!(
+                    "{} of {} not found: {}",
+                    field,
+                    location,
+                    resolved.display()
+                )))
+            }
+        };
+        if let Some(path) = &route.body_file {
+            check_file(path, "body_file")?;
+        }
+        for part in route.multipart.iter().flatten() {
+            match (&part.value, &part.file) {
+                (Some(_), None) => {}
+                (None, Some(path)) => check_file(path, "multipart file")?,
+                _ => {
+                    return Err(HttpDiffError::invalid_config(format!(
+                        "Multipart part '{}' of {} must set exactly one of 'value' and 'file'",
+                        part.name, location
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the `[global.secrets]` table
+    fn validate_secrets(secrets: &SecretsConfig) -> Result<()> {
+        if secrets.command.as_deref().is_some_and(|c| c.trim().is_empty()) {
+            return Err(HttpDiffError::invalid_config(
+                "secrets.command must not be empty",
+            ));
+        }
+        if let Some(name) = secrets
+            .masked_headers
+            .iter()
+            .flatten()
+            .find(|name| reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err())
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Invalid header name '{}' in secrets.masked_headers",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate the auth provider of an environment
+    fn validate_auth(config: &HttpDiffConfig, auth: &AuthConfig, env_name: &str) -> Result<()> {
+        let location = format!("environment '{}'", env_name);
+        match auth {
+            AuthConfig::OAuth2ClientCredentials { token_url, .. }
+            | AuthConfig::OAuth2Password { token_url, .. } => {
+                // URLs built from placeholders are only known at request time
+                if !token_url.contains('{') && url::Url::parse(token_url).is_err() {
+                    return Err(HttpDiffError::invalid_config(format!(
+                        "Invalid auth.token_url '{}' in {}",
+                        token_url, location
+                    )));
+                }
+            }
+            AuthConfig::ApiKey { name, .. } if name.trim().is_empty() => {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "auth.name of {} must not be empty",
+                    location
+                )));
+            }
+            AuthConfig::LoginRoute {
+                route, token_path, ..
+            } => {
+                if !config.routes.iter().any(|r| &r.name == route) {
+                    return Err(HttpDiffError::invalid_config(format!(
+                        "auth.route of {} references unknown route '{}'",
+                        location, route
+                    )));
+                }
+                Self::validate_json_paths(
+                    Some(std::slice::from_ref(token_path)),
+                    "auth.token_path",
+                    &location,
+                )?;
+            }
+            AuthConfig::Basic { .. } | AuthConfig::ApiKey { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Validate the rate limit of an environment
+    fn validate_rate_limit(rate_limit: &RateLimit, env_name: &str) -> Result<()> {
+        if rate_limit
+            .requests_per_second
+            .is_some_and(|rps| !rps.is_finite() || rps <= 0.0)
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "rate_limit.requests_per_second of environment '{}' must be greater than 0",
+                env_name
+            )));
+        }
+        if rate_limit.burst == Some(0) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "rate_limit.burst of environment '{}' must be at least 1",
+                env_name
+            )));
+        }
+        if rate_limit
+            .max_concurrent_requests
+            .is_some_and(|n| n == 0 || n > 100)
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "rate_limit.max_concurrent_requests of environment '{}' must be between 1 and 100",
+                env_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate a latency regression threshold
+    fn validate_latency_threshold(threshold: &LatencyThreshold, location: &str) -> Result<()> {
+        if threshold.ratio.is_none() && threshold.min_delta_ms.is_none() {
+            return Err(HttpDiffError::invalid_config(format!(
+                "latency_threshold of {} must set 'ratio' or 'min_delta_ms'",
+                location
+            )));
+        }
+        if threshold.ratio.is_some_and(|r| !r.is_finite() || r < 1.0) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "latency_threshold.ratio of {} must be at least 1.0",
+                location
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate the `assert` block of a route
+    fn validate_assertions(
+        config: &HttpDiffConfig,
+        route: &Route,
+        assertions: &RouteAssertions,
+        location: &str,
+    ) -> Result<()> {
+        let codes = match &assertions.status {
+            Some(ExpectedStatus::Code(code)) => vec![*code],
+            Some(ExpectedStatus::AnyOf(codes)) => codes.clone(),
+            None => Vec::new(),
+        };
+        if let Some(code) = codes.iter().find(|code| !(100..=599).contains(*code)) {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Invalid expected status {} in assert of {}",
+                code, location
+            )));
+        }
+
+        for check in assertions.json.iter().flatten() {
+            Self::validate_json_paths(Some(std::slice::from_ref(&check.path)), "assert.json", location)?;
+            if let Some(pattern) = &check.matches {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    return Err(HttpDiffError::invalid_config(format!(
+                        "Invalid pattern '{}' in assert.json of {}: {}",
+                        pattern, location, e
+                    )));
+                }
+            }
+        }
+
+        if let Some(source) = &assertions.schema {
+            if let Err(e) = load_schema(route, source).and_then(|schema| compile_schema(&schema)) {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "Invalid assert.schema of {}: {}",
+                    location, e
+                )));
+            }
+        }
+
+        for env in assertions.environments.iter().flatten() {
+            if !config.environments.contains_key(env) {
+                return Err(HttpDiffError::invalid_config(format!(
+                    "Unknown environment '{}' in assert.environments of {}",
+                    env, location
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 
@@ -153,9 +722,12 @@ mod tests {
         environments.insert(
             "dev".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://dev.example.com".to_string(),
                 headers: None,
                 is_base: true,
+                rate_limit: None,
+                auth: None,
             },
         );
 
@@ -171,15 +743,305 @@ mod tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes: vec![route],
         }
     }
 
+    #[test]
+    fn test_invalid_ignore_field_jsonpath_fails() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        config.routes[0].ignore_fields = Some(vec!["$.items[".to_string()]);
+
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("Invalid JSONPath '$.items['"));
+
+        config.routes[0].ignore_fields = Some(vec!["$.items[*].id".to_string()]);
+        assert!(ConfigValidatorImpl::new().validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_retry_policy_fails() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        config.routes[0].retry = Some(RetryConfig {
+            max_attempts: Some(0),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("retry.max_attempts of route 'test_route'"));
+
+        config.routes[0].retry = Some(RetryConfig {
+            retry_on_status: Some(vec![503, 999]),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("Invalid status 999"));
+
+        config.routes[0].retry = Some(RetryConfig {
+            max_attempts: Some(3),
+            retry_on_status: Some(vec![502, 503]),
+            ..Default::default()
+        });
+        assert!(ConfigValidatorImpl::new().validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_body_declarations_fail() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        config.routes[0].body = Some("{}".to_string());
+        config.routes[0].json = Some(serde_json::json!({"id": 1}));
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("more than one body: body, json"));
+
+        config.routes[0].body = None;
+        config.routes[0].json = None;
+        config.routes[0].body_file = Some("missing/order.json".to_string());
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("body_file of route 'test_route' not found"));
+
+        // Relative files are looked up next to the configuration file
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("missing")).unwrap();
+        std::fs::write(dir.path().join("missing/order.json"), "{}").unwrap();
+        config.routes[0].base_dir = Some(dir.path().to_path_buf());
+        assert!(ConfigValidatorImpl::new().validate(&config).is_ok());
+        config.routes[0].base_dir = None;
+
+        config.routes[0].body_file = Some("fixtures/{orderId}.json".to_string());
+        assert!(ConfigValidatorImpl::new().validate(&config).is_ok());
+
+        config.routes[0].body_file = None;
+        config.routes[0].multipart = Some(vec![crate::config::MultipartPart {
+            name: "file".to_string(),
+            value: Some("a".to_string()),
+            file: Some("b.txt".to_string()),
+            ..Default::default()
+        }]);
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("exactly one of 'value' and 'file'"));
+    }
+
+    #[test]
+    fn test_find_problems_reports_every_problem() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        config.routes[0].headers = Some(HashMap::from([(
+            "Content-Type".to_string(),
+            "application/json".to_string(),
+        )]));
+        config.routes[0].body = Some(r#"{"id": {userId}, "tags": ["{tag}"]}"#.to_string());
+        assert!(ConfigValidatorImpl::new().validate(&config).is_ok());
+
+        config.routes[0].body = Some(r#"{"id": {userId}"#.to_string());
+        config.routes[0].extract = Some(vec![ValueExtractionRule {
+            name: "user_id".to_string(),
+            extractor_type: ExtractorType::JsonPath,
+            source: "$.[user".to_string(),
+            default_value: None,
+            required: false,
+        }]);
+        let mut duplicate = config.routes[0].clone();
+        duplicate.body = None;
+        duplicate.extract = None;
+        duplicate.depends_on = Some(vec!["missing".to_string()]);
+        config.routes.push(duplicate);
+
+        let problems = ConfigValidatorImpl::new().find_problems(&config, "http-diff.toml");
+        let messages: Vec<String> = problems.iter().map(|p| p.error.to_string()).collect();
+        assert_eq!(problems.len(), 4, "{:#?}", messages);
+        assert!(problems
+            .iter()
+            .all(|p| p.subject == ConfigSubject::Route("test_route".to_string())));
+        assert!(messages[0].contains("Invalid JSONPath '$.[user' in extraction rule 'user_id'"));
+        assert!(messages[1].contains("body of route 'test_route' is sent as JSON but is not valid JSON"));
+        assert!(messages[2].contains("Route name 'test_route' is used by more than one route"));
+        assert!(messages[3].contains("depends on non-existent route 'missing'"));
+
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert_eq!(err.to_string(), messages[0]);
+    }
+
+    #[test]
+    fn test_invalid_masked_header_fails() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        config.global = Some(crate::config::GlobalConfig {
+            secrets: Some(SecretsConfig {
+                masked_headers: Some(vec!["X-Api-Key".to_string(), "Bad Header".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("Invalid header name 'Bad Header'"));
+    }
+
+    #[test]
+    fn test_invalid_rate_limit_fails() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        let env = config.environments.get_mut("dev").unwrap();
+        env.rate_limit = Some(RateLimit {
+            requests_per_second: Some(0.0),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("rate_limit.requests_per_second of environment 'dev'"));
+
+        let env = config.environments.get_mut("dev").unwrap();
+        env.rate_limit = Some(RateLimit {
+            max_concurrent_requests: Some(0),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("must be between 1 and 100"));
+
+        let env = config.environments.get_mut("dev").unwrap();
+        env.rate_limit = Some(RateLimit {
+            requests_per_second: Some(2.5),
+            burst: Some(5),
+            max_concurrent_requests: Some(2),
+        });
+        assert!(ConfigValidatorImpl::new().validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_auth_fails() {
+        let mut config = create_test_config_with_conditions(vec![]);
+        let env = config.environments.get_mut("dev").unwrap();
+        env.auth = Some(AuthConfig::LoginRoute {
+            route: "login".to_string(),
+            token_path: "$.token".to_string(),
+            header: None,
+            scheme: None,
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("references unknown route 'login'"));
+
+        let env = config.environments.get_mut("dev").unwrap();
+        env.auth = Some(AuthConfig::OAuth2ClientCredentials {
+            token_url: "not a url".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "{secret:CLIENT_SECRET}".to_string(),
+            scope: None,
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("Invalid auth.token_url 'not a url'"));
+    }
+
+    #[test]
+    fn test_invalid_compare_rules_fail() {
+        use crate::config::types::{CompareRules, LatencyThreshold, NumericTolerance, ValueMask};
+
+        let mut config = create_test_config_with_conditions(vec![]);
+        config.routes[0].compare = Some(CompareRules {
+            numeric_tolerance: Some(NumericTolerance::default()),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("must set 'absolute' or 'relative'"));
+
+        config.routes[0].compare = Some(CompareRules {
+            numeric_tolerance: Some(NumericTolerance {
+                relative: Some(-0.1),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("numeric_tolerance.relative"));
+
+        config.routes[0].compare = Some(CompareRules {
+            mask: Some(vec![ValueMask {
+                pattern: "(".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("Invalid mask pattern '('"));
+
+        config.routes[0].compare = Some(CompareRules {
+            unordered_arrays: Some(vec!["$.tags[".to_string()]),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("in unordered_arrays of route 'test_route'"));
+
+        config.routes[0].compare = Some(CompareRules {
+            latency_threshold: Some(LatencyThreshold {
+                ratio: Some(0.5),
+                min_delta_ms: None,
+            }),
+            ..Default::default()
+        });
+        let err = ConfigValidatorImpl::new().validate(&config).unwrap_err();
+        assert!(err.to_string().contains("latency_threshold.ratio"));
+    }
+
+    #[test]
+    fn test_invalid_assertions_fail() {
+        use crate::config::types::{ExpectedStatus, JsonAssertion, RouteAssertions, SchemaSource};
+
+        let mut config = create_test_config_with_conditions(vec![]);
+        let validate = |config: &HttpDiffConfig| {
+            ConfigValidatorImpl::new()
+                .validate(config)
+                .map_err(|e| e.to_string())
+        };
+
+        config.routes[0].assertions = Some(RouteAssertions {
+            status: Some(ExpectedStatus::AnyOf(vec![200, 999])),
+            ..Default::default()
+        });
+        assert!(validate(&config).unwrap_err().contains("Invalid expected status 999"));
+
+        config.routes[0].assertions = Some(RouteAssertions {
+            json: Some(vec![JsonAssertion {
+                path: "$.id".to_string(),
+                matches: Some("(".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        assert!(validate(&config).unwrap_err().contains("Invalid pattern '('"));
+
+        config.routes[0].assertions = Some(RouteAssertions {
+            schema: Some(SchemaSource::File("missing-schema.json".to_string())),
+            ..Default::default()
+        });
+        assert!(validate(&config).unwrap_err().contains("cannot read schema file"));
+
+        config.routes[0].assertions = Some(RouteAssertions {
+            environments: Some(vec!["prod".to_string()]),
+            ..Default::default()
+        });
+        assert!(validate(&config).unwrap_err().contains("Unknown environment 'prod'"));
+
+        config.routes[0].assertions = Some(RouteAssertions {
+            status: Some(ExpectedStatus::Code(200)),
+            schema: Some(SchemaSource::Inline(serde_json::json!({"type": "object"}))),
+            environments: Some(vec!["dev".to_string()]),
+            ..Default::default()
+        });
+        assert!(validate(&config).is_ok());
+    }
+
     #[test]
     fn test_valid_conditions() {
         let validator = ConfigValidatorImpl::new();
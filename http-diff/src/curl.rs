@@ -3,17 +3,21 @@
 //! This module handles the generation of curl commands from HTTP diff configurations
 //! and provides utilities for writing curl commands to shell script files.
 
+use crate::body_builder::{BodyBuilder, RequestBody};
 use crate::config::{HttpDiffConfig, Route, UserData};
 use crate::error::Result;
+use crate::secrets::SecretStore;
 use crate::url_builder::UrlBuilder;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
 
 /// Generator for curl commands and file operations
 pub struct CurlGenerator {
     config: HttpDiffConfig,
+    secrets: Arc<SecretStore>,
 }
 
 /// Represents a curl command with metadata
@@ -43,38 +47,58 @@ impl CurlCommand {
 }
 
 impl CurlGenerator {
-    /// Create a new curl generator
+    /// Create a new curl generator redacting secrets with the active store
     pub fn new(config: HttpDiffConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            secrets: crate::secrets::active(),
+        }
+    }
+
+    /// Redact secrets with `secrets`, usually the store installed by the entry point
+    pub fn with_secrets(mut self, secrets: Arc<SecretStore>) -> Self {
+        self.secrets = secrets;
+        self
     }
 
     /// Generate curl command for a specific request with proper escaping
+    ///
+    /// Values of masked headers and resolved secrets are redacted, so the
+    /// commands can be shared without leaking credentials.
     pub fn generate_curl_command(
         &self,
         route: &Route,
         environment: &str,
         user_data: &UserData,
     ) -> Result<CurlCommand> {
+        let secrets = &self.secrets;
         let url = UrlBuilder::new(&self.config, route, environment, user_data).build()?;
-        let url_str = url.as_str();
-        let mut command = format!("curl -X {} '{}'", route.method, escape_argument(url_str));
+        let url_str = secrets.redact(url.as_str());
+        let mut command = format!("curl -X {} '{}'", route.method, escape_argument(&url_str));
 
         // Add headers with CSV substitution and proper escaping
-        let headers =
+        let mut headers =
             crate::url_builder::resolve_headers(&self.config, route, environment, user_data)?;
+        let body = BodyBuilder::new(route, user_data).build()?;
+        if let Some(body) = &body {
+            body.apply_content_type(&mut headers);
+        }
         for (key, value) in headers {
             command.push_str(&format!(
                 " \\\n  -H '{}: {}'",
                 escape_argument(&key),
-                escape_argument(&value)
+                escape_argument(&secrets.mask_header(&key, &value))
             ));
         }
 
         // Add body with CSV substitution and proper escaping
-        if let Some(body) = &route.body {
-            let substituted_body = user_data.substitute_placeholders(body, false, false)?;
-            let escaped_body = escape_argument(&substituted_body);
-            command.push_str(&format!(" \\\n  -d '{}'", escaped_body));
+        for (option, argument) in body
+            .as_ref()
+            .map(RequestBody::curl_options)
+            .unwrap_or_default()
+        {
+            let escaped_argument = escape_argument(&secrets.redact(&argument));
+            command.push_str(&format!(" \\\n  {} '{}'", option, escaped_argument));
         }
 
         Ok(CurlCommand {
@@ -218,6 +242,7 @@ mod tests {
         environments.insert(
             "test".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://api-test.example.com".to_string(),
                 headers: Some({
                     let mut headers = HashMap::new();
@@ -225,6 +250,8 @@ mod tests {
                     headers
                 }),
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
 
@@ -244,9 +271,20 @@ mod tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }];
 
         HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -325,7 +363,7 @@ mod tests {
             headers: Some({
                 let mut headers = HashMap::new();
                 headers.insert(
-                    "Authorization".to_string(),
+                    "X-Signature".to_string(),
                     "Bearer token'with'quotes".to_string(),
                 );
                 headers.insert("Content-Type".to_string(), "application/json".to_string());
@@ -340,6 +378,16 @@ mod tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         });
 
         let generator = CurlGenerator::new(config);
@@ -363,10 +411,42 @@ mod tests {
 
         // Verify the command structure is valid
         assert!(command.command.starts_with("curl -X POST"));
-        assert!(command.command.contains("-H 'Authorization:"));
+        assert!(command.command.contains("-H 'X-Signature:"));
         assert!(command.command.contains("-d '{"));
     }
 
+    #[test]
+    fn test_curl_command_redacts_secrets() {
+        std::env::set_var("CURL_TEST_API_KEY", "k-123456");
+        let mut config = create_test_config();
+        config.routes[0].headers = Some(HashMap::from([
+            (
+                "Authorization".to_string(),
+                "Bearer plain-text-token".to_string(),
+            ),
+            (
+                "X-Client".to_string(),
+                "{secret:CURL_TEST_API_KEY}".to_string(),
+            ),
+        ]));
+        config.routes[0].params = Some(HashMap::from([(
+            "key".to_string(),
+            "{secret:CURL_TEST_API_KEY}".to_string(),
+        )]));
+
+        let generator = CurlGenerator::new(config);
+        let route = &generator.config.routes[0];
+        let command = generator
+            .generate_curl_command(route, "test", &create_test_user_data())
+            .unwrap();
+
+        assert!(command.command.contains("-H 'Authorization: Bearer ***'"));
+        assert!(command.command.contains("-H 'X-Client: ***'"));
+        assert!(command.command.contains("key=***"));
+        assert!(!command.command.contains("k-123456"));
+        assert!(!command.command.contains("plain-text-token"));
+    }
+
     #[test]
     fn test_curl_command_with_url_encoding() {
         let config = create_test_config();
@@ -396,9 +476,12 @@ mod tests {
         config.environments.insert(
             "prod".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://api.example.com".to_string(),
                 headers: None,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
 
@@ -42,6 +42,13 @@ pub enum HttpDiffError {
     #[error("Invalid configuration: {message}")]
     InvalidConfig { message: String },
 
+    #[error("Invalid configuration at {}:{line}: {message}", path.display())]
+    ConfigAt {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+
     #[error("Request execution failed for {route} in {environment}: {message}")]
     RequestFailed {
         route: String,
@@ -49,6 +56,12 @@ pub enum HttpDiffError {
         message: String,
     },
 
+    #[error("Authentication failed for environment '{environment}': {message}")]
+    AuthenticationFailed {
+        environment: String,
+        message: String,
+    },
+
     #[error("Response comparison failed: {message}")]
     ComparisonFailed { message: String },
 
@@ -92,6 +105,12 @@ pub enum HttpDiffError {
         message: String,
     },
 
+    #[error("Invalid placeholder expression '{{{expression}}}': {message}")]
+    TemplateError {
+        expression: String,
+        message: String,
+    },
+
     #[error("General error: {message}")]
     General { message: String },
 }
@@ -104,6 +123,15 @@ impl HttpDiffError {
         }
     }
 
+    /// Create a new configuration error pointing at the file and line it comes from
+    pub fn config_at<P: Into<PathBuf>, S: Into<String>>(path: P, line: usize, message: S) -> Self {
+        Self::ConfigAt {
+            path: path.into(),
+            line,
+            message: message.into(),
+        }
+    }
+
     /// Create a new request failed error
     pub fn request_failed<S: Into<String>>(route: S, environment: S, message: S) -> Self {
         Self::RequestFailed {
@@ -113,6 +141,14 @@ impl HttpDiffError {
         }
     }
 
+    /// Create a new authentication failed error
+    pub fn authentication_failed<S: Into<String>>(environment: S, message: S) -> Self {
+        Self::AuthenticationFailed {
+            environment: environment.into(),
+            message: message.into(),
+        }
+    }
+
     /// Create a new comparison failed error
     pub fn comparison_failed<S: Into<String>>(message: S) -> Self {
         Self::ComparisonFailed {
@@ -182,6 +218,14 @@ impl HttpDiffError {
         }
     }
 
+    /// Create a new placeholder expression error
+    pub fn template_error<S: Into<String>>(expression: S, message: S) -> Self {
+        Self::TemplateError {
+            expression: expression.into(),
+            message: message.into(),
+        }
+    }
+
     /// Create a new general error
     pub fn general<S: Into<String>>(message: S) -> Self {
         Self::General {
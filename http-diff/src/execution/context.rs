@@ -1,5 +1,6 @@
 use crate::config::types::UserData;
 use crate::error::{HttpDiffError, Result};
+use crate::execution::cookies::{CookieJar, CookieJarSnapshot, SessionCookies};
 use crate::types::{ExtractedValue, ExtractionType};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -271,6 +272,8 @@ pub struct ContextScope {
     completed_routes: HashSet<String>,
     /// Routes that have completed extraction in this scope
     extraction_completed_routes: HashSet<String>,
+    /// Cookie jars of this user, shared by every clone of the scope
+    cookies: Arc<SessionCookies>,
 }
 
 impl ContextScope {
@@ -281,6 +284,7 @@ impl ContextScope {
             context: DynamicContext::for_user_data(user_data_index),
             completed_routes: HashSet::new(),
             extraction_completed_routes: HashSet::new(),
+            cookies: Arc::new(SessionCookies::new()),
         }
     }
 
@@ -328,6 +332,16 @@ impl ContextScope {
         Ok(())
     }
 
+    /// Get the cookie jar of this user for an environment
+    pub fn cookie_jar(&self, environment: &str) -> Arc<CookieJar> {
+        self.cookies.jar(environment)
+    }
+
+    /// Get the non-empty cookie jars of this user by environment name
+    pub fn cookie_jars(&self) -> Vec<(String, Arc<CookieJar>)> {
+        self.cookies.jars()
+    }
+
     /// Get completion statistics for this scope
     pub fn get_completion_stats(&self, total_routes: usize) -> ContextScopeStats {
         ContextScopeStats {
@@ -344,6 +358,7 @@ impl ContextScope {
         self.completed_routes.clear();
         self.extraction_completed_routes.clear();
         self.context = DynamicContext::for_user_data(self.user_data_index);
+        self.cookies = Arc::new(SessionCookies::new());
     }
 }
 
@@ -420,42 +435,52 @@ impl VariableResolver {
         context: &DynamicContext,
         user_data: &UserData,
     ) -> Result<Option<String>> {
-        // Check context first if context has priority
-        if self.context_priority {
-            if let Some(value) = context.get_value_string(param_name) {
-                return Ok(Some(value.to_string()));
+        match self.lookup_parameter(param_name, context, user_data) {
+            Some(value) => Ok(Some(value)),
+            None if self.strict_mode => {
+                Err(Self::missing_parameter_error(param_name, context, user_data))
             }
+            None => Ok(None),
         }
+    }
 
-        // Check user data
-        if let Some(value) = user_data.data.get(param_name) {
-            return Ok(Some(value.clone()));
-        }
+    /// Look up a parameter value, respecting the configured priority
+    fn lookup_parameter(
+        &self,
+        param_name: &str,
+        context: &DynamicContext,
+        user_data: &UserData,
+    ) -> Option<String> {
+        let from_context = || context.get_value_string(param_name).map(str::to_string);
+        let from_user_data = || user_data.data.get(param_name).cloned();
 
-        // Check context second if user data has priority
-        if !self.context_priority {
-            if let Some(value) = context.get_value_string(param_name) {
-                return Ok(Some(value.to_string()));
-            }
+        if self.context_priority {
+            from_context().or_else(from_user_data)
+        } else {
+            from_user_data().or_else(from_context)
         }
+    }
 
-        // Handle missing parameter
-        if self.strict_mode {
-            let available_context = context.get_all_keys().join(", ");
-            let available_user_data = user_data.data.keys().cloned().collect::<Vec<_>>().join(", ");
-            return Err(HttpDiffError::MissingPathParameter {
-                param: param_name.to_string(),
-                available_params: format!(
-                    "Context: [{}], User Data: [{}]",
-                    available_context, available_user_data
-                ),
-            });
+    /// Build the error reported for a parameter missing in strict mode
+    fn missing_parameter_error(
+        param_name: &str,
+        context: &DynamicContext,
+        user_data: &UserData,
+    ) -> HttpDiffError {
+        let available_context = context.get_all_keys().join(", ");
+        let available_user_data = user_data.data.keys().cloned().collect::<Vec<_>>().join(", ");
+        HttpDiffError::MissingPathParameter {
+            param: param_name.to_string(),
+            available_params: format!(
+                "Context: [{}], User Data: [{}]",
+                available_context, available_user_data
+            ),
         }
-
-        Ok(None)
     }
 
     /// Substitute placeholders in text using context and user data
+    ///
+    /// Placeholders may also hold expressions; see [`crate::template`].
     pub fn substitute_placeholders(
         &self,
         text: &str,
@@ -463,58 +488,19 @@ impl VariableResolver {
         user_data: &UserData,
         url_encode: bool,
     ) -> Result<String> {
-        let mut result = String::with_capacity(text.len() + 50);
-        let mut chars = text.char_indices().peekable();
-
-        while let Some((_pos, ch)) = chars.next() {
-            if ch == '{' {
-                // Found potential parameter start, collect parameter name
-                let mut param_name = String::new();
-                let mut found_end = false;
-
-                while let Some((_, next_ch)) = chars.peek() {
-                    if *next_ch == '}' {
-                        chars.next(); // consume the '}'
-                        found_end = true;
-                        break;
-                    } else if *next_ch == '{' {
-                        // Nested braces, not a valid parameter
-                        break;
-                    } else {
-                        param_name.push(chars.next().unwrap().1);
-                    }
-                }
-
-                if found_end && is_valid_param_name(&param_name) {
-                    match self.resolve_parameter(&param_name, context, user_data)? {
-                        Some(value) => {
-                            if url_encode {
-                                result.push_str(&urlencoding::encode(&value));
-                            } else {
-                                result.push_str(&value);
-                            }
-                        }
-                        None => {
-                            // Non-strict mode: preserve the original placeholder
-                            result.push('{');
-                            result.push_str(&param_name);
-                            result.push('}');
-                        }
-                    }
+        crate::template::render(
+            text,
+            url_encode,
+            |name| self.lookup_parameter(name, context, user_data),
+            |name| {
+                if self.strict_mode {
+                    Err(Self::missing_parameter_error(name, context, user_data))
                 } else {
-                    // Invalid parameter format, preserve the original text
-                    result.push(ch);
-                    result.push_str(&param_name);
-                    if found_end {
-                        result.push('}');
-                    }
+                    // Non-strict mode: preserve the original placeholder
+                    Ok(())
                 }
-            } else {
-                result.push(ch);
-            }
-        }
-
-        Ok(result)
+            },
+        )
     }
 
     /// Get all available parameters from context and user data
@@ -552,11 +538,6 @@ impl Default for VariableResolver {
     }
 }
 
-/// Check if a parameter name is a valid identifier (letters, numbers, underscore)
-fn is_valid_param_name(name: &str) -> bool {
-    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
-}
-
 /// Manages the lifecycle of dynamic contexts and provides thread-safe access
 #[derive(Debug)]
 pub struct ContextManager {
@@ -721,6 +702,36 @@ impl ContextManager {
         Ok(())
     }
 
+    /// Get the cookie jar of a user for an environment
+    pub fn cookie_jar(&self, user_data_index: usize, environment: &str) -> Result<Arc<CookieJar>> {
+        Ok(self
+            .get_or_create_scope(user_data_index)?
+            .cookie_jar(environment))
+    }
+
+    /// Get the contents of every non-empty cookie jar, ordered by user data index
+    pub fn cookie_jar_snapshots(&self) -> Result<Vec<CookieJarSnapshot>> {
+        let scopes = self.scopes.read().map_err(|_| {
+            HttpDiffError::general("Failed to acquire read lock on context scopes")
+        })?;
+
+        let mut indices: Vec<usize> = scopes.keys().copied().collect();
+        indices.sort_unstable();
+        Ok(indices
+            .into_iter()
+            .flat_map(|index| {
+                scopes[&index]
+                    .cookie_jars()
+                    .into_iter()
+                    .map(move |(environment, jar)| CookieJarSnapshot {
+                        user_data_index: index,
+                        environment,
+                        cookies: jar.cookies(),
+                    })
+            })
+            .collect())
+    }
+
     /// Get the number of active scopes
     pub fn scope_count(&self) -> Result<usize> {
         let scopes = self.scopes.read().map_err(|_| {
@@ -1033,4 +1044,20 @@ mod tests {
         assert_eq!(deserialized.value_count(), 1);
         assert_eq!(deserialized.get_value_string("user_id"), Some("123"));
     }
+
+    #[test]
+    fn test_cookie_jars_survive_scope_updates_and_reset() {
+        let manager = ContextManager::new();
+        let jar = manager.cookie_jar(0, "dev").unwrap();
+
+        // A scope updated while a request holds the jar keeps the same jar
+        let scope = manager.get_or_create_scope(0).unwrap();
+        manager.update_scope(0, scope).unwrap();
+        assert!(Arc::ptr_eq(&jar, &manager.cookie_jar(0, "dev").unwrap()));
+        assert!(!Arc::ptr_eq(&jar, &manager.cookie_jar(1, "dev").unwrap()));
+
+        manager.reset_all_scopes().unwrap();
+        assert!(!Arc::ptr_eq(&jar, &manager.cookie_jar(0, "dev").unwrap()));
+        assert!(manager.cookie_jar_snapshots().unwrap().is_empty());
+    }
 }
\ No newline at end of file
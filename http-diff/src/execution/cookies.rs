@@ -0,0 +1,205 @@
+//! Session cookies for chained routes
+//!
+//! When `cookie_jar` is enabled, every user data row keeps one cookie jar per
+//! environment in its [`ContextScope`](super::context::ContextScope). Cookies
+//! set by a response are sent on later requests of the same user to the same
+//! environment, following the usual domain, path and expiry rules.
+
+use crate::types::HttpResponse;
+use cookie_store::CookieStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use url::Url;
+
+/// Cookies of one user in one environment
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    store: RwLock<CookieStore>,
+}
+
+impl CookieJar {
+    /// Create an empty jar
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value of the `Cookie` header for a request to `url`, if any cookie applies
+    pub fn cookie_header(&self, url: &Url) -> Option<String> {
+        let store = self.store.read().ok()?;
+        let header = store
+            .get_request_values(url)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        (!header.is_empty()).then_some(header)
+    }
+
+    /// Store the cookies set by a response
+    ///
+    /// Invalid cookies, and cookies the response is not allowed to set, are ignored.
+    pub fn store_response_cookies(&self, response: &HttpResponse) {
+        let Ok(url) = Url::parse(&response.url) else {
+            return;
+        };
+        let Some(set_cookie) = response
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+            .map(|(_, value)| value)
+        else {
+            return;
+        };
+
+        if let Ok(mut store) = self.store.write() {
+            // Repeated Set-Cookie headers are kept on separate lines
+            for cookie in set_cookie.lines() {
+                let _ = store.parse(cookie, &url);
+            }
+        }
+    }
+
+    /// Cookies currently held, sorted by domain, path and name
+    pub fn cookies(&self) -> Vec<StoredCookie> {
+        let Ok(store) = self.store.read() else {
+            return Vec::new();
+        };
+        let mut cookies: Vec<StoredCookie> = store
+            .iter_unexpired()
+            .map(|cookie| StoredCookie {
+                name: cookie.name().to_string(),
+                value: cookie.value().to_string(),
+                domain: String::from(&cookie.domain),
+                path: cookie.path.as_ref().to_string(),
+            })
+            .collect();
+        cookies.sort_by(|a, b| (&a.domain, &a.path, &a.name).cmp(&(&b.domain, &b.path, &b.name)));
+        cookies
+    }
+
+    /// Check whether the jar holds no cookies
+    pub fn is_empty(&self) -> bool {
+        self.store
+            .read()
+            .map(|store| store.iter_unexpired().next().is_none())
+            .unwrap_or(true)
+    }
+}
+
+/// Cookie jars of one user, one per environment
+///
+/// Shared by every clone of a context scope, so jars created while requests
+/// are in flight are never lost when a scope is updated.
+#[derive(Debug, Default)]
+pub struct SessionCookies {
+    jars: RwLock<HashMap<String, Arc<CookieJar>>>,
+}
+
+impl SessionCookies {
+    /// Create a session without cookies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The jar of an environment, created on first use
+    pub fn jar(&self, environment: &str) -> Arc<CookieJar> {
+        if let Some(jar) = self
+            .jars
+            .read()
+            .ok()
+            .and_then(|jars| jars.get(environment).cloned())
+        {
+            return jar;
+        }
+        let mut jars = self
+            .jars
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        jars.entry(environment.to_string()).or_default().clone()
+    }
+
+    /// Non-empty jars by environment name
+    pub fn jars(&self) -> Vec<(String, Arc<CookieJar>)> {
+        let Ok(jars) = self.jars.read() else {
+            return Vec::new();
+        };
+        let mut jars: Vec<_> = jars
+            .iter()
+            .filter(|(_, jar)| !jar.is_empty())
+            .map(|(environment, jar)| (environment.clone(), jar.clone()))
+            .collect();
+        jars.sort_by(|a, b| a.0.cmp(&b.0));
+        jars
+    }
+}
+
+/// A cookie held in a jar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+}
+
+/// Contents of a cookie jar at the end of a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CookieJarSnapshot {
+    /// Index of the user data row owning the jar
+    pub user_data_index: usize,
+    pub environment: String,
+    pub cookies: Vec<StoredCookie>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(url: &str, set_cookie: &str) -> HttpResponse {
+        HttpResponse::new(
+            200,
+            HashMap::from([("set-cookie".to_string(), set_cookie.to_string())]),
+            String::new(),
+            url.to_string(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_cookies_follow_domain_and_path() {
+        let jar = CookieJar::new();
+        jar.store_response_cookies(&response(
+            "https://api.example.com/auth/login",
+            "session=abc; Path=/\nscoped=1; Path=/auth\nexpired=x; Max-Age=0",
+        ));
+
+        let url = |u: &str| Url::parse(u).unwrap();
+        let header = jar
+            .cookie_header(&url("https://api.example.com/auth/me"))
+            .unwrap();
+        assert!(header.contains("session=abc") && header.contains("scoped=1"));
+        assert_eq!(
+            jar.cookie_header(&url("https://api.example.com/orders")),
+            Some("session=abc".to_string())
+        );
+        assert_eq!(jar.cookie_header(&url("https://other.example.com/")), None);
+
+        let names: Vec<_> = jar.cookies().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["session", "scoped"]);
+    }
+
+    #[test]
+    fn test_session_jars_are_per_environment() {
+        let session = SessionCookies::new();
+        session
+            .jar("dev")
+            .store_response_cookies(&response("https://dev.example.com/", "session=dev"));
+
+        assert!(Arc::ptr_eq(&session.jar("dev"), &session.jar("dev")));
+        assert!(session.jar("prod").is_empty());
+
+        let jars = session.jars();
+        assert_eq!(jars.len(), 1);
+        assert_eq!(jars[0].0, "dev");
+    }
+}
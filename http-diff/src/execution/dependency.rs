@@ -1007,6 +1007,16 @@ mod tests {
             extract: None,
             depends_on,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }
 
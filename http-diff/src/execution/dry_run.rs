@@ -0,0 +1,605 @@
+//! Dry runs: the requests of a run, resolved without sending them
+//!
+//! [`DryRunPlanner`] orders the routes in the batches of the dependency
+//! [`ExecutionPlan`], evaluates the conditions of every route for every user
+//! data row and resolves URLs, headers and bodies the same way the HTTP client
+//! does. Values extracted from the responses of upstream routes are only known
+//! once those routes run, so their placeholders are kept as written and listed
+//! as unresolved on each request.
+//!
+//! Credentials added by an environment's `auth` provider are obtained at run
+//! time, so the plan shows where they go with an [`AUTH_PLACEHOLDER`] value;
+//! secrets and masked headers are redacted.
+
+use crate::auth::Credential;
+use crate::body_builder::{BodyBuilder, PartContent, RequestBody};
+use crate::config::{AuthConfig, HttpDiffConfig, Route, UserData};
+use crate::error::{HttpDiffError, Result};
+use crate::execution::dependency::{DependencyResolver, ExecutionPlan};
+use crate::secrets::SecretStore;
+use crate::traits::ConditionEvaluator;
+use crate::url_builder::{resolve_headers, UrlBuilder};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Value shown for credentials added by an environment's `auth` provider
+pub const AUTH_PLACEHOLDER: &str = "<from auth provider>";
+
+/// Every request a run would send, per environment
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunPlan {
+    pub environments: Vec<EnvironmentPlan>,
+}
+
+/// The requests a run would send to one environment, in execution order
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentPlan {
+    pub environment: String,
+    pub requests: Vec<PlannedRequest>,
+}
+
+/// A request resolved for a route, user data row and environment
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedRequest {
+    /// Dependency batch the request is sent in, starting at 0
+    pub batch: usize,
+    pub route: String,
+    /// Index of the user data row in the users CSV
+    pub user_index: usize,
+    /// The user data row the request is made with
+    pub user_data: BTreeMap<String, String>,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Values extracted from upstream routes, left as `{name}` placeholders
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unresolved: Vec<String>,
+    /// Why the conditions of the route skip this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+    /// Why the request cannot be built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PlannedRequest {
+    /// Whether the request would be sent
+    pub fn is_sent(&self) -> bool {
+        self.skipped.is_none() && self.error.is_none()
+    }
+}
+
+impl DryRunPlan {
+    /// Number of requests that would be sent, across all environments
+    pub fn request_count(&self) -> usize {
+        self.requests().filter(|request| request.is_sent()).count()
+    }
+
+    /// Number of requests skipped by route conditions, across all environments
+    pub fn skipped_count(&self) -> usize {
+        self.requests()
+            .filter(|request| request.skipped.is_some())
+            .count()
+    }
+
+    /// Number of requests that cannot be built, across all environments
+    pub fn error_count(&self) -> usize {
+        self.requests()
+            .filter(|request| request.error.is_some())
+            .count()
+    }
+
+    fn requests(&self) -> impl Iterator<Item = &PlannedRequest> {
+        self.environments
+            .iter()
+            .flat_map(|environment| &environment.requests)
+    }
+}
+
+/// Computes the requests a run would send without sending any
+pub struct DryRunPlanner<'a, E: ConditionEvaluator> {
+    config: &'a HttpDiffConfig,
+    condition_evaluator: E,
+    secrets: Arc<SecretStore>,
+}
+
+impl<'a, E: ConditionEvaluator> DryRunPlanner<'a, E> {
+    /// Create a new dry-run planner redacting secrets with the active store
+    pub fn new(config: &'a HttpDiffConfig, condition_evaluator: E) -> Self {
+        Self {
+            config,
+            condition_evaluator,
+            secrets: crate::secrets::active(),
+        }
+    }
+
+    /// Redact secrets with `secrets`, usually the store installed by the entry point
+    pub fn with_secrets(mut self, secrets: Arc<SecretStore>) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Plan the requests of a run
+    ///
+    /// Environments and routes default to all configured ones, as in a run;
+    /// environments are listed by name and the requests of each environment in
+    /// the order of their dependency batches and of the configuration.
+    pub fn plan(
+        &self,
+        user_data: &[UserData],
+        environments: Option<Vec<String>>,
+        routes: Option<Vec<String>>,
+    ) -> Result<DryRunPlan> {
+        let environments = self.resolve_environments(environments)?;
+        let routes = self.resolve_routes(routes)?;
+        let execution_plan = DependencyResolver::from_routes(&routes)?.compute_execution_plan()?;
+        let batches = self.ordered_batches(&execution_plan, &routes);
+
+        let mut planned = Vec::new();
+        for environment in &environments {
+            let mut requests = Vec::new();
+            // Names of the values extracted by the routes of earlier batches
+            let mut upstream: Vec<&str> = Vec::new();
+            for (batch, batch_routes) in batches.iter().enumerate() {
+                for route in batch_routes {
+                    for (user_index, user) in user_data.iter().enumerate() {
+                        requests.push(self.plan_request(
+                            batch,
+                            route,
+                            environment,
+                            user_index,
+                            user,
+                            &upstream,
+                        ));
+                    }
+                }
+                upstream.extend(
+                    batch_routes
+                        .iter()
+                        .flat_map(|route| route.extract.iter().flatten())
+                        .map(|rule| rule.name.as_str()),
+                );
+            }
+            planned.push(EnvironmentPlan {
+                environment: environment.clone(),
+                requests,
+            });
+        }
+
+        Ok(DryRunPlan {
+            environments: planned,
+        })
+    }
+
+    fn resolve_environments(&self, environments: Option<Vec<String>>) -> Result<Vec<String>> {
+        let mut environments = match environments {
+            Some(environments) => {
+                if let Some(unknown) = environments
+                    .iter()
+                    .find(|name| !self.config.environments.contains_key(*name))
+                {
+                    return Err(HttpDiffError::InvalidEnvironment {
+                        environment: unknown.clone(),
+                    });
+                }
+                environments
+            }
+            None => self.config.environments.keys().cloned().collect(),
+        };
+        environments.sort();
+        environments.dedup();
+        Ok(environments)
+    }
+
+    fn resolve_routes(&self, routes: Option<Vec<String>>) -> Result<Vec<Route>> {
+        let Some(names) = routes else {
+            return Ok(self.config.routes.clone());
+        };
+        names
+            .iter()
+            .map(|name| {
+                self.config
+                    .routes
+                    .iter()
+                    .find(|route| route.name == *name)
+                    .cloned()
+                    .ok_or_else(|| {
+                        HttpDiffError::invalid_config(format!(
+                            "Route '{}' not found in configuration",
+                            name
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    /// Routes of each batch, in the order of the configuration
+    fn ordered_batches<'r>(
+        &self,
+        plan: &ExecutionPlan,
+        routes: &'r [Route],
+    ) -> Vec<Vec<&'r Route>> {
+        plan.batches
+            .iter()
+            .map(|batch| {
+                routes
+                    .iter()
+                    .filter(|route| batch.routes.contains(&route.name))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn plan_request(
+        &self,
+        batch: usize,
+        route: &Route,
+        environment: &str,
+        user_index: usize,
+        user: &UserData,
+        upstream: &[&str],
+    ) -> PlannedRequest {
+        let mut request = PlannedRequest {
+            batch,
+            route: route.name.clone(),
+            user_index,
+            user_data: user.data.clone().into_iter().collect(),
+            method: route.method.clone(),
+            url: None,
+            headers: BTreeMap::new(),
+            body: None,
+            unresolved: Vec::new(),
+            skipped: None,
+            error: None,
+        };
+
+        // Conditions see the user data row only, as in a run
+        match self.condition_evaluator.should_execute_route(route, user) {
+            Ok(true) => {}
+            Ok(false) => {
+                request.skipped = Some(self.skip_reason(route, user));
+                return request;
+            }
+            Err(e) => {
+                request.skipped = Some(format!("condition evaluation failed: {}", e));
+                return request;
+            }
+        }
+
+        let markers = Markers::new(upstream);
+        let user = markers.apply(user);
+        if let Err(e) = self.resolve(route, environment, &user, &markers, &mut request) {
+            request.error = Some(self.secrets.redact(&e.to_string()));
+        }
+        request
+    }
+
+    /// Resolve the URL, headers and body of a request
+    fn resolve(
+        &self,
+        route: &Route,
+        environment: &str,
+        user: &UserData,
+        markers: &Markers,
+        request: &mut PlannedRequest,
+    ) -> Result<()> {
+        let secrets = &self.secrets;
+        let mut unresolved = Vec::new();
+
+        let url = UrlBuilder::new(self.config, route, environment, user).build()?;
+        let mut url_text = markers.restore(&secrets.redact(url.as_str()), &mut unresolved);
+        let credential = self.auth_credential(route, environment);
+        if let Some(Credential::Query { name, value }) = &credential {
+            if !url.query_pairs().any(|(key, _)| key == name.as_str()) {
+                let separator = if url.query().is_some() { '&' } else { '?' };
+                url_text.push_str(&format!("{}{}={}", separator, name, value));
+            }
+        }
+        request.url = Some(url_text);
+
+        let mut headers = resolve_headers(self.config, route, environment, user)?;
+        let body = BodyBuilder::new(route, user).build()?;
+        if let Some(body) = &body {
+            body.apply_content_type(&mut headers);
+        }
+        request.headers = headers
+            .into_iter()
+            .map(|(name, value)| {
+                // Markers are restored first so masked headers still report what they wait for
+                let value = secrets.mask_header(&name, &markers.restore(&value, &mut unresolved));
+                (name, value)
+            })
+            .collect();
+        // Headers set on the route win over the credentials, as in a run
+        if let Some(Credential::Header { name, value }) = credential {
+            if !request
+                .headers
+                .keys()
+                .any(|existing| existing.eq_ignore_ascii_case(&name))
+            {
+                request.headers.insert(name, value);
+            }
+        }
+        request.body = body
+            .as_ref()
+            .map(|body| markers.restore(&secrets.redact(&body_text(body)), &mut unresolved));
+
+        unresolved.sort();
+        unresolved.dedup();
+        request.unresolved = unresolved;
+        Ok(())
+    }
+
+    /// Where the environment's auth provider puts its credential, with a placeholder value
+    fn auth_credential(&self, route: &Route, environment: &str) -> Option<Credential> {
+        let auth = self.config.environments.get(environment)?.auth.as_ref()?;
+        let authorization = || Credential::Header {
+            name: reqwest::header::AUTHORIZATION.to_string(),
+            value: AUTH_PLACEHOLDER.to_string(),
+        };
+        match auth {
+            AuthConfig::Basic { .. }
+            | AuthConfig::OAuth2ClientCredentials { .. }
+            | AuthConfig::OAuth2Password { .. } => Some(authorization()),
+            AuthConfig::ApiKey { name, location, .. } => {
+                Some(Credential::api_key(*location, name, AUTH_PLACEHOLDER))
+            }
+            // The login route itself is sent without credentials
+            AuthConfig::LoginRoute { route: login, .. } if route.name == *login => None,
+            AuthConfig::LoginRoute { header, .. } => Some(match header {
+                Some(name) => Credential::Header {
+                    name: name.clone(),
+                    value: AUTH_PLACEHOLDER.to_string(),
+                },
+                None => authorization(),
+            }),
+        }
+    }
+
+    /// Describe the first condition a user data row does not meet
+    fn skip_reason(&self, route: &Route, user: &UserData) -> String {
+        let conditions = route.conditions.as_deref().unwrap_or_default();
+        let failed = self
+            .condition_evaluator
+            .evaluate_conditions(conditions, user)
+            .ok()
+            .and_then(|results| results.into_iter().find(|result| !result.passed));
+        match failed {
+            Some(result) => {
+                let mut reason = format!("condition on '{}' not met", result.condition.variable);
+                if let Some(detail) = result.reason {
+                    reason.push_str(&format!(": {}", detail));
+                }
+                reason
+            }
+            None => "conditions not met".to_string(),
+        }
+    }
+}
+
+/// Stand-ins for the values of upstream extractions
+///
+/// Each value is replaced by a marker made of characters that survive URL
+/// encoding and JSON serialization, so the URL and body builders run
+/// unchanged; markers are turned back into `{name}` placeholders afterwards.
+struct Markers<'n> {
+    names: Vec<(String, &'n str)>,
+}
+
+impl<'n> Markers<'n> {
+    fn new(names: &[&'n str]) -> Self {
+        let names = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (format!("__unresolved_{}__", index), *name))
+            .collect();
+        Self { names }
+    }
+
+    /// User data with the markers in place of upstream values
+    ///
+    /// Extracted values take precedence over columns of the same name in a
+    /// run, so markers do too.
+    fn apply(&self, user: &UserData) -> UserData {
+        let mut data = user.data.clone();
+        for (marker, name) in &self.names {
+            data.insert(name.to_string(), marker.clone());
+        }
+        UserData::new(data)
+    }
+
+    /// Replace markers in resolved text, recording the names found
+    fn restore(&self, text: &str, found: &mut Vec<String>) -> String {
+        let mut text = text.to_string();
+        for (marker, name) in &self.names {
+            if text.contains(marker.as_str()) {
+                text = text.replace(marker.as_str(), &format!("{{{}}}", name));
+                found.push(name.to_string());
+            }
+        }
+        text
+    }
+}
+
+/// Text of a body as it is sent, with multipart parts listed one per line
+fn body_text(body: &RequestBody) -> String {
+    match body {
+        RequestBody::Text(text) | RequestBody::Json(text) => text.clone(),
+        RequestBody::Form(fields) => url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(fields)
+            .finish(),
+        RequestBody::Multipart(parts) => parts
+            .iter()
+            .map(|part| match &part.content {
+                PartContent::Text(value) => format!("{}={}", part.name, value),
+                PartContent::File { path, .. } => format!("{}=@{}", part.name, path),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conditions::{ConditionEvaluatorImpl, ExecutionCondition};
+    use crate::config::types::{ExtractorType, ValueExtractionRule};
+    use crate::config::HttpDiffConfigBuilder;
+    use std::collections::HashMap;
+
+    fn user(pairs: &[(&str, &str)]) -> UserData {
+        UserData::new(
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_plan_marks_values_from_upstream_extractions() {
+        let mut config = HttpDiffConfigBuilder::new()
+            .environment("staging", "https://staging.example.com", None)
+            .environment("prod", "https://api.example.com", None)
+            .post_route("login", "/login", r#"{"user": "{userId}"}"#)
+            .get_route("get_user", "/users/{userId}/orders/{orderId}")
+            .build()
+            .unwrap();
+        config.routes[0].extract = Some(vec![ValueExtractionRule {
+            name: "token".to_string(),
+            extractor_type: ExtractorType::JsonPath,
+            source: "$.token".to_string(),
+            default_value: None,
+            required: true,
+        }]);
+        config.routes[1].depends_on = Some(vec!["login".to_string()]);
+        config.routes[1].headers = Some(HashMap::from([(
+            "X-Session".to_string(),
+            "{token}".to_string(),
+        )]));
+        config.routes[1].conditions = Some(vec![ExecutionCondition::equals("tier", "gold")]);
+
+        let planner = DryRunPlanner::new(&config, ConditionEvaluatorImpl::new());
+        let users = [
+            user(&[("userId", "42"), ("tier", "gold")]),
+            user(&[("userId", "7"), ("tier", "free")]),
+        ];
+        let plan = planner.plan(&users, None, None).unwrap();
+
+        let names: Vec<_> = plan
+            .environments
+            .iter()
+            .map(|e| e.environment.as_str())
+            .collect();
+        assert_eq!(names, vec!["prod", "staging"]);
+        assert_eq!(plan.request_count(), 4);
+        assert_eq!(plan.skipped_count(), 2);
+        assert_eq!(plan.error_count(), 2);
+
+        let requests = &plan.environments[1].requests;
+        assert_eq!(requests.len(), 4);
+        let login = &requests[0];
+        assert_eq!((login.batch, login.route.as_str()), (0, "login"));
+        assert_eq!(
+            login.url.as_deref(),
+            Some("https://staging.example.com/login")
+        );
+        assert_eq!(login.body.as_deref(), Some(r#"{"user": "42"}"#));
+        assert!(login.unresolved.is_empty());
+
+        // orderId is neither a column nor extracted, so the path cannot be built
+        let get_user = &requests[2];
+        assert_eq!((get_user.batch, get_user.user_index), (1, 0));
+        assert!(get_user.error.as_deref().unwrap().contains("orderId"));
+        assert_eq!(
+            requests[3].skipped.as_deref(),
+            Some("condition on 'tier' not met")
+        );
+
+        config.routes[1].path = "/users/{userId}/sessions/{token}".to_string();
+        let planner = DryRunPlanner::new(&config, ConditionEvaluatorImpl::new());
+        let plan = planner
+            .plan(&users, Some(vec!["staging".to_string()]), None)
+            .unwrap();
+        let get_user = &plan.environments[0].requests[2];
+        assert_eq!(
+            get_user.url.as_deref(),
+            Some("https://staging.example.com/users/42/sessions/{token}")
+        );
+        assert_eq!(get_user.headers["X-Session"], "{token}");
+        assert_eq!(get_user.unresolved, vec!["token"]);
+    }
+
+    #[test]
+    fn test_plan_shows_where_auth_credentials_go() {
+        let config = HttpDiffConfigBuilder::new()
+            .environment("staging", "https://staging.example.com", None)
+            .environment("prod", "https://api.example.com", None)
+            .auth(
+                "staging",
+                AuthConfig::ApiKey {
+                    name: "api_key".to_string(),
+                    value: "{secret:DRY_RUN_TEST_KEY}".to_string(),
+                    location: crate::config::ApiKeyLocation::Query,
+                },
+            )
+            .auth(
+                "prod",
+                AuthConfig::LoginRoute {
+                    route: "login".to_string(),
+                    token_path: "$.token".to_string(),
+                    header: None,
+                    scheme: None,
+                },
+            )
+            .get_route("login", "/login")
+            .get_route("get_user", "/users?page=1")
+            .build()
+            .unwrap();
+        let planner = DryRunPlanner::new(&config, ConditionEvaluatorImpl::new());
+        let plan = planner.plan(&[user(&[])], None, None).unwrap();
+
+        let prod = &plan.environments[0].requests;
+        assert!(prod[0].headers.is_empty());
+        assert_eq!(prod[1].headers["authorization"], AUTH_PLACEHOLDER);
+
+        let staging = &plan.environments[1].requests;
+        assert_eq!(
+            staging[1].url.as_deref(),
+            Some("https://staging.example.com/users?page=1&api_key=<from auth provider>")
+        );
+    }
+
+    #[test]
+    fn test_plan_selects_routes_by_name() {
+        let config = HttpDiffConfigBuilder::new()
+            .environment("staging", "https://staging.example.com", None)
+            .get_route("login", "/login")
+            .get_route("get_user", "/users")
+            .build()
+            .unwrap();
+        let planner = DryRunPlanner::new(&config, ConditionEvaluatorImpl::new());
+        let error = planner
+            .plan(&[user(&[])], None, Some(vec!["profile".to_string()]))
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("'profile' not found"),
+            "{}",
+            error
+        );
+
+        let plan = planner
+            .plan(&[user(&[])], None, Some(vec!["get_user".to_string()]))
+            .unwrap();
+        assert_eq!(plan.environments[0].requests.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&plan).unwrap()["environments"][0]["requests"][0]["url"],
+            "https://staging.example.com/users"
+        );
+    }
+}
@@ -1,14 +1,24 @@
 pub mod context;
+pub mod cookies;
 pub mod dependency;
+pub mod dry_run;
+pub mod policy;
 pub mod progress;
+pub mod rate_limit;
+pub mod retry;
 pub mod runner;
 
 pub use context::{
     ContextManager, ContextScope, ContextScopeStats, DynamicContext, VariableResolver,
 };
+pub use cookies::{CookieJar, CookieJarSnapshot, SessionCookies, StoredCookie};
 pub use dependency::{
     DependencyGraph, DependencyResolver, DynamicDependency, DynamicExecutionState,
     DynamicExecutionStats, ExecutionBatch, ExecutionPlan, ExecutionStats,
 };
+pub use dry_run::{DryRunPlan, DryRunPlanner, EnvironmentPlan, PlannedRequest};
+pub use policy::{FailurePolicy, PolicyVerdict};
 pub use progress::{ProgressCallback, ProgressTracker};
+pub use rate_limit::{EnvironmentRateLimiter, ThrottlePermit, TokenBucket};
+pub use retry::RetryPolicy;
 pub use runner::{DefaultTestRunner, TestRunnerImpl};
@@ -0,0 +1,482 @@
+//! Pass/fail policy for test runs
+//!
+//! Evaluates an `ExecutionResult` against a configurable failure policy so
+//! that callers can gate deployments on the outcome of a run. Each verdict
+//! maps to a distinct process exit code.
+
+use crate::baseline::BaselineReport;
+use crate::config::{FailOn, GlobalConfig};
+use crate::types::{DifferenceCategory, ErrorSummary, ExecutionResult};
+
+/// Exit code for a run that passed the failure policy
+pub const EXIT_SUCCESS: i32 = 0;
+/// Exit code for a run where differences violated the failure policy
+pub const EXIT_DIFFERENCES_FOUND: i32 = 1;
+/// Exit code for a run where execution errors violated the failure policy
+pub const EXIT_EXECUTION_ERRORS: i32 = 2;
+/// Exit code for invalid or missing configuration
+pub const EXIT_CONFIG_ERROR: i32 = 3;
+/// Exit code for a run where route assertions failed
+pub const EXIT_ASSERTIONS_FAILED: i32 = 4;
+
+/// Policy deciding whether a test run should be considered failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailurePolicy {
+    /// Which differences cause the run to fail
+    pub fail_on: FailOn,
+    /// Fail when more than this many comparisons have mixed status codes
+    pub max_mixed_responses: Option<usize>,
+    /// Fail when any execution error occurs
+    pub fail_on_errors: bool,
+    /// Fail when any route assertion fails
+    pub fail_on_assertions: bool,
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        Self {
+            fail_on: FailOn::default(),
+            max_mixed_responses: None,
+            fail_on_errors: false,
+            fail_on_assertions: true,
+        }
+    }
+}
+
+/// Outcome of evaluating a run against a failure policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyVerdict {
+    /// The run satisfied the policy
+    Passed,
+    /// Differences between environments violated the policy
+    DifferencesFound(Vec<String>),
+    /// Route assertions failed (takes precedence over differences)
+    AssertionsFailed(Vec<String>),
+    /// Execution errors violated the policy (takes precedence over differences)
+    ExecutionErrors(Vec<String>),
+}
+
+impl FailurePolicy {
+    /// Create the default policy: only failing route assertions fail a run
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy from the `[global]` configuration section
+    pub fn from_global(global: Option<&GlobalConfig>) -> Self {
+        let Some(global) = global else {
+            return Self::default();
+        };
+
+        Self {
+            fail_on: global.fail_on.unwrap_or_default(),
+            max_mixed_responses: global.max_mixed_responses,
+            fail_on_errors: global.fail_on_errors.unwrap_or(false),
+            fail_on_assertions: global.fail_on_assertions.unwrap_or(true),
+        }
+    }
+
+    /// Set which differences cause the run to fail
+    pub fn with_fail_on(mut self, fail_on: FailOn) -> Self {
+        self.fail_on = fail_on;
+        self
+    }
+
+    /// Set the maximum number of mixed-status responses before the run fails
+    pub fn with_max_mixed_responses(mut self, max_mixed: usize) -> Self {
+        self.max_mixed_responses = Some(max_mixed);
+        self
+    }
+
+    /// Set whether execution errors cause the run to fail
+    pub fn with_fail_on_errors(mut self, fail: bool) -> Self {
+        self.fail_on_errors = fail;
+        self
+    }
+
+    /// Set whether failing route assertions cause the run to fail
+    pub fn with_fail_on_assertions(mut self, fail: bool) -> Self {
+        self.fail_on_assertions = fail;
+        self
+    }
+
+    /// Evaluate an execution result against this policy
+    pub fn evaluate(&self, result: &ExecutionResult) -> PolicyVerdict {
+        self.evaluate_with_baseline(result, None)
+    }
+
+    /// Evaluate an execution result compared to a baseline against this policy
+    ///
+    /// Responses that changed since the baseline count like differences of the
+    /// run: any change fails `any_difference`, and a changed status code fails
+    /// `status_difference`. A new difference between environments always comes
+    /// with a change of one of them, so it is not counted separately.
+    pub fn evaluate_with_baseline(
+        &self,
+        result: &ExecutionResult,
+        baseline: Option<&BaselineReport>,
+    ) -> PolicyVerdict {
+        let mut reasons = Vec::new();
+
+        if self.fail_on_errors && result.has_errors() {
+            reasons.push(format!(
+                "{} execution error(s) occurred ({} request, {} comparison, {} execution)",
+                result.errors.len(),
+                result.request_errors().len(),
+                result.comparison_errors().len(),
+                result.execution_errors().len()
+            ));
+        }
+        let has_execution_errors = !reasons.is_empty();
+
+        let failing_assertions = result
+            .comparisons
+            .iter()
+            .filter(|c| c.has_assertion_failures())
+            .count();
+        let has_assertion_failures = self.fail_on_assertions && failing_assertions > 0;
+        if has_assertion_failures {
+            reasons.push(format!(
+                "{} comparison(s) with failing assertions",
+                failing_assertions
+            ));
+        }
+
+        let failing = match self.fail_on {
+            FailOn::Never => 0,
+            FailOn::AnyDifference => result
+                .comparisons
+                .iter()
+                .filter(|c| !c.is_identical)
+                .count(),
+            FailOn::StatusDifference => result
+                .comparisons
+                .iter()
+                .filter(|c| {
+                    !c.has_consistent_status()
+                        || c.differences
+                            .iter()
+                            .any(|d| d.category == DifferenceCategory::Status)
+                })
+                .count(),
+        };
+        if failing > 0 {
+            let kind = match self.fail_on {
+                FailOn::StatusDifference => "status differences",
+                _ => "differences",
+            };
+            reasons.push(format!("{} comparison(s) with {}", failing, kind));
+        }
+
+        if let Some(report) = baseline {
+            let changed = report
+                .changes
+                .iter()
+                .filter(|change| match self.fail_on {
+                    FailOn::Never => false,
+                    FailOn::AnyDifference => true,
+                    FailOn::StatusDifference => change
+                        .differences
+                        .iter()
+                        .any(|d| d.category == DifferenceCategory::Status),
+                })
+                .count();
+            if changed > 0 {
+                reasons.push(format!(
+                    "{} response(s) changed since baseline '{}'",
+                    changed, report.baseline
+                ));
+            }
+        }
+
+        if let Some(max_mixed) = self.max_mixed_responses {
+            let summary = ErrorSummary::from_comparison_results(&result.comparisons);
+            if summary.mixed_responses > max_mixed {
+                reasons.push(format!(
+                    "{} mixed responses exceed the threshold of {}",
+                    summary.mixed_responses, max_mixed
+                ));
+            }
+        }
+
+        if reasons.is_empty() {
+            PolicyVerdict::Passed
+        } else if has_execution_errors {
+            PolicyVerdict::ExecutionErrors(reasons)
+        } else if has_assertion_failures {
+            PolicyVerdict::AssertionsFailed(reasons)
+        } else {
+            PolicyVerdict::DifferencesFound(reasons)
+        }
+    }
+}
+
+impl PolicyVerdict {
+    /// Whether the run satisfied the policy
+    pub fn is_passed(&self) -> bool {
+        matches!(self, PolicyVerdict::Passed)
+    }
+
+    /// Process exit code for this verdict
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PolicyVerdict::Passed => EXIT_SUCCESS,
+            PolicyVerdict::DifferencesFound(_) => EXIT_DIFFERENCES_FOUND,
+            PolicyVerdict::ExecutionErrors(_) => EXIT_EXECUTION_ERRORS,
+            PolicyVerdict::AssertionsFailed(_) => EXIT_ASSERTIONS_FAILED,
+        }
+    }
+
+    /// Reasons the policy was violated (empty when passed)
+    pub fn reasons(&self) -> &[String] {
+        match self {
+            PolicyVerdict::Passed => &[],
+            PolicyVerdict::DifferencesFound(reasons)
+            | PolicyVerdict::ExecutionErrors(reasons)
+            | PolicyVerdict::AssertionsFailed(reasons) => reasons,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::progress::ProgressTracker;
+    use crate::types::{ComparisonResult, Difference, ExecutionError, HttpResponse};
+    use std::collections::HashMap;
+
+    fn create_comparison(status1: u16, status2: u16, identical: bool) -> ComparisonResult {
+        let mut result = ComparisonResult::new("route".to_string(), HashMap::new());
+        for (env, status) in [("dev", status1), ("prod", status2)] {
+            result.add_response(
+                env.to_string(),
+                HttpResponse::new(
+                    status,
+                    HashMap::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ),
+            );
+        }
+        if !identical {
+            result.add_difference(Difference::new(
+                DifferenceCategory::Body,
+                "Response bodies differ".to_string(),
+            ));
+        }
+        result
+    }
+
+    fn create_result(
+        comparisons: Vec<ComparisonResult>,
+        errors: Vec<ExecutionError>,
+    ) -> ExecutionResult {
+        let progress = ProgressTracker::new(comparisons.len());
+        ExecutionResult::new(comparisons, progress, errors, None)
+    }
+
+    #[test]
+    fn test_default_policy_always_passes() {
+        let result = create_result(
+            vec![create_comparison(200, 500, false)],
+            vec![ExecutionError::general_execution_error("boom".to_string())],
+        );
+
+        let verdict = FailurePolicy::new().evaluate(&result);
+        assert!(verdict.is_passed());
+        assert_eq!(verdict.exit_code(), EXIT_SUCCESS);
+    }
+
+    #[test]
+    fn test_fail_on_any_difference() {
+        let result = create_result(
+            vec![
+                create_comparison(200, 200, false),
+                create_comparison(200, 200, true),
+            ],
+            vec![],
+        );
+
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::AnyDifference)
+            .evaluate(&result);
+        assert_eq!(verdict.exit_code(), EXIT_DIFFERENCES_FOUND);
+        assert_eq!(verdict.reasons(), ["1 comparison(s) with differences"]);
+
+        // Body-only differences do not fail a status-only policy
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::StatusDifference)
+            .evaluate(&result);
+        assert!(verdict.is_passed());
+    }
+
+    #[test]
+    fn test_fail_on_status_difference() {
+        let result = create_result(vec![create_comparison(200, 404, true)], vec![]);
+
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::StatusDifference)
+            .evaluate(&result);
+        assert_eq!(verdict.exit_code(), EXIT_DIFFERENCES_FOUND);
+    }
+
+    #[test]
+    fn test_baseline_changes_fail() {
+        let result = create_result(vec![create_comparison(200, 200, true)], vec![]);
+        let change = |category: DifferenceCategory| crate::baseline::BaselineChange {
+            route: "route".to_string(),
+            environment: "prod".to_string(),
+            user_data: Default::default(),
+            differences: vec![Difference::new(category, "changed".to_string())],
+        };
+        let body_change = BaselineReport {
+            baseline: "v1".to_string(),
+            changes: vec![change(DifferenceCategory::Body)],
+            ..BaselineReport::default()
+        };
+
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::AnyDifference)
+            .evaluate_with_baseline(&result, Some(&body_change));
+        assert_eq!(verdict.exit_code(), EXIT_DIFFERENCES_FOUND);
+        assert_eq!(
+            verdict.reasons(),
+            ["1 response(s) changed since baseline 'v1'"]
+        );
+
+        // Body changes pass a status-only policy and the default policy
+        let policy = FailurePolicy::new().with_fail_on(FailOn::StatusDifference);
+        assert!(policy
+            .evaluate_with_baseline(&result, Some(&body_change))
+            .is_passed());
+        assert!(FailurePolicy::new()
+            .evaluate_with_baseline(&result, Some(&body_change))
+            .is_passed());
+    }
+
+    #[test]
+    fn test_single_environment_status_change_since_baseline_fails() {
+        let recorded = |status: u16| {
+            let recorder = crate::recording::ResponseRecorder::new("run");
+            let user = crate::config::UserData::new(HashMap::new());
+            let response = HttpResponse::new(
+                status,
+                HashMap::new(),
+                "{}".to_string(),
+                String::new(),
+                String::new(),
+            );
+            recorder.record("route", 0, "prod", &user, &response);
+            recorder.recording()
+        };
+        let config: crate::config::HttpDiffConfig = toml::from_str(
+            r#"
+[environments.prod]
+base_url = "https://prod.example.com"
+
+[[routes]]
+name = "route"
+method = "GET"
+path = "/"
+"#,
+        )
+        .unwrap();
+        let baseline = crate::baseline::Baseline::from_recording("v1", &recorded(200));
+        let report =
+            crate::baseline::BaselineComparator::new(&config).compare(&baseline, &recorded(500));
+
+        // A single-environment run has no differences between environments
+        let result = create_result(vec![], vec![]);
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::StatusDifference)
+            .evaluate_with_baseline(&result, Some(&report));
+        assert_eq!(verdict.exit_code(), EXIT_DIFFERENCES_FOUND);
+        assert_eq!(
+            verdict.reasons(),
+            ["1 response(s) changed since baseline 'v1'"]
+        );
+    }
+
+    #[test]
+    fn test_mixed_responses_threshold() {
+        let result = create_result(
+            vec![
+                create_comparison(200, 500, true),
+                create_comparison(200, 404, true),
+            ],
+            vec![],
+        );
+
+        let policy = FailurePolicy::new().with_max_mixed_responses(2);
+        assert!(policy.evaluate(&result).is_passed());
+
+        let policy = FailurePolicy::new().with_max_mixed_responses(1);
+        assert_eq!(policy.evaluate(&result).exit_code(), EXIT_DIFFERENCES_FOUND);
+    }
+
+    #[test]
+    fn test_execution_errors_take_precedence() {
+        let result = create_result(
+            vec![create_comparison(200, 200, false)],
+            vec![ExecutionError::request_error(
+                "route".to_string(),
+                "dev".to_string(),
+                "connection refused".to_string(),
+            )],
+        );
+
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::AnyDifference)
+            .with_fail_on_errors(true)
+            .evaluate(&result);
+        assert_eq!(verdict.exit_code(), EXIT_EXECUTION_ERRORS);
+        assert_eq!(verdict.reasons().len(), 2);
+    }
+
+    #[test]
+    fn test_failing_assertions_fail_by_default() {
+        let mut comparison = create_comparison(200, 200, false);
+        comparison
+            .assertions
+            .push(crate::types::AssertionResult::failed(
+                "prod",
+                "status is 201".to_string(),
+                "got 200".to_string(),
+            ));
+        let result = create_result(vec![comparison], vec![]);
+
+        let verdict = FailurePolicy::new()
+            .with_fail_on(FailOn::AnyDifference)
+            .evaluate(&result);
+        assert_eq!(verdict.exit_code(), EXIT_ASSERTIONS_FAILED);
+        assert_eq!(
+            verdict.reasons(),
+            [
+                "1 comparison(s) with failing assertions",
+                "1 comparison(s) with differences"
+            ]
+        );
+
+        let verdict = FailurePolicy::new()
+            .with_fail_on_assertions(false)
+            .evaluate(&result);
+        assert!(verdict.is_passed());
+    }
+
+    #[test]
+    fn test_policy_from_global_config() {
+        let global = crate::config::GlobalConfigBuilder::new()
+            .fail_on(FailOn::StatusDifference)
+            .max_mixed_responses(3)
+            .fail_on_errors(true)
+            .build();
+
+        let policy = FailurePolicy::from_global(Some(&global));
+        assert_eq!(policy.fail_on, FailOn::StatusDifference);
+        assert_eq!(policy.max_mixed_responses, Some(3));
+        assert!(policy.fail_on_errors);
+
+        assert_eq!(FailurePolicy::from_global(None), FailurePolicy::default());
+    }
+}
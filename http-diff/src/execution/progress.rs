@@ -15,6 +15,12 @@ pub struct ProgressTracker {
     pub total_batches: usize,
     pub extraction_completed: usize,
     pub dependency_wait_count: usize,
+    /// Requests delayed by a per-environment rate limit
+    #[serde(default)]
+    pub throttled_requests: usize,
+    /// Total time requests spent waiting for per-environment rate limits
+    #[serde(default)]
+    pub throttle_wait_ms: u64,
 }
 
 impl ProgressTracker {
@@ -30,6 +36,8 @@ impl ProgressTracker {
             total_batches: 0,
             extraction_completed: 0,
             dependency_wait_count: 0,
+            throttled_requests: 0,
+            throttle_wait_ms: 0,
         }
     }
     
@@ -46,6 +54,8 @@ impl ProgressTracker {
             total_batches,
             extraction_completed: 0,
             dependency_wait_count: 0,
+            throttled_requests: 0,
+            throttle_wait_ms: 0,
         }
     }
 
@@ -58,6 +68,17 @@ impl ProgressTracker {
         }
     }
 
+    /// Record a request that waited for a per-environment rate limit
+    pub fn request_throttled(&mut self, wait: std::time::Duration) {
+        self.throttled_requests += 1;
+        self.throttle_wait_ms += wait.as_millis() as u64;
+    }
+
+    /// Total time requests spent waiting for per-environment rate limits
+    pub fn throttle_wait(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.throttle_wait_ms)
+    }
+
     pub fn route_skipped(&mut self) {
         self.skipped_routes += 1;
     }
@@ -0,0 +1,252 @@
+//! Per-environment request throttling
+//!
+//! Environments with a `rate_limit` table get their own token bucket and,
+//! optionally, their own concurrency limit. Both apply on top of the global
+//! `max_concurrent_requests` semaphore, so a slow production API can be
+//! throttled without slowing down the other environments.
+
+use crate::config::{HttpDiffConfig, RateLimit};
+use crate::error::{HttpDiffError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Token bucket refilled continuously at a fixed rate
+///
+/// Tokens are reserved rather than awaited: a request that finds the bucket
+/// empty takes a token "on credit" and is told how long to wait, which keeps
+/// concurrent callers in FIFO order without holding the lock while sleeping.
+#[derive(Debug)]
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket holding `burst` tokens refilled at `rate` per second
+    pub fn new(rate: f64, burst: u32) -> Self {
+        let capacity = f64::from(burst.max(1));
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take one token and return how long the caller must wait before using it
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Limits configured for a single environment
+#[derive(Debug)]
+struct EnvironmentLimit {
+    bucket: Option<Mutex<TokenBucket>>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+impl EnvironmentLimit {
+    fn from_rate_limit(rate_limit: &RateLimit) -> Option<Self> {
+        let bucket = rate_limit
+            .requests_per_second
+            .filter(|rps| rps.is_finite() && *rps > 0.0)
+            .map(|rps| Mutex::new(TokenBucket::new(rps, rate_limit.burst.unwrap_or(1))));
+        let concurrency = rate_limit
+            .max_concurrent_requests
+            .map(|n| Arc::new(Semaphore::new(n.max(1))));
+
+        if bucket.is_none() && concurrency.is_none() {
+            None
+        } else {
+            Some(Self {
+                bucket,
+                concurrency,
+            })
+        }
+    }
+}
+
+/// Permission to send one request to an environment
+///
+/// The environment's concurrency slot is released when the permit is dropped.
+#[derive(Debug, Default)]
+pub struct ThrottlePermit {
+    _concurrency: Option<OwnedSemaphorePermit>,
+    /// Time spent waiting for the environment's limits
+    pub waited: Duration,
+}
+
+impl ThrottlePermit {
+    /// Check whether the request had to wait for a limit
+    pub fn was_throttled(&self) -> bool {
+        !self.waited.is_zero()
+    }
+}
+
+/// Rate limiter holding the limits of every throttled environment
+#[derive(Debug, Default)]
+pub struct EnvironmentRateLimiter {
+    limits: HashMap<String, EnvironmentLimit>,
+}
+
+impl EnvironmentRateLimiter {
+    /// Create a rate limiter without any limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the limits declared in `[environments.<name>.rate_limit]`
+    pub fn from_config(config: &HttpDiffConfig) -> Self {
+        let limits = config
+            .environments
+            .iter()
+            .filter_map(|(name, env)| {
+                let limit = EnvironmentLimit::from_rate_limit(env.rate_limit.as_ref()?)?;
+                Some((name.clone(), limit))
+            })
+            .collect();
+
+        Self { limits }
+    }
+
+    /// Check whether no environment is throttled
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty()
+    }
+
+    /// Wait until a request to the environment is allowed
+    ///
+    /// Environments without limits are admitted immediately.
+    pub async fn acquire(&self, environment: &str) -> Result<ThrottlePermit> {
+        let Some(limit) = self.limits.get(environment) else {
+            return Ok(ThrottlePermit::default());
+        };
+        let started = Instant::now();
+        let mut throttled = false;
+
+        let concurrency = match &limit.concurrency {
+            Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    throttled = true;
+                    Some(semaphore.clone().acquire_owned().await.map_err(|e| {
+                        HttpDiffError::general(format!(
+                            "Failed to acquire rate limit for environment '{}': {}",
+                            environment, e
+                        ))
+                    })?)
+                }
+            },
+            None => None,
+        };
+
+        if let Some(bucket) = &limit.bucket {
+            let delay = bucket
+                .lock()
+                .map_err(|_| HttpDiffError::general("Rate limiter lock poisoned"))?
+                .reserve(Instant::now());
+            if !delay.is_zero() {
+                throttled = true;
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        Ok(ThrottlePermit {
+            _concurrency: concurrency,
+            waited: if throttled {
+                started.elapsed()
+            } else {
+                Duration::ZERO
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_rate_limit(rate_limit: RateLimit) -> HttpDiffConfig {
+        let mut config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.dev]
+base_url = "https://dev.example.com"
+
+[environments.prod]
+base_url = "https://prod.example.com"
+
+[[routes]]
+name = "users"
+method = "GET"
+path = "/users"
+"#,
+        )
+        .unwrap();
+        config.environments.get_mut("prod").unwrap().rate_limit = Some(rate_limit);
+        config
+    }
+
+    #[test]
+    fn test_token_bucket_spaces_requests_after_burst() {
+        let mut bucket = TokenBucket::new(10.0, 2);
+        let now = Instant::now();
+
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(200));
+
+        // Refilled tokens pay back the debt before new requests are admitted
+        let later = now + Duration::from_millis(500);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_only_configured_environments_are_throttled() {
+        let limiter = EnvironmentRateLimiter::from_config(&config_with_rate_limit(RateLimit {
+            requests_per_second: Some(20.0),
+            ..Default::default()
+        }));
+
+        assert!(!limiter.is_empty());
+        assert!(!limiter.acquire("prod").await.unwrap().was_throttled());
+        assert!(limiter.acquire("prod").await.unwrap().waited >= Duration::from_millis(40));
+        assert!(!limiter.acquire("dev").await.unwrap().was_throttled());
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_holds_until_permit_dropped() {
+        let limiter = Arc::new(EnvironmentRateLimiter::from_config(
+            &config_with_rate_limit(RateLimit {
+                max_concurrent_requests: Some(1),
+                ..Default::default()
+            }),
+        ));
+
+        let first = limiter.acquire("prod").await.unwrap();
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire("prod").await.unwrap().waited })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(first);
+
+        assert!(waiter.await.unwrap() >= Duration::from_millis(40));
+    }
+}
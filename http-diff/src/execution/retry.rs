@@ -0,0 +1,198 @@
+//! Retry policy for transient request failures
+//!
+//! The policy is resolved per route from the `[global.retry]` and
+//! `[routes.retry]` tables. Without either table every request is attempted
+//! exactly once.
+
+use crate::config::{GlobalConfig, RetryConfig, Route};
+use std::time::Duration;
+
+/// Default number of attempts when a retry table is present
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+/// Default delay before the first retry
+pub const DEFAULT_INITIAL_BACKOFF_MS: u64 = 200;
+/// Default upper bound for the delay between attempts
+pub const DEFAULT_MAX_BACKOFF_MS: u64 = 5_000;
+/// Default factor applied to the delay after each retry
+pub const DEFAULT_BACKOFF_MULTIPLIER: f64 = 2.0;
+/// Status codes retried by default
+pub const DEFAULT_RETRY_ON_STATUS: &[u16] = &[502, 503, 504];
+
+/// HTTP methods that can safely be repeated after a transport error
+const IDEMPOTENT_METHODS: &[&str] = &["GET", "HEAD", "OPTIONS", "TRACE", "PUT", "DELETE"];
+
+/// Resolved retry policy for a single route
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts including the first one
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Randomize each delay between zero and the computed backoff ("full jitter")
+    pub jitter: bool,
+    /// Status codes that trigger a retry
+    pub retry_on_status: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_retry_config(&RetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy that attempts every request exactly once
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Resolve a policy from a retry table, applying defaults for unset fields
+    pub fn from_retry_config(config: &RetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+            initial_backoff: Duration::from_millis(
+                config
+                    .initial_backoff_ms
+                    .unwrap_or(DEFAULT_INITIAL_BACKOFF_MS),
+            ),
+            max_backoff: Duration::from_millis(
+                config.max_backoff_ms.unwrap_or(DEFAULT_MAX_BACKOFF_MS),
+            ),
+            multiplier: config.multiplier.unwrap_or(DEFAULT_BACKOFF_MULTIPLIER),
+            jitter: config.jitter.unwrap_or(true),
+            retry_on_status: config
+                .retry_on_status
+                .clone()
+                .unwrap_or_else(|| DEFAULT_RETRY_ON_STATUS.to_vec()),
+        }
+    }
+
+    /// Resolve the policy of a route, with route fields overriding global ones
+    pub fn for_route(global: Option<&GlobalConfig>, route: &Route) -> Self {
+        let global = global.and_then(|g| g.retry.as_ref());
+        match (global, &route.retry) {
+            (None, None) => Self::none(),
+            (Some(global), None) => Self::from_retry_config(global),
+            (None, Some(route)) => Self::from_retry_config(route),
+            (Some(global), Some(route)) => Self::from_retry_config(&global.merged_with(route)),
+        }
+    }
+
+    /// Check whether this policy allows any retry at all
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 1
+    }
+
+    /// Check whether a response status should be retried
+    pub fn should_retry_status(&self, status: u16) -> bool {
+        self.retry_on_status.contains(&status)
+    }
+
+    /// Check whether a transport error (connect failure, timeout) should be
+    /// retried; only idempotent methods are repeated
+    pub fn should_retry_transport_error(&self, method: &str) -> bool {
+        IDEMPOTENT_METHODS.contains(&method.to_ascii_uppercase().as_str())
+    }
+
+    /// Delay before the given retry (1 for the first retry), without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.saturating_sub(1) as i32);
+        self.initial_backoff.mul_f64(factor).min(self.max_backoff)
+    }
+
+    /// Delay to wait before the given retry, with jitter applied when enabled
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if self.jitter {
+            backoff.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route_with_retry(retry: Option<RetryConfig>) -> Route {
+        let mut route: Route = toml::from_str(
+            r#"
+name = "users"
+method = "GET"
+path = "/users"
+"#,
+        )
+        .unwrap();
+        route.retry = retry;
+        route
+    }
+
+    #[test]
+    fn test_no_retry_without_configuration() {
+        let policy = RetryPolicy::for_route(None, &route_with_retry(None));
+        assert_eq!(policy.max_attempts, 1);
+        assert!(!policy.is_enabled());
+    }
+
+    #[test]
+    fn test_route_retry_overrides_global_fields() {
+        let global = GlobalConfig {
+            retry: Some(RetryConfig {
+                max_attempts: Some(5),
+                retry_on_status: Some(vec![503]),
+                ..Default::default()
+            }),
+            ..GlobalConfig::default()
+        };
+        let route = route_with_retry(Some(RetryConfig {
+            max_attempts: Some(2),
+            jitter: Some(false),
+            ..Default::default()
+        }));
+
+        let policy = RetryPolicy::for_route(Some(&global), &route);
+
+        assert_eq!(policy.max_attempts, 2);
+        assert!(!policy.jitter);
+        assert_eq!(policy.retry_on_status, vec![503]);
+        assert!(policy.should_retry_status(503));
+        assert!(!policy.should_retry_status(502));
+    }
+
+    #[test]
+    fn test_exponential_backoff_is_capped() {
+        let policy = RetryPolicy::from_retry_config(&RetryConfig {
+            initial_backoff_ms: Some(100),
+            max_backoff_ms: Some(350),
+            jitter: Some(false),
+            ..Default::default()
+        });
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(350));
+
+        let jittered = RetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        assert!(jittered.delay(2) <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_transport_errors_only_retried_for_idempotent_methods() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry_transport_error("GET"));
+        assert!(policy.should_retry_transport_error("put"));
+        assert!(!policy.should_retry_transport_error("POST"));
+        assert!(!policy.should_retry_transport_error("PATCH"));
+    }
+}
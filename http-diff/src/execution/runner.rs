@@ -3,13 +3,16 @@ use crate::error::{HttpDiffError, Result};
 use crate::execution::progress::{ProgressCallback, ProgressTracker};
 use crate::execution::dependency::DependencyResolver;
 use crate::execution::context::ContextManager;
+use crate::execution::rate_limit::EnvironmentRateLimiter;
 use crate::extraction::ValueExtractionEngine;
+use crate::recording::ResponseRecorder;
 use crate::traits::{ConditionEvaluator, HttpClient, ResponseComparator, TestRunner};
 use crate::types::{ExecutionError, ExecutionResult, ExtractionResult, ExtractionRule, ExtractionType};
 use crate::utils::environment_utils::EnvironmentOrderResolver;
 use futures::stream::{FuturesUnordered, StreamExt};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Semaphore;
 
 // Simplify complex task result types for readability and to satisfy clippy::type_complexity
@@ -22,6 +25,7 @@ type RequestTaskOutput = (
     Option<crate::types::HttpResponse>,
     bool,
     Option<ExecutionError>,
+    Duration,
 );
 type RequestJoinHandle = tokio::task::JoinHandle<Result<RequestTaskOutput>>;
 
@@ -61,6 +65,10 @@ where
     extraction_engine: ValueExtractionEngine,
     /// Context manager for dynamic variable resolution
     context_manager: Arc<ContextManager>,
+    /// Per-environment rate limits applied on top of the global concurrency limit
+    rate_limiter: Arc<EnvironmentRateLimiter>,
+    /// Recorder receiving every response, when the run is recorded
+    recorder: Option<Arc<ResponseRecorder>>,
 }
 
 impl<C, R, E> TestRunnerImpl<C, R, E>
@@ -82,6 +90,7 @@ where
             .as_ref()
             .and_then(|g| g.max_concurrent_requests)
             .unwrap_or(10);
+        let rate_limiter = Arc::new(EnvironmentRateLimiter::from_config(&config));
 
         Ok(Self {
             config,
@@ -91,6 +100,8 @@ where
             max_concurrent_requests,
             extraction_engine: ValueExtractionEngine::new(),
             context_manager: Arc::new(ContextManager::new()),
+            rate_limiter,
+            recorder: None,
         })
     }
 
@@ -112,6 +123,41 @@ where
         self
     }
 
+    /// Configure with custom per-environment rate limits
+    pub fn with_rate_limiter(mut self, rate_limiter: EnvironmentRateLimiter) -> Self {
+        self.rate_limiter = Arc::new(rate_limiter);
+        self
+    }
+
+    /// Record every response received into `recorder`
+    pub fn with_recorder(mut self, recorder: Arc<ResponseRecorder>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Record a response when the run is recorded
+    fn record_response(
+        &self,
+        route_name: &str,
+        user_data: &[crate::config::UserData],
+        user_idx: usize,
+        env_name: &str,
+        response: &crate::types::HttpResponse,
+    ) {
+        if let (Some(recorder), Some(user)) = (&self.recorder, user_data.get(user_idx)) {
+            recorder.record(route_name, user_idx, env_name, user, response);
+        }
+    }
+
+    /// Whether session cookies are kept per user and environment
+    fn cookie_jar_enabled(&self) -> bool {
+        self.config
+            .global
+            .as_ref()
+            .and_then(|g| g.cookie_jar)
+            .unwrap_or(false)
+    }
+
     /// Filter route-user combinations based on conditions for performance optimization
     fn filter_executable_combinations<'a>(
         &self,
@@ -188,8 +234,12 @@ where
                 let route_for_extraction = route.clone();
                 let client = self.client.clone();
                 let semaphore_clone = semaphore.clone();
+                let rate_limiter = self.rate_limiter.clone();
 
                 let task = tokio::spawn(async move {
+                    // Wait for the environment's own limits before taking a global slot so a
+                    // throttled environment does not starve the others
+                    let throttle = rate_limiter.acquire(&env_name).await?;
                     let _permit = semaphore_clone.acquire().await.map_err(|e| {
                         HttpDiffError::general(format!("Failed to acquire semaphore: {}", e))
                     })?;
@@ -200,7 +250,7 @@ where
                     {
                         Ok(response) => {
                             let success = response.is_success();
-                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, Some(response), success, None))
+                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, Some(response), success, None, throttle.waited))
                         }
                         Err(e) => {
                             let error = ExecutionError::request_error(
@@ -208,7 +258,7 @@ where
                                 env_name.clone(),
                                 e.to_string(),
                             );
-                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, None, false, Some(error)))
+                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, None, false, Some(error), throttle.waited))
                         }
                     }
                 });
@@ -220,8 +270,11 @@ where
         // Process requests as they complete for streaming progress updates
         while let Some(task_result) = request_tasks.next().await {
             match task_result {
-                Ok(Ok((_route_idx, user_idx, env_name, route_name, _route, response_opt, success, error_opt))) => {
+                Ok(Ok((_route_idx, user_idx, env_name, route_name, _route, response_opt, success, error_opt, throttle_wait))) => {
                     // Update progress immediately for each completed request
+                    if !throttle_wait.is_zero() {
+                        progress.request_throttled(throttle_wait);
+                    }
                     progress.request_completed(success);
 
                     if let Some(ref callback) = progress_callback {
@@ -230,6 +283,7 @@ where
 
                     // Collect response for later comparison
                     if let Some(response) = response_opt {
+                        self.record_response(&route_name, user_data, user_idx, &env_name, &response);
                         let key = (route_name, user_idx);
                         route_user_responses
                             .entry(key)
@@ -487,6 +541,10 @@ where
             
         // Check if there were dependency waits
         chain_metadata.had_dependency_waits = progress.dependency_wait_count > 0;
+
+        if self.cookie_jar_enabled() {
+            chain_metadata.cookie_jars = self.context_manager.cookie_jar_snapshots()?;
+        }
         
         Ok(crate::types::ExecutionResult::new(
             results, 
@@ -526,6 +584,7 @@ where
         
         // Create request tasks for this batch
         let mut request_tasks: FuturesUnordered<RequestJoinHandle> = FuturesUnordered::new();
+        let cookie_jar_enabled = self.cookie_jar_enabled();
 
         for (route_idx, user_idx, route, user) in batch_combinations {
             for env in environments {
@@ -537,8 +596,12 @@ where
                 let client = self.client.clone();
                 let context_manager = self.context_manager.clone();
                 let semaphore_clone = semaphore.clone();
+                let rate_limiter = self.rate_limiter.clone();
 
                 let task = tokio::spawn(async move {
+                    // Wait for the environment's own limits before taking a global slot so a
+                    // throttled environment does not starve the others
+                    let throttle = rate_limiter.acquire(&env_name).await?;
                     let _permit = semaphore_clone.acquire().await.map_err(|e| {
                         HttpDiffError::general(format!("Failed to acquire semaphore: {}", e))
                     })?;
@@ -552,13 +615,21 @@ where
                     };
 
                     let merged_user_data = enhanced_user_data.to_merged_user_data();
-                    match client
-                        .execute_request(&route_arc, &env_name, &merged_user_data)
-                        .await
+                    let response = if cookie_jar_enabled {
+                        let jar = context_manager.cookie_jar(user_idx, &env_name)?;
+                        client
+                            .execute_request_with_cookies(&route_arc, &env_name, &merged_user_data, &jar)
+                            .await
+                    } else {
+                        client
+                            .execute_request(&route_arc, &env_name, &merged_user_data)
+                            .await
+                    };
+                    match response
                     {
                         Ok(response) => {
                             let success = response.is_success();
-                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, Some(response), success, None))
+                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, Some(response), success, None, throttle.waited))
                         }
                         Err(e) => {
                             let error = ExecutionError::request_error(
@@ -566,7 +637,7 @@ where
                                 env_name.clone(),
                                 e.to_string(),
                             );
-                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, None, false, Some(error)))
+                            Ok((route_idx, user_idx, env_name, route_name, route_for_extraction, None, false, Some(error), throttle.waited))
                         }
                     }
                 });
@@ -578,8 +649,11 @@ where
         // Process batch requests as they complete
         while let Some(task_result) = request_tasks.next().await {
             match task_result {
-                Ok(Ok((_route_idx, user_idx, env_name, route_name, route, response_opt, success, error_opt))) => {
+                Ok(Ok((_route_idx, user_idx, env_name, route_name, route, response_opt, success, error_opt, throttle_wait))) => {
                     // Update progress immediately for each completed request
+                    if !throttle_wait.is_zero() {
+                        progress.request_throttled(throttle_wait);
+                    }
                     progress.request_completed(success);
 
                     if let Some(ref callback) = progress_callback {
@@ -588,6 +662,7 @@ where
 
                     // Collect response for later comparison
                     if let Some(response) = response_opt {
+                        self.record_response(&route_name, user_data, user_idx, &env_name, &response);
                         let key = (route_name.clone(), user_idx);
                         route_user_responses
                             .entry(key)
@@ -603,6 +678,11 @@ where
                                     crate::config::types::ExtractorType::Regex => ExtractionType::Regex,
                                     crate::config::types::ExtractorType::Header => ExtractionType::Header,
                                     crate::config::types::ExtractorType::StatusCode => ExtractionType::StatusCode,
+                                    crate::config::types::ExtractorType::XPath => ExtractionType::XPath,
+                                    crate::config::types::ExtractorType::Cookie => ExtractionType::Cookie,
+                                    crate::config::types::ExtractorType::Body => ExtractionType::Body,
+                                    crate::config::types::ExtractorType::JmesPath => ExtractionType::JmesPath,
+                                    crate::config::types::ExtractorType::Computed => ExtractionType::Computed,
                                 };
                                 ExtractionRule {
                                     key: rule.name.clone(),
@@ -613,11 +693,16 @@ where
                                 }
                             }).collect();
                             
+                            // Computed extractors may combine values extracted by earlier routes
+                            let user_context = match self.context_manager.get_or_create_scope(user_idx) {
+                                Ok(scope) => user_data[user_idx].with_context(scope.get_context()).to_merged_user_data().data,
+                                Err(_) => user_data[user_idx].data.clone(),
+                            };
                             let extraction_result = self.extraction_engine.extract_values(
                                 route.name.clone(),
                                 env_name.clone(),
                                 response,
-                                user_data[user_idx].data.clone(),
+                                user_context,
                                 &converted_rules,
                             );
                             
@@ -759,17 +844,23 @@ mod tests {
         environments.insert(
             "base".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://base.example.com".to_string(),
                 headers: None,
                 is_base: true,
+                rate_limit: None,
+                auth: None,
             },
         );
         environments.insert(
             "other".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://other.example.com".to_string(),
                 headers: None,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
 
@@ -785,9 +876,20 @@ mod tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes: vec![route.clone()],
@@ -818,15 +920,139 @@ mod tests {
         assert!(cmp.responses.contains_key("other"));
     }
 
+    #[tokio::test]
+    async fn test_rate_limited_environment_records_throttle_waits() {
+        let mut environments = HashMap::new();
+        environments.insert(
+            "dev".to_string(),
+            Environment {
+                extends: None,
+                base_url: "https://dev.example.com".to_string(),
+                headers: None,
+                is_base: false,
+                rate_limit: None,
+                auth: None,
+            },
+        );
+        environments.insert(
+            "prod".to_string(),
+            Environment {
+                extends: None,
+                base_url: "https://prod.example.com".to_string(),
+                headers: None,
+                is_base: false,
+                rate_limit: Some(crate::config::RateLimit {
+                    requests_per_second: Some(50.0),
+                    burst: Some(1),
+                    max_concurrent_requests: Some(1),
+                }),
+                auth: None,
+            },
+        );
+        let config = HttpDiffConfig {
+            profiles: Default::default(),
+            environments,
+            global: None,
+            routes: vec![toml::from_str("name = \"health\"\nmethod = \"GET\"\npath = \"/health\"")
+                .unwrap()],
+        };
+
+        let client = MockHttpClient::new()
+            .with_response("health:dev".to_string(), create_mock_response(200, "ok"))
+            .with_response("health:prod".to_string(), create_mock_response(200, "ok"));
+        let runner = TestRunnerImpl::new(
+            config,
+            client,
+            MockResponseComparator::new(),
+            crate::conditions::ConditionEvaluatorImpl::new(),
+        )
+        .unwrap();
+
+        let user_data: Vec<_> = (0..3).map(|_| create_mock_user_data(vec![])).collect();
+        let result = runner
+            .execute_with_data(&user_data, None, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.progress.completed_requests, 6);
+        // Only the second and third prod requests wait for the bucket to refill
+        assert_eq!(result.progress.throttled_requests, 2);
+        assert!(result.progress.throttle_wait() >= Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_run_replays_as_virtual_environment() {
+        let environment = Environment {
+            extends: None,
+            base_url: "https://prod.example.com".to_string(),
+            headers: None,
+            is_base: false,
+            rate_limit: None,
+            auth: None,
+        };
+        let mut config = HttpDiffConfig {
+            profiles: Default::default(),
+            environments: HashMap::from([("prod".to_string(), environment.clone())]),
+            global: None,
+            routes: vec![toml::from_str("name = \"health\"\nmethod = \"GET\"\npath = \"/health\"")
+                .unwrap()],
+        };
+        let user_data: Vec<_> = (0..2)
+            .map(|i| create_mock_user_data(vec![("id", &i.to_string())]))
+            .collect();
+
+        let recorder = Arc::new(ResponseRecorder::new("snapshot"));
+        let client = MockHttpClient::new()
+            .with_response("health:prod".to_string(), create_mock_response(200, "before"));
+        let runner = TestRunnerImpl::new(
+            config.clone(),
+            client,
+            MockResponseComparator::new(),
+            crate::conditions::ConditionEvaluatorImpl::new(),
+        )
+        .unwrap()
+        .with_recorder(recorder.clone());
+        runner.execute_with_data(&user_data, None, None, None).await.unwrap();
+
+        let recording = Arc::new(recorder.recording());
+        assert_eq!(recording.responses.len(), 2);
+        assert_eq!(recording.responses[1].user_index, 1);
+
+        // Live prod has changed since the recording was made
+        config.environments.insert("prod@snapshot".to_string(), environment);
+        let client = crate::recording::ReplayClient::new(
+            MockHttpClient::new()
+                .with_response("health:prod".to_string(), create_mock_response(200, "after")),
+        )
+        .with_replay("prod@snapshot", recording, "prod");
+        let runner = TestRunnerImpl::new(
+            config,
+            client,
+            MockResponseComparator::new(),
+            crate::conditions::ConditionEvaluatorImpl::new(),
+        )
+        .unwrap();
+        let result = runner.execute_with_data(&user_data, None, None, None).await.unwrap();
+
+        assert_eq!(result.comparisons.len(), 2);
+        for comparison in &result.comparisons {
+            assert_eq!(comparison.responses["prod"].body, "after");
+            assert_eq!(comparison.responses["prod@snapshot"].body, "before");
+        }
+    }
+
     #[tokio::test]
     async fn test_chain_execution_detection() {
         let mut environments = HashMap::new();
         environments.insert(
             "dev".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://dev.example.com".to_string(),
                 headers: None,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
 
@@ -843,6 +1069,16 @@ mod tests {
             extract: None,
             depends_on: Some(vec!["auth".to_string()]),
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let auth_route = Route {
@@ -863,9 +1099,20 @@ mod tests {
             }]),
             depends_on: None,
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes: vec![auth_route.clone(), dependent_route.clone()],
@@ -896,6 +1143,16 @@ mod tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
         let normal_routes = vec![&normal_route];
         assert!(!runner.has_chaining_requirements(&normal_routes));
@@ -907,17 +1164,23 @@ mod tests {
         environments.insert(
             "dev".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://dev.example.com".to_string(),
                 headers: None,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
         environments.insert(
             "staging".to_string(),
             Environment {
+                extends: None,
                 base_url: "https://staging.example.com".to_string(),
                 headers: None,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
 
@@ -939,9 +1202,20 @@ mod tests {
             }]),
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes: vec![auth_route.clone()],
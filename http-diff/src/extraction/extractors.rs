@@ -406,6 +406,340 @@ impl ValueExtractor for StatusCodeExtractor {
     }
 }
 
+/// Run an extractor over every rule it supports, collecting values and errors
+fn extract_supported(
+    extractor: &dyn ValueExtractor,
+    label: &str,
+    context: &ValueExtractionContext,
+    rules: &[ExtractionRule],
+) -> Result<ExtractionResult> {
+    let mut result = ExtractionResult::new(context.clone());
+
+    for rule in rules.iter().filter(|rule| extractor.supports_rule(rule)) {
+        match extractor.extract_single_value(context, rule) {
+            Ok(Some(value)) => {
+                result.add_value(ExtractedValue::new(
+                    rule.key.clone(),
+                    value,
+                    rule.pattern.clone(),
+                    rule.extraction_type.clone(),
+                    context.environment.clone(),
+                    context.route_name.clone(),
+                ));
+            }
+            Ok(None) => {
+                // Value not found - handled by the calling code
+            }
+            Err(err) => {
+                result.add_error(ExtractionError::new(
+                    rule.clone(),
+                    format!("{} extraction failed: {}", label, err),
+                    context.environment.clone(),
+                    context.route_name.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Look up a response header case-insensitively
+fn response_header<'a>(context: &'a ValueExtractionContext, name: &str) -> Option<&'a str> {
+    context
+        .response
+        .headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Error returned when an extractor is given a rule of another type
+fn unsupported_rule(context: &ValueExtractionContext, rule: &ExtractionRule, label: &str) -> HttpDiffError {
+    HttpDiffError::value_extraction_failed(
+        context.route_name.clone(),
+        rule.key.clone(),
+        format!("{} extractor does not support this rule type", label),
+    )
+}
+
+/// XPath extractor for extracting values from XML and HTML response bodies
+///
+/// Bodies that are not well-formed XML, or that are served as HTML, are parsed
+/// leniently as HTML first. Node sets yield the text of their first node.
+#[derive(Debug, Clone, Default)]
+pub struct XPathExtractor;
+
+impl XPathExtractor {
+    /// Create a new XPath extractor
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ValueExtractor for XPathExtractor {
+    fn extract_values(
+        &self,
+        context: &ValueExtractionContext,
+        rules: &[ExtractionRule],
+    ) -> Result<ExtractionResult> {
+        extract_supported(self, "XPath", context, rules)
+    }
+
+    fn extract_single_value(
+        &self,
+        context: &ValueExtractionContext,
+        rule: &ExtractionRule,
+    ) -> Result<Option<String>> {
+        if !self.supports_rule(rule) {
+            return Err(unsupported_rule(context, rule, "XPath"));
+        }
+
+        let failed = |message: String| {
+            HttpDiffError::value_extraction_failed(context.route_name.clone(), rule.key.clone(), message)
+        };
+
+        let is_html = response_header(context, "content-type")
+            .is_some_and(|content_type| content_type.contains("html"));
+        let package = match sxd_document::parser::parse(&context.response.body) {
+            Ok(package) if !is_html => package,
+            _ => html_to_xml(&context.response.body),
+        };
+        let document = package.as_document();
+
+        let value = sxd_xpath::evaluate_xpath(&document, &rule.pattern)
+            .map_err(|err| failed(format!("Invalid XPath expression '{}': {}", rule.pattern, err)))?;
+
+        Ok(match value {
+            sxd_xpath::Value::Nodeset(nodes) => nodes.document_order_first().map(|node| node.string_value()),
+            value => Some(value.into_string()),
+        })
+    }
+
+    fn supports_rule(&self, rule: &ExtractionRule) -> bool {
+        rule.extraction_type == ExtractionType::XPath
+    }
+}
+
+/// Convert an HTML document into an XML document XPath can be evaluated on
+fn html_to_xml(body: &str) -> sxd_document::Package {
+    fn convert<'d>(
+        document: &sxd_document::dom::Document<'d>,
+        element: scraper::ElementRef,
+    ) -> sxd_document::dom::Element<'d> {
+        let converted = document.create_element(element.value().name());
+        for (name, value) in element.value().attrs() {
+            converted.set_attribute_value(name, value);
+        }
+        for child in element.children() {
+            if let Some(child_element) = scraper::ElementRef::wrap(child) {
+                converted.append_child(convert(document, child_element));
+            } else if let scraper::Node::Text(text) = child.value() {
+                converted.append_child(document.create_text(text));
+            }
+        }
+        converted
+    }
+
+    let html = scraper::Html::parse_document(body);
+    let package = sxd_document::Package::new();
+    {
+        let document = package.as_document();
+        let root = convert(&document, html.root_element());
+        document.root().append_child(root);
+    }
+    package
+}
+
+/// Cookie extractor for extracting cookie values set by the response
+///
+/// The rule pattern is the cookie name, matched case-sensitively.
+#[derive(Debug, Clone, Default)]
+pub struct CookieExtractor;
+
+impl CookieExtractor {
+    /// Create a new Cookie extractor
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ValueExtractor for CookieExtractor {
+    fn extract_values(
+        &self,
+        context: &ValueExtractionContext,
+        rules: &[ExtractionRule],
+    ) -> Result<ExtractionResult> {
+        extract_supported(self, "Cookie", context, rules)
+    }
+
+    fn extract_single_value(
+        &self,
+        context: &ValueExtractionContext,
+        rule: &ExtractionRule,
+    ) -> Result<Option<String>> {
+        if !self.supports_rule(rule) {
+            return Err(unsupported_rule(context, rule, "Cookie"));
+        }
+
+        // Repeated Set-Cookie headers are kept on separate lines
+        let cookies = response_header(context, "set-cookie").unwrap_or_default();
+        Ok(cookies.lines().find_map(|cookie| {
+            let (name, value) = cookie.split(';').next()?.split_once('=')?;
+            (name.trim() == rule.pattern).then(|| value.trim().trim_matches('"').to_string())
+        }))
+    }
+
+    fn supports_rule(&self, rule: &ExtractionRule) -> bool {
+        rule.extraction_type == ExtractionType::Cookie
+    }
+}
+
+/// Body extractor capturing the whole response body
+#[derive(Debug, Clone, Default)]
+pub struct BodyExtractor;
+
+impl BodyExtractor {
+    /// Create a new Body extractor
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ValueExtractor for BodyExtractor {
+    fn extract_values(
+        &self,
+        context: &ValueExtractionContext,
+        rules: &[ExtractionRule],
+    ) -> Result<ExtractionResult> {
+        extract_supported(self, "Body", context, rules)
+    }
+
+    fn extract_single_value(
+        &self,
+        context: &ValueExtractionContext,
+        rule: &ExtractionRule,
+    ) -> Result<Option<String>> {
+        if !self.supports_rule(rule) {
+            return Err(unsupported_rule(context, rule, "Body"));
+        }
+
+        let body = &context.response.body;
+        Ok((!body.is_empty()).then(|| body.clone()))
+    }
+
+    fn supports_rule(&self, rule: &ExtractionRule) -> bool {
+        rule.extraction_type == ExtractionType::Body
+    }
+}
+
+/// JMESPath extractor for querying JSON response bodies
+#[derive(Debug, Clone, Default)]
+pub struct JmesPathExtractor;
+
+impl JmesPathExtractor {
+    /// Create a new JMESPath extractor
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ValueExtractor for JmesPathExtractor {
+    fn extract_values(
+        &self,
+        context: &ValueExtractionContext,
+        rules: &[ExtractionRule],
+    ) -> Result<ExtractionResult> {
+        extract_supported(self, "JMESPath", context, rules)
+    }
+
+    fn extract_single_value(
+        &self,
+        context: &ValueExtractionContext,
+        rule: &ExtractionRule,
+    ) -> Result<Option<String>> {
+        if !self.supports_rule(rule) {
+            return Err(unsupported_rule(context, rule, "JMESPath"));
+        }
+
+        let failed = |message: String| {
+            HttpDiffError::value_extraction_failed(context.route_name.clone(), rule.key.clone(), message)
+        };
+
+        let expression = jmespath::compile(&rule.pattern)
+            .map_err(|err| failed(format!("Invalid JMESPath expression '{}': {}", rule.pattern, err)))?;
+        let json_value: JsonValue = serde_json::from_str(&context.response.body)
+            .map_err(|err| failed(format!("Failed to parse response body as JSON: {}", err)))?;
+        let found = expression
+            .search(json_value)
+            .map_err(|err| failed(format!("JMESPath evaluation failed: {}", err)))?;
+
+        if found.is_null() {
+            return Ok(None);
+        }
+        let found = serde_json::to_value(&*found)
+            .map_err(|err| failed(format!("Failed to convert JMESPath result: {}", err)))?;
+        Ok(Some(json_value_to_string(&found)))
+    }
+
+    fn supports_rule(&self, rule: &ExtractionRule) -> bool {
+        rule.extraction_type == ExtractionType::JmesPath
+    }
+}
+
+/// Computed extractor rendering a placeholder template
+///
+/// The template may reference user data, values extracted by earlier routes
+/// and values extracted earlier from the same response, and may use
+/// placeholder expressions such as `{upper(name)}`. The value is missing when
+/// a referenced variable is.
+#[derive(Debug, Clone, Default)]
+pub struct ComputedExtractor;
+
+impl ComputedExtractor {
+    /// Create a new Computed extractor
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ValueExtractor for ComputedExtractor {
+    fn extract_values(
+        &self,
+        context: &ValueExtractionContext,
+        rules: &[ExtractionRule],
+    ) -> Result<ExtractionResult> {
+        extract_supported(self, "Computed", context, rules)
+    }
+
+    fn extract_single_value(
+        &self,
+        context: &ValueExtractionContext,
+        rule: &ExtractionRule,
+    ) -> Result<Option<String>> {
+        if !self.supports_rule(rule) {
+            return Err(unsupported_rule(context, rule, "Computed"));
+        }
+
+        let missing = std::cell::Cell::new(false);
+        let value = crate::template::render(
+            &rule.pattern,
+            false,
+            |name| context.user_context.get(name).cloned(),
+            |_| {
+                missing.set(true);
+                Ok(())
+            },
+        )?;
+
+        Ok((!missing.get()).then_some(value))
+    }
+
+    fn supports_rule(&self, rule: &ExtractionRule) -> bool {
+        rule.extraction_type == ExtractionType::Computed
+    }
+}
+
 /// Helper function to convert JSON values to strings
 fn json_value_to_string(value: &JsonValue) -> String {
     match value {
@@ -678,4 +1012,105 @@ mod tests {
         let object = serde_json::json!({"key": "value"});
         assert_eq!(json_value_to_string(&object), r#"{"key":"value"}"#);
     }
-}
\ No newline at end of file
+
+    fn context_with(headers: &[(&str, &str)], body: &str) -> ValueExtractionContext {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        ValueExtractionContext::new(
+            "test_route".to_string(),
+            "test_env".to_string(),
+            HttpResponse::new(200, headers, body.to_string(), String::new(), String::new()),
+            HashMap::from([("first".to_string(), "Jane".to_string())]),
+        )
+    }
+
+    fn extract(extractor: &dyn ValueExtractor, context: &ValueExtractionContext, extraction_type: ExtractionType, pattern: &str) -> Option<String> {
+        let rule = ExtractionRule::new("value".to_string(), extraction_type, pattern.to_string());
+        extractor.extract_single_value(context, &rule).unwrap()
+    }
+
+    #[test]
+    fn test_xpath_extractor() {
+        let extractor = XPathExtractor::new();
+        let xml = context_with(
+            &[("Content-Type", "application/xml")],
+            r#"<order id="7"><item sku="A1">Pen</item><item sku="B2">Ink</item></order>"#,
+        );
+
+        assert_eq!(extract(&extractor, &xml, ExtractionType::XPath, "/order/@id"), Some("7".to_string()));
+        assert_eq!(extract(&extractor, &xml, ExtractionType::XPath, "//item[2]"), Some("Ink".to_string()));
+        assert_eq!(extract(&extractor, &xml, ExtractionType::XPath, "count(//item)"), Some("2".to_string()));
+        assert_eq!(extract(&extractor, &xml, ExtractionType::XPath, "//missing"), None);
+
+        // HTML does not need to be well-formed XML
+        let html = context_with(
+            &[("Content-Type", "text/html; charset=utf-8")],
+            r#"<html><body><form><input type="hidden" name="csrf" value="tok-1"><br></form></body></html>"#,
+        );
+        assert_eq!(
+            extract(&extractor, &html, ExtractionType::XPath, "//input[@name='csrf']/@value"),
+            Some("tok-1".to_string())
+        );
+
+        let rule = ExtractionRule::new("bad".to_string(), ExtractionType::XPath, "//[".to_string());
+        assert!(extractor.extract_single_value(&xml, &rule).is_err());
+    }
+
+    #[test]
+    fn test_cookie_extractor() {
+        let extractor = CookieExtractor::new();
+        let context = context_with(
+            &[(
+                "set-cookie",
+                "theme=dark; Path=/\nsession=abc123; Expires=Wed, 21 Oct 2026 07:28:00 GMT; HttpOnly",
+            )],
+            "",
+        );
+
+        assert_eq!(extract(&extractor, &context, ExtractionType::Cookie, "session"), Some("abc123".to_string()));
+        assert_eq!(extract(&extractor, &context, ExtractionType::Cookie, "theme"), Some("dark".to_string()));
+        assert_eq!(extract(&extractor, &context, ExtractionType::Cookie, "Session"), None);
+    }
+
+    #[test]
+    fn test_body_extractor() {
+        let extractor = BodyExtractor::new();
+        assert_eq!(
+            extract(&extractor, &context_with(&[], "plain text"), ExtractionType::Body, ""),
+            Some("plain text".to_string())
+        );
+        assert_eq!(extract(&extractor, &context_with(&[], ""), ExtractionType::Body, ""), None);
+    }
+
+    #[test]
+    fn test_jmespath_extractor() {
+        let extractor = JmesPathExtractor::new();
+        let context = create_test_context();
+
+        assert_eq!(extract(&extractor, &context, ExtractionType::JmesPath, "metadata.tags[-1]"), Some("premium".to_string()));
+        assert_eq!(extract(&extractor, &context, ExtractionType::JmesPath, "length(metadata.tags)"), Some("2".to_string()));
+        assert_eq!(
+            extract(&extractor, &context, ExtractionType::JmesPath, "{id: user_id, name: name}"),
+            Some(r#"{"id":42,"name":"John Doe"}"#.to_string())
+        );
+        assert_eq!(extract(&extractor, &context, ExtractionType::JmesPath, "missing"), None);
+    }
+
+    #[test]
+    fn test_computed_extractor() {
+        let extractor = ComputedExtractor::new();
+        let context = context_with(&[], "");
+
+        assert_eq!(
+            extract(&extractor, &context, ExtractionType::Computed, "{upper(first)}-{first}"),
+            Some("JANE-Jane".to_string())
+        );
+        assert_eq!(extract(&extractor, &context, ExtractionType::Computed, "{first} {last}"), None);
+        assert_eq!(
+            extract(&extractor, &context, ExtractionType::Computed, "{first} {last ?? 'Doe'}"),
+            Some("Jane Doe".to_string())
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Value extraction module for extracting values from HTTP responses
 //! 
 //! This module provides functionality to extract values from HTTP responses using
-//! various extraction methods including JsonPath, Regex, Header, and StatusCode.
+//! various extraction methods including JsonPath, JMESPath, XPath, Regex, Header,
+//! Cookie, StatusCode, whole-body capture and computed values.
 
 pub mod extractors;
 
@@ -15,7 +16,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 pub use extractors::{
-    JsonPathExtractor, RegexExtractor, HeaderExtractor, StatusCodeExtractor,
+    JsonPathExtractor, RegexExtractor, HeaderExtractor, StatusCodeExtractor, XPathExtractor,
+    CookieExtractor, BodyExtractor, JmesPathExtractor, ComputedExtractor,
 };
 
 /// Main value extraction engine that orchestrates different extractors
@@ -41,6 +43,11 @@ impl ValueExtractionEngine {
         extractors.insert(ExtractionType::Regex, Arc::new(RegexExtractor::new()));
         extractors.insert(ExtractionType::Header, Arc::new(HeaderExtractor::new()));
         extractors.insert(ExtractionType::StatusCode, Arc::new(StatusCodeExtractor::new()));
+        extractors.insert(ExtractionType::XPath, Arc::new(XPathExtractor::new()));
+        extractors.insert(ExtractionType::Cookie, Arc::new(CookieExtractor::new()));
+        extractors.insert(ExtractionType::Body, Arc::new(BodyExtractor::new()));
+        extractors.insert(ExtractionType::JmesPath, Arc::new(JmesPathExtractor::new()));
+        extractors.insert(ExtractionType::Computed, Arc::new(ComputedExtractor::new()));
         
         Self { extractors }
     }
@@ -59,7 +66,7 @@ impl ValueExtractionEngine {
         user_context: HashMap<String, String>,
         rules: &[ExtractionRule],
     ) -> Result<ExtractionResult> {
-        let context = ValueExtractionContext::new(
+        let mut context = ValueExtractionContext::new(
             route_name.clone(),
             environment.clone(),
             response,
@@ -71,6 +78,9 @@ impl ValueExtractionEngine {
         for rule in rules {
             match self.extract_single_value(&context, rule) {
                 Ok(Some(value)) => {
+                    // Later computed rules may reference this value
+                    context.user_context.insert(rule.key.clone(), value.clone());
+
                     let extracted_value = ExtractedValue::new(
                         rule.key.clone(),
                         value,
@@ -197,15 +207,41 @@ impl ValueExtractionEngine {
                         )));
                     }
                 }
-                ExtractionType::StatusCode => {
-                    // StatusCode doesn't use pattern, but we can validate it's not misleading
+                ExtractionType::StatusCode | ExtractionType::Body => {
+                    // These don't use a pattern, but we can validate it's not misleading
                     if !rule.pattern.is_empty() {
                         return Err(HttpDiffError::invalid_config(format!(
-                            "StatusCode extraction does not use pattern, but pattern '{}' was provided for rule '{}'",
+                            "{} extraction does not use pattern, but pattern '{}' was provided for rule '{}'",
+                            rule.extraction_type.name(), rule.pattern, rule.key
+                        )));
+                    }
+                }
+                ExtractionType::XPath => {
+                    let compiled = sxd_xpath::Factory::new().build(&rule.pattern);
+                    if !matches!(compiled, Ok(Some(_))) {
+                        return Err(HttpDiffError::invalid_config(format!(
+                            "Invalid XPath expression '{}' for rule '{}'",
                             rule.pattern, rule.key
                         )));
                     }
                 }
+                ExtractionType::JmesPath => {
+                    if let Err(err) = jmespath::compile(&rule.pattern) {
+                        return Err(HttpDiffError::invalid_config(format!(
+                            "Invalid JMESPath expression '{}' for rule '{}': {}",
+                            rule.pattern, rule.key, err
+                        )));
+                    }
+                }
+                ExtractionType::Cookie | ExtractionType::Computed => {
+                    if rule.pattern.is_empty() {
+                        return Err(HttpDiffError::invalid_config(format!(
+                            "{} pattern cannot be empty for rule '{}'",
+                            rule.extraction_type.name(),
+                            rule.key
+                        )));
+                    }
+                }
             }
         }
 
@@ -272,9 +308,14 @@ mod tests {
         assert!(engine.supports_extraction_type(&ExtractionType::Regex));
         assert!(engine.supports_extraction_type(&ExtractionType::Header));
         assert!(engine.supports_extraction_type(&ExtractionType::StatusCode));
+        assert!(engine.supports_extraction_type(&ExtractionType::XPath));
+        assert!(engine.supports_extraction_type(&ExtractionType::Cookie));
+        assert!(engine.supports_extraction_type(&ExtractionType::Body));
+        assert!(engine.supports_extraction_type(&ExtractionType::JmesPath));
+        assert!(engine.supports_extraction_type(&ExtractionType::Computed));
         
         let available_types = engine.available_extraction_types();
-        assert_eq!(available_types.len(), 4);
+        assert_eq!(available_types.len(), 9);
     }
 
     #[test]
@@ -336,6 +377,28 @@ mod tests {
         assert_eq!(value_map.get("status_code"), Some(&"200".to_string()));
     }
 
+    #[test]
+    fn test_computed_rules_see_earlier_values() {
+        let engine = ValueExtractionEngine::new();
+        let rules = vec![
+            ExtractionRule::new("user_id".to_string(), ExtractionType::JsonPath, "$.user_id".to_string()),
+            ExtractionRule::new("user_key".to_string(), ExtractionType::Computed, "{tenant}:{user_id}".to_string()),
+        ];
+
+        let result = engine
+            .extract_values(
+                "test_route".to_string(),
+                "test_env".to_string(),
+                create_test_response(),
+                HashMap::from([("tenant".to_string(), "acme".to_string())]),
+                &rules,
+            )
+            .unwrap();
+
+        let value_map = result.to_key_value_map();
+        assert_eq!(value_map.get("user_key"), Some(&"acme:42".to_string()));
+    }
+
     #[test]
     fn test_extraction_with_required_rule_failure() {
         let engine = ValueExtractionEngine::new();
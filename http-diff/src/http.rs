@@ -1,21 +1,121 @@
-use crate::config::{HttpDiffConfig, Route, UserData};
+use crate::auth::{
+    Credential, IssuedToken, OAuth2Grant, OAuth2TokenRequest, TokenCache, TokenResponse,
+};
+use crate::body_builder::{BodyBuilder, RequestBody};
+use crate::config::{AuthConfig, HttpDiffConfig, Route, UserData};
 use crate::error::{HttpDiffError, Result};
-use crate::traits::HttpClient;
-use crate::types::HttpResponse;
+use crate::execution::cookies::CookieJar;
+use crate::execution::retry::RetryPolicy;
+use crate::extraction::JsonPathExtractor;
+use crate::secrets::SecretStore;
+use crate::traits::{HttpClient, ValueExtractor};
+use crate::types::{
+    ExtractionRule, ExtractionType, HttpResponse, ResponseTiming, ValueExtractionContext,
+};
 use crate::url_builder::UrlBuilder;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
 use reqwest::{Client, Method, Request};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    /// Connection phases recorded while the current request is in flight
+    static CONNECTION_TIMINGS: Arc<Mutex<ConnectionTimings>>;
+}
+
+/// DNS and connect durations of a newly opened connection
+#[derive(Debug, Default, Clone, Copy)]
+struct ConnectionTimings {
+    dns: Option<Duration>,
+    connect: Option<Duration>,
+}
+
+/// Record a connection phase for the request in flight
+///
+/// Connections established in the background (after the request already got a
+/// pooled connection) run outside the request's task and are not recorded.
+fn record_connection_timing(update: impl FnOnce(&mut ConnectionTimings)) {
+    let _ = CONNECTION_TIMINGS.try_with(|timings| {
+        if let Ok(mut timings) = timings.lock() {
+            update(&mut timings);
+        }
+    });
+}
+
+/// DNS resolver that records how long each lookup takes
+struct TimedResolver;
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let started = Instant::now();
+            let addrs = tokio::net::lookup_host((name.as_str(), 0)).await?;
+            record_connection_timing(|timings| timings.dns = Some(started.elapsed()));
+            Ok(Box::new(addrs.collect::<Vec<_>>().into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Connector layer that records how long connection setup takes
+#[derive(Clone)]
+struct TimedConnectLayer;
+
+impl<S> tower::Layer<S> for TimedConnectLayer {
+    type Service = TimedConnect<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimedConnect { inner }
+    }
+}
+
+/// Connector wrapper created by [`TimedConnectLayer`]
+#[derive(Clone)]
+struct TimedConnect<S> {
+    inner: S,
+}
+
+impl<S, R> tower::Service<R> for TimedConnect<S>
+where
+    S: tower::Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let started = Instant::now();
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let connection = connecting.await;
+            record_connection_timing(|timings| timings.connect = Some(started.elapsed()));
+            connection
+        })
+    }
+}
 
 /// HTTP client implementation
 #[derive(Clone)]
 pub struct HttpClientImpl {
     client: Client,
     config: HttpDiffConfig,
+    tokens: Arc<TokenCache>,
+    secrets: Arc<SecretStore>,
 }
 
 impl HttpClientImpl {
     /// Create a new HTTP client with configuration
+    ///
+    /// Secrets are redacted with the active store unless another one is set
+    /// with [`HttpClientImpl::with_secrets`].
     pub fn new(config: HttpDiffConfig) -> Result<Self> {
         let timeout = config
             .global
@@ -30,6 +130,8 @@ impl HttpClientImpl {
             .unwrap_or(true);
 
         let client = Client::builder()
+            .dns_resolver(Arc::new(TimedResolver))
+            .connector_layer(TimedConnectLayer)
             .timeout(Duration::from_secs(timeout))
             .redirect(if follow_redirects {
                 reqwest::redirect::Policy::default()
@@ -38,15 +140,51 @@ impl HttpClientImpl {
             })
             .build()?;
 
-        Ok(Self { client, config })
+        let secrets = crate::secrets::active();
+        Ok(Self {
+            client,
+            config,
+            tokens: Arc::new(TokenCache::new(secrets.clone())),
+            secrets,
+        })
     }
 
-    /// Build an HTTP request from route configuration
+    /// Resolve and redact secrets with `secrets`, usually the store installed by the entry point
+    pub fn with_secrets(mut self, secrets: Arc<SecretStore>) -> Self {
+        self.tokens = Arc::new(TokenCache::new(secrets.clone()));
+        self.secrets = secrets;
+        self
+    }
+
+    /// Build an HTTP request from route configuration, authenticated for the environment
+    ///
+    /// Returns the access token attached to the request, if any, so it can be
+    /// renewed when the server rejects it.
     async fn build_request(
         &self,
         route: &Route,
         environment: &str,
         user_data: &UserData,
+        body: Option<RequestBody>,
+    ) -> Result<(Request, Option<IssuedToken>)> {
+        let mut request = self
+            .build_unauthenticated_request(route, environment, user_data, body)
+            .await?;
+        let token = self
+            .authenticate(&mut request, route, environment, user_data)
+            .await?;
+        Ok((request, token))
+    }
+
+    /// Build an HTTP request from route configuration without environment credentials
+    ///
+    /// `body` is the route's body built by [`BodyBuilder`] for the same user data.
+    async fn build_unauthenticated_request(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+        body: Option<RequestBody>,
     ) -> Result<Request> {
         // Use UrlBuilder to construct the URL
         let url_builder = UrlBuilder::new(&self.config, route, environment, user_data);
@@ -61,27 +199,226 @@ impl HttpClientImpl {
         let mut request_builder = self.client.request(method, url);
 
         // Add headers with CSV parameter substitution
-        request_builder = self.add_headers(request_builder, route, environment, user_data)?;
+        request_builder = self.add_headers(
+            request_builder,
+            route,
+            environment,
+            user_data,
+            body.as_ref(),
+        )?;
 
-        // Add body with CSV parameter substitution if present
-        if let Some(body) = &route.body {
-            let substituted_body = user_data.substitute_placeholders(body, false, false)?;
-            request_builder = request_builder.body(substituted_body);
+        let mut request = request_builder.build()?;
+
+        if let Some(body) = body {
+            body.apply(&mut request)?;
         }
 
-        request_builder.build().map_err(Into::into)
+        Ok(request)
+    }
+
+    /// Attach the credentials of the environment's auth provider to a request
+    async fn authenticate(
+        &self,
+        request: &mut Request,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+    ) -> Result<Option<IssuedToken>> {
+        let Some(auth) = self
+            .config
+            .environments
+            .get(environment)
+            .and_then(|env| env.auth.as_ref())
+        else {
+            return Ok(None);
+        };
+        let render = |text: &str| user_data.substitute_placeholders(text, false, false);
+        let render_opt = |text: &Option<String>| text.as_deref().map(render).transpose();
+
+        let (credential, token) = match auth {
+            AuthConfig::Basic { username, password } => {
+                let (username, password) = (render(username)?, render(password)?);
+                // The base64 of the password alone does not appear in the encoded
+                // `user:password` pair, so the pair is redacted as a whole
+                self.secrets.register(&format!("{}:{}", username, password));
+                (Credential::basic(&username, &password), None)
+            }
+            AuthConfig::ApiKey {
+                name,
+                value,
+                location,
+            } => (Credential::api_key(*location, name, &render(value)?), None),
+            AuthConfig::OAuth2ClientCredentials {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+            } => {
+                let token_request = OAuth2TokenRequest {
+                    token_url: render(token_url)?,
+                    client_id: render(client_id)?,
+                    client_secret: Some(render(client_secret)?),
+                    scope: render_opt(scope)?,
+                    grant: OAuth2Grant::ClientCredentials,
+                };
+                let token = self.oauth2_token(&token_request, environment).await?;
+                (
+                    Credential::token(None, None, &token.access_token),
+                    Some(token),
+                )
+            }
+            AuthConfig::OAuth2Password {
+                token_url,
+                client_id,
+                client_secret,
+                username,
+                password,
+                scope,
+            } => {
+                let token_request = OAuth2TokenRequest {
+                    token_url: render(token_url)?,
+                    client_id: render(client_id)?,
+                    client_secret: render_opt(client_secret)?,
+                    scope: render_opt(scope)?,
+                    grant: OAuth2Grant::Password {
+                        username: render(username)?,
+                        password: render(password)?,
+                    },
+                };
+                let token = self.oauth2_token(&token_request, environment).await?;
+                (
+                    Credential::token(None, None, &token.access_token),
+                    Some(token),
+                )
+            }
+            AuthConfig::LoginRoute {
+                route: login_route,
+                token_path,
+                header,
+                scheme,
+            } => {
+                // The login route itself is sent without credentials
+                if route.name == *login_route {
+                    return Ok(None);
+                }
+                let token = self
+                    .login_token(login_route, token_path, environment, user_data)
+                    .await?;
+                let credential =
+                    Credential::token(header.as_deref(), scheme.as_deref(), &token.access_token);
+                (credential, Some(token))
+            }
+        };
+
+        credential.apply(request)?;
+        Ok(token)
+    }
+
+    /// Get a cached OAuth2 token or request a new one
+    async fn oauth2_token(
+        &self,
+        token_request: &OAuth2TokenRequest,
+        environment: &str,
+    ) -> Result<IssuedToken> {
+        self.tokens
+            .get_or_fetch(&token_request.cache_key(environment), |refresh_token| {
+                token_request.send(&self.client, environment, refresh_token)
+            })
+            .await
     }
 
-    /// Add headers to request with CSV parameter substitution
+    /// Get a cached login token or send the login route to obtain one
+    async fn login_token(
+        &self,
+        login_route: &str,
+        token_path: &str,
+        environment: &str,
+        user_data: &UserData,
+    ) -> Result<IssuedToken> {
+        let failed = |message: String| {
+            HttpDiffError::authentication_failed(environment.to_string(), message)
+        };
+        let route = self
+            .config
+            .routes
+            .iter()
+            .find(|r| r.name == login_route)
+            .ok_or_else(|| failed(format!("login route '{}' is not defined", login_route)))?;
+        let body = BodyBuilder::new(route, user_data).build()?;
+        let request = self
+            .build_unauthenticated_request(route, environment, user_data, body)
+            .await?;
+
+        // Logins with different credentials, e.g. per CSV user, get their own token
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(String::from_utf8_lossy)
+            .unwrap_or_default();
+        let key = format!(
+            "login:{}:{} {}:{}",
+            environment,
+            request.method(),
+            request.url(),
+            body
+        );
+
+        self.tokens
+            .get_or_fetch(&key, |_| async move {
+                let response = self
+                    .send_request(request, String::new())
+                    .await
+                    .map_err(|e| {
+                        failed(
+                            self.secrets
+                                .redact(&format!("login route '{}' failed: {}", login_route, e)),
+                        )
+                    })?;
+                if !(200..300).contains(&response.status) {
+                    return Err(failed(format!(
+                        "login route '{}' returned status {}",
+                        login_route, response.status
+                    )));
+                }
+
+                let context = ValueExtractionContext::new(
+                    login_route.to_string(),
+                    environment.to_string(),
+                    response,
+                    user_data.data.clone(),
+                );
+                let rule = ExtractionRule::new(
+                    "token".to_string(),
+                    ExtractionType::JsonPath,
+                    token_path.to_string(),
+                );
+                JsonPathExtractor::new()
+                    .extract_single_value(&context, &rule)?
+                    .map(TokenResponse::new)
+                    .ok_or_else(|| {
+                        failed(format!(
+                            "no token at '{}' in the response of login route '{}'",
+                            token_path, login_route
+                        ))
+                    })
+            })
+            .await
+    }
+
+    /// Add headers to request with CSV parameter substitution and the body's content type
     fn add_headers(
         &self,
         request_builder: reqwest::RequestBuilder,
         route: &Route,
         environment: &str,
         user_data: &UserData,
+        body: Option<&RequestBody>,
     ) -> Result<reqwest::RequestBuilder> {
-        let headers =
+        let mut headers =
             crate::url_builder::resolve_headers(&self.config, route, environment, user_data)?;
+        if let Some(body) = body {
+            body.apply_content_type(&mut headers);
+        }
         let mut rb = request_builder;
         for (key, value) in headers {
             rb = rb.header(key, value);
@@ -94,15 +431,27 @@ impl HttpClientImpl {
         &self,
         response: reqwest::Response,
         curl_command: String,
-    ) -> Result<HttpResponse> {
+    ) -> std::result::Result<HttpResponse, reqwest::Error> {
         let status = response.status().as_u16();
         let url = response.url().to_string();
 
-        // Extract headers
-        let mut headers = HashMap::new();
+        // Extract headers, combining repeated ones. Set-Cookie values may contain
+        // commas, so they are kept on separate lines instead.
+        let mut headers: HashMap<String, String> = HashMap::new();
         for (name, value) in response.headers() {
             if let Ok(value_str) = value.to_str() {
-                headers.insert(name.to_string(), value_str.to_string());
+                let separator = if name == reqwest::header::SET_COOKIE {
+                    "\n"
+                } else {
+                    ", "
+                };
+                headers
+                    .entry(name.to_string())
+                    .and_modify(|existing| {
+                        existing.push_str(separator);
+                        existing.push_str(value_str);
+                    })
+                    .or_insert_with(|| value_str.to_string());
             }
         }
 
@@ -115,11 +464,57 @@ impl HttpClientImpl {
             body,
             url,
             curl_command,
+            timing: None,
+            attempts: 1,
         })
     }
 
+    /// Send a single request attempt, measuring its timing
+    async fn send_request(
+        &self,
+        request: Request,
+        curl_command: String,
+    ) -> std::result::Result<HttpResponse, reqwest::Error> {
+        let connection_timings = Arc::new(Mutex::new(ConnectionTimings::default()));
+        let started = Instant::now();
+        let response = CONNECTION_TIMINGS
+            .scope(connection_timings.clone(), self.client.execute(request))
+            .await?;
+        let ttfb = started.elapsed();
+
+        let response = self.convert_response(response, curl_command).await?;
+        let total = started.elapsed();
+
+        let connection = connection_timings
+            .lock()
+            .map(|timings| *timings)
+            .unwrap_or_default();
+        let dns = connection.dns.unwrap_or_default();
+        let timing = ResponseTiming {
+            dns_ms: connection.dns.map(|d| d.as_millis() as u64),
+            // The connector also performs the DNS lookup, so it is subtracted here
+            connect_ms: connection
+                .connect
+                .map(|c| c.saturating_sub(dns).as_millis() as u64),
+            ttfb_ms: ttfb.as_millis() as u64,
+            total_ms: total.as_millis() as u64,
+            size_bytes: response.body.len() as u64,
+        };
+        Ok(response.with_timing(timing))
+    }
+
     /// Generate a curl command equivalent for the request (optimized for memory efficiency)
-    fn generate_curl_command(&self, request: &reqwest::Request, route: &Route) -> String {
+    ///
+    /// Sensitive header values and resolved secrets are redacted.
+    fn generate_curl_command(
+        &self,
+        request: &reqwest::Request,
+        body: Option<&RequestBody>,
+    ) -> String {
+        let body_options = body.map(RequestBody::curl_options).unwrap_or_default();
+        // curl chooses its own multipart boundary
+        let skip_content_type = matches!(body, Some(RequestBody::Multipart(_)));
+
         // More accurate capacity estimation to minimize reallocations
         let mut estimated_capacity = 4; // "curl"
 
@@ -136,8 +531,8 @@ impl HttpClientImpl {
         }
 
         // Body
-        if let Some(body) = &route.body {
-            estimated_capacity += 5 + body.len(); // " -d '" + body + "'"
+        for (option, argument) in &body_options {
+            estimated_capacity += 4 + option.len() + argument.len(); // " " + option + " '" + argument + "'"
         }
 
         // URL
@@ -147,6 +542,7 @@ impl HttpClientImpl {
         estimated_capacity = (estimated_capacity as f32 * 1.1) as usize;
 
         let mut result = String::with_capacity(estimated_capacity);
+        let secrets = &self.secrets;
 
         result.push_str("curl");
 
@@ -158,21 +554,25 @@ impl HttpClientImpl {
 
         // Add headers with optimized string building
         for (name, value) in request.headers() {
+            if skip_content_type && name == reqwest::header::CONTENT_TYPE {
+                continue;
+            }
             if let Ok(value_str) = value.to_str() {
                 // Build header string efficiently without temporary allocations
                 result.push_str(" -H '");
                 result.push_str(name.as_str());
                 result.push_str(": ");
-                result.push_str(value_str);
+                result.push_str(&secrets.mask_header(name.as_str(), value_str));
                 result.push('\'');
             }
         }
 
         // Add body if present
-        if let Some(body) = &route.body {
-            result.reserve(5 + body.len()); // Ensure capacity before appending
-            result.push_str(" -d '");
-            result.push_str(body);
+        for (option, argument) in &body_options {
+            result.push(' ');
+            result.push_str(option);
+            result.push_str(" '");
+            result.push_str(&secrets.redact(argument));
             result.push('\'');
         }
 
@@ -180,11 +580,88 @@ impl HttpClientImpl {
         let url = request.url().as_str();
         result.reserve(3 + url.len()); // Ensure capacity before appending
         result.push_str(" '");
-        result.push_str(url);
+        result.push_str(&secrets.redact(url));
         result.push('\'');
 
         result
     }
+
+    /// Execute a request with retries, optionally keeping session cookies in a jar
+    async fn execute(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+        cookie_jar: Option<&CookieJar>,
+    ) -> Result<HttpResponse> {
+        let retry_policy = RetryPolicy::for_route(self.config.global.as_ref(), route);
+        let mut attempt = 1;
+        let mut reauthenticated = false;
+        // Files are read and placeholders substituted once for all attempts
+        let body = BodyBuilder::new(route, user_data).build()?;
+
+        loop {
+            // Rebuild the request for every attempt since request bodies are consumed
+            let (mut request, token) = self
+                .build_request(route, environment, user_data, body.clone())
+                .await?;
+            if let Some(jar) = cookie_jar {
+                add_jar_cookies(&mut request, jar);
+            }
+            let curl_command = self.generate_curl_command(&request, body.as_ref());
+            let can_retry = attempt < retry_policy.max_attempts;
+
+            let result = self.send_request(request, curl_command).await;
+            if let (Some(jar), Ok(response)) = (cookie_jar, &result) {
+                jar.store_response_cookies(response);
+            }
+
+            match result {
+                // A rejected token is renewed once, without counting as a retry
+                Ok(response) if response.status == 401 && !reauthenticated && token.is_some() => {
+                    if let Some(token) = &token {
+                        self.tokens.invalidate(token).await;
+                    }
+                    reauthenticated = true;
+                    continue;
+                }
+                Ok(response) if can_retry && retry_policy.should_retry_status(response.status) => {}
+                Ok(response) => return Ok(response.with_attempts(attempt)),
+                Err(e)
+                    if can_retry
+                        && (e.is_connect() || e.is_timeout())
+                        && retry_policy.should_retry_transport_error(&route.method) => {}
+                Err(e) => {
+                    let message = if attempt > 1 {
+                        format!("Request failed after {} attempts: {}", attempt, e)
+                    } else {
+                        format!("Request failed: {}", e)
+                    };
+                    return Err(HttpDiffError::request_failed(
+                        route.name.clone(),
+                        environment.to_string(),
+                        self.secrets.redact(&message),
+                    ));
+                }
+            }
+
+            tokio::time::sleep(retry_policy.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Send the jar's cookies, unless the route sets a `Cookie` header itself
+fn add_jar_cookies(request: &mut Request, jar: &CookieJar) {
+    if request.headers().contains_key(reqwest::header::COOKIE) {
+        return;
+    }
+    if let Some(value) = jar
+        .cookie_header(request.url())
+        .and_then(|cookies| reqwest::header::HeaderValue::from_str(&cookies).ok())
+    {
+        request.headers_mut().insert(reqwest::header::COOKIE, value);
+    }
 }
 
 impl HttpClient for HttpClientImpl {
@@ -194,17 +671,452 @@ impl HttpClient for HttpClientImpl {
         environment: &str,
         user_data: &UserData,
     ) -> Result<HttpResponse> {
-        let request = self.build_request(route, environment, user_data).await?;
-        let curl_command = self.generate_curl_command(&request, route);
-
-        let response = self.client.execute(request).await.map_err(|e| {
-            HttpDiffError::request_failed(
-                route.name.clone(),
-                environment.to_string(),
-                format!("Request failed: {}", e),
+        self.execute(route, environment, user_data, None).await
+    }
+
+    async fn execute_request_with_cookies(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+        cookie_jar: &CookieJar,
+    ) -> Result<HttpResponse> {
+        self.execute(route, environment, user_data, Some(cookie_jar))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HttpDiffConfigBuilder;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_execute_request_records_timing() {
+        let server = MockServer::start().await;
+        Mock::given(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        // Use a host name so the request goes through DNS resolution
+        let base_url = server.uri().replace("127.0.0.1", "localhost");
+        let config = HttpDiffConfigBuilder::new()
+            .environment("local", base_url.as_str(), None)
+            .get_route("health", "/health")
+            .build()
+            .unwrap();
+        let client = HttpClientImpl::new(config.clone()).unwrap();
+        let user_data = UserData::new(HashMap::new());
+
+        let first = client
+            .execute_request(&config.routes[0], "local", &user_data)
+            .await
+            .unwrap();
+        let timing = first.timing.unwrap();
+        assert!(timing.dns_ms.is_some());
+        assert!(timing.connect_ms.is_some());
+        assert!(timing.ttfb_ms <= timing.total_ms);
+        assert_eq!(timing.size_bytes, 2);
+
+        // The second request reuses the pooled connection
+        let second = client
+            .execute_request(&config.routes[0], "local", &user_data)
+            .await
+            .unwrap();
+        let timing = second.timing.unwrap();
+        assert_eq!(timing.dns_ms, None);
+        assert_eq!(timing.connect_ms, None);
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_sent_but_redacted_from_curl_command() {
+        std::env::set_var("HTTP_TEST_SECRET_TOKEN", "tok-987654");
+        let server = MockServer::start().await;
+        Mock::given(path("/me"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer tok-987654",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let mut config = HttpDiffConfigBuilder::new()
+            .environment("local", server.uri().as_str(), None)
+            .get_route("me", "/me")
+            .build()
+            .unwrap();
+        config.routes[0].headers = Some(HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer {secret:HTTP_TEST_SECRET_TOKEN}".to_string(),
+        )]));
+        let client = HttpClientImpl::new(config.clone()).unwrap();
+
+        let response = client
+            .execute_request(&config.routes[0], "local", &UserData::new(HashMap::new()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert!(response
+            .curl_command
+            .contains("-H 'authorization: Bearer ***'"));
+        assert!(!response.curl_command.contains("tok-987654"));
+    }
+
+    #[tokio::test]
+    async fn test_basic_credentials_are_redacted_with_the_given_store() {
+        let server = MockServer::start().await;
+        Mock::given(path("/me"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Basic dXNlcjpzM2NyM3Q=",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string("echo dXNlcjpzM2NyM3Q="))
+            .mount(&server)
+            .await;
+
+        let config = HttpDiffConfigBuilder::new()
+            .environment("local", server.uri().as_str(), None)
+            .auth(
+                "local",
+                AuthConfig::Basic {
+                    username: "user".to_string(),
+                    password: "s3cr3t".to_string(),
+                },
             )
-        })?;
+            .get_route("me", "/me")
+            .build()
+            .unwrap();
+        let secrets = Arc::new(SecretStore::new());
+        let client = HttpClientImpl::new(config.clone())
+            .unwrap()
+            .with_secrets(secrets.clone());
+
+        let response = client
+            .execute_request(&config.routes[0], "local", &UserData::new(HashMap::new()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(secrets.redact(&response.body), "echo ***=");
+    }
+
+    fn retrying_client(base_url: &str, method: &str) -> (HttpClientImpl, HttpDiffConfig) {
+        let config = HttpDiffConfigBuilder::new()
+            .environment("local", base_url, None)
+            .add_route("flaky", method, "/flaky", None::<&str>)
+            .configure_global(|global| {
+                global.retry(crate::config::RetryConfig {
+                    max_attempts: Some(3),
+                    initial_backoff_ms: Some(1),
+                    ..Default::default()
+                })
+            })
+            .build()
+            .unwrap();
+        (HttpClientImpl::new(config.clone()).unwrap(), config)
+    }
+
+    #[tokio::test]
+    async fn test_execute_request_retries_on_status() {
+        let server = MockServer::start().await;
+        Mock::given(path("/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let (client, config) = retrying_client(&server.uri(), "GET");
+        let response = client
+            .execute_request(&config.routes[0], "local", &UserData::new(HashMap::new()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_connect_errors_only_retried_for_idempotent_methods() {
+        // Reserve a port and close it again so connections are refused
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let user_data = UserData::new(HashMap::new());
+
+        let (client, config) = retrying_client(&base_url, "GET");
+        let err = client
+            .execute_request(&config.routes[0], "local", &user_data)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("after 3 attempts"));
+
+        let (client, config) = retrying_client(&base_url, "POST");
+        let err = client
+            .execute_request(&config.routes[0], "local", &user_data)
+            .await
+            .unwrap_err();
+        assert!(!err.to_string().contains("attempts"));
+    }
+
+    #[tokio::test]
+    async fn test_oauth2_token_is_cached_and_renewed_on_401() {
+        let server = MockServer::start().await;
+        Mock::given(path("/token"))
+            .and(wiremock::matchers::body_string_contains(
+                "grant_type=client_credentials",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "oauth-token-1", "expires_in": 3600
+            })))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "oauth-token-2", "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/me"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer oauth-token-1",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        // The first token is revoked after two requests
+        Mock::given(path("/me"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer oauth-token-1",
+            ))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        Mock::given(path("/me"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer oauth-token-2",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let config = HttpDiffConfigBuilder::new()
+            .environment("local", server.uri().as_str(), None)
+            .auth(
+                "local",
+                AuthConfig::OAuth2ClientCredentials {
+                    token_url: format!("{}/token", server.uri()),
+                    client_id: "http-diff".to_string(),
+                    client_secret: "client-secret".to_string(),
+                    scope: None,
+                },
+            )
+            .get_route("me", "/me")
+            .build()
+            .unwrap();
+        let client = HttpClientImpl::new(config.clone()).unwrap();
+        let user_data = UserData::new(HashMap::new());
+
+        for _ in 0..3 {
+            let response = client
+                .execute_request(&config.routes[0], "local", &user_data)
+                .await
+                .unwrap();
+            assert_eq!(response.status, 200);
+            assert_eq!(response.attempts, 1);
+            assert!(!response.curl_command.contains("oauth-token"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_route_token_is_attached_to_other_routes() {
+        let server = MockServer::start().await;
+        Mock::given(path("/login"))
+            .and(wiremock::matchers::body_string_contains(
+                "\"user\":\"alice\"",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"session": {"token": "login-token"}})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/profile"))
+            .and(wiremock::matchers::header("x-session", "login-token"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let config = HttpDiffConfigBuilder::new()
+            .environment("local", server.uri().as_str(), None)
+            .auth(
+                "local",
+                AuthConfig::LoginRoute {
+                    route: "login".to_string(),
+                    token_path: "$.session.token".to_string(),
+                    header: Some("X-Session".to_string()),
+                    scheme: Some(String::new()),
+                },
+            )
+            .add_route("login", "POST", "/login", Some(r#"{"user":"{user}"}"#))
+            .get_route("profile", "/profile")
+            .build()
+            .unwrap();
+        let client = HttpClientImpl::new(config.clone()).unwrap();
+        let user_data = UserData::new(HashMap::from([("user".to_string(), "alice".to_string())]));
+
+        for _ in 0..2 {
+            let response = client
+                .execute_request(&config.routes[1], "local", &user_data)
+                .await
+                .unwrap();
+            assert_eq!(response.status, 200);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cookie_jar_carries_session_to_later_routes() {
+        let server = MockServer::start().await;
+        Mock::given(path("/login"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .append_header("Set-Cookie", "session=s3cr3t; Path=/")
+                    .append_header("Set-Cookie", "theme=dark; Path=/settings"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/profile"))
+            .and(wiremock::matchers::header("cookie", "session=s3cr3t"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(path("/profile"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let config = HttpDiffConfigBuilder::new()
+            .environment("local", server.uri().as_str(), None)
+            .get_route("login", "/login")
+            .get_route("profile", "/profile")
+            .build()
+            .unwrap();
+        let client = HttpClientImpl::new(config.clone()).unwrap();
+        let user_data = UserData::new(HashMap::new());
+        let jar = CookieJar::new();
+
+        client
+            .execute_request_with_cookies(&config.routes[0], "local", &user_data, &jar)
+            .await
+            .unwrap();
+        assert_eq!(jar.cookies().len(), 2);
+
+        let response = client
+            .execute_request_with_cookies(&config.routes[1], "local", &user_data, &jar)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert!(!response.curl_command.contains("s3cr3t"));
+
+        // Without a jar no cookie is sent
+        let response = client
+            .execute_request(&config.routes[1], "local", &user_data)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 401);
+    }
+
+    #[tokio::test]
+    async fn test_form_and_multipart_bodies() {
+        let server = MockServer::start().await;
+        Mock::given(path("/form"))
+            .and(wiremock::matchers::header(
+                "content-type",
+                "application/x-www-form-urlencoded",
+            ))
+            .and(wiremock::matchers::body_string("name=alice+b&role=admin"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(path("/upload"))
+            .and(wiremock::matchers::body_string_contains("report for alice"))
+            .and(wiremock::matchers::body_string_contains(
+                "filename=\"alice.txt\"",
+            ))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("report.txt");
+        std::fs::write(&file, "report for {user}").unwrap();
+
+        let mut config = HttpDiffConfigBuilder::new()
+            .environment("local", server.uri().as_str(), None)
+            .global_header("Content-Type", "application/json")
+            .add_route("form", "POST", "/form", None::<&str>)
+            .add_route("upload", "POST", "/upload", None::<&str>)
+            .build()
+            .unwrap();
+        config.routes[0].form = Some(
+            [("name", "{user} b"), ("role", "admin")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        config.routes[1].multipart = Some(vec![crate::config::MultipartPart {
+            name: "report".to_string(),
+            file: Some(file.to_string_lossy().to_string()),
+            filename: Some("{user}.txt".to_string()),
+            ..Default::default()
+        }]);
+        let client = HttpClientImpl::new(config.clone()).unwrap();
+        let user_data = UserData::new(HashMap::from([("user".to_string(), "alice".to_string())]));
+
+        let response = client
+            .execute_request(&config.routes[0], "local", &user_data)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert!(response
+            .curl_command
+            .contains("--data-urlencode 'name=alice b'"));
+
+        // Placeholders in text files are substituted, and curl sends the result inline
+        let response = client
+            .execute_request(&config.routes[1], "local", &user_data)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 201);
+        assert!(response
+            .curl_command
+            .contains(" -F 'report=\"report for alice\";filename=alice.txt'"));
 
-        self.convert_response(response, curl_command).await
+        std::fs::write(&file, "report for alice").unwrap();
+        let response = client
+            .execute_request(&config.routes[1], "local", &user_data)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 201);
+        assert!(response.curl_command.contains(" -F 'report=@"));
+        assert!(!response.curl_command.contains("content-type"));
     }
 }
@@ -0,0 +1,505 @@
+//! Routes from captured traffic
+//!
+//! HAR files and curl command lines describe concrete requests rather than an
+//! API. [`CapturedTraffic`] turns them into routes:
+//!
+//! - the most frequent origin becomes the environment, and paths are split from it
+//! - numeric and UUID path segments are detected as likely parameters, named
+//!   after the segment before them (`/users/42` gives `userId`)
+//! - requests with the same method, path and query parameter names are merged
+//! - static assets, CORS preflights and volatile headers are left out
+
+use super::{
+    contains_null, environment_name_from, new_route, route_name_from, ImportBuilder, ImportedConfig,
+};
+use crate::error::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use url::Url;
+
+/// Headers set by browsers and HTTP clients rather than by the application
+const IGNORED_HEADERS: &[&str] = &[
+    "accept-encoding",
+    "accept-language",
+    "cache-control",
+    "connection",
+    "content-length",
+    "cookie",
+    "host",
+    "if-modified-since",
+    "if-none-match",
+    "origin",
+    "pragma",
+    "priority",
+    "referer",
+    "te",
+    "upgrade-insecure-requests",
+    "user-agent",
+];
+
+/// File extensions of static assets that are not API requests
+const STATIC_EXTENSIONS: &[&str] = &[
+    "css", "gif", "ico", "jpeg", "jpg", "js", "map", "png", "svg", "ttf", "webp", "woff", "woff2",
+];
+
+/// A request captured from traffic
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedRequest {
+    pub method: String,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// A path segment detected as a likely parameter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedParameter {
+    /// User data column the value moves to, e.g. `userId`
+    pub name: String,
+    /// First value seen in the captured paths
+    pub example: String,
+}
+
+/// Requests captured from traffic, ready to become routes
+#[derive(Debug, Clone)]
+pub struct CapturedTraffic {
+    source: String,
+    requests: Vec<CapturedRequest>,
+    warnings: Vec<String>,
+}
+
+impl CapturedTraffic {
+    /// Wrap captured requests; `source` names them in the generated configuration
+    pub fn new<S: Into<String>>(source: S, requests: Vec<CapturedRequest>) -> Self {
+        Self {
+            source: source.into(),
+            requests,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Add a warning about input that could not be read
+    pub fn with_warning<S: Into<String>>(mut self, warning: S) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// The captured requests
+    pub fn requests(&self) -> &[CapturedRequest] {
+        &self.requests
+    }
+
+    /// Path segments that look like IDs, in the order they were first seen
+    pub fn detected_parameters(&self) -> Vec<DetectedParameter> {
+        let mut detected: Vec<DetectedParameter> = Vec::new();
+        for request in self.api_requests(self.primary_origin().as_deref()) {
+            for (name, example) in path_template(request.url.path()).1 {
+                if !detected.iter().any(|p| p.name == name) {
+                    detected.push(DetectedParameter { name, example });
+                }
+            }
+        }
+        detected
+    }
+
+    /// Generate the configuration
+    ///
+    /// With `parameterize`, detected IDs are replaced by `{placeholders}` and
+    /// become `users.csv` columns; otherwise paths keep their literal values.
+    pub fn import(&self, parameterize: bool) -> Result<ImportedConfig> {
+        let mut import = ImportBuilder::new(self.source.clone());
+        for warning in &self.warnings {
+            import.warn(warning.clone());
+        }
+
+        let primary = self.primary_origin();
+        match &primary {
+            Some(origin) => {
+                let host = Url::parse(origin)
+                    .ok()
+                    .and_then(|url| url.host_str().map(environment_name_from))
+                    .unwrap_or_else(|| "captured".to_string());
+                import.environment(&host, origin);
+            }
+            None => import.environment("captured", "http://localhost"),
+        }
+
+        let skipped = self.requests.len() - self.api_requests(primary.as_deref()).count();
+        if skipped > 0 {
+            import.warn(format!(
+                "Skipped {} requests to other hosts, static assets or CORS preflights",
+                skipped
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut replaced_authorization = false;
+        for request in self.api_requests(primary.as_deref()) {
+            let (path, parameters) = if parameterize {
+                path_template(request.url.path())
+            } else {
+                (request.url.path().to_string(), Vec::new())
+            };
+
+            let mut query_names: Vec<String> = request
+                .url
+                .query_pairs()
+                .map(|(k, _)| k.into_owned())
+                .collect();
+            query_names.sort();
+            if !seen.insert((request.method.clone(), path.clone(), query_names)) {
+                continue;
+            }
+
+            for (name, example) in parameters {
+                import.parameter(&name, Some(example));
+            }
+            let mut route = new_route(
+                route_name_from(&request.method, &path),
+                request.method.clone(),
+                path,
+            );
+
+            let params: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+            route.params = (!params.is_empty()).then_some(params);
+
+            let mut headers = HashMap::new();
+            for (name, value) in &request.headers {
+                let lower = name.to_lowercase();
+                if name.starts_with(':')
+                    || lower.starts_with("sec-")
+                    || IGNORED_HEADERS.contains(&lower.as_str())
+                {
+                    continue;
+                }
+                // Captured credentials expire and must not end up in the configuration
+                if lower == "authorization" {
+                    replaced_authorization = true;
+                    headers.insert(name.clone(), "{secret:AUTHORIZATION}".to_string());
+                    continue;
+                }
+                headers.insert(name.clone(), value.clone());
+            }
+
+            if let Some(body) = &request.body {
+                set_body(&mut import, &mut route, &mut headers, body);
+            }
+            route.headers = (!headers.is_empty()).then_some(headers);
+            import.route(route);
+        }
+
+        if replaced_authorization {
+            import.warn(
+                "Authorization headers were replaced by {secret:AUTHORIZATION}; set AUTHORIZATION in the environment or .env",
+            );
+        }
+        import.build()
+    }
+
+    /// Origin with the most requests, e.g. `https://api.example.com`
+    fn primary_origin(&self) -> Option<String> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for request in &self.requests {
+            *counts.entry(origin(&request.url)).or_default() += 1;
+        }
+        // Ties go to the first origin in sorted order, so imports are reproducible
+        counts
+            .into_iter()
+            .fold(
+                None,
+                |best: Option<(String, usize)>, (origin, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((origin, count)),
+                },
+            )
+            .map(|(origin, _)| origin)
+    }
+
+    /// Requests to `origin` that are not static assets or CORS preflights
+    fn api_requests<'a>(
+        &'a self,
+        origin_filter: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a CapturedRequest> + 'a {
+        self.requests.iter().filter(move |request| {
+            let extension = request
+                .url
+                .path()
+                .rsplit('/')
+                .next()
+                .and_then(|segment| segment.rsplit_once('.'))
+                .map(|(_, extension)| extension.to_lowercase());
+            Some(origin(&request.url).as_str()) == origin_filter
+                && !request.method.eq_ignore_ascii_case("OPTIONS")
+                && !extension.is_some_and(|e| STATIC_EXTENSIONS.contains(&e.as_str()))
+        })
+    }
+}
+
+fn origin(url: &Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+/// Set the route body, using `json` and `form` tables when the content type allows
+fn set_body(
+    import: &mut ImportBuilder,
+    route: &mut crate::config::Route,
+    headers: &mut HashMap<String, String>,
+    body: &str,
+) {
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(name, value)| (name.clone(), value.to_lowercase()));
+    let essence = content_type
+        .as_ref()
+        .map(|(_, value)| {
+            value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    if essence == "application/json" || essence.ends_with("+json") || essence.is_empty() {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(body) {
+            if contains_null(&json) {
+                if content_type.is_none() {
+                    headers.insert("Content-Type".to_string(), "application/json".to_string());
+                }
+                route.body = Some(body.to_string());
+                return;
+            }
+            if essence == "application/json" {
+                if let Some((name, _)) = &content_type {
+                    headers.remove(name);
+                }
+            }
+            route.json = Some(json);
+            return;
+        }
+    }
+
+    if essence == "application/x-www-form-urlencoded" {
+        if let Some((name, _)) = &content_type {
+            headers.remove(name);
+        }
+        route.form = Some(
+            url::form_urlencoded::parse(body.as_bytes())
+                .into_owned()
+                .collect(),
+        );
+        return;
+    }
+
+    if essence.starts_with("multipart/") {
+        import.warn(format!(
+            "Route '{}': multipart bodies are not imported; declare its parts with `multipart`",
+            route.name
+        ));
+        if let Some((name, _)) = &content_type {
+            headers.remove(name);
+        }
+        return;
+    }
+
+    route.body = Some(body.to_string());
+}
+
+/// Replace numeric and UUID segments of a path by named placeholders
+///
+/// Returns the templated path and the detected parameters with their values.
+fn path_template(path: &str) -> (String, Vec<(String, String)>) {
+    let mut parameters: Vec<(String, String)> = Vec::new();
+    let mut previous: Option<&str> = None;
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            if !is_identifier_value(segment) {
+                if !segment.is_empty() {
+                    previous = Some(segment);
+                }
+                return segment.to_string();
+            }
+
+            let base = previous
+                .map(|p| format!("{}Id", camel_case(&singular(p))))
+                .unwrap_or_else(|| "id".to_string());
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while parameters.iter().any(|(n, _)| n == &name) {
+                name = format!("{}{}", base, suffix);
+                suffix += 1;
+            }
+            parameters.push((name.clone(), segment.to_string()));
+            previous = None;
+            format!("{{{}}}", name)
+        })
+        .collect();
+    (segments.join("/"), parameters)
+}
+
+fn is_identifier_value(segment: &str) -> bool {
+    let is_numeric = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+    let is_uuid = segment.len() == 36
+        && segment.chars().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    is_numeric || is_uuid
+}
+
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if word.ends_with("ss") {
+        word.to_string()
+    } else {
+        word.strip_suffix('s').unwrap_or(word).to_string()
+    }
+}
+
+/// `line-items` to `lineItems`
+fn camel_case(word: &str) -> String {
+    let mut result = String::new();
+    for (i, part) in word
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|p| !p.is_empty())
+        .enumerate()
+    {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                result.push(first.to_ascii_lowercase());
+            } else {
+                result.push(first.to_ascii_uppercase());
+            }
+            result.push_str(chars.as_str());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, url: &str, body: Option<&str>, content_type: &str) -> CapturedRequest {
+        CapturedRequest {
+            method: method.to_string(),
+            url: Url::parse(url).unwrap(),
+            headers: vec![
+                ("Content-Type".to_string(), content_type.to_string()),
+                ("User-Agent".to_string(), "Mozilla/5.0".to_string()),
+                ("X-Client".to_string(), "web".to_string()),
+            ],
+            body: body.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_path_templates() {
+        assert_eq!(
+            path_template("/api/users/42/line-items/7"),
+            (
+                "/api/users/{userId}/line-items/{lineItemId}".to_string(),
+                vec![
+                    ("userId".to_string(), "42".to_string()),
+                    ("lineItemId".to_string(), "7".to_string())
+                ]
+            )
+        );
+        assert_eq!(
+            path_template("/categories/5f0c3c9e-2b7a-4c1e-9d0a-1234567890ab").0,
+            "/categories/{categoryId}"
+        );
+        assert_eq!(path_template("/1/2").0, "/{id}/{id2}");
+    }
+
+    #[test]
+    fn test_import_captured_traffic() {
+        let traffic = CapturedTraffic::new(
+            "capture.har",
+            vec![
+                request(
+                    "GET",
+                    "https://api.example.com/users/42?expand=orders",
+                    None,
+                    "application/json",
+                ),
+                request(
+                    "GET",
+                    "https://api.example.com/users/43?expand=plan",
+                    None,
+                    "application/json",
+                ),
+                request(
+                    "OPTIONS",
+                    "https://api.example.com/users/42",
+                    None,
+                    "text/plain",
+                ),
+                request("GET", "https://api.example.com/app.js", None, "text/plain"),
+                request("GET", "https://cdn.example.com/logo", None, "text/plain"),
+                request(
+                    "POST",
+                    "https://api.example.com/users",
+                    Some(r#"{"name": "Ann", "manager": null}"#),
+                    "application/json",
+                ),
+                request(
+                    "POST",
+                    "https://api.example.com/login",
+                    Some("user=ann&remember=1"),
+                    "application/x-www-form-urlencoded",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            traffic.detected_parameters(),
+            vec![DetectedParameter {
+                name: "userId".to_string(),
+                example: "42".to_string()
+            }]
+        );
+
+        let imported = traffic.import(true).unwrap();
+        let config = &imported.config;
+        assert_eq!(
+            config.environments["api-example-com"].base_url,
+            "https://api.example.com"
+        );
+
+        let names: Vec<_> = config.routes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["get_users_userid", "post_users", "post_login"]);
+
+        let get_user = &config.routes[0];
+        assert_eq!(get_user.path, "/users/{userId}");
+        assert_eq!(get_user.params.as_ref().unwrap()["expand"], "orders");
+        let headers = get_user.headers.as_ref().unwrap();
+        assert!(headers.contains_key("X-Client") && !headers.contains_key("User-Agent"));
+
+        // The null is kept by sending the body as captured
+        let post_user = &config.routes[1];
+        assert!(post_user.json.is_none());
+        assert_eq!(
+            post_user.body.as_deref(),
+            Some(r#"{"name": "Ann", "manager": null}"#)
+        );
+        assert_eq!(
+            post_user.headers.as_ref().unwrap()["Content-Type"],
+            "application/json"
+        );
+        assert_eq!(config.routes[2].form.as_ref().unwrap()["remember"], "1");
+
+        assert_eq!(imported.users_csv().unwrap(), "userId\n42\n");
+        assert!(imported.warnings[0].starts_with("Skipped 3 requests"));
+
+        // Without parameterization similar requests with different IDs stay apart
+        let literal = traffic.import(false).unwrap();
+        assert_eq!(literal.config.routes[0].path, "/users/42");
+        assert_eq!(literal.config.routes.len(), 4);
+        assert!(literal.parameters.is_empty());
+    }
+}
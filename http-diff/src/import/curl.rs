@@ -0,0 +1,406 @@
+//! curl command importer
+//!
+//! Reads curl command lines, as copied with "Copy as cURL" from a browser,
+//! into [`CapturedTraffic`]. A file may hold several commands, one per line,
+//! continued over several lines with a trailing `\`, or chained with `;` and
+//! `&&`. Bash quoting is understood, including the `$'...'` strings browsers
+//! use for bodies.
+
+use super::capture::{CapturedRequest, CapturedTraffic};
+use crate::error::{HttpDiffError, Result};
+use std::path::Path;
+use url::Url;
+
+/// Options taking a value that do not affect the imported route
+///
+/// Their values are skipped so that they are not mistaken for the URL.
+const IGNORED_VALUE_OPTIONS: &[&str] = &[
+    "-A",
+    "--user-agent",
+    "-b",
+    "--cookie",
+    "-c",
+    "--cookie-jar",
+    "-D",
+    "--dump-header",
+    "-e",
+    "--referer",
+    "-E",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "--max-redirs",
+    "-o",
+    "--output",
+    "-x",
+    "--proxy",
+    "-U",
+    "--proxy-user",
+    "--noproxy",
+    "--resolve",
+    "--connect-to",
+    "--interface",
+    "--unix-socket",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "--limit-rate",
+    "-Y",
+    "--speed-limit",
+    "-y",
+    "--speed-time",
+    "-w",
+    "--write-out",
+    "--stderr",
+    "--trace",
+    "--trace-ascii",
+    "--cacert",
+    "--capath",
+    "--cert",
+    "--cert-type",
+    "--key",
+    "--key-type",
+    "--ciphers",
+];
+
+/// Imports requests from curl command lines
+pub struct CurlImporter;
+
+impl CurlImporter {
+    /// Read the curl commands of a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<CapturedTraffic> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            HttpDiffError::invalid_config(format!(
+                "Cannot read curl commands {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::parse(&path.display().to_string(), &content)
+    }
+
+    /// Parse curl commands; `source` names them in the generated configuration
+    pub fn parse(source: &str, content: &str) -> Result<CapturedTraffic> {
+        let mut requests = Vec::new();
+        let mut warnings = Vec::new();
+        for (index, words) in split_commands(content)?.into_iter().enumerate() {
+            match parse_command(&words) {
+                Ok(Some(request)) => requests.push(request),
+                Ok(None) => {}
+                Err(message) => warnings.push(format!("Command {}: {}", index + 1, message)),
+            }
+        }
+
+        if requests.is_empty() && warnings.is_empty() {
+            return Err(HttpDiffError::invalid_config(format!(
+                "No curl command found in {}",
+                source
+            )));
+        }
+        Ok(warnings.into_iter().fold(
+            CapturedTraffic::new(format!("curl commands {}", source), requests),
+            CapturedTraffic::with_warning,
+        ))
+    }
+}
+
+/// Build a request from the words of one command, or `None` when it is not a curl command
+fn parse_command(words: &[String]) -> std::result::Result<Option<CapturedRequest>, String> {
+    let mut words = words.iter();
+    if words.next().map(String::as_str) != Some("curl") {
+        return Ok(None);
+    }
+
+    let mut method = None;
+    let mut url = None;
+    let mut headers = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut get = false;
+
+    while let Some(word) = words.next() {
+        let mut value = |name: &str| {
+            words
+                .next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+        match word.as_str() {
+            "-X" | "--request" => method = Some(value(word)?.to_uppercase()),
+            "-H" | "--header" => {
+                let header = value(word)?;
+                if let Some((name, value)) = header.split_once(':') {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
+                data.push(value(word)?)
+            }
+            "--data-urlencode" => {
+                let field = value(word)?;
+                data.push(match field.split_once('=') {
+                    Some((name, content)) => {
+                        format!("{}={}", name, urlencoding::encode(content))
+                    }
+                    None => urlencoding::encode(&field).into_owned(),
+                });
+            }
+            "--json" => {
+                data.push(value(word)?);
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                headers.push(("Accept".to_string(), "application/json".to_string()));
+            }
+            "-F" | "--form" => {
+                return Err("multipart bodies (-F) are not imported".to_string());
+            }
+            "-u" | "--user" | "--oauth2-bearer" => {
+                value(word)?;
+                headers.push((
+                    "Authorization".to_string(),
+                    "{secret:AUTHORIZATION}".to_string(),
+                ));
+            }
+            "-G" | "--get" => get = true,
+            "--url" => {
+                let value = value(word)?;
+                url.get_or_insert(value);
+            }
+            option if IGNORED_VALUE_OPTIONS.contains(&option) => {
+                value(word)?;
+            }
+            option if option.starts_with('-') => {}
+            // The first bare word is the URL; later ones are values of
+            // options that are not known
+            _ => {
+                url.get_or_insert_with(|| word.clone());
+            }
+        }
+    }
+
+    let url = url.ok_or("no URL")?;
+    let mut url = Url::parse(&url).map_err(|e| format!("invalid URL '{}': {}", url, e))?;
+    let mut body = (!data.is_empty()).then(|| data.join("&"));
+
+    if get {
+        if let Some(query) = body.take() {
+            let existing = url.query().map(str::to_string);
+            url.set_query(Some(&match existing {
+                Some(existing) => format!("{}&{}", existing, query),
+                None => query,
+            }));
+        }
+    }
+
+    let has_content_type = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+    if body.is_some() && !has_content_type {
+        // curl sends -d data as a form unless told otherwise
+        headers.push((
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        ));
+    }
+
+    let method = method.unwrap_or_else(|| if body.is_some() { "POST" } else { "GET" }.to_string());
+    Ok(Some(CapturedRequest {
+        method,
+        url,
+        headers,
+        body,
+    }))
+}
+
+/// Split text into commands made of shell words
+///
+/// Unquoted newlines, `;` and `&&` end a command; a backslash before a
+/// newline continues it.
+fn split_commands(content: &str) -> Result<Vec<Vec<String>>> {
+    let mut commands = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = content.chars().peekable();
+
+    let unterminated = || HttpDiffError::invalid_config("Unterminated quote in curl command");
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next().ok_or_else(unterminated)? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                loop {
+                    match chars.next().ok_or_else(unterminated)? {
+                        '\'' => break,
+                        '\\' => match chars.next().ok_or_else(unterminated)? {
+                            'n' => word.push('\n'),
+                            't' => word.push('\t'),
+                            'r' => word.push('\r'),
+                            'u' | 'x' => {
+                                let digits: String =
+                                    std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_hexdigit()))
+                                        .take(4)
+                                        .collect();
+                                if let Some(c) = u32::from_str_radix(&digits, 16)
+                                    .ok()
+                                    .and_then(char::from_u32)
+                                {
+                                    word.push(c);
+                                }
+                            }
+                            other => word.push(other),
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next().ok_or_else(unterminated)? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or_else(unterminated)? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            '\n' => {}
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+                None => {}
+            },
+            '\n' | ';' => {
+                finish_word(&mut words, &mut word, &mut in_word);
+                finish_command(&mut commands, &mut words);
+            }
+            '&' if chars.peek() == Some(&'&') => {
+                chars.next();
+                finish_word(&mut words, &mut word, &mut in_word);
+                finish_command(&mut commands, &mut words);
+            }
+            c if c.is_whitespace() => finish_word(&mut words, &mut word, &mut in_word),
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    finish_word(&mut words, &mut word, &mut in_word);
+    finish_command(&mut commands, &mut words);
+    Ok(commands)
+}
+
+fn finish_word(words: &mut Vec<String>, word: &mut String, in_word: &mut bool) {
+    if *in_word {
+        words.push(std::mem::take(word));
+        *in_word = false;
+    }
+}
+
+fn finish_command(commands: &mut Vec<Vec<String>>, words: &mut Vec<String>) {
+    if !words.is_empty() {
+        commands.push(std::mem::take(words));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_copied_curl_commands() {
+        let commands = r#"
+curl 'https://api.example.com/users/42' \
+  -H 'accept: application/json' \
+  -H 'authorization: Bearer abc' \
+  --compressed
+curl 'https://api.example.com/users' -H 'content-type: application/json' --data-raw $'{"name":"O\'Brien"}'
+curl -G https://api.example.com/search -d q=ann && curl -X DELETE "https://api.example.com/users/42"
+curl --resolve api.example.com:443:127.0.0.1 https://api.example.com/teams --unknown-option value; curl --oauth2-bearer abc https://api.example.com/me
+"#;
+
+        let traffic = CurlImporter::parse("requests.sh", commands).unwrap();
+        let requests = traffic.requests();
+        assert_eq!(requests.len(), 6);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(requests[1].body.as_deref(), Some(r#"{"name":"O'Brien"}"#));
+        assert_eq!(requests[2].url.query(), Some("q=ann"));
+        assert!(requests[2].body.is_none());
+        assert_eq!(requests[3].method, "DELETE");
+        assert_eq!(requests[4].url.path(), "/teams");
+        assert_eq!(
+            requests[5].headers,
+            vec![(
+                "Authorization".to_string(),
+                "{secret:AUTHORIZATION}".to_string()
+            )]
+        );
+
+        let imported = traffic.import(true).unwrap();
+        let names: Vec<_> = imported
+            .config
+            .routes
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "get_users_userid",
+                "post_users",
+                "get_search",
+                "delete_users_userid",
+                "get_teams",
+                "get_me"
+            ]
+        );
+        let headers = imported.config.routes[0].headers.as_ref().unwrap();
+        assert_eq!(headers["authorization"], "{secret:AUTHORIZATION}");
+        assert_eq!(
+            imported.config.routes[1].json,
+            Some(serde_json::json!({"name": "O'Brien"}))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_commands_become_warnings() {
+        let traffic = CurlImporter::parse(
+            "requests.sh",
+            "curl -F file=@a.txt https://api.example.com/upload\ncurl https://api.example.com/ok",
+        )
+        .unwrap();
+        assert_eq!(traffic.requests().len(), 1);
+
+        let imported = traffic.import(true).unwrap();
+        assert_eq!(
+            imported.warnings,
+            vec!["Command 1: multipart bodies (-F) are not imported"]
+        );
+
+        assert!(CurlImporter::parse("empty.sh", "echo hello").is_err());
+        assert!(CurlImporter::parse("bad.sh", "curl 'https://x").is_err());
+    }
+}
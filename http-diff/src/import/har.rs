@@ -0,0 +1,157 @@
+//! HAR importer
+//!
+//! Reads the requests of a HAR 1.2 file, as exported by the network panel of
+//! a browser, into [`CapturedTraffic`].
+
+use super::capture::{CapturedRequest, CapturedTraffic};
+use crate::error::{HttpDiffError, Result};
+use serde::Deserialize;
+use std::path::Path;
+use url::Url;
+
+#[derive(Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Deserialize)]
+struct HarLog {
+    #[serde(default)]
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Deserialize)]
+struct HarEntry {
+    request: HarRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<HarPair>,
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Deserialize)]
+struct HarPair {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarPostData {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    params: Vec<HarPair>,
+}
+
+/// Imports requests from HAR files
+pub struct HarImporter;
+
+impl HarImporter {
+    /// Read the requests of a HAR file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<CapturedTraffic> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            HttpDiffError::invalid_config(format!("Cannot read HAR file {}: {}", path.display(), e))
+        })?;
+        Self::parse(&path.display().to_string(), &content)
+    }
+
+    /// Parse a HAR document; `source` names it in the generated configuration
+    pub fn parse(source: &str, content: &str) -> Result<CapturedTraffic> {
+        let har: Har = serde_json::from_str(content).map_err(|e| {
+            HttpDiffError::invalid_config(format!("Failed to parse HAR file {}: {}", source, e))
+        })?;
+
+        let mut requests = Vec::new();
+        let mut invalid = 0;
+        for entry in har.log.entries {
+            let request = entry.request;
+            let Ok(url) = Url::parse(&request.url) else {
+                invalid += 1;
+                continue;
+            };
+            let body = request.post_data.and_then(|post_data| {
+                post_data.text.filter(|text| !text.is_empty()).or_else(|| {
+                    (!post_data.params.is_empty()).then(|| {
+                        url::form_urlencoded::Serializer::new(String::new())
+                            .extend_pairs(post_data.params.iter().map(|p| (&p.name, &p.value)))
+                            .finish()
+                    })
+                })
+            });
+            requests.push(CapturedRequest {
+                method: request.method.to_uppercase(),
+                url,
+                headers: request
+                    .headers
+                    .into_iter()
+                    .map(|header| (header.name, header.value))
+                    .collect(),
+                body,
+            });
+        }
+
+        let traffic = CapturedTraffic::new(format!("HAR file {}", source), requests);
+        Ok(if invalid > 0 {
+            traffic.with_warning(format!("Skipped {} entries with an invalid URL", invalid))
+        } else {
+            traffic
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_har_entries() {
+        let har = r#"{
+            "log": {
+                "version": "1.2",
+                "entries": [
+                    {
+                        "request": {
+                            "method": "GET",
+                            "url": "https://api.example.com/orders/17",
+                            "headers": [{"name": ":authority", "value": "api.example.com"}]
+                        }
+                    },
+                    {
+                        "request": {
+                            "method": "post",
+                            "url": "https://api.example.com/login",
+                            "headers": [{"name": "Content-Type", "value": "application/x-www-form-urlencoded"}],
+                            "postData": {
+                                "mimeType": "application/x-www-form-urlencoded",
+                                "params": [{"name": "user", "value": "ann"}]
+                            }
+                        }
+                    },
+                    {"request": {"method": "GET", "url": "not a url"}}
+                ]
+            }
+        }"#;
+
+        let traffic = HarImporter::parse("capture.har", har).unwrap();
+        assert_eq!(traffic.requests().len(), 2);
+        assert_eq!(traffic.requests()[1].method, "POST");
+        assert_eq!(traffic.requests()[1].body.as_deref(), Some("user=ann"));
+
+        let imported = traffic.import(true).unwrap();
+        assert_eq!(imported.config.routes[0].path, "/orders/{orderId}");
+        assert!(imported.config.routes[0].headers.is_none());
+        assert_eq!(
+            imported.warnings,
+            vec!["Skipped 1 entries with an invalid URL"]
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Importers generating configuration from API descriptions, collections and captured traffic
+//!
+//! Every importer produces an [`ImportedConfig`]: a configuration assembled
+//! with the [`HttpDiffConfigBuilder`], the user data columns its routes
+//! reference, and warnings about parts of the source that were skipped.
+//! HAR files and curl commands are first read into [`CapturedTraffic`].
+
+pub mod capture;
+pub mod curl;
+pub mod har;
+pub mod openapi;
+pub mod postman;
+
+pub use capture::{CapturedRequest, CapturedTraffic, DetectedParameter};
+pub use curl::CurlImporter;
+pub use har::HarImporter;
+pub use openapi::OpenApiImporter;
+pub use postman::{PostmanExport, PostmanExporter, PostmanImporter};
+
+use crate::config::{
+    AuthConfig, Environment, GlobalConfig, HttpDiffConfig, HttpDiffConfigBuilder, Route,
+};
+use crate::error::{HttpDiffError, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Configuration generated by an importer
+#[derive(Debug, Clone)]
+pub struct ImportedConfig {
+    /// Description of what the configuration was generated from
+    pub source: String,
+    pub config: HttpDiffConfig,
+    /// User data columns referenced by the routes, with an example value
+    pub parameters: Vec<(String, String)>,
+    /// Parts of the source that could not be imported
+    pub warnings: Vec<String>,
+}
+
+/// Configuration document written with environments sorted by name
+#[derive(Serialize)]
+struct ConfigDocument<'a> {
+    environments: BTreeMap<&'a String, &'a Environment>,
+    global: &'a Option<GlobalConfig>,
+    routes: &'a [Route],
+}
+
+impl ImportedConfig {
+    /// Render the configuration as an `http-diff.toml` document
+    pub fn to_toml(&self) -> Result<String> {
+        let document = ConfigDocument {
+            environments: self.config.environments.iter().collect(),
+            global: &self.config.global,
+            routes: &self.config.routes,
+        };
+        let content = toml::to_string_pretty(&document).map_err(|e| {
+            HttpDiffError::general(format!("Failed to serialize configuration: {}", e))
+        })?;
+        Ok(format!("# Generated from {}\n\n{}", self.source, content))
+    }
+
+    /// Render a `users.csv` skeleton with one column per parameter and a row of examples
+    pub fn users_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(self.parameters.iter().map(|(name, _)| name))?;
+        writer.write_record(self.parameters.iter().map(|(_, example)| example))?;
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| HttpDiffError::general(format!("Failed to write users.csv: {}", e)))?;
+        String::from_utf8(bytes)
+            .map_err(|e| HttpDiffError::general(format!("Failed to write users.csv: {}", e)))
+    }
+}
+
+/// Collects the routes and parameters of an import
+///
+/// Route names are made unique and every parameter is listed once, in the
+/// order it was first seen.
+pub(crate) struct ImportBuilder {
+    source: String,
+    builder: HttpDiffConfigBuilder,
+    route_names: HashSet<String>,
+    parameters: Vec<(String, String)>,
+    warnings: Vec<String>,
+}
+
+impl ImportBuilder {
+    pub(crate) fn new<S: Into<String>>(source: S) -> Self {
+        Self {
+            source: source.into(),
+            builder: HttpDiffConfigBuilder::new(),
+            route_names: HashSet::new(),
+            parameters: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn environment(&mut self, name: &str, base_url: &str) {
+        self.environment_with(name, base_url, None, None);
+    }
+
+    /// Add an environment sending headers or authenticating its requests
+    pub(crate) fn environment_with(
+        &mut self,
+        name: &str,
+        base_url: &str,
+        headers: Option<HashMap<String, String>>,
+        auth: Option<AuthConfig>,
+    ) {
+        let mut builder = std::mem::take(&mut self.builder).environment(name, base_url, headers);
+        if let Some(auth) = auth {
+            builder = builder.auth(name, auth);
+        }
+        self.builder = builder;
+    }
+
+    /// Add a route, renaming it when the name is already taken
+    pub(crate) fn route(&mut self, mut route: Route) {
+        let base = route.name.clone();
+        let mut suffix = 2;
+        while !self.route_names.insert(route.name.clone()) {
+            route.name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        let builder = std::mem::take(&mut self.builder);
+        self.builder = builder.route(route);
+    }
+
+    /// Add a user data column, keeping the first example seen
+    pub(crate) fn parameter(&mut self, name: &str, example: Option<String>) {
+        match self.parameters.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) if existing.is_empty() => {
+                *existing = example.unwrap_or_default();
+            }
+            Some(_) => {}
+            None => self
+                .parameters
+                .push((name.to_string(), example.unwrap_or_default())),
+        }
+    }
+
+    pub(crate) fn warn<S: Into<String>>(&mut self, warning: S) {
+        self.warnings.push(warning.into());
+    }
+
+    pub(crate) fn build(self) -> Result<ImportedConfig> {
+        Ok(ImportedConfig {
+            source: self.source,
+            config: self.builder.build()?,
+            parameters: self.parameters,
+            warnings: self.warnings,
+        })
+    }
+}
+
+/// A route without headers, parameters or body
+pub(crate) fn new_route(name: String, method: String, path: String) -> Route {
+    Route {
+        name,
+        method,
+        path,
+        headers: None,
+        params: None,
+        base_urls: None,
+        body: None,
+        conditions: None,
+        extract: None,
+        depends_on: None,
+        wait_for_extraction: None,
+        ignore_fields: None,
+        compare: None,
+        assertions: None,
+        retry: None,
+        body_file: None,
+        json: None,
+        form: None,
+        multipart: None,
+        group: None,
+        base_dir: None,
+    }
+}
+
+/// Route name derived from a method and path, e.g. `get_users_userid`
+pub(crate) fn route_name_from(method: &str, path: &str) -> String {
+    let mut name = method.to_lowercase();
+    for segment in path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+    {
+        name.push('_');
+        name.push_str(&segment.to_lowercase());
+    }
+    name
+}
+
+/// Environment name derived from a free-form label, e.g. `Production API` to `production-api`
+pub(crate) fn environment_name_from(label: &str) -> String {
+    label
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Names of the `{param}` placeholders in a path, in order
+pub(crate) fn path_parameters(path: &str) -> Vec<String> {
+    let mut parameters = Vec::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + end];
+        if !name.is_empty() && !parameters.iter().any(|p| p == name) {
+            parameters.push(name.to_string());
+        }
+        rest = &rest[start + end + 1..];
+    }
+    parameters
+}
+
+/// User data column for a parameter name, usable as a `{placeholder}`
+///
+/// Characters placeholders do not accept, such as `-` in `X-Tenant-Id`, become `_`.
+pub(crate) fn column_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Secret name for a variable name, e.g. `apiToken` to `API_TOKEN`
+pub(crate) fn secret_name(name: &str) -> String {
+    let mut secret = String::with_capacity(name.len() + 4);
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            previous = None;
+            continue;
+        }
+        let word_start = match previous {
+            None => !secret.is_empty(),
+            Some(p) => c.is_ascii_uppercase() && (p.is_ascii_lowercase() || p.is_ascii_digit()),
+        };
+        if word_start {
+            secret.push('_');
+        }
+        secret.push(c.to_ascii_uppercase());
+        previous = Some(c);
+    }
+    secret
+}
+
+/// Whether a JSON value holds a `null`, which TOML cannot represent
+///
+/// Such bodies are imported as `body` text rather than a `json` table so
+/// that their `null` values are still sent.
+pub(crate) fn contains_null(value: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.iter().any(contains_null),
+        Value::Object(map) => map.values().any(contains_null),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_builder_dedupes_routes_and_parameters() {
+        let mut import = ImportBuilder::new("test");
+        import.environment("prod", "https://api.example.com");
+        for _ in 0..2 {
+            import.route(new_route(
+                route_name_from("GET", "/users/{userId}"),
+                "GET".to_string(),
+                "/users/{userId}".to_string(),
+            ));
+        }
+        import.parameter("userId", None);
+        import.parameter("userId", Some("42".to_string()));
+
+        let imported = import.build().unwrap();
+        let names: Vec<_> = imported.config.routes.iter().map(|r| &r.name).collect();
+        assert_eq!(names, vec!["get_users_userid", "get_users_userid_2"]);
+        assert_eq!(imported.users_csv().unwrap(), "userId\n42\n");
+        assert!(imported
+            .to_toml()
+            .unwrap()
+            .starts_with("# Generated from test\n"));
+        assert_eq!(environment_name_from("Production API"), "production-api");
+        assert_eq!(path_parameters("/a/{x}/b/{y}/{x}"), vec!["x", "y"]);
+        assert_eq!(column_name("X-Tenant.Id"), "X_Tenant_Id");
+        assert_eq!(secret_name("apiToken"), "API_TOKEN");
+        assert_eq!(secret_name("production-api"), "PRODUCTION_API");
+        assert!(contains_null(&serde_json::json!({"a": [1, {"b": null}]})));
+        assert!(!contains_null(&serde_json::json!({"a": [1, {"b": 2}]})));
+    }
+}
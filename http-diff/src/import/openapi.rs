@@ -0,0 +1,578 @@
+//! OpenAPI 3 importer
+//!
+//! Generates one route per operation of an OpenAPI 3 document (JSON or YAML):
+//!
+//! - the route name is the `operationId`, or is derived from the method and path
+//! - path parameters keep their `{param}` placeholders
+//! - required query and header parameters become `{param}` placeholders
+//! - the request body comes from the first example of a JSON or form media type,
+//!   or is generated from its schema
+//!
+//! Every placeholder becomes a column of the `users.csv` skeleton, and every
+//! server becomes an environment. Only local `$ref`s (`#/components/...`) are
+//! followed.
+
+use super::{
+    column_name, contains_null, environment_name_from, new_route, path_parameters, route_name_from,
+    ImportBuilder, ImportedConfig,
+};
+use crate::error::{HttpDiffError, Result};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// HTTP methods of an OpenAPI path item, in the order routes are generated
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Maximum depth followed when resolving `$ref`s and generating schema examples
+const MAX_DEPTH: usize = 16;
+
+/// Imports routes from an OpenAPI 3 document
+#[derive(Debug, Clone)]
+pub struct OpenApiImporter {
+    source: String,
+    document: Value,
+}
+
+impl OpenApiImporter {
+    /// Read an OpenAPI document from a JSON or YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            HttpDiffError::invalid_config(format!(
+                "Cannot read OpenAPI document {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::parse(&path.display().to_string(), &content)
+    }
+
+    /// Parse an OpenAPI document; `source` names it in the generated configuration
+    pub fn parse(source: &str, content: &str) -> Result<Self> {
+        // YAML is a superset of JSON, so one parser reads both
+        let document: Value = serde_yaml::from_str(content).map_err(|e| {
+            HttpDiffError::invalid_config(format!(
+                "Failed to parse OpenAPI document {}: {}",
+                source, e
+            ))
+        })?;
+
+        let version = document
+            .get("openapi")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if !version.starts_with("3.") {
+            return Err(HttpDiffError::invalid_config(format!(
+                "{} is not an OpenAPI 3 document (openapi: '{}')",
+                source, version
+            )));
+        }
+
+        Ok(Self {
+            source: format!("OpenAPI document {}", source),
+            document,
+        })
+    }
+
+    /// Generate the configuration
+    pub fn import(&self) -> Result<ImportedConfig> {
+        let mut import = ImportBuilder::new(self.source.clone());
+        self.import_servers(&mut import);
+
+        let paths = self
+            .document
+            .get("paths")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        for (path, item) in &paths {
+            let item = self.resolve(item);
+            let shared_parameters = self.parameters(item);
+            for method in METHODS {
+                if let Some(operation) = item.get(*method) {
+                    self.import_operation(&mut import, path, method, operation, &shared_parameters);
+                }
+            }
+        }
+
+        import.build()
+    }
+
+    fn import_servers(&self, import: &mut ImportBuilder) {
+        let servers = self
+            .document
+            .get("servers")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let mut names: Vec<String> = Vec::new();
+
+        for (index, server) in servers.iter().enumerate() {
+            let Some(url) = server.get("url").and_then(Value::as_str) else {
+                continue;
+            };
+            let mut url = url.to_string();
+            if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+                for (name, variable) in variables {
+                    if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                        url = url.replace(&format!("{{{}}}", name), default);
+                    }
+                }
+            }
+            if url.starts_with('/') {
+                import.warn(format!(
+                    "Server '{}' has a relative URL; edit the base_url of its environment",
+                    url
+                ));
+                url = format!("http://localhost{}", url);
+            }
+
+            let mut name = server
+                .get("description")
+                .and_then(Value::as_str)
+                .map(environment_name_from)
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("server{}", index + 1));
+            if names.contains(&name) {
+                name = format!("{}-{}", name, index + 1);
+            }
+            import.environment(&name, url.trim_end_matches('/'));
+            names.push(name);
+        }
+
+        if names.is_empty() {
+            import.warn(
+                "The document declares no servers; edit the base_url of the 'local' environment",
+            );
+            import.environment("local", "http://localhost");
+        }
+    }
+
+    fn import_operation(
+        &self,
+        import: &mut ImportBuilder,
+        path: &str,
+        method: &str,
+        operation: &Value,
+        shared_parameters: &[Value],
+    ) {
+        let name = operation
+            .get("operationId")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| route_name_from(method, path));
+        let mut route = new_route(name, method.to_uppercase(), path.to_string());
+
+        // Operation parameters override path item parameters with the same name and location
+        let mut parameters: Vec<Value> = shared_parameters.to_vec();
+        for parameter in self.parameters(operation) {
+            parameters.retain(|p| !same_parameter(p, &parameter));
+            parameters.push(parameter);
+        }
+
+        let path_names = path_parameters(path);
+        let mut headers = HashMap::new();
+        let mut params = HashMap::new();
+        for name in &path_names {
+            let example = parameters
+                .iter()
+                .find(|p| str_field(p, "name") == Some(name) && str_field(p, "in") == Some("path"))
+                .and_then(|p| self.parameter_example(p));
+            let column = column_name(name);
+            route.path = route
+                .path
+                .replace(&format!("{{{}}}", name), &format!("{{{}}}", column));
+            import.parameter(&column, example);
+        }
+        for parameter in &parameters {
+            let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+            let (Some(name), Some(location)) =
+                (str_field(parameter, "name"), str_field(parameter, "in"))
+            else {
+                continue;
+            };
+            if !required {
+                continue;
+            }
+            let target = match location {
+                "query" => &mut params,
+                "header" => &mut headers,
+                _ => continue,
+            };
+            let column = column_name(name);
+            target.insert(name.to_string(), format!("{{{}}}", column));
+            import.parameter(&column, self.parameter_example(parameter));
+        }
+
+        if let Some(request_body) = operation.get("requestBody") {
+            self.import_body(import, &mut route, &mut headers, request_body);
+        }
+
+        route.headers = (!headers.is_empty()).then_some(headers);
+        route.params = (!params.is_empty()).then_some(params);
+        import.route(route);
+    }
+
+    fn import_body(
+        &self,
+        import: &mut ImportBuilder,
+        route: &mut crate::config::Route,
+        headers: &mut HashMap<String, String>,
+        request_body: &Value,
+    ) {
+        let Some(content) = self
+            .resolve(request_body)
+            .get("content")
+            .and_then(Value::as_object)
+        else {
+            return;
+        };
+
+        let json = content
+            .iter()
+            .find(|(media_type, _)| is_json_media_type(media_type));
+        if let Some((media_type, media)) = json {
+            match self.media_example(media) {
+                None | Some(Value::Null) => {}
+                Some(example) if contains_null(&example) => {
+                    headers.insert("Content-Type".to_string(), media_type.clone());
+                    route.body = serde_json::to_string_pretty(&example).ok();
+                }
+                example => route.json = example,
+            }
+            return;
+        }
+
+        if let Some(media) = content.get("application/x-www-form-urlencoded") {
+            if let Some(Value::Object(fields)) = self.media_example(media) {
+                let form: BTreeMap<String, String> = fields
+                    .into_iter()
+                    .filter_map(|(key, value)| scalar_to_string(&value).map(|v| (key, v)))
+                    .collect();
+                route.form = Some(form);
+            }
+            return;
+        }
+
+        match content
+            .iter()
+            .find_map(|(media_type, media)| match self.media_example(media) {
+                Some(Value::String(text)) => Some((media_type, text)),
+                _ => None,
+            }) {
+            Some((media_type, text)) => {
+                headers.insert("Content-Type".to_string(), media_type.clone());
+                route.body = Some(text);
+            }
+            None => import.warn(format!(
+                "Route '{}': no example for request body of type {}; add the body by hand",
+                route.name,
+                content.keys().cloned().collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+
+    /// Resolved parameters of a path item or operation
+    fn parameters(&self, item: &Value) -> Vec<Value> {
+        item.get("parameters")
+            .and_then(Value::as_array)
+            .map(|parameters| parameters.iter().map(|p| self.resolve(p).clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn parameter_example(&self, parameter: &Value) -> Option<String> {
+        let example = parameter
+            .get("example")
+            .cloned()
+            .or_else(|| first_example(self, parameter))
+            .or_else(|| {
+                let schema = self.resolve(parameter.get("schema")?);
+                schema
+                    .get("example")
+                    .or_else(|| schema.get("default"))
+                    .or_else(|| schema.get("enum").and_then(|e| e.get(0)))
+                    .cloned()
+            })?;
+        scalar_to_string(&example)
+    }
+
+    fn media_example(&self, media: &Value) -> Option<Value> {
+        media
+            .get("example")
+            .cloned()
+            .or_else(|| first_example(self, media))
+            .or_else(|| Some(self.schema_example(media.get("schema")?, 0)))
+    }
+
+    /// Example value generated from a schema
+    fn schema_example(&self, schema: &Value, depth: usize) -> Value {
+        let schema = self.resolve(schema);
+        if depth > MAX_DEPTH {
+            return Value::Null;
+        }
+        if let Some(example) = schema.get("example").or_else(|| schema.get("default")) {
+            return example.clone();
+        }
+        if let Some(first) = schema.get("enum").and_then(|e| e.get(0)) {
+            return first.clone();
+        }
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for part in all_of {
+                if let Value::Object(fields) = self.schema_example(part, depth + 1) {
+                    merged.extend(fields);
+                }
+            }
+            return Value::Object(merged);
+        }
+        if let Some(first) = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(*key).and_then(|v| v.get(0)))
+        {
+            return self.schema_example(first, depth + 1);
+        }
+
+        let schema_type = match schema.get("type") {
+            Some(Value::String(t)) => t.as_str(),
+            // OpenAPI 3.1 allows a list of types, e.g. ["string", "null"]
+            Some(Value::Array(types)) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|t| *t != "null")
+                .unwrap_or("null"),
+            _ if schema.get("properties").is_some() => "object",
+            _ => "",
+        };
+        match schema_type {
+            "object" => Value::Object(
+                schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .map(|properties| {
+                        properties
+                            .iter()
+                            .map(|(name, property)| {
+                                (name.clone(), self.schema_example(property, depth + 1))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ),
+            "array" => match schema.get("items") {
+                Some(items) => Value::Array(vec![self.schema_example(items, depth + 1)]),
+                None => Value::Array(Vec::new()),
+            },
+            "string" => Value::String(
+                match schema.get("format").and_then(Value::as_str) {
+                    Some("date-time") => "2024-01-01T00:00:00Z",
+                    Some("date") => "2024-01-01",
+                    Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                    Some("email") => "user@example.com",
+                    Some("uri") => "https://example.com",
+                    _ => "string",
+                }
+                .to_string(),
+            ),
+            "integer" | "number" => Value::from(0),
+            "boolean" => Value::Bool(false),
+            _ => Value::Null,
+        }
+    }
+
+    /// Follow local `$ref`s (`#/components/...`) to the referenced value
+    fn resolve<'a>(&'a self, mut value: &'a Value) -> &'a Value {
+        for _ in 0..MAX_DEPTH {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                break;
+            };
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.document.pointer(pointer))
+            {
+                Some(target) => value = target,
+                None => break,
+            }
+        }
+        value
+    }
+}
+
+/// Value of the first entry of an `examples` map
+fn first_example(importer: &OpenApiImporter, item: &Value) -> Option<Value> {
+    let (_, example) = item.get("examples")?.as_object()?.iter().next()?;
+    importer.resolve(example).get("value").cloned()
+}
+
+fn str_field<'a>(value: &'a Value, field: &str) -> Option<&'a str> {
+    value.get(field).and_then(Value::as_str)
+}
+
+fn same_parameter(a: &Value, b: &Value) -> bool {
+    str_field(a, "name") == str_field(b, "name") && str_field(a, "in") == str_field(b, "in")
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"
+openapi: 3.0.3
+info:
+  title: Users
+  version: "1.0"
+servers:
+  - url: https://api.example.com/{version}
+    description: Production
+    variables:
+      version:
+        default: v1
+  - url: https://staging.example.com/v1
+    description: Staging
+paths:
+  /users/{userId}:
+    parameters:
+      - $ref: "#/components/parameters/UserId"
+    get:
+      operationId: getUser
+      parameters:
+        - name: X-Tenant-Id
+          in: header
+          required: true
+          schema:
+            type: string
+            example: acme
+        - name: expand
+          in: query
+          schema:
+            type: string
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/User"
+  /teams:
+    post:
+      requestBody:
+        content:
+          application/json:
+            example:
+              name: core
+              lead: null
+  /sessions:
+    post:
+      operationId: login
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            example:
+              username: ann
+              remember: true
+components:
+  parameters:
+    UserId:
+      name: userId
+      in: path
+      required: true
+      schema:
+        type: integer
+        example: 42
+  schemas:
+    User:
+      type: object
+      properties:
+        name:
+          type: string
+          example: Ann
+        tags:
+          type: array
+          items:
+            type: string
+        created:
+          type: string
+          format: date-time
+          nullable: true
+"##;
+
+    #[test]
+    fn test_import_openapi_document() {
+        let imported = OpenApiImporter::parse("users.yaml", SPEC)
+            .unwrap()
+            .import()
+            .unwrap();
+        let config = &imported.config;
+
+        assert_eq!(
+            config.environments["production"].base_url,
+            "https://api.example.com/v1"
+        );
+        assert!(config.environments.contains_key("staging"));
+
+        let names: Vec<_> = config.routes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["login", "post_teams", "getUser", "put_users_userid"]
+        );
+
+        // Examples holding null are kept as text, as TOML has no null
+        let post_team = &config.routes[1];
+        assert!(post_team.json.is_none());
+        let body: Value = serde_json::from_str(post_team.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({"name": "core", "lead": null}));
+        assert_eq!(
+            post_team.headers.as_ref().unwrap()["Content-Type"],
+            "application/json"
+        );
+
+        let get_user = &config.routes[2];
+        assert_eq!(get_user.path, "/users/{userId}");
+        assert_eq!(
+            get_user.headers.as_ref().unwrap()["X-Tenant-Id"],
+            "{X_Tenant_Id}"
+        );
+        // Optional query parameters are left out
+        assert!(get_user.params.is_none());
+
+        let put_user = &config.routes[3];
+        assert_eq!(
+            put_user.json,
+            Some(serde_json::json!({
+                "name": "Ann",
+                "tags": ["string"],
+                "created": "2024-01-01T00:00:00Z"
+            }))
+        );
+
+        let login = &config.routes[0];
+        assert_eq!(login.form.as_ref().unwrap()["remember"], "true");
+
+        assert_eq!(
+            imported.users_csv().unwrap(),
+            "userId,X_Tenant_Id\n42,acme\n"
+        );
+        assert!(imported.to_toml().unwrap().contains("[[routes]]"));
+    }
+
+    #[test]
+    fn test_rejects_swagger_2_documents() {
+        let err = OpenApiImporter::parse("old.json", r#"{"swagger": "2.0"}"#).unwrap_err();
+        assert!(err.to_string().contains("not an OpenAPI 3 document"));
+    }
+}
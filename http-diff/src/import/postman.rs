@@ -0,0 +1,2033 @@
+//! Postman collection import and export
+//!
+//! Converts between Postman v2.1 collections and environments and
+//! [`HttpDiffConfig`]:
+//!
+//! - collection folders become route groups, and groups become folders again
+//! - Postman environments become environments; the variable the request URLs
+//!   start with (`{{baseUrl}}`) gives their `base_url`, and collection
+//!   authentication gives their headers
+//! - `{{var}}` becomes `{var}`; variables that are not set by a script become
+//!   `users.csv` columns
+//! - `pm.environment.set(...)` calls in test scripts become extraction rules
+//!   when the value is read from the response in a recognizable way
+//! - tokens, API keys and basic authentication credentials become
+//!   `{secret:NAME}` placeholders named after the variable holding them
+
+use super::{
+    column_name, contains_null, new_route, route_name_from, secret_name, ImportBuilder,
+    ImportedConfig,
+};
+use crate::config::{
+    ApiKeyLocation, AuthConfig, ExtractorType, HttpDiffConfig, MultipartPart, Route,
+    ValueExtractionRule,
+};
+use crate::error::{HttpDiffError, Result};
+use crate::template::{map_placeholders, Expression};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+const SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Variable holding the environment base URL in exported collections
+const BASE_URL_VARIABLE: &str = "baseUrl";
+
+/// Separator between nested folder names in a route group
+const GROUP_SEPARATOR: &str = " / ";
+
+/// A test script line setting a variable, capturing its name and value expression
+static SETTER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^\s*(?:pm\.(?:environment|collectionVariables|globals|variables)\.set|postman\.set(?:Environment|Global)Variable)\(\s*["']([^"']+)["']\s*,\s*(.+?)\s*\)\s*;?\s*$"#,
+    )
+    .expect("valid setter pattern")
+});
+
+/// A test script line naming the parsed response body, e.g. `const data = pm.response.json();`
+static ALIAS_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:var|let|const)\s+(\w+)\s*=\s*(?:pm\.response\.json\(\)|JSON\.parse\(\s*responseBody\s*\))\s*;?\s*$",
+    )
+    .expect("valid alias pattern")
+});
+
+/// Property and index accesses following the response body, e.g. `.items[0]["id"]`
+static ACCESSOR_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^((\.[A-Za-z_$][\w$]*)|(\[\d+\])|(\[["'][^"']*["']\]))*$"#)
+        .expect("valid accessor pattern")
+});
+
+/// A JSONPath made of property and index accesses only, which scripts can express
+static SIMPLE_JSON_PATH_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\$((\.[A-Za-z_$][\w$]*)|(\[\d+\])|(\['[^']*'\]))*$"#)
+        .expect("valid JSONPath pattern")
+});
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Collection {
+    info: Info,
+    #[serde(default)]
+    item: Vec<Item>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variable: Vec<Variable>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<Auth>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Info {
+    name: String,
+    #[serde(default)]
+    schema: String,
+}
+
+/// A request, or a folder when `item` is set
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Item {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    item: Option<Vec<Item>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<RequestDefinition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    event: Vec<Event>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<Auth>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum RequestDefinition {
+    Url(String),
+    Request(Box<Request>),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Request {
+    #[serde(default = "default_method")]
+    method: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    header: Vec<Pair>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<UrlDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Body>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<Auth>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum UrlDefinition {
+    Raw(String),
+    Url(Url),
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct Url {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    host: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    query: Vec<Pair>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variable: Vec<Variable>,
+}
+
+/// Header, query parameter or body field
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct Pair {
+    key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    src: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Variable {
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+}
+
+impl Variable {
+    fn new(key: &str, value: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            value: Value::String(value.to_string()),
+            enabled: Some(true),
+            kind: Some("default".to_string()),
+        }
+    }
+
+    fn value(&self) -> String {
+        json_to_text(&self.value)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct Body {
+    mode: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    urlencoded: Vec<Pair>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    formdata: Vec<Pair>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<BodyFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    graphql: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    options: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct BodyFile {
+    #[serde(default)]
+    src: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Event {
+    listen: String,
+    script: Script,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Script {
+    #[serde(default)]
+    exec: Value,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+}
+
+impl Script {
+    fn lines(&self) -> Vec<String> {
+        match &self.exec {
+            Value::String(text) => text.lines().map(str::to_string).collect(),
+            Value::Array(lines) => lines.iter().map(json_to_text).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Authentication; the attributes are listed under a key named after the type
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Auth {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(flatten)]
+    attributes: BTreeMap<String, Value>,
+}
+
+impl Auth {
+    fn attribute(&self, key: &str) -> Option<String> {
+        self.attributes
+            .get(&self.kind)?
+            .as_array()?
+            .iter()
+            .find(|attribute| attribute.get("key").and_then(Value::as_str) == Some(key))
+            .and_then(|attribute| attribute.get("value"))
+            .map(json_to_text)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct EnvironmentFile {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    values: Vec<Variable>,
+    #[serde(
+        rename = "_postman_variable_scope",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    scope: Option<String>,
+}
+
+fn json_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(
+    what: &str,
+    source: &str,
+    content: &str,
+) -> Result<T> {
+    serde_json::from_str(content).map_err(|e| {
+        HttpDiffError::invalid_config(format!("Failed to parse {} {}: {}", what, source, e))
+    })
+}
+
+fn read_file(what: &str, path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        HttpDiffError::invalid_config(format!("Cannot read {} {}: {}", what, path.display(), e))
+    })
+}
+
+/// Imports routes from a Postman v2.1 collection and its environments
+pub struct PostmanImporter {
+    source: String,
+    collection: Collection,
+    environments: Vec<EnvironmentFile>,
+}
+
+impl PostmanImporter {
+    /// Read a collection file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = read_file("Postman collection", path)?;
+        Self::parse(&path.display().to_string(), &content)
+    }
+
+    /// Parse a collection; `source` names it in the generated configuration
+    pub fn parse(source: &str, content: &str) -> Result<Self> {
+        let collection: Collection = parse_json("Postman collection", source, content)?;
+        if !collection.info.schema.is_empty() && !collection.info.schema.contains("v2.") {
+            return Err(HttpDiffError::invalid_config(format!(
+                "{} is not a Postman v2 collection (schema {})",
+                source, collection.info.schema
+            )));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            collection,
+            environments: Vec::new(),
+        })
+    }
+
+    /// Add a Postman environment file, imported as an environment
+    pub fn with_environment_file<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = read_file("Postman environment", path)?;
+        self.with_environment(&path.display().to_string(), &content)
+    }
+
+    /// Add a parsed Postman environment
+    pub fn with_environment(mut self, source: &str, content: &str) -> Result<Self> {
+        let mut environment: EnvironmentFile = parse_json("Postman environment", source, content)?;
+        if environment.name.is_empty() {
+            environment.name = Path::new(source)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.split('.').next())
+                .unwrap_or("environment")
+                .to_string();
+        }
+        self.environments.push(environment);
+        Ok(self)
+    }
+
+    /// Generate the configuration
+    pub fn import(&self) -> Result<ImportedConfig> {
+        let mut requests = Vec::new();
+        flatten(&self.collection.item, None, None, &mut requests);
+
+        let mut conversion = Conversion::new(
+            ImportBuilder::new(format!("Postman collection {}", self.source)),
+            &self.collection,
+            &self.environments,
+        );
+
+        // Variables set by scripts are extracted rather than read from users.csv
+        let mut extractions = Vec::with_capacity(requests.len());
+        for request in &requests {
+            let (rules, unrecognized) = extraction_rules(request.events);
+            for line in unrecognized {
+                conversion.import.warn(format!(
+                    "Request '{}': could not map `{}` to an extraction rule",
+                    request.name,
+                    line.trim()
+                ));
+            }
+            for rule in &rules {
+                conversion.extracted.insert(rule.name.clone());
+            }
+            extractions.push(rules);
+        }
+
+        let bases: Vec<_> = requests.iter().map(|r| split_url(&r.request)).collect();
+        let primary = most_frequent(bases.iter().map(|url| &url.base));
+        for environment in conversion.environment_names() {
+            let base_url = match &primary {
+                Some(base) => conversion.base_value(&environment, base),
+                None => "http://localhost".to_string(),
+            };
+            let (headers, auth) = conversion.collection_auth(&environment);
+            conversion
+                .import
+                .environment_with(&environment, &base_url, headers, auth);
+        }
+
+        let mut names = HashSet::new();
+        let mut routes = Vec::with_capacity(requests.len());
+        let mut extracted_by = HashMap::new();
+        for ((request, url), extract) in requests.iter().zip(&bases).zip(extractions) {
+            let mut route = conversion.route(request, url);
+            route.name = unique_name(&mut names, &route.name);
+            if Some(&url.base) != primary.as_ref() {
+                route.base_urls = Some(
+                    conversion
+                        .environment_names()
+                        .into_iter()
+                        .map(|env| {
+                            let value = conversion.base_value(&env, &url.base);
+                            (env, value)
+                        })
+                        .collect(),
+                );
+            }
+            for rule in &extract {
+                extracted_by
+                    .entry(rule.name.clone())
+                    .or_insert_with(|| route.name.clone());
+            }
+            route.extract = (!extract.is_empty()).then_some(extract);
+            routes.push(route);
+        }
+
+        for route in &mut routes {
+            let mut depends_on: Vec<String> = Vec::new();
+            for name in referenced_variables(route) {
+                if let Some(source) = extracted_by.get(&name) {
+                    if *source != route.name && !depends_on.contains(source) {
+                        depends_on.push(source.clone());
+                    }
+                }
+            }
+            if !depends_on.is_empty() {
+                route.depends_on = Some(depends_on);
+                route.wait_for_extraction = Some(true);
+            }
+        }
+
+        let prerequest_scripts = requests
+            .iter()
+            .filter(|r| has_script(r.events, "prerequest"))
+            .count();
+        if prerequest_scripts > 0 {
+            conversion.import.warn(format!(
+                "{} pre-request scripts were not imported",
+                prerequest_scripts
+            ));
+        }
+
+        if !conversion.secrets.is_empty() {
+            let names: Vec<&str> = conversion.secrets.iter().map(String::as_str).collect();
+            conversion.import.warn(format!(
+                "Credentials were replaced by secret placeholders; set {} in the environment or .env",
+                names.join(", ")
+            ));
+        }
+
+        let mut import = conversion.import;
+        for route in routes {
+            import.route(route);
+        }
+        import.build()
+    }
+}
+
+/// A request of the collection with the folders it is nested in
+struct FlatRequest<'a> {
+    name: &'a str,
+    group: Option<String>,
+    request: Request,
+    events: &'a [Event],
+    /// Authentication set on the request or inherited from a folder
+    auth: Option<&'a Auth>,
+}
+
+fn flatten<'a>(
+    items: &'a [Item],
+    group: Option<&str>,
+    folder_auth: Option<&'a Auth>,
+    requests: &mut Vec<FlatRequest<'a>>,
+) {
+    for item in items {
+        if let Some(children) = &item.item {
+            let group = match group {
+                Some(parent) => format!("{}{}{}", parent, GROUP_SEPARATOR, item.name),
+                None => item.name.clone(),
+            };
+            flatten(
+                children,
+                Some(&group),
+                item.auth.as_ref().or(folder_auth),
+                requests,
+            );
+            continue;
+        }
+        let Some(definition) = &item.request else {
+            continue;
+        };
+        let request = match definition {
+            RequestDefinition::Url(url) => Request {
+                method: default_method(),
+                header: Vec::new(),
+                url: Some(UrlDefinition::Raw(url.clone())),
+                body: None,
+                auth: None,
+            },
+            RequestDefinition::Request(request) => request.as_ref().clone(),
+        };
+        let auth = match definition {
+            RequestDefinition::Request(request) => request
+                .auth
+                .as_ref()
+                .filter(|auth| auth.kind != "inherit")
+                .or(folder_auth),
+            RequestDefinition::Url(_) => folder_auth,
+        };
+        requests.push(FlatRequest {
+            name: &item.name,
+            group: group.map(str::to_string),
+            request,
+            events: &item.event,
+            auth,
+        });
+    }
+}
+
+fn has_script(events: &[Event], listen: &str) -> bool {
+    events.iter().any(|event| {
+        event.listen == listen && event.script.lines().iter().any(|l| !l.trim().is_empty())
+    })
+}
+
+/// Where the URL of a request points to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Base {
+    /// A variable the URL starts with, such as `{{baseUrl}}`
+    Variable(String),
+    /// A literal scheme and host
+    Origin(String),
+}
+
+struct SplitUrl {
+    base: Base,
+    path: String,
+    query: Vec<(String, String)>,
+    path_variables: HashMap<String, String>,
+}
+
+fn split_url(request: &Request) -> SplitUrl {
+    let (raw, details) = match &request.url {
+        Some(UrlDefinition::Raw(raw)) => (raw.clone(), None),
+        Some(UrlDefinition::Url(url)) => {
+            (url.raw.clone().unwrap_or_else(|| join_url(url)), Some(url))
+        }
+        None => (String::new(), None),
+    };
+    let raw = raw.split('#').next().unwrap_or_default().trim();
+    let (location, raw_query) = raw.split_once('?').unwrap_or((raw, ""));
+
+    let (base, path) = if let Some(rest) = location.strip_prefix("{{") {
+        match rest.split_once("}}") {
+            Some((name, path)) => (Base::Variable(name.trim().to_string()), path),
+            None => (Base::Origin(String::new()), location),
+        }
+    } else {
+        let (scheme, rest) = location.split_once("://").unwrap_or(("http", location));
+        let (host, path) = rest.find('/').map_or((rest, ""), |i| rest.split_at(i));
+        (Base::Origin(format!("{}://{}", scheme, host)), path)
+    };
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    };
+
+    let query = match details {
+        Some(url) if !url.query.is_empty() => url
+            .query
+            .iter()
+            .filter(|pair| !pair.disabled)
+            .map(|pair| (pair.key.clone(), pair.value.clone().unwrap_or_default()))
+            .collect(),
+        _ => raw_query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (key.to_string(), value.to_string())
+            })
+            .collect(),
+    };
+    let path_variables = details
+        .map(|url| {
+            url.variable
+                .iter()
+                .map(|variable| (variable.key.clone(), variable.value()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    SplitUrl {
+        base,
+        path,
+        query,
+        path_variables,
+    }
+}
+
+/// Rebuild a URL from its host and path when the collection omits `raw`
+fn join_url(url: &Url) -> String {
+    let join = |value: &Option<Value>, separator: &str| match value {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(json_to_text)
+            .collect::<Vec<_>>()
+            .join(separator),
+        Some(other) => json_to_text(other),
+        None => String::new(),
+    };
+    format!("{}/{}", join(&url.host, "."), join(&url.path, "/"))
+}
+
+fn most_frequent<'a, I: Iterator<Item = &'a Base>>(bases: I) -> Option<Base> {
+    let mut counts: Vec<(&Base, usize)> = Vec::new();
+    for base in bases {
+        match counts.iter_mut().find(|(b, _)| *b == base) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((base, 1)),
+        }
+    }
+    // Ties go to the base seen first
+    counts
+        .iter()
+        .fold(
+            None,
+            |best: Option<(&Base, usize)>, &(base, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((base, count)),
+            },
+        )
+        .map(|(base, _)| base.clone())
+}
+
+fn unique_name(names: &mut HashSet<String>, base: &str) -> String {
+    let mut name = base.to_string();
+    let mut suffix = 2;
+    while !names.insert(name.clone()) {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+/// Route name from a request name, e.g. `Get user` to `get_user`
+fn route_name_from_label(label: &str) -> String {
+    label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Placeholder names referenced anywhere in a route
+fn referenced_variables(route: &Route) -> Vec<String> {
+    let mut texts = vec![route.path.clone()];
+    texts.extend(route.headers.iter().flat_map(|h| h.values().cloned()));
+    texts.extend(route.params.iter().flat_map(|p| p.values().cloned()));
+    texts.extend(route.body.clone());
+    texts.extend(route.json.as_ref().map(Value::to_string));
+    texts.extend(route.form.iter().flat_map(|f| f.values().cloned()));
+    texts.extend(
+        route
+            .multipart
+            .iter()
+            .flatten()
+            .filter_map(|part| part.value.clone()),
+    );
+    texts
+        .iter()
+        .flat_map(|text| super::path_parameters(text))
+        .collect()
+}
+
+/// State shared while converting the requests of a collection
+struct Conversion<'a> {
+    import: ImportBuilder,
+    collection: &'a Collection,
+    environments: &'a [EnvironmentFile],
+    /// Variables set by test scripts
+    extracted: HashSet<String>,
+    /// Dynamic variables and variables with per-environment values already warned about
+    warned: HashSet<String>,
+    /// Names of the `{secret:NAME}` placeholders credentials were replaced by
+    secrets: BTreeSet<String>,
+}
+
+impl<'a> Conversion<'a> {
+    fn new(
+        import: ImportBuilder,
+        collection: &'a Collection,
+        environments: &'a [EnvironmentFile],
+    ) -> Self {
+        Self {
+            import,
+            collection,
+            environments,
+            extracted: HashSet::new(),
+            warned: HashSet::new(),
+            secrets: BTreeSet::new(),
+        }
+    }
+
+    /// Environment names, `default` when no Postman environment was given
+    fn environment_names(&self) -> Vec<String> {
+        if self.environments.is_empty() {
+            return vec!["default".to_string()];
+        }
+        self.environments
+            .iter()
+            .map(|environment| super::environment_name_from(&environment.name))
+            .collect()
+    }
+
+    /// Value of a variable in an environment, falling back to the collection variables
+    fn lookup(&self, environment: &str, name: &str) -> Option<String> {
+        let enabled =
+            |variable: &&Variable| variable.key == name && variable.enabled != Some(false);
+        self.environments
+            .iter()
+            .find(|env| super::environment_name_from(&env.name) == environment)
+            .and_then(|env| env.values.iter().find(enabled))
+            .or_else(|| self.collection.variable.iter().find(enabled))
+            .map(Variable::value)
+    }
+
+    fn base_value(&mut self, environment: &str, base: &Base) -> String {
+        match base {
+            Base::Origin(origin) => origin.clone(),
+            Base::Variable(name) => match self.lookup(environment, name) {
+                Some(value) => self
+                    .resolve(environment, &value)
+                    .trim_end_matches('/')
+                    .to_string(),
+                None => {
+                    self.import.warn(format!(
+                        "Environment '{}' does not define {{{{{}}}}}; using http://localhost",
+                        environment, name
+                    ));
+                    "http://localhost".to_string()
+                }
+            },
+        }
+    }
+
+    /// Substitute the variables an environment defines, converting the others to placeholders
+    fn resolve(&mut self, environment: &str, text: &str) -> String {
+        replace_variables(text, |name| match self.lookup(environment, name) {
+            Some(value) if !self.extracted.contains(name) => value,
+            _ => self.placeholder(name),
+        })
+    }
+
+    /// Convert `{{var}}` references to placeholders
+    fn convert(&mut self, text: &str) -> String {
+        replace_variables(text, |name| self.placeholder(name))
+    }
+
+    fn placeholder(&mut self, name: &str) -> String {
+        if let Some(dynamic) = name.strip_prefix('$') {
+            let function = match dynamic {
+                "guid" | "randomUUID" => "uuid()",
+                "timestamp" => "now(\"%s\")",
+                "isoTimestamp" => "now()",
+                "randomInt" => "random_int(0, 1000)",
+                _ => {
+                    if self.warned.insert(name.to_string()) {
+                        self.import.warn(format!(
+                            "Dynamic variable {{{{{}}}}} has no equivalent and was kept as is",
+                            name
+                        ));
+                    }
+                    return format!("{{{{{}}}}}", name);
+                }
+            };
+            return format!("{{{}}}", function);
+        }
+
+        let column = column_name(name);
+        if !self.extracted.contains(name) {
+            let environments = self.environment_names();
+            let values: BTreeSet<String> = environments
+                .iter()
+                .filter_map(|env| self.lookup(env, name))
+                .collect();
+            if values.len() > 1 && self.warned.insert(name.to_string()) {
+                self.import.warn(format!(
+                    "Variable '{}' differs between environments; users.csv holds its first value",
+                    name
+                ));
+            }
+            let example = environments.iter().find_map(|env| self.lookup(env, name));
+            self.import.parameter(&column, example);
+        }
+        format!("{{{}}}", column)
+    }
+
+    /// Secret placeholder for a credential, named after the variable it is read from
+    ///
+    /// Credentials set by a test script stay placeholders, and literal values
+    /// are named `fallback`. `environment` prefixes the name so that each
+    /// environment can hold its own credential.
+    fn secret(&mut self, value: &str, fallback: &str, environment: Option<&str>) -> String {
+        let variable = value
+            .trim()
+            .strip_prefix("{{")
+            .and_then(|rest| rest.strip_suffix("}}"))
+            .map(str::trim)
+            .filter(|name| !name.contains("{{") && !name.starts_with('$'));
+        if variable.is_some_and(|name| self.extracted.contains(name)) {
+            return self.convert(value);
+        }
+        let name = variable.map_or_else(|| fallback.to_string(), secret_name);
+        let name = match environment {
+            Some(environment) => format!("{}_{}", secret_name(environment), name),
+            None => name,
+        };
+        self.secrets.insert(name.clone());
+        format!("{{secret:{}}}", name)
+    }
+
+    /// Environment headers and authentication from the collection authentication
+    fn collection_auth(
+        &mut self,
+        environment: &str,
+    ) -> (Option<HashMap<String, String>>, Option<AuthConfig>) {
+        let collection = self.collection;
+        let Some(auth) = &collection.auth else {
+            return (None, None);
+        };
+        let attribute = |conversion: &mut Self, key: &str| {
+            let value = auth.attribute(key).unwrap_or_default();
+            conversion.resolve(environment, &value)
+        };
+        // Environments only need their own secrets when there are several
+        let prefix = (self.environment_names().len() > 1).then_some(environment);
+        let secret = |conversion: &mut Self, key: &str, fallback: &str| {
+            let value = auth.attribute(key).unwrap_or_default();
+            conversion.secret(&value, fallback, prefix)
+        };
+        match auth.kind.as_str() {
+            "bearer" => {
+                let token = secret(self, "token", "BEARER_TOKEN");
+                let headers =
+                    HashMap::from([("Authorization".to_string(), format!("Bearer {}", token))]);
+                (Some(headers), None)
+            }
+            "apikey" => {
+                let name = attribute(self, "key");
+                let value = secret(self, "value", "API_KEY");
+                if auth.attribute("in").as_deref() == Some("query") {
+                    let auth = AuthConfig::ApiKey {
+                        name,
+                        value,
+                        location: ApiKeyLocation::Query,
+                    };
+                    (None, Some(auth))
+                } else {
+                    (Some(HashMap::from([(name, value)])), None)
+                }
+            }
+            "basic" => {
+                let username = secret(self, "username", "USERNAME");
+                let password = secret(self, "password", "PASSWORD");
+                (None, Some(AuthConfig::Basic { username, password }))
+            }
+            "noauth" => (None, None),
+            other => {
+                if self.warned.insert(format!("auth:{}", other)) {
+                    self.import.warn(format!(
+                        "Collection authentication '{}' is not supported",
+                        other
+                    ));
+                }
+                (None, None)
+            }
+        }
+    }
+
+    fn route(&mut self, request: &FlatRequest, url: &SplitUrl) -> Route {
+        let method = request.request.method.to_uppercase();
+
+        let mut path = String::new();
+        for segment in url.path.split('/').skip(1) {
+            path.push('/');
+            match segment.strip_prefix(':') {
+                Some(name) if !name.is_empty() => {
+                    let column = column_name(name);
+                    let example = url.path_variables.get(name).cloned();
+                    self.import.parameter(&column, example);
+                    path.push_str(&format!("{{{}}}", column));
+                }
+                _ => path.push_str(&self.convert(segment)),
+            }
+        }
+
+        let name = match route_name_from_label(request.name) {
+            name if name.is_empty() => route_name_from(&method, &path),
+            name => name,
+        };
+        let mut route = new_route(name, method, path);
+        route.group = request.group.clone();
+
+        let mut headers: HashMap<String, String> = HashMap::new();
+        for header in request.request.header.iter().filter(|h| !h.disabled) {
+            let value = header.value.clone().unwrap_or_default();
+            headers.insert(self.convert(&header.key), self.convert(&value));
+        }
+        let mut params: HashMap<String, String> = HashMap::new();
+        for (key, value) in &url.query {
+            params.insert(self.convert(key), self.convert(value));
+        }
+
+        if let Some(auth) = request.auth {
+            self.request_auth(&route.name, auth, &mut headers, &mut params);
+        }
+        if let Some(body) = &request.request.body {
+            self.set_body(&mut route, body, &mut headers);
+        }
+
+        route.headers = (!headers.is_empty()).then_some(headers);
+        route.params = (!params.is_empty()).then_some(params);
+        route
+    }
+
+    fn request_auth(
+        &mut self,
+        route: &str,
+        auth: &Auth,
+        headers: &mut HashMap<String, String>,
+        params: &mut HashMap<String, String>,
+    ) {
+        let attribute = |key: &str| auth.attribute(key).unwrap_or_default();
+        match auth.kind.as_str() {
+            "bearer" => {
+                let token = self.secret(&attribute("token"), "BEARER_TOKEN", None);
+                headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+            }
+            "apikey" => {
+                let name = self.convert(&attribute("key"));
+                let value = self.secret(&attribute("value"), "API_KEY", None);
+                if auth.attribute("in").as_deref() == Some("query") {
+                    params.insert(name, value);
+                } else {
+                    headers.insert(name, value);
+                }
+            }
+            "inherit" => {}
+            "noauth" if self.collection.auth.is_some() => self.import.warn(format!(
+                "Request '{}' disables the collection authentication, which still applies",
+                route
+            )),
+            "noauth" => {}
+            other => self.import.warn(format!(
+                "Request '{}': authentication '{}' is not supported",
+                route, other
+            )),
+        }
+    }
+
+    fn set_body(&mut self, route: &mut Route, body: &Body, headers: &mut HashMap<String, String>) {
+        let content_type = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(name, value)| (name.clone(), value.to_lowercase()));
+        // Form, multipart and JSON bodies set their own Content-Type
+        let remove_content_type = |headers: &mut HashMap<String, String>| {
+            if let Some((name, _)) = &content_type {
+                headers.remove(name);
+            }
+        };
+
+        match body.mode.as_str() {
+            "raw" => {
+                let text = self.convert(body.raw.as_deref().unwrap_or_default());
+                if text.trim().is_empty() {
+                    return;
+                }
+                let language = body
+                    .options
+                    .as_ref()
+                    .and_then(|options| options.pointer("/raw/language"))
+                    .and_then(Value::as_str);
+                let json = language == Some("json")
+                    || content_type
+                        .as_ref()
+                        .map_or(language.is_none(), |(_, value)| value.contains("json"));
+                match serde_json::from_str::<Value>(&text) {
+                    Ok(value)
+                        if json
+                            && (value.is_object() || value.is_array())
+                            && !contains_null(&value) =>
+                    {
+                        remove_content_type(headers);
+                        route.json = Some(value);
+                    }
+                    parsed => {
+                        let keeps_nulls = json && parsed.as_ref().is_ok_and(contains_null);
+                        if content_type.is_none() && (language == Some("json") || keeps_nulls) {
+                            headers
+                                .insert("Content-Type".to_string(), "application/json".to_string());
+                        }
+                        route.body = Some(text);
+                    }
+                }
+            }
+            "urlencoded" => {
+                remove_content_type(headers);
+                let form: BTreeMap<String, String> = body
+                    .urlencoded
+                    .iter()
+                    .filter(|pair| !pair.disabled)
+                    .map(|pair| {
+                        let value = pair.value.clone().unwrap_or_default();
+                        (self.convert(&pair.key), self.convert(&value))
+                    })
+                    .collect();
+                route.form = (!form.is_empty()).then_some(form);
+            }
+            "formdata" => {
+                remove_content_type(headers);
+                let parts: Vec<MultipartPart> = body
+                    .formdata
+                    .iter()
+                    .filter(|pair| !pair.disabled)
+                    .map(|pair| {
+                        let file =
+                            (pair.kind.as_deref() == Some("file")).then(|| match &pair.src {
+                                Some(Value::Array(sources)) => {
+                                    sources.first().map(json_to_text).unwrap_or_default()
+                                }
+                                Some(source) => json_to_text(source),
+                                None => String::new(),
+                            });
+                        MultipartPart {
+                            name: self.convert(&pair.key),
+                            value: match file {
+                                Some(_) => None,
+                                None => Some(self.convert(&pair.value.clone().unwrap_or_default())),
+                            },
+                            file,
+                            filename: None,
+                            content_type: None,
+                        }
+                    })
+                    .collect();
+                route.multipart = (!parts.is_empty()).then_some(parts);
+            }
+            "file" => {
+                route.body_file = body.file.as_ref().and_then(|file| file.src.clone());
+            }
+            "graphql" => {
+                let Some(graphql) = &body.graphql else {
+                    return;
+                };
+                let query =
+                    self.convert(&graphql.get("query").map(json_to_text).unwrap_or_default());
+                let mut document = serde_json::json!({ "query": query });
+                let variables = graphql
+                    .get("variables")
+                    .map(json_to_text)
+                    .unwrap_or_default();
+                if let Ok(variables) = serde_json::from_str::<Value>(&self.convert(&variables)) {
+                    document["variables"] = variables;
+                }
+                remove_content_type(headers);
+                if contains_null(&document) {
+                    headers.insert("Content-Type".to_string(), "application/json".to_string());
+                    route.body = Some(document.to_string());
+                } else {
+                    route.json = Some(document);
+                }
+            }
+            other => self.import.warn(format!(
+                "Route '{}': body mode '{}' is not supported",
+                route.name, other
+            )),
+        }
+    }
+}
+
+/// Replace every `{{name}}` in `text`
+fn replace_variables<F: FnMut(&str) -> String>(text: &str, mut replace: F) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push_str(&replace(rest[start + 2..start + 2 + end].trim()));
+        rest = &rest[start + 2 + end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Extraction rules for the variables a test script sets, and the setter lines not understood
+fn extraction_rules(events: &[Event]) -> (Vec<ValueExtractionRule>, Vec<String>) {
+    let mut rules = Vec::new();
+    let mut unrecognized = Vec::new();
+    let mut aliases = Vec::new();
+    for event in events.iter().filter(|event| event.listen == "test") {
+        for line in event.script.lines() {
+            if let Some(captures) = ALIAS_PATTERN.captures(&line) {
+                aliases.push(captures[1].to_string());
+                continue;
+            }
+            let Some(captures) = SETTER_PATTERN.captures(&line) else {
+                continue;
+            };
+            let name = captures[1].to_string();
+            match extractor_for(&captures[2], &aliases) {
+                Some((extractor_type, source)) => rules.push(ValueExtractionRule {
+                    name,
+                    extractor_type,
+                    source,
+                    default_value: None,
+                    required: false,
+                }),
+                None => unrecognized.push(line.clone()),
+            }
+        }
+    }
+    (rules, unrecognized)
+}
+
+/// Extractor reading the value a script expression reads from the response
+fn extractor_for(expression: &str, aliases: &[String]) -> Option<(ExtractorType, String)> {
+    let call_argument = |prefix: &str| {
+        let argument = expression.strip_prefix(prefix)?.strip_suffix(')')?.trim();
+        let unquoted = argument
+            .strip_prefix('"')
+            .and_then(|a| a.strip_suffix('"'))
+            .or_else(|| {
+                argument
+                    .strip_prefix('\'')
+                    .and_then(|a| a.strip_suffix('\''))
+            })?;
+        Some(unquoted.to_string())
+    };
+
+    let json_roots = ["pm.response.json()", "JSON.parse(responseBody)"];
+    for root in json_roots
+        .iter()
+        .copied()
+        .chain(aliases.iter().map(String::as_str))
+    {
+        if let Some(path) = expression.strip_prefix(root) {
+            if ACCESSOR_PATTERN.is_match(path) {
+                return Some((
+                    ExtractorType::JsonPath,
+                    format!("${}", path.replace('"', "'")),
+                ));
+            }
+        }
+    }
+
+    if let Some(header) = call_argument("pm.response.headers.get(")
+        .or_else(|| call_argument("postman.getResponseHeader("))
+    {
+        return Some((ExtractorType::Header, header));
+    }
+    if let Some(cookie) = call_argument("pm.cookies.get(") {
+        return Some((ExtractorType::Cookie, cookie));
+    }
+    match expression {
+        "pm.response.code" | "responseCode.code" => {
+            Some((ExtractorType::StatusCode, String::new()))
+        }
+        "pm.response.text()" | "responseBody" => Some((ExtractorType::Body, String::new())),
+        _ => None,
+    }
+}
+
+/// A collection and its environments, as Postman JSON documents
+#[derive(Debug, Clone)]
+pub struct PostmanExport {
+    pub collection: Value,
+    /// Environment names with their Postman environment document
+    pub environments: Vec<(String, Value)>,
+    /// Parts of the configuration Postman cannot represent
+    pub warnings: Vec<String>,
+}
+
+/// Exports a configuration as a Postman v2.1 collection and environments
+pub struct PostmanExporter<'a> {
+    config: &'a HttpDiffConfig,
+    name: String,
+    variables: BTreeMap<String, String>,
+}
+
+impl<'a> PostmanExporter<'a> {
+    pub fn new(config: &'a HttpDiffConfig) -> Self {
+        Self {
+            config,
+            name: "http-diff".to_string(),
+            variables: BTreeMap::new(),
+        }
+    }
+
+    /// Name of the exported collection
+    #[must_use]
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Collection variables, such as one row of users.csv
+    #[must_use]
+    pub fn variables(mut self, variables: BTreeMap<String, String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    pub fn export(&self) -> Result<PostmanExport> {
+        let mut warnings = Vec::new();
+        let mut environment_names: Vec<&String> = self.config.environments.keys().collect();
+        environment_names.sort();
+        let mut environment_values: BTreeMap<&String, Vec<Variable>> = environment_names
+            .iter()
+            .map(|&name| {
+                let base_url = to_postman(&self.config.environments[name].base_url, &mut warnings);
+                (name, vec![Variable::new(BASE_URL_VARIABLE, &base_url)])
+            })
+            .collect();
+
+        // Headers of every environment are sent by each request, through a
+        // variable when their value differs between environments
+        let mut shared_headers: BTreeMap<String, String> = BTreeMap::new();
+        let header_names: BTreeSet<&String> = self
+            .config
+            .environments
+            .values()
+            .flat_map(|env| env.headers.iter().flat_map(|headers| headers.keys()))
+            .collect();
+        for header in header_names {
+            let values: Vec<Option<&String>> = environment_names
+                .iter()
+                .map(|&env| {
+                    self.config.environments[env]
+                        .headers
+                        .as_ref()
+                        .and_then(|headers| headers.get(header))
+                })
+                .collect();
+            let first = values.first().copied().flatten();
+            if let Some(value) = first.filter(|_| values.iter().all(|v| *v == first)) {
+                shared_headers.insert(header.clone(), to_postman(value, &mut warnings));
+                continue;
+            }
+            let variable = column_name(header);
+            shared_headers.insert(header.clone(), format!("{{{{{}}}}}", variable));
+            for (&env, value) in environment_names.iter().zip(values) {
+                let value =
+                    to_postman(value.map(String::as_str).unwrap_or_default(), &mut warnings);
+                if let Some(values) = environment_values.get_mut(env) {
+                    values.push(Variable::new(&variable, &value));
+                }
+            }
+        }
+        for name in &environment_names {
+            if self.config.environments[*name].auth.is_some() {
+                warnings.push(format!(
+                    "Environment '{}': authentication is not exported",
+                    name
+                ));
+            }
+        }
+
+        let global = self.config.global.as_ref();
+        for (header, value) in sorted(global.and_then(|g| g.headers.as_ref())) {
+            let value = to_postman(value, &mut warnings);
+            shared_headers.entry(header.clone()).or_insert(value);
+        }
+        let global_params: Vec<(String, String)> = sorted(global.and_then(|g| g.params.as_ref()))
+            .into_iter()
+            .map(|(key, value)| (key.clone(), to_postman(value, &mut warnings)))
+            .collect();
+
+        let mut items = Vec::new();
+        for route in &self.config.routes {
+            let base = match &route.base_urls {
+                Some(overrides) => {
+                    let variable = format!("{}_base_url", column_name(&route.name));
+                    for &env in &environment_names {
+                        let base_url = overrides
+                            .get(env)
+                            .unwrap_or(&self.config.environments[env].base_url);
+                        let base_url = to_postman(base_url, &mut warnings);
+                        if let Some(values) = environment_values.get_mut(env) {
+                            values.push(Variable::new(&variable, &base_url));
+                        }
+                    }
+                    variable
+                }
+                None => BASE_URL_VARIABLE.to_string(),
+            };
+            let item =
+                self.export_route(route, &base, &shared_headers, &global_params, &mut warnings);
+            match &route.group {
+                Some(group) => {
+                    let folders: Vec<&str> = group.split(GROUP_SEPARATOR).collect();
+                    insert_into_folder(&mut items, &folders, item);
+                }
+                None => items.push(item),
+            }
+        }
+
+        let collection = Collection {
+            info: Info {
+                name: self.name.clone(),
+                schema: SCHEMA.to_string(),
+            },
+            item: items,
+            variable: self
+                .variables
+                .iter()
+                .map(|(key, value)| Variable::new(key, value))
+                .collect(),
+            auth: None,
+        };
+        let environments = environment_values
+            .into_iter()
+            .map(|(name, values)| {
+                let environment = EnvironmentFile {
+                    name: name.clone(),
+                    values,
+                    scope: Some("environment".to_string()),
+                };
+                Ok((name.clone(), to_json(&environment)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PostmanExport {
+            collection: to_json(&collection)?,
+            environments,
+            warnings,
+        })
+    }
+
+    fn export_route(
+        &self,
+        route: &Route,
+        base: &str,
+        shared_headers: &BTreeMap<String, String>,
+        global_params: &[(String, String)],
+        warnings: &mut Vec<String>,
+    ) -> Item {
+        let mut headers = shared_headers.clone();
+        for (name, value) in sorted(route.headers.as_ref()) {
+            headers.insert(name.clone(), to_postman(value, warnings));
+        }
+        let mut query: BTreeMap<String, String> = global_params.iter().cloned().collect();
+        for (name, value) in sorted(route.params.as_ref()) {
+            query.insert(name.clone(), to_postman(value, warnings));
+        }
+
+        let path = to_postman(&route.path, warnings);
+        let mut raw = format!("{{{{{}}}}}{}", base, path);
+        if !query.is_empty() {
+            let pairs: Vec<String> = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            raw = format!("{}?{}", raw, pairs.join("&"));
+        }
+        let url = Url {
+            raw: Some(raw),
+            host: Some(Value::Array(vec![Value::String(format!(
+                "{{{{{}}}}}",
+                base
+            ))])),
+            path: Some(Value::Array(
+                path.split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| Value::String(segment.to_string()))
+                    .collect(),
+            )),
+            query: query
+                .into_iter()
+                .map(|(key, value)| Pair {
+                    key,
+                    value: Some(value),
+                    ..Pair::default()
+                })
+                .collect(),
+            variable: Vec::new(),
+        };
+
+        for (unsupported, present) in [
+            ("conditions", route.conditions.is_some()),
+            ("assertions", route.assertions.is_some()),
+        ] {
+            if present {
+                warnings.push(format!(
+                    "Route '{}': {} are not exported",
+                    route.name, unsupported
+                ));
+            }
+        }
+
+        let script = export_extractions(route, warnings);
+        Item {
+            name: route.name.clone(),
+            item: None,
+            request: Some(RequestDefinition::Request(Box::new(Request {
+                method: route.method.to_uppercase(),
+                header: headers
+                    .into_iter()
+                    .map(|(key, value)| Pair {
+                        key,
+                        value: Some(value),
+                        ..Pair::default()
+                    })
+                    .collect(),
+                url: Some(UrlDefinition::Url(url)),
+                body: export_body(route, warnings),
+                auth: None,
+            }))),
+            event: (!script.is_empty())
+                .then(|| Event {
+                    listen: "test".to_string(),
+                    script: Script {
+                        exec: Value::Array(script.into_iter().map(Value::String).collect()),
+                        kind: Some("text/javascript".to_string()),
+                    },
+                })
+                .into_iter()
+                .collect(),
+            auth: None,
+        }
+    }
+}
+
+fn sorted(map: Option<&HashMap<String, String>>) -> Vec<(&String, &String)> {
+    let mut entries: Vec<_> = map.into_iter().flatten().collect();
+    entries.sort();
+    entries
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value)
+        .map_err(|e| HttpDiffError::general(format!("Failed to serialize Postman document: {}", e)))
+}
+
+/// Convert placeholders to Postman `{{variables}}`
+fn to_postman(text: &str, warnings: &mut Vec<String>) -> String {
+    map_placeholders(text, |source, expression| {
+        let converted = match expression {
+            Expression::Variable(name) | Expression::EnvVar(name) | Expression::Secret(name) => {
+                Some(name.clone())
+            }
+            Expression::Call { name, args } => match (name.as_str(), args.len()) {
+                ("uuid", _) => Some("$guid".to_string()),
+                ("now", 0) => Some("$isoTimestamp".to_string()),
+                ("random_int", _) => Some("$randomInt".to_string()),
+                _ => None,
+            },
+            Expression::Default(operands) => match operands.first() {
+                Some(
+                    Expression::Variable(name)
+                    | Expression::EnvVar(name)
+                    | Expression::Secret(name),
+                ) => Some(name.clone()),
+                _ => None,
+            },
+            Expression::Literal(_) => None,
+        };
+        if converted.is_none() {
+            let warning = format!("Placeholder {{{}}} has no Postman equivalent", source);
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+        converted.map(|name| format!("{{{{{}}}}}", name))
+    })
+}
+
+fn export_body(route: &Route, warnings: &mut Vec<String>) -> Option<Body> {
+    let json_options = || Some(serde_json::json!({ "raw": { "language": "json" } }));
+    if let Some(json) = &route.json {
+        let text = serde_json::to_string_pretty(json).unwrap_or_default();
+        return Some(Body {
+            mode: "raw".to_string(),
+            raw: Some(to_postman(&text, warnings)),
+            options: json_options(),
+            ..Body::default()
+        });
+    }
+    if let Some(body) = &route.body {
+        let is_json = serde_json::from_str::<Value>(body).is_ok();
+        return Some(Body {
+            mode: "raw".to_string(),
+            raw: Some(to_postman(body, warnings)),
+            options: if is_json { json_options() } else { None },
+            ..Body::default()
+        });
+    }
+    if let Some(form) = &route.form {
+        return Some(Body {
+            mode: "urlencoded".to_string(),
+            urlencoded: form
+                .iter()
+                .map(|(key, value)| Pair {
+                    key: key.clone(),
+                    value: Some(to_postman(value, warnings)),
+                    ..Pair::default()
+                })
+                .collect(),
+            ..Body::default()
+        });
+    }
+    if let Some(parts) = &route.multipart {
+        return Some(Body {
+            mode: "formdata".to_string(),
+            formdata: parts
+                .iter()
+                .map(|part| match &part.file {
+                    Some(file) => Pair {
+                        key: part.name.clone(),
+                        kind: Some("file".to_string()),
+                        src: Some(Value::String(file.clone())),
+                        ..Pair::default()
+                    },
+                    None => Pair {
+                        key: part.name.clone(),
+                        value: Some(to_postman(
+                            part.value.as_deref().unwrap_or_default(),
+                            warnings,
+                        )),
+                        kind: Some("text".to_string()),
+                        ..Pair::default()
+                    },
+                })
+                .collect(),
+            ..Body::default()
+        });
+    }
+    route.body_file.as_ref().map(|file| Body {
+        mode: "file".to_string(),
+        file: Some(BodyFile {
+            src: Some(file.clone()),
+        }),
+        ..Body::default()
+    })
+}
+
+/// Test script lines setting the values a route extracts
+fn export_extractions(route: &Route, warnings: &mut Vec<String>) -> Vec<String> {
+    let quote = |text: &str| serde_json::to_string(text).unwrap_or_default();
+
+    let mut lines = Vec::new();
+    for rule in route.extract.iter().flatten() {
+        let expression = match rule.extractor_type {
+            ExtractorType::JsonPath if SIMPLE_JSON_PATH_PATTERN.is_match(&rule.source) => {
+                Some(format!("pm.response.json(){}", &rule.source[1..]))
+            }
+            ExtractorType::Header => {
+                Some(format!("pm.response.headers.get({})", quote(&rule.source)))
+            }
+            ExtractorType::Cookie => Some(format!("pm.cookies.get({})", quote(&rule.source))),
+            ExtractorType::StatusCode => Some("pm.response.code".to_string()),
+            ExtractorType::Body => Some("pm.response.text()".to_string()),
+            _ => None,
+        };
+        match expression {
+            Some(expression) => lines.push(format!(
+                "pm.environment.set({}, {});",
+                quote(&rule.name),
+                expression
+            )),
+            None => warnings.push(format!(
+                "Route '{}': extraction of '{}' is not exported",
+                route.name, rule.name
+            )),
+        }
+    }
+    lines
+}
+
+/// Add an item to nested folders, creating them as needed
+fn insert_into_folder(items: &mut Vec<Item>, folders: &[&str], item: Item) {
+    let Some((folder, rest)) = folders.split_first() else {
+        items.push(item);
+        return;
+    };
+    let index = match items
+        .iter()
+        .position(|i| i.item.is_some() && i.name == *folder)
+    {
+        Some(index) => index,
+        None => {
+            items.push(Item {
+                name: folder.to_string(),
+                item: Some(Vec::new()),
+                request: None,
+                event: Vec::new(),
+                auth: None,
+            });
+            items.len() - 1
+        }
+    };
+    if let Some(children) = items[index].item.as_mut() {
+        insert_into_folder(children, rest, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"{
+        "info": {
+            "name": "Shop",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{apiToken}}", "type": "string"}]},
+        "item": [
+            {
+                "name": "Auth",
+                "item": [{
+                    "name": "Login",
+                    "event": [{
+                        "listen": "test",
+                        "script": {"exec": [
+                            "var data = pm.response.json();",
+                            "pm.environment.set(\"sessionId\", data.session.id);",
+                            "pm.environment.set('requestId', pm.response.headers.get('X-Request-Id'));",
+                            "pm.environment.set(\"odd\", Date.now());"
+                        ]}
+                    }],
+                    "request": {
+                        "method": "POST",
+                        "header": [{"key": "Content-Type", "value": "application/json"}],
+                        "url": {"raw": "{{baseUrl}}/login"},
+                        "body": {"mode": "raw", "raw": "{\"user\": \"{{username}}\"}", "options": {"raw": {"language": "json"}}}
+                    }
+                }]
+            },
+            {
+                "name": "Get order",
+                "request": {
+                    "method": "GET",
+                    "header": [{"key": "X-Session", "value": "{{sessionId}}"}],
+                    "url": {
+                        "raw": "{{baseUrl}}/orders/:orderId?expand=items",
+                        "query": [{"key": "expand", "value": "items"}],
+                        "variable": [{"key": "orderId", "value": "17"}]
+                    }
+                }
+            },
+            {
+                "name": "Status",
+                "request": {"method": "GET", "url": "https://status.example.com/health"}
+            }
+        ]
+    }"#;
+
+    fn environment(name: &str, base_url: &str, token: &str) -> String {
+        serde_json::json!({
+            "name": name,
+            "values": [
+                {"key": "baseUrl", "value": base_url, "enabled": true},
+                {"key": "apiToken", "value": token, "enabled": true},
+                {"key": "username", "value": "ann", "enabled": true}
+            ]
+        })
+        .to_string()
+    }
+
+    fn import() -> ImportedConfig {
+        PostmanImporter::parse("shop.json", COLLECTION)
+            .unwrap()
+            .with_environment(
+                "prod.json",
+                &environment("Production", "https://shop.example.com/", "p"),
+            )
+            .unwrap()
+            .with_environment(
+                "staging.json",
+                &environment("Staging", "https://staging.example.com", "s"),
+            )
+            .unwrap()
+            .import()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_import_collection() {
+        let imported = import();
+        let config = &imported.config;
+
+        let production = &config.environments["production"];
+        assert_eq!(production.base_url, "https://shop.example.com");
+        assert_eq!(
+            production.headers.as_ref().unwrap()["Authorization"],
+            "Bearer {secret:PRODUCTION_API_TOKEN}"
+        );
+        assert_eq!(
+            config.environments["staging"].base_url,
+            "https://staging.example.com"
+        );
+
+        let login = &config.routes[0];
+        assert_eq!(login.name, "login");
+        assert_eq!(login.group.as_deref(), Some("Auth"));
+        assert_eq!(login.json, Some(serde_json::json!({"user": "{username}"})));
+        assert!(login.headers.is_none());
+        let extract = login.extract.as_ref().unwrap();
+        assert_eq!(extract.len(), 2);
+        assert_eq!(extract[0].source, "$.session.id");
+        assert!(matches!(extract[1].extractor_type, ExtractorType::Header));
+
+        let order = &config.routes[1];
+        assert_eq!(order.name, "get_order");
+        assert_eq!(order.path, "/orders/{orderId}");
+        assert_eq!(order.params.as_ref().unwrap()["expand"], "items");
+        assert_eq!(order.headers.as_ref().unwrap()["X-Session"], "{sessionId}");
+        assert_eq!(order.depends_on, Some(vec!["login".to_string()]));
+
+        let status = &config.routes[2];
+        assert_eq!(
+            status.base_urls.as_ref().unwrap()["staging"],
+            "https://status.example.com"
+        );
+
+        assert_eq!(
+            imported.parameters,
+            vec![
+                ("username".to_string(), "ann".to_string()),
+                ("orderId".to_string(), "17".to_string()),
+            ]
+        );
+        assert!(imported.warnings[0].contains("Date.now()"));
+        assert!(imported
+            .warnings
+            .iter()
+            .any(|w| w.contains("set PRODUCTION_API_TOKEN, STAGING_API_TOKEN in")));
+    }
+
+    /// Import a collection made of `items`, without environments
+    fn import_items(auth: Value, items: Value) -> ImportedConfig {
+        let collection = serde_json::json!({
+            "info": {"name": "Test", "schema": SCHEMA},
+            "auth": auth,
+            "item": items
+        });
+        PostmanImporter::parse("test.json", &collection.to_string())
+            .unwrap()
+            .import()
+            .unwrap()
+    }
+
+    fn get(name: &str, url: &str) -> Value {
+        serde_json::json!({"name": name, "request": {"method": "GET", "url": url}})
+    }
+
+    fn has_warning(imported: &ImportedConfig, text: &str) -> bool {
+        imported
+            .warnings
+            .iter()
+            .any(|warning| warning.contains(text))
+    }
+
+    #[test]
+    fn test_nested_folders_become_groups() {
+        let imported = import_items(
+            Value::Null,
+            serde_json::json!([{
+                "name": "Admin",
+                "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{adminToken}}"}]},
+                "item": [
+                    {"name": "Users", "item": [get("List users", "{{baseUrl}}/admin/users")]},
+                    get("Stats", "{{baseUrl}}/admin/stats")
+                ]
+            }, get("Health", "{{baseUrl}}/health")]),
+        );
+        let routes = &imported.config.routes;
+        assert_eq!(routes[0].name, "list_users");
+        assert_eq!(routes[0].group.as_deref(), Some("Admin / Users"));
+        assert_eq!(routes[1].group.as_deref(), Some("Admin"));
+        assert!(routes[2].group.is_none());
+        // Folder authentication applies to the requests nested at any depth
+        assert_eq!(
+            routes[0].headers.as_ref().unwrap()["Authorization"],
+            "Bearer {secret:ADMIN_TOKEN}"
+        );
+        assert!(routes[2].headers.is_none());
+    }
+
+    #[test]
+    fn test_unsupported_auth_and_body_modes_warn() {
+        let imported = import_items(
+            serde_json::json!({"type": "awsv4", "awsv4": []}),
+            serde_json::json!([{
+                "name": "Upload",
+                "request": {
+                    "method": "POST",
+                    "url": "{{baseUrl}}/upload",
+                    "auth": {"type": "digest", "digest": []},
+                    "body": {"mode": "binary"}
+                }
+            }]),
+        );
+        assert!(has_warning(
+            &imported,
+            "Collection authentication 'awsv4' is not supported"
+        ));
+        assert!(has_warning(
+            &imported,
+            "Request 'upload': authentication 'digest' is not supported"
+        ));
+        assert!(has_warning(
+            &imported,
+            "Route 'upload': body mode 'binary' is not supported"
+        ));
+        let upload = &imported.config.routes[0];
+        assert!(upload.headers.is_none());
+        assert!(upload.body.is_none() && upload.json.is_none());
+    }
+
+    #[test]
+    fn test_dynamic_variables() {
+        let imported = import_items(
+            Value::Null,
+            serde_json::json!([{
+                "name": "Create",
+                "request": {
+                    "method": "POST",
+                    "url": "{{baseUrl}}/items?at={{$timestamp}}",
+                    "header": [
+                        {"key": "X-Request-Id", "value": "{{$guid}}"},
+                        {"key": "X-Color", "value": "{{$randomColor}}"},
+                        {"key": "X-Other-Color", "value": "{{$randomColor}}"}
+                    ]
+                }
+            }]),
+        );
+        let create = &imported.config.routes[0];
+        let headers = create.headers.as_ref().unwrap();
+        assert_eq!(headers["X-Request-Id"], "{uuid()}");
+        assert_eq!(headers["X-Color"], "{{$randomColor}}");
+        assert_eq!(create.params.as_ref().unwrap()["at"], "{now(\"%s\")}");
+        // Dynamic variables are not user data, and are warned about once
+        assert!(imported.parameters.is_empty());
+        let warnings = imported
+            .warnings
+            .iter()
+            .filter(|w| w.contains("{{$randomColor}}"))
+            .count();
+        assert_eq!(warnings, 1);
+    }
+
+    #[test]
+    fn test_api_key_in_query() {
+        let api_key = |value: &str| {
+            serde_json::json!({"type": "apikey", "apikey": [
+                {"key": "key", "value": "api_key"},
+                {"key": "value", "value": value},
+                {"key": "in", "value": "query"}
+            ]})
+        };
+        let imported = import_items(
+            api_key("{{apiKey}}"),
+            serde_json::json!([{
+                "name": "Search",
+                "request": {
+                    "method": "GET",
+                    "url": "{{baseUrl}}/search?q=shoes",
+                    "auth": api_key("literal-key")
+                }
+            }]),
+        );
+
+        let environment = &imported.config.environments["default"];
+        assert!(environment.headers.is_none());
+        match environment.auth.as_ref().unwrap() {
+            AuthConfig::ApiKey {
+                name,
+                value,
+                location,
+            } => {
+                assert_eq!(name, "api_key");
+                assert_eq!(value, "{secret:API_KEY}");
+                assert!(matches!(location, ApiKeyLocation::Query));
+            }
+            other => panic!("unexpected authentication {:?}", other),
+        }
+
+        let params = imported.config.routes[0].params.as_ref().unwrap();
+        assert_eq!(params["q"], "shoes");
+        assert_eq!(params["api_key"], "{secret:API_KEY}");
+        assert!(imported.config.routes[0].headers.is_none());
+        assert!(has_warning(&imported, "set API_KEY in the environment"));
+    }
+
+    #[test]
+    fn test_unrecognized_setters_warn() {
+        let imported = import_items(
+            Value::Null,
+            serde_json::json!([{
+                "name": "Token",
+                "event": [{"listen": "test", "script": {"exec": [
+                    "pm.test(\"ok\", () => pm.response.to.have.status(200));",
+                    "pm.environment.set(\"token\", pm.response.json().data.token);",
+                    "pm.environment.set(\"expires\", computeExpiry(pm.response));",
+                    "pm.collectionVariables.set(\"tags\", pm.response.json().tags.join(','));"
+                ]}}],
+                "request": {"method": "POST", "url": "{{baseUrl}}/token"}
+            }, get("Profile", "{{baseUrl}}/profile?token={{token}}&since={{expires}}")]),
+        );
+
+        let token = &imported.config.routes[0];
+        let extract = token.extract.as_ref().unwrap();
+        assert_eq!(extract.len(), 1);
+        assert_eq!(extract[0].source, "$.data.token");
+        assert!(has_warning(
+            &imported,
+            "Request 'Token': could not map `pm.environment.set(\"expires\", computeExpiry(pm.response));`"
+        ));
+        assert!(has_warning(
+            &imported,
+            "could not map `pm.collectionVariables.set(\"tags\""
+        ));
+        assert!(!has_warning(&imported, "pm.test"));
+
+        // Values that are not extracted are read from users.csv instead
+        let profile = &imported.config.routes[1];
+        assert_eq!(profile.depends_on, Some(vec!["token".to_string()]));
+        let names: Vec<_> = imported.parameters.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["expires"]);
+    }
+
+    #[test]
+    fn test_json_bodies_keep_nulls() {
+        let imported = import_items(
+            Value::Null,
+            serde_json::json!([{
+                "name": "Update",
+                "request": {
+                    "method": "PUT",
+                    "url": "{{baseUrl}}/users/1",
+                    "body": {"mode": "raw", "raw": "{\"manager\": null, \"name\": \"{{name}}\"}"}
+                }
+            }]),
+        );
+        let update = &imported.config.routes[0];
+        assert!(update.json.is_none());
+        assert_eq!(
+            update.body.as_deref(),
+            Some("{\"manager\": null, \"name\": \"{name}\"}")
+        );
+        assert_eq!(
+            update.headers.as_ref().unwrap()["Content-Type"],
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let imported = import();
+        let export = PostmanExporter::new(&imported.config)
+            .name("Shop")
+            .variables(BTreeMap::from([("orderId".to_string(), "17".to_string())]))
+            .export()
+            .unwrap();
+
+        assert_eq!(export.environments.len(), 2);
+        let (name, production) = &export.environments[0];
+        assert_eq!(name, "production");
+        let values = production["values"].as_array().unwrap();
+        assert!(values.iter().any(
+            |v| v["key"] == "Authorization" && v["value"] == "Bearer {{PRODUCTION_API_TOKEN}}"
+        ));
+        assert!(values.iter().any(|v| v["key"] == "status_base_url"));
+
+        let collection = &export.collection;
+        assert_eq!(collection["item"][0]["name"], "Auth");
+        let order = &collection["item"][1]["request"];
+        assert_eq!(
+            order["url"]["raw"],
+            "{{baseUrl}}/orders/{{orderId}}?expand=items"
+        );
+        let login = &collection["item"][0]["item"][0];
+        assert_eq!(
+            login["event"][0]["script"]["exec"][0],
+            "pm.environment.set(\"sessionId\", pm.response.json().session.id);"
+        );
+
+        let reimported = PostmanImporter::parse("export.json", &collection.to_string())
+            .unwrap()
+            .with_environment("production.json", &production.to_string())
+            .unwrap()
+            .import()
+            .unwrap();
+        let paths: Vec<_> = reimported
+            .config
+            .routes
+            .iter()
+            .map(|r| r.path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["/login", "/orders/{orderId}", "/health"]);
+        assert_eq!(reimported.config.routes[0].group.as_deref(), Some("Auth"));
+        assert_eq!(
+            reimported.config.routes[1].depends_on,
+            Some(vec!["login".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_export_keeps_environment_variable_references() {
+        let config: HttpDiffConfig = toml::from_str(
+            r#"
+[environments.prod]
+base_url = 'https://{$API_HOST ?? "api.example.com"}'
+headers = { "Authorization" = "Bearer {$API_TOKEN}" }
+
+[[routes]]
+name = "health"
+method = "GET"
+path = "/health"
+"#,
+        )
+        .unwrap();
+
+        let export = PostmanExporter::new(&config).export().unwrap();
+        let values = export.environments[0].1["values"].as_array().unwrap();
+        assert!(values
+            .iter()
+            .any(|v| v["key"] == BASE_URL_VARIABLE && v["value"] == "https://{{API_HOST}}"));
+        let headers = &export.collection["item"][0]["request"]["header"];
+        assert_eq!(headers[0]["value"], "Bearer {{API_TOKEN}}");
+    }
+}
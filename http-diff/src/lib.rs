@@ -12,6 +12,7 @@ pub mod error;
 pub mod types;
 
 // New structured modules
+pub mod auth;
 pub mod execution;
 pub mod http;
 
@@ -21,12 +22,18 @@ pub mod comparison;
 pub mod conditions;
 pub mod curl;
 pub mod extraction;
+pub mod import;
 
 // Presentation modules
 pub mod renderers;
 
 // Shared utility modules
+pub mod baseline;
+pub mod body_builder;
 pub mod output_manager;
+pub mod recording;
+pub mod secrets;
+pub mod template;
 pub mod url_builder;
 pub mod utils;
 
@@ -41,17 +48,21 @@ pub use traits::{ConditionEvaluator, ConfigValidator, HttpClient, ProgressCallba
 pub use analysis::{ErrorAnalysis, ErrorAnalyzer, ErrorClassifierImpl, ErrorGroup, RouteError};
 
 // Re-export main types
-pub use config::{Environment, HttpDiffConfig, HttpDiffConfigBuilder, Route, UserData};
+pub use config::{Environment, FailOn, HttpDiffConfig, HttpDiffConfigBuilder, Route, UserData};
 pub use conditions::{ConditionEvaluatorImpl, ConditionOperator, ConditionResult, ExecutionCondition};
 pub use types::{
-    ComparisonResult, DiffViewStyle, Difference, DifferenceCategory, ErrorSummary, ExecutionError,
+    AssertionResult, ComparisonResult, DiffViewStyle, Difference, DifferenceCategory, EnvironmentLatency, ErrorSummary, ExecutionError,
     ExecutionErrorType, ExecutionResult, HttpResponse, ExtractionRule, ExtractionResult,
     ExtractionType, ExtractedValue, ValueExtractionContext, ExtractionError, ChainExecutionMetadata,
+    ResponseTiming,
 };
 
 // Re-export implementations (clean API without "Impl" suffix)
-pub use comparison::{ResponseComparator as DefaultResponseComparator, ResponseValidatorImpl};
-pub use execution::{DefaultTestRunner, ProgressTracker, TestRunnerImpl};
+pub use comparison::{
+    JsonChange, JsonChangeKind, JsonDiffer, ResponseComparator as DefaultResponseComparator,
+    ResponseValidatorImpl,
+};
+pub use execution::{DefaultTestRunner, FailurePolicy, PolicyVerdict, ProgressTracker, TestRunnerImpl};
 pub use http::HttpClientImpl as DefaultHttpClient;
 
 // Re-export extraction implementations
@@ -74,7 +85,7 @@ pub use url_builder::UrlBuilder;
 /// Create a test runner with default implementations
 pub fn create_default_test_runner(config: HttpDiffConfig) -> Result<DefaultTestRunner> {
     let client = DefaultHttpClient::new(config.clone())?;
-    let comparator = DefaultResponseComparator::new();
+    let comparator = DefaultResponseComparator::new().with_config(&config);
     let condition_evaluator = ConditionEvaluatorImpl::new();
     DefaultTestRunner::new(config, client, comparator, condition_evaluator)
 }
@@ -106,6 +117,7 @@ mod tests {
         let routes = Vec::new();
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
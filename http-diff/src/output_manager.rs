@@ -95,12 +95,17 @@ impl OutputManager {
         Ok(self.resolve_output_path(&filename, category))
     }
 
+    /// Write a file through a temporary file, redacting resolved secrets from text content
     pub fn write_file_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(
         &self,
         path: P,
         content: C,
     ) -> Result<()> {
         let path = path.as_ref();
+        let content = match std::str::from_utf8(content.as_ref()) {
+            Ok(text) => crate::secrets::redact(text).into_bytes(),
+            Err(_) => content.as_ref().to_vec(),
+        };
 
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|e| {
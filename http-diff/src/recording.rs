@@ -0,0 +1,369 @@
+//! Record and replay of HTTP responses
+//!
+//! A run can record every response it receives into a named recording stored
+//! in the cache directory of the [`OutputManager`]. A later run can serve a
+//! recording as a virtual environment through [`ReplayClient`], so a live
+//! environment can be compared against responses captured earlier without
+//! contacting the recorded environment again.
+
+use crate::config::{Route, UserData};
+use crate::error::{HttpDiffError, Result};
+use crate::execution::cookies::CookieJar;
+use crate::output_manager::{OutputCategory, OutputManager};
+use crate::traits::HttpClient;
+use crate::types::HttpResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Subdirectory of the cache directory holding recordings
+pub const RECORDINGS_DIR: &str = "recordings";
+
+/// A response captured for one route, user data row and environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub route: String,
+    pub user_index: usize,
+    pub environment: String,
+    /// The user data row the request was made with
+    pub user_data: HashMap<String, String>,
+    pub response: HttpResponse,
+}
+
+/// A named set of recorded responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub name: String,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    pub responses: Vec<RecordedResponse>,
+}
+
+impl Recording {
+    /// Path of the recording file for `name`
+    pub fn path(output_manager: &OutputManager, name: &str) -> Result<PathBuf> {
+        if name.is_empty()
+            || name.starts_with('.')
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(HttpDiffError::invalid_config(format!(
+                "Invalid recording name '{}': use letters, digits, '-', '_' and '.'",
+                name
+            )));
+        }
+        Ok(output_manager
+            .category_path(OutputCategory::Cache)
+            .join(RECORDINGS_DIR)
+            .join(format!("{}.json", name)))
+    }
+
+    /// Load a recording by name
+    pub fn load(output_manager: &OutputManager, name: &str) -> Result<Self> {
+        let path = Self::path(output_manager, name)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            HttpDiffError::invalid_config(format!(
+                "Cannot read recording '{}' at {}: {}",
+                name,
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            HttpDiffError::invalid_config(format!("Recording '{}' is not valid: {}", name, e))
+        })
+    }
+
+    /// Save the recording, returning the path written
+    ///
+    /// Resolved secrets are redacted from the stored responses.
+    pub fn save(&self, output_manager: &OutputManager) -> Result<PathBuf> {
+        let path = Self::path(output_manager, &self.name)?;
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| HttpDiffError::general(format!("Failed to serialize recording: {}", e)))?;
+        output_manager.write_file_atomic(&path, content)?;
+        Ok(path)
+    }
+
+    /// Environments with recorded responses, sorted by name
+    pub fn environments(&self) -> Vec<String> {
+        let mut environments: Vec<String> = self
+            .responses
+            .iter()
+            .map(|r| r.environment.clone())
+            .collect();
+        environments.sort();
+        environments.dedup();
+        environments
+    }
+
+    /// Find the response recorded for a route and environment with this user data
+    ///
+    /// A recorded row matches when all its values appear in `user_data`, which
+    /// may also hold values extracted by chained routes.
+    pub fn find(&self, route: &str, environment: &str, user_data: &UserData) -> Option<&HttpResponse> {
+        self.responses
+            .iter()
+            .filter(|r| r.route == route && r.environment == environment)
+            .find(|r| {
+                r.user_data
+                    .iter()
+                    .all(|(key, value)| user_data.data.get(key) == Some(value))
+            })
+            .map(|r| &r.response)
+    }
+}
+
+/// Collects responses while a run executes
+#[derive(Debug)]
+pub struct ResponseRecorder {
+    name: String,
+    responses: Mutex<Vec<RecordedResponse>>,
+}
+
+impl ResponseRecorder {
+    /// Create a recorder for a recording named `name`
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            responses: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record a response
+    pub fn record(
+        &self,
+        route: &str,
+        user_index: usize,
+        environment: &str,
+        user_data: &UserData,
+        response: &HttpResponse,
+    ) {
+        if let Ok(mut responses) = self.responses.lock() {
+            responses.push(RecordedResponse {
+                route: route.to_string(),
+                user_index,
+                environment: environment.to_string(),
+                user_data: user_data.data.clone(),
+                response: response.clone(),
+            });
+        }
+    }
+
+    /// The recording of every response recorded so far, ordered by route, user and environment
+    pub fn recording(&self) -> Recording {
+        let mut responses = self
+            .responses
+            .lock()
+            .map(|responses| responses.clone())
+            .unwrap_or_default();
+        responses.sort_by(|a, b| {
+            (&a.route, a.user_index, &a.environment).cmp(&(&b.route, b.user_index, &b.environment))
+        });
+        Recording {
+            name: self.name.clone(),
+            recorded_at: chrono::Utc::now(),
+            responses,
+        }
+    }
+}
+
+/// Name of the virtual environment serving `environment` from `recording`
+pub fn virtual_environment_name(environment: &str, recording: &str) -> String {
+    format!("{}@{}", environment, recording)
+}
+
+/// A recorded environment served as a virtual environment
+#[derive(Debug, Clone)]
+struct ReplaySource {
+    recording: Arc<Recording>,
+    environment: String,
+}
+
+/// HTTP client serving virtual environments from recordings
+///
+/// Requests to any other environment are sent by the wrapped client, so a run
+/// can mix live and recorded environments.
+#[derive(Debug, Clone)]
+pub struct ReplayClient<C> {
+    inner: C,
+    sources: HashMap<String, ReplaySource>,
+}
+
+impl<C: HttpClient> ReplayClient<C> {
+    /// Wrap a client without any virtual environment
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Serve `environment` of `recording` as the virtual environment `name`
+    #[must_use]
+    pub fn with_replay<S: Into<String>>(
+        mut self,
+        name: S,
+        recording: Arc<Recording>,
+        environment: &str,
+    ) -> Self {
+        self.sources.insert(
+            name.into(),
+            ReplaySource {
+                recording,
+                environment: environment.to_string(),
+            },
+        );
+        self
+    }
+
+    /// The recorded response for a virtual environment, or `None` for live environments
+    fn replay(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+    ) -> Option<Result<HttpResponse>> {
+        let source = self.sources.get(environment)?;
+        Some(
+            source
+                .recording
+                .find(&route.name, &source.environment, user_data)
+                .cloned()
+                .ok_or_else(|| {
+                    HttpDiffError::request_failed(
+                        route.name.clone(),
+                        environment.to_string(),
+                        format!(
+                            "No response recorded in '{}' for environment '{}' and this user data",
+                            source.recording.name, source.environment
+                        ),
+                    )
+                }),
+        )
+    }
+}
+
+impl<C: HttpClient> HttpClient for ReplayClient<C> {
+    async fn execute_request(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+    ) -> Result<HttpResponse> {
+        match self.replay(route, environment, user_data) {
+            Some(replayed) => replayed,
+            None => {
+                self.inner
+                    .execute_request(route, environment, user_data)
+                    .await
+            }
+        }
+    }
+
+    async fn execute_request_with_cookies(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+        cookie_jar: &CookieJar,
+    ) -> Result<HttpResponse> {
+        match self.replay(route, environment, user_data) {
+            Some(replayed) => replayed,
+            None => {
+                self.inner
+                    .execute_request_with_cookies(route, environment, user_data, cookie_jar)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mocks::MockHttpClient;
+
+    fn user(id: &str) -> UserData {
+        UserData::new(HashMap::from([("userId".to_string(), id.to_string())]))
+    }
+
+    fn route(name: &str) -> Route {
+        toml::from_str(&format!(
+            "name = \"{}\"\nmethod = \"GET\"\npath = \"/users/{{userId}}\"",
+            name
+        ))
+        .unwrap()
+    }
+
+    fn response(body: &str) -> HttpResponse {
+        HttpResponse::new(
+            200,
+            HashMap::new(),
+            body.to_string(),
+            "https://prod.example.com/users".to_string(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_manager = OutputManager::new(dir.path());
+
+        let recorder = ResponseRecorder::new("last-week");
+        recorder.record("users", 1, "prod", &user("2"), &response("second"));
+        recorder.record("users", 0, "prod", &user("1"), &response("first"));
+        let path = recorder.recording().save(&output_manager).unwrap();
+        assert!(path.ends_with(".http-diff/cache/recordings/last-week.json"));
+
+        let recording = Recording::load(&output_manager, "last-week").unwrap();
+        assert_eq!(recording.responses[0].user_index, 0);
+        assert_eq!(recording.environments(), vec!["prod"]);
+
+        // Values extracted by chained routes do not prevent a match
+        let mut chained = user("2");
+        chained.data.insert("token".to_string(), "abc".to_string());
+        assert_eq!(
+            recording.find("users", "prod", &chained).map(|r| r.body.as_str()),
+            Some("second")
+        );
+        assert!(recording.find("users", "staging", &user("1")).is_none());
+
+        assert!(Recording::path(&output_manager, "../escape").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_client_mixes_live_and_recorded_environments() {
+        let recorder = ResponseRecorder::new("snapshot");
+        recorder.record("users", 0, "prod", &user("1"), &response("recorded"));
+        let recording = Arc::new(recorder.recording());
+
+        let live =
+            MockHttpClient::new().with_response("users:staging".to_string(), response("live"));
+        let client = ReplayClient::new(live).with_replay(
+            virtual_environment_name("prod", "snapshot"),
+            recording,
+            "prod",
+        );
+
+        let replayed = client
+            .execute_request(&route("users"), "prod@snapshot", &user("1"))
+            .await
+            .unwrap();
+        assert_eq!(replayed.body, "recorded");
+
+        let live = client
+            .execute_request(&route("users"), "staging", &user("1"))
+            .await
+            .unwrap();
+        assert_eq!(live.body, "live");
+
+        let err = client
+            .execute_request(&route("users"), "prod@snapshot", &user("9"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No response recorded in 'snapshot'"));
+    }
+}
@@ -2,7 +2,9 @@ use super::table::{cells, TableBuilder, TableStyle};
 use super::text_formatter::TextFormatter;
 /// Comparison-specific formatting for diff output - handles presentation of differences
 use crate::comparison::analyzer::{BodyDiff, HeaderDiff};
-use crate::types::DiffViewStyle;
+use crate::comparison::json_diff::JsonChange;
+use crate::types::{ComparisonResult, DiffViewStyle, EnvironmentLatency};
+use crate::utils::response_summary::{format_duration_ms, format_size};
 
 /// Formatter for comparison differences - handles all presentation logic
 pub struct ComparisonFormatter {
@@ -125,6 +127,87 @@ impl ComparisonFormatter {
         }
     }
 
+    /// Format structural JSON changes, one line per change up to `limit`
+    pub fn format_json_changes(&self, changes: &[JsonChange], limit: usize) -> String {
+        let mut output = String::new();
+        for change in changes.iter().take(limit) {
+            output.push_str(&format!("     {} {}\n", change.kind.symbol(), change));
+        }
+        if changes.len() > limit {
+            output.push_str(&format!(
+                "     … and {} more changes\n",
+                changes.len() - limit
+            ));
+        }
+        output
+    }
+
+    /// Format per-environment request timing of a comparison, if it was measured
+    pub fn format_timing_table(&self, result: &ComparisonResult) -> Option<String> {
+        let environments = result.get_environment_names_ordered();
+        let timed: Vec<_> = environments
+            .iter()
+            .filter_map(|env| {
+                let response = result.responses.get(env)?;
+                Some((env, response, response.timing.as_ref()?))
+            })
+            .collect();
+        if timed.is_empty() {
+            return None;
+        }
+        let show_attempts = timed.iter().any(|(_, response, _)| response.was_retried());
+
+        let mut table = TableBuilder::new();
+        table.apply_style(TableStyle::Diff);
+        let mut headers = vec![
+            "Environment",
+            "Status",
+            "DNS",
+            "Connect",
+            "TTFB",
+            "Total",
+            "Size",
+        ];
+        if show_attempts {
+            headers.push("Attempts");
+        }
+        table.headers(headers);
+        for (env, response, timing) in timed {
+            let mut row = vec![
+                env.to_uppercase(),
+                response.status.to_string(),
+                format_duration_ms(timing.dns_ms),
+                format_duration_ms(timing.connect_ms),
+                format_duration_ms(Some(timing.ttfb_ms)),
+                format_duration_ms(Some(timing.total_ms)),
+                format_size(timing.size_bytes),
+            ];
+            if show_attempts {
+                row.push(response.attempts.to_string());
+            }
+            table.row(row);
+        }
+
+        Some(table.build())
+    }
+
+    /// Format request latency statistics per environment
+    pub fn format_latency_summary(&self, latency: &[EnvironmentLatency]) -> String {
+        let mut table = TableBuilder::new();
+        table.apply_style(TableStyle::Diff);
+        table.headers(vec!["Environment", "Requests", "Avg", "p95", "Max"]);
+        for env in latency {
+            table.row(vec![
+                env.environment.to_uppercase(),
+                env.requests.to_string(),
+                format_duration_ms(Some(env.avg_ms)),
+                format_duration_ms(Some(env.p95_ms)),
+                format_duration_ms(Some(env.max_ms)),
+            ]);
+        }
+        table.build()
+    }
+
     /// Format unified diff for body content
     fn format_unified_body_diff(&self, text1: &str, text2: &str, env1: &str, env2: &str) -> String {
         let diff_output = self.text_formatter.unified_diff(text1, text2, env1, env2);
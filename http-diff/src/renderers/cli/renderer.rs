@@ -5,11 +5,15 @@ use crate::analysis::{ErrorAnalyzer, ErrorClassifierImpl};
 use crate::comparison::analyzer::{BodyDiff, HeaderDiff};
 use crate::renderers::OutputRenderer;
 use crate::types::{
-    ComparisonResult, DiffViewStyle, DifferenceCategory, ErrorSummary, ExecutionResult,
+    ComparisonResult, DiffViewStyle, DifferenceCategory, EnvironmentLatency, ErrorSummary,
+    ExecutionResult,
 };
 use crate::utils::environment_utils::EnvironmentValidator;
 use std::collections::HashMap;
 
+/// Maximum number of structural JSON changes listed per difference
+const MAX_LISTED_JSON_CHANGES: usize = 20;
+
 /// CLI renderer that produces the original colored terminal output
 pub struct CliRenderer {
     /// Whether to include error analysis in output
@@ -165,6 +169,52 @@ fn format_comparison_results(
         output.push_str("No test scenarios found\n");
     }
 
+    // List the ignore rules that excluded body fields so reviewers know what was skipped
+    let mut ignored_fields: Vec<&str> = Vec::new();
+    for field in results.iter().flat_map(|r| &r.ignored_fields) {
+        if !ignored_fields.contains(&field.as_str()) {
+            ignored_fields.push(field);
+        }
+    }
+    if !ignored_fields.is_empty() {
+        output.push_str(&format!(
+            "🙈 Ignored fields: {}\n",
+            ignored_fields.join(", ")
+        ));
+    }
+
+    let assertion_total: usize = results.iter().map(|r| r.assertions.len()).sum();
+    if assertion_total > 0 {
+        let assertion_failed: usize = results.iter().map(|r| r.failed_assertions().count()).sum();
+        output.push_str(&format!(
+            "🧪 Assertions:    {}/{} passed\n",
+            assertion_total - assertion_failed,
+            assertion_total
+        ));
+    }
+
+    let retried: Vec<u32> = results
+        .iter()
+        .flat_map(|r| r.responses.values())
+        .filter(|response| response.was_retried())
+        .map(|response| response.attempts)
+        .collect();
+    if !retried.is_empty() {
+        output.push_str(&format!(
+            "🔁 Retried:       {} request(s), {} extra attempt(s)\n",
+            retried.len(),
+            retried.iter().map(|attempts| attempts - 1).sum::<u32>()
+        ));
+    }
+
+    let latency = EnvironmentLatency::from_comparison_results(results);
+    if !latency.is_empty() {
+        output.push_str(&format!(
+            "\n⏱️  Latency by environment:\n{}\n",
+            formatter.format_latency_summary(&latency)
+        ));
+    }
+
     // Generate error analysis using clean business logic + presentation separation
     let error_summary = ErrorSummary::from_comparison_results(results);
     if error_summary.failed_requests > 0 && include_errors {
@@ -174,6 +224,27 @@ fn format_comparison_results(
         output.push_str(&error_renderer.render_error_analysis(&error_analysis));
     }
 
+    // Assertion failures are listed even for routes whose responses are identical
+    if results.iter().any(|r| r.has_assertion_failures()) {
+        output.push_str("\nASSERTION FAILURES\n");
+        output.push_str("══════════════════\n");
+        for result in results.iter().filter(|r| r.has_assertion_failures()) {
+            output.push_str(&format!(
+                "🧪 Route: {} | User: {}\n",
+                result.route_name,
+                format_user_context(&result.user_context)
+            ));
+            for assertion in result.failed_assertions() {
+                output.push_str(&format!(
+                    "  ❌ [{}] {}: {}\n",
+                    assertion.environment,
+                    assertion.assertion,
+                    assertion.message.as_deref().unwrap_or("failed")
+                ));
+            }
+        }
+    }
+
     if different_count > 0 {
         output.push_str("\nDIFFERENCES FOUND\n");
         output.push_str("═════════════════\n");
@@ -187,6 +258,19 @@ fn format_comparison_results(
     output
 }
 
+/// Format user context concisely as `key=value` pairs
+fn format_user_context(user_context: &HashMap<String, String>) -> String {
+    if user_context.is_empty() {
+        "default".to_string()
+    } else {
+        user_context
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 /// Format route differences with improved visual grouping
 fn format_route_group(
     result: &ComparisonResult,
@@ -195,21 +279,22 @@ fn format_route_group(
 ) -> String {
     let mut output = String::new();
 
-    // Format user context more concisely
-    let user_context = if result.user_context.is_empty() {
-        "default".to_string()
-    } else {
-        result
-            .user_context
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join(", ")
-    };
-
     // Simple route header only
-    let route_header = format!("📍 Route: {} | User: {}", result.route_name, user_context);
+    let route_header = format!(
+        "📍 Route: {} | User: {}",
+        result.route_name,
+        format_user_context(&result.user_context)
+    );
     output.push_str(&format!("{}\n", route_header));
+    if !result.ignored_fields.is_empty() {
+        output.push_str(&format!(
+            "   Ignored fields: {}\n",
+            result.ignored_fields.join(", ")
+        ));
+    }
+    if let Some(timing_table) = formatter.format_timing_table(result) {
+        output.push_str(&format!("{}\n", timing_table));
+    }
 
     // Add detailed diff output using consistent environment ordering
     let resolver = result.create_environment_resolver();
@@ -229,6 +314,7 @@ fn format_route_group(
                 DifferenceCategory::Status => "🚨",
                 DifferenceCategory::Headers => "📝",
                 DifferenceCategory::Body => "📄",
+                DifferenceCategory::Latency => "⏱️",
             };
 
             if let Some(diff_data) = &difference.diff_output {
@@ -270,8 +356,8 @@ fn format_route_group(
                             output.push_str(&format!("  {} {}\n", icon, difference.description));
                         }
                     }
-                    DifferenceCategory::Status => {
-                        // Status differences don't need special formatting
+                    DifferenceCategory::Status | DifferenceCategory::Latency => {
+                        // Status and latency differences don't need special formatting
                         output.push_str(&format!("  {} {}\n", icon, difference.description));
                     }
                 }
@@ -279,6 +365,10 @@ fn format_route_group(
                 // Show at least the description if no diff output is available
                 output.push_str(&format!("  {} {}\n", icon, difference.description));
             }
+
+            if let Some(changes) = difference.json_changes.as_ref().filter(|c| !c.is_empty()) {
+                output.push_str(&formatter.format_json_changes(changes, MAX_LISTED_JSON_CHANGES));
+            }
         }
     }
 
@@ -301,6 +391,8 @@ mod tests {
                 body: "test response".to_string(),
                 url: "https://test.example.com".to_string(),
                 curl_command: "curl test".to_string(),
+                timing: None,
+                attempts: 1,
             },
         );
 
@@ -318,6 +410,8 @@ mod tests {
             has_errors: false,
             error_bodies: None,
             base_environment: None,
+            ignored_fields: Vec::new(),
+            assertions: Vec::new(),
         }
     }
 
@@ -337,4 +431,132 @@ mod tests {
         assert!(output.contains("✅ Identical:"));
         assert!(output.contains("1/1 (100.0%)"));
     }
+
+    #[test]
+    fn test_cli_renderer_lists_ignored_fields() {
+        use crate::types::ExecutionResult;
+
+        let mut result = create_test_result();
+        result.ignored_fields = vec!["$.meta.generatedAt".to_string()];
+
+        let execution_result = ExecutionResult {
+            comparisons: vec![result],
+            progress: crate::execution::progress::ProgressTracker::new(1),
+            errors: Vec::new(),
+            chain_metadata: None,
+        };
+        let output = CliRenderer::new().render(&execution_result);
+
+        assert!(output.contains("Ignored fields: $.meta.generatedAt"));
+    }
+
+    #[test]
+    fn test_cli_renderer_lists_failed_assertions() {
+        use crate::types::{AssertionResult, ExecutionResult};
+
+        let mut result = create_test_result();
+        result.assertions = vec![
+            AssertionResult::passed("dev", "status is 200".to_string()),
+            AssertionResult::failed(
+                "prod",
+                "$.user.plan equals \"premium\"".to_string(),
+                "got \"basic\"".to_string(),
+            ),
+        ];
+
+        let execution_result = ExecutionResult {
+            comparisons: vec![result],
+            progress: crate::execution::progress::ProgressTracker::new(1),
+            errors: Vec::new(),
+            chain_metadata: None,
+        };
+        let output = CliRenderer::new().render(&execution_result);
+
+        assert!(output.contains("🧪 Assertions:    1/2 passed"));
+        assert!(output.contains("ASSERTION FAILURES"));
+        assert!(output.contains("❌ [prod] $.user.plan equals \"premium\": got \"basic\""));
+    }
+
+    #[test]
+    fn test_cli_renderer_shows_timing() {
+        use crate::types::{Difference, ExecutionResult, HttpResponse, ResponseTiming};
+
+        let timed = |total_ms| {
+            HttpResponse::new(
+                200,
+                HashMap::new(),
+                "ok".to_string(),
+                "https://example.com".to_string(),
+                "curl".to_string(),
+            )
+            .with_timing(ResponseTiming {
+                ttfb_ms: total_ms / 2,
+                total_ms,
+                size_bytes: 2048,
+                ..Default::default()
+            })
+        };
+        let mut result = ComparisonResult::new("slow-route".to_string(), HashMap::new());
+        result.add_response("prod".to_string(), timed(100));
+        result.add_response("staging".to_string(), timed(500).with_attempts(3));
+        result.add_difference(Difference::new(
+            DifferenceCategory::Latency,
+            "Latency differs between prod and staging: 100ms vs 500ms (staging is 5.0x slower)"
+                .to_string(),
+        ));
+
+        let execution_result = ExecutionResult {
+            comparisons: vec![result],
+            progress: crate::execution::progress::ProgressTracker::new(1),
+            errors: Vec::new(),
+            chain_metadata: None,
+        };
+        let output = CliRenderer::new().render(&execution_result);
+
+        assert!(output.contains("Latency by environment"));
+        assert!(output.contains("STAGING"));
+        assert!(output.contains("250ms"));
+        assert!(output.contains("2.0 KB"));
+        assert!(output.contains("🔁 Retried:       1 request(s), 2 extra attempt(s)"));
+        assert!(output.contains("Attempts"));
+        assert!(output.contains("⏱️ Latency differs between prod and staging"));
+    }
+
+    #[test]
+    fn test_cli_renderer_lists_json_changes() {
+        use crate::comparison::analyzer::DifferenceAnalyzer;
+        use crate::types::{ExecutionResult, HttpResponse};
+
+        let response = |body: &str| {
+            HttpResponse::new(
+                200,
+                HashMap::new(),
+                body.to_string(),
+                "https://example.com".to_string(),
+                "curl".to_string(),
+            )
+        };
+        let (dev, prod) = (
+            response(r#"{"user":{"plan":"basic"}}"#),
+            response(r#"{"user":{"plan":"premium"}}"#),
+        );
+        let analyzer = DifferenceAnalyzer::new(vec![], true, 50_000);
+
+        let mut result = ComparisonResult::new("user-route".to_string(), HashMap::new());
+        for difference in analyzer.analyze_responses(&dev, &prod, "dev", "prod", false) {
+            result.add_difference(difference);
+        }
+        result.add_response("dev".to_string(), dev);
+        result.add_response("prod".to_string(), prod);
+
+        let execution_result = ExecutionResult {
+            comparisons: vec![result],
+            progress: crate::execution::progress::ProgressTracker::new(1),
+            errors: Vec::new(),
+            chain_metadata: None,
+        };
+        let output = CliRenderer::new().render(&execution_result);
+
+        assert!(output.contains("~ `$.user.plan` changed from `basic` to `premium`"));
+    }
 }
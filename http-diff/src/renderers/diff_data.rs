@@ -4,6 +4,8 @@
 //! without any formatting concerns. These structures can be used by different
 //! renderers (CLI, TUI, etc.) to apply their own presentation logic.
 
+use crate::comparison::json_diff::JsonChange;
+
 /// Type of operation in a diff row
 #[derive(Debug, Clone, PartialEq)]
 pub enum DiffOperation {
@@ -133,6 +135,8 @@ pub struct BodyDiffData {
     pub has_differences: bool,
     /// Summary information for large responses
     pub summary: Option<BodyDiffSummary>,
+    /// Structural JSON changes (empty for non-JSON bodies)
+    pub changes: Vec<JsonChange>,
 }
 
 // BodyDiffSummary moved to utils::response_summary::ResponseDiffSummary to avoid duplication
@@ -149,6 +153,7 @@ impl BodyDiffData {
             total_size: 0,
             has_differences: false,
             summary: None,
+            changes: Vec::new(),
         }
     }
 
@@ -167,6 +172,7 @@ impl BodyDiffData {
             total_size,
             has_differences: true, // Large responses always have differences if we're showing a summary
             summary: Some(summary),
+            changes: Vec::new(),
         }
     }
 
@@ -187,6 +193,11 @@ impl BodyDiffData {
     pub fn set_total_size(&mut self, size: usize) {
         self.total_size = size;
     }
+
+    /// Set the structural JSON changes
+    pub fn set_changes(&mut self, changes: Vec<JsonChange>) {
+        self.changes = changes;
+    }
 }
 
 /// Combined diff data for a complete comparison result
@@ -64,7 +64,10 @@ impl DiffProcessor {
                     // Use structured data directly instead of JSON deserialization
                     if let Some(ref body_diff) = difference.body_diff {
                         let envs = self.extract_environment_names(result)?;
-                        let body_diff_data = self.process_body_diff(body_diff, &envs.0, &envs.1);
+                        let mut body_diff_data = self.process_body_diff(body_diff, &envs.0, &envs.1);
+                        if let Some(ref changes) = difference.json_changes {
+                            body_diff_data.set_changes(changes.clone());
+                        }
                         diff_data.set_body(body_diff_data);
                     } else if let Some(ref diff_output) = difference.diff_output {
                         // Fallback for backward compatibility
@@ -3,8 +3,9 @@
 use super::super::ReportMetadata;
 use super::json_diff_renderer::JsonDiffRenderer;
 use crate::renderers::diff_processor::DiffProcessor;
-use crate::types::ComparisonResult;
+use crate::types::{ComparisonResult, DifferenceCategory, EnvironmentLatency};
 use crate::utils::environment_utils::EnvironmentOrderResolver;
+use crate::utils::response_summary::{format_duration_ms, format_size};
 
 /// Reusable HTML components for report generation
 pub struct HtmlComponents;
@@ -80,6 +81,7 @@ impl HtmlComponents {
                     <span class="stat-value">{:.1}s</span>
                 </div>
             </div>
+            {}
         </div>
         "#,
             metadata.timestamp.format("%Y-%m-%d %H:%M:%S %Z"),
@@ -145,7 +147,36 @@ impl HtmlComponents {
             },
             total_tests,
             metadata.environments.join(", "),
-            metadata.execution_duration.as_secs_f64()
+            metadata.execution_duration.as_secs_f64(),
+            Self::render_latency_table(results)
+        )
+    }
+
+    /// Render request latency statistics per environment
+    fn render_latency_table(results: &[ComparisonResult]) -> String {
+        let latency = EnvironmentLatency::from_comparison_results(results);
+        if latency.is_empty() {
+            return String::new();
+        }
+
+        let rows = latency
+            .iter()
+            .map(|env| {
+                format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    Self::escape_html(&env.environment.to_uppercase()),
+                    env.requests,
+                    format_duration_ms(Some(env.avg_ms)),
+                    format_duration_ms(Some(env.p95_ms)),
+                    format_duration_ms(Some(env.max_ms))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("");
+
+        format!(
+            r#"<table class="latency-table"><thead><tr><th>Environment</th><th>Requests</th><th>Avg</th><th>p95</th><th>Max</th></tr></thead><tbody>{}</tbody></table>"#,
+            rows
         )
     }
 
@@ -268,8 +299,11 @@ impl HtmlComponents {
                     if let Some(body_diff) = &diff_data.body {
                         if body_diff.has_differences {
                             let diff_summary = json_renderer.render_diff_summary(body_diff);
-                            let diff_content =
-                                json_renderer.render_body_diff(body_diff, show_unchanged);
+                            let diff_content = format!(
+                                "{}{}",
+                                json_renderer.render_json_changes(body_diff),
+                                json_renderer.render_body_diff(body_diff, show_unchanged)
+                            );
                             Self::render_different_route_content(
                                 result,
                                 &diff_summary,
@@ -300,11 +334,15 @@ impl HtmlComponents {
                             <span class="route-context">{}</span>
                         </div>
                         <div class="route-status-codes">{}</div>
+                        <div class="route-timings">{}</div>
                     </div>
                 </div>
             </div>
             <div class="route-diff-body">
                 {}
+                {}
+                {}
+                {}
             </div>
         </div>
         "#,
@@ -314,10 +352,81 @@ impl HtmlComponents {
             status_badge,
             user_context,
             status_codes,
+            Self::format_timings(result, &resolver),
+            Self::render_ignored_fields(result),
+            Self::render_assertions(result),
+            Self::render_latency_differences(result),
             expandable_content
         )
     }
 
+    /// Render the ignore rules that excluded body fields from comparison
+    fn render_ignored_fields(result: &ComparisonResult) -> String {
+        if result.ignored_fields.is_empty() {
+            return String::new();
+        }
+
+        let fields = result
+            .ignored_fields
+            .iter()
+            .map(|field| format!("<code>{}</code>", Self::escape_html(field)))
+            .collect::<Vec<_>>()
+            .join("");
+
+        format!(
+            r#"<div class="ignored-fields"><span class="ignored-label">Ignored fields:</span> {}</div>"#,
+            fields
+        )
+    }
+
+    /// Render the outcome of each route assertion per environment
+    fn render_assertions(result: &ComparisonResult) -> String {
+        if result.assertions.is_empty() {
+            return String::new();
+        }
+
+        let items = result
+            .assertions
+            .iter()
+            .map(|assertion| {
+                let message = assertion
+                    .message
+                    .as_ref()
+                    .map(|m| format!(" — {}", Self::escape_html(m)))
+                    .unwrap_or_default();
+                format!(
+                    r#"<li class="assertion {}">{} <span class="assertion-env">[{}]</span> <code>{}</code>{}</li>"#,
+                    if assertion.passed { "passed" } else { "failed" },
+                    if assertion.passed { "✅" } else { "❌" },
+                    Self::escape_html(&assertion.environment),
+                    Self::escape_html(&assertion.assertion),
+                    message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("");
+
+        format!(
+            r#"<div class="assertions"><span class="assertions-label">Assertions:</span><ul>{}</ul></div>"#,
+            items
+        )
+    }
+
+    /// Render latency regressions detected between environments
+    fn render_latency_differences(result: &ComparisonResult) -> String {
+        result
+            .differences
+            .iter()
+            .filter(|d| d.category == DifferenceCategory::Latency)
+            .map(|d| {
+                format!(
+                    r#"<div class="latency-difference">⏱ {}</div>"#,
+                    Self::escape_html(&d.description)
+                )
+            })
+            .collect()
+    }
+
     /// Get route status for filtering
     fn get_route_status(result: &ComparisonResult) -> &'static str {
         if result.has_errors {
@@ -379,6 +488,34 @@ impl HtmlComponents {
             .join(" ")
     }
 
+    /// Format per-environment request timing, with the phase breakdown as a tooltip
+    fn format_timings(result: &ComparisonResult, resolver: &EnvironmentOrderResolver) -> String {
+        result
+            .get_ordered_environment_names(resolver)
+            .iter()
+            .filter_map(|env| {
+                let response = result.responses.get(env)?;
+                let timing = response.timing.as_ref()?;
+                let retries = if response.was_retried() {
+                    format!(" ({} attempts)", response.attempts)
+                } else {
+                    String::new()
+                };
+                Some(format!(
+                    r#"<span class="timing" title="DNS {} · Connect {} · TTFB {} · {}">⏱ {}: {}{}</span>"#,
+                    format_duration_ms(timing.dns_ms),
+                    format_duration_ms(timing.connect_ms),
+                    format_duration_ms(Some(timing.ttfb_ms)),
+                    format_size(timing.size_bytes),
+                    Self::escape_html(&env.to_uppercase()),
+                    format_duration_ms(Some(timing.total_ms)),
+                    retries
+                ))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// Render curl commands for a route with shared resolver
     fn render_curl_commands(result: &ComparisonResult, resolver: &EnvironmentOrderResolver) -> String {
         let mut curl_commands = String::new();
@@ -3,7 +3,12 @@
 //! Produces a side-by-side, GitHub-like diff using the `.json-diff-*` CSS classes
 //! referenced by the embedded stylesheet and JavaScript in `templates.rs`.
 
+use crate::comparison::json_diff::{JsonChange, JsonChangeKind};
 use crate::renderers::diff_data::{BodyDiffData, BodyDiffSummary, DiffOperation, DiffRow};
+use serde_json::Value;
+
+/// Maximum number of structural changes listed before collapsing the rest
+const MAX_LISTED_CHANGES: usize = 50;
 
 /// Renderer that converts `BodyDiffData` into HTML blocks styled specifically
 /// for JSON diffs in the report.
@@ -79,6 +84,80 @@ impl JsonDiffRenderer {
         )
     }
 
+    /// Render the structural JSON changes as a list of path-addressed changes
+    pub fn render_json_changes(&self, body_diff: &BodyDiffData) -> String {
+        if body_diff.changes.is_empty() {
+            return String::new();
+        }
+
+        let mut items: String = body_diff
+            .changes
+            .iter()
+            .take(MAX_LISTED_CHANGES)
+            .map(|change| self.render_json_change(change))
+            .collect();
+        if body_diff.changes.len() > MAX_LISTED_CHANGES {
+            items.push_str(&format!(
+                r#"<li class="json-change json-change-more">… and {} more changes</li>"#,
+                body_diff.changes.len() - MAX_LISTED_CHANGES
+            ));
+        }
+
+        format!(
+            r#"
+        <div class="json-changes">
+            <h5>Structural changes ({})</h5>
+            <ul>{}</ul>
+        </div>
+        "#,
+            body_diff.changes.len(),
+            items
+        )
+    }
+
+    fn render_json_change(&self, change: &JsonChange) -> String {
+        let value = |v: &Option<Value>| {
+            let text = match v {
+                Some(Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            };
+            format!("<code>{}</code>", Self::escape_html(&text))
+        };
+
+        let (kind_class, label, detail) = match change.kind {
+            JsonChangeKind::Added => ("added", "added", value(&change.new_value)),
+            JsonChangeKind::Removed => ("removed", "removed", value(&change.old_value)),
+            JsonChangeKind::Changed => (
+                "changed",
+                "changed",
+                format!(
+                    "{} → {}",
+                    value(&change.old_value),
+                    value(&change.new_value)
+                ),
+            ),
+            JsonChangeKind::TypeChanged => (
+                "type_changed",
+                "type changed",
+                format!(
+                    "{} → {}",
+                    value(&change.old_value),
+                    value(&change.new_value)
+                ),
+            ),
+        };
+
+        format!(
+            r#"<li class="json-change json-change-{}" title="{}"><span class="json-change-kind">{}</span> <code>{}</code> {}</li>"#,
+            kind_class,
+            Self::escape_html(&change.pointer),
+            label,
+            Self::escape_html(&change.path),
+            detail
+        )
+    }
+
     fn render_large_response_summary(
         &self,
         env1: &str,
@@ -239,4 +318,22 @@ mod tests {
         assert!(html.contains("json-row-removed"));
         assert!(html.contains("json-row-changed"));
     }
+
+    #[test]
+    fn renders_structural_changes() {
+        let r = JsonDiffRenderer::new();
+        let mut diff = BodyDiffData::new("env1".into(), "env2".into());
+        assert!(r.render_json_changes(&diff).is_empty());
+
+        diff.set_changes(crate::comparison::JsonDiffer::new().diff(
+            &serde_json::json!({"user": {"plan": "basic"}}),
+            &serde_json::json!({"user": {"plan": "<premium>"}}),
+        ));
+
+        let html = r.render_json_changes(&diff);
+        assert!(html.contains("Structural changes (1)"));
+        assert!(html.contains("json-change-changed"));
+        assert!(html.contains("<code>$.user.plan</code>"));
+        assert!(html.contains("<code>basic</code> → <code>&lt;premium&gt;</code>"));
+    }
 }
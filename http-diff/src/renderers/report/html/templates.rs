@@ -1056,6 +1056,78 @@ impl HtmlTemplate {
             background: var(--bg-primary);
         }
         
+        .ignored-fields {
+            padding: var(--space-3) var(--space-6);
+            color: var(--color-gray-600);
+            font-size: var(--text-sm);
+            border-bottom: 1px solid var(--color-gray-100);
+        }
+        
+        .ignored-fields code {
+            font-family: var(--font-family-mono);
+            background: var(--color-gray-100);
+            padding: var(--space-1) var(--space-2);
+            border-radius: var(--radius-sm);
+            margin-right: var(--space-1);
+        }
+        
+        .route-timings {
+            display: flex;
+            flex-wrap: wrap;
+            gap: var(--space-2);
+            font-size: var(--text-sm);
+            color: var(--color-gray-600);
+        }
+        
+        .latency-difference {
+            padding: var(--space-3) var(--space-6);
+            color: var(--color-warning);
+            font-size: var(--text-sm);
+            border-bottom: 1px solid var(--color-gray-100);
+        }
+        
+        .latency-table {
+            width: 100%;
+            margin-top: var(--space-4);
+            border-collapse: collapse;
+            font-size: var(--text-sm);
+        }
+        
+        .latency-table th,
+        .latency-table td {
+            padding: var(--space-2) var(--space-3);
+            text-align: left;
+            border-bottom: 1px solid var(--color-gray-100);
+        }
+        
+        .latency-table th {
+            color: var(--color-gray-600);
+        }
+        
+        .assertions {
+            padding: var(--space-3) var(--space-6);
+            font-size: var(--text-sm);
+            border-bottom: 1px solid var(--color-gray-100);
+        }
+        
+        .assertions ul {
+            list-style: none;
+            margin: var(--space-2) 0 0;
+            padding: 0;
+        }
+        
+        .assertions code {
+            font-family: var(--font-family-mono);
+        }
+        
+        .assertion.failed {
+            color: var(--color-error);
+        }
+        
+        .assertion-env {
+            color: var(--color-gray-600);
+        }
+        
         /* Content type specific styling */
         .identical-route-content {
             padding: var(--space-6);
@@ -1266,6 +1338,46 @@ impl HtmlTemplate {
             border-color: var(--color-warning-border);
         }
 
+        /* Structural JSON changes (path-addressed) */
+        .json-changes {
+            padding: var(--space-4) var(--space-6);
+            border-bottom: 1px solid var(--color-gray-200);
+        }
+
+        .json-changes h5 {
+            margin: 0 0 var(--space-2) 0;
+            font-size: var(--text-sm);
+            color: var(--color-gray-700);
+        }
+
+        .json-changes ul {
+            margin: 0;
+            padding: 0;
+            list-style: none;
+        }
+
+        .json-change {
+            font-size: var(--text-sm);
+            padding: var(--space-1) 0;
+        }
+
+        .json-change code {
+            font-family: var(--font-family-mono);
+            background: var(--color-gray-100);
+            padding: 0 var(--space-1);
+            border-radius: var(--radius-sm);
+        }
+
+        .json-change-kind {
+            display: inline-block;
+            min-width: 5.5rem;
+            font-weight: var(--font-weight-semibold);
+        }
+        .json-change-added .json-change-kind { color: var(--color-success); }
+        .json-change-removed .json-change-kind { color: var(--color-error); }
+        .json-change-changed .json-change-kind,
+        .json-change-type_changed .json-change-kind { color: var(--color-warning); }
+
         .failed-route-content {
             padding: var(--space-6);
         }
@@ -0,0 +1,240 @@
+//! JSON report renderer for machine-readable reports
+//!
+//! Produces a versioned JSON document that dashboards and scripts can consume
+//! without scraping the HTML report. Map keys are emitted in sorted order so the
+//! output is deterministic across runs.
+
+use super::{ReportMetadata, ReportRenderer};
+use crate::types::{ComparisonResult, ErrorSummary};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Version of the JSON report schema, bumped on breaking changes to the layout
+pub const JSON_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Top-level JSON report document
+#[derive(Debug, Serialize)]
+struct JsonReport<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    metadata: JsonReportMetadata,
+    statistics: JsonReportStatistics,
+    error_summary: ErrorSummary,
+    results: &'a [ComparisonResult],
+}
+
+/// Serializable view of `ReportMetadata`
+#[derive(Debug, Serialize)]
+struct JsonReportMetadata {
+    generated_at: String,
+    execution_duration_ms: u64,
+    environments: Vec<String>,
+    total_routes: usize,
+    context: BTreeMap<String, String>,
+}
+
+impl From<&ReportMetadata> for JsonReportMetadata {
+    fn from(metadata: &ReportMetadata) -> Self {
+        Self {
+            generated_at: metadata.timestamp.to_rfc3339(),
+            execution_duration_ms: metadata.execution_duration.as_millis() as u64,
+            environments: metadata.environments.clone(),
+            total_routes: metadata.total_routes,
+            context: metadata
+                .context
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Outcome counts using the same mutually exclusive buckets as the CLI summary
+#[derive(Debug, Serialize)]
+struct JsonReportStatistics {
+    total: usize,
+    identical: usize,
+    different: usize,
+    failed: usize,
+}
+
+impl JsonReportStatistics {
+    fn from_results(results: &[ComparisonResult]) -> Self {
+        let failed = results.iter().filter(|r| r.has_errors).count();
+        let identical = results
+            .iter()
+            .filter(|r| r.is_identical && !r.has_errors)
+            .count();
+
+        Self {
+            total: results.len(),
+            identical,
+            different: results.len() - identical - failed,
+            failed,
+        }
+    }
+}
+
+/// JSON report renderer producing a stable, versioned schema
+pub struct JsonReportRenderer {
+    /// Whether to pretty-print the output
+    pub pretty: bool,
+}
+
+impl JsonReportRenderer {
+    /// Create a new JSON report renderer with pretty-printed output
+    pub fn new() -> Self {
+        Self { pretty: true }
+    }
+
+    /// Create a JSON report renderer producing compact single-line output
+    pub fn compact() -> Self {
+        Self { pretty: false }
+    }
+}
+
+impl Default for JsonReportRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportRenderer for JsonReportRenderer {
+    fn render_report(&self, results: &[ComparisonResult], metadata: &ReportMetadata) -> String {
+        let report = JsonReport {
+            schema_version: JSON_REPORT_SCHEMA_VERSION,
+            generator: concat!("fnc-http-diff/", env!("CARGO_PKG_VERSION")),
+            metadata: JsonReportMetadata::from(metadata),
+            statistics: JsonReportStatistics::from_results(results),
+            error_summary: ErrorSummary::from_comparison_results(results),
+            results,
+        };
+
+        // Round-trip through `Value` so HashMap-backed fields come out with sorted keys
+        let rendered = serde_json::to_value(&report).and_then(|value| {
+            if self.pretty {
+                serde_json::to_string_pretty(&value)
+            } else {
+                serde_json::to_string(&value)
+            }
+        });
+
+        match rendered {
+            Ok(json) => json,
+            Err(e) => serde_json::json!({
+                "schema_version": JSON_REPORT_SCHEMA_VERSION,
+                "error": format!("Failed to serialize report: {}", e),
+            })
+            .to_string(),
+        }
+    }
+
+    fn supported_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/json"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparison::analyzer::{BodyDiff, HeaderDiff};
+    use crate::types::{Difference, HttpResponse};
+    use std::collections::HashMap;
+
+    fn create_result(route: &str, body1: &str, body2: &str, status2: u16) -> ComparisonResult {
+        let mut result = ComparisonResult::new(route.to_string(), HashMap::new());
+        result.add_response(
+            "dev".to_string(),
+            HttpResponse::new(
+                200,
+                HashMap::new(),
+                body1.to_string(),
+                "https://dev.example.com".to_string(),
+                "curl dev".to_string(),
+            ),
+        );
+        result.add_response(
+            "prod".to_string(),
+            HttpResponse::new(
+                status2,
+                HashMap::new(),
+                body2.to_string(),
+                "https://prod.example.com".to_string(),
+                "curl prod".to_string(),
+            ),
+        );
+        result
+    }
+
+    #[test]
+    fn test_json_report_schema() {
+        let mut different = create_result("users", r#"{"a":1}"#, r#"{"a":2}"#, 200);
+        different.add_difference(Difference::with_body_diff(
+            "Body differences detected".to_string(),
+            BodyDiff {
+                normalized_body1: "{\n  \"a\": 1\n}".to_string(),
+                normalized_body2: "{\n  \"a\": 2\n}".to_string(),
+                is_large_response: false,
+                total_size: 14,
+            },
+        ));
+        different.add_difference(Difference::with_header_diff(
+            "Header differences detected".to_string(),
+            vec![HeaderDiff {
+                name: "X-Version".to_string(),
+                value1: Some("1".to_string()),
+                value2: None,
+            }],
+        ));
+        let identical = create_result("health", "ok", "ok", 200);
+        let failed = create_result("orders", "ok", "boom", 500);
+
+        let metadata = ReportMetadata::new(vec!["dev".to_string(), "prod".to_string()], 3)
+            .with_duration(std::time::Duration::from_millis(1500))
+            .with_context("config_file", "http-diff.toml");
+
+        let output =
+            JsonReportRenderer::new().render_report(&[different, identical, failed], &metadata);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(json["schema_version"], JSON_REPORT_SCHEMA_VERSION);
+        assert_eq!(json["metadata"]["execution_duration_ms"], 1500);
+        assert_eq!(json["metadata"]["context"]["config_file"], "http-diff.toml");
+        assert_eq!(json["statistics"]["total"], 3);
+        assert_eq!(json["statistics"]["identical"], 1);
+        assert_eq!(json["statistics"]["different"], 1);
+        assert_eq!(json["statistics"]["failed"], 1);
+        assert_eq!(json["error_summary"]["mixed_responses"], 1);
+
+        let differences = &json["results"][0]["differences"];
+        assert_eq!(differences[0]["category"], "Body");
+        assert_eq!(differences[0]["body_diff"]["total_size"], 14);
+        assert_eq!(differences[1]["header_diff"][0]["name"], "X-Version");
+        assert_eq!(json["results"][2]["status_codes"]["prod"], 500);
+    }
+
+    #[test]
+    fn test_json_report_is_deterministic() {
+        let results = vec![create_result("users", "a", "b", 200)];
+        let metadata = ReportMetadata::new(vec!["dev".to_string(), "prod".to_string()], 1);
+        let renderer = JsonReportRenderer::compact();
+
+        let first = renderer.render_report(&results, &metadata);
+        let second = renderer.render_report(&results, &metadata);
+
+        assert_eq!(first, second);
+        assert!(!first.contains('\n'));
+    }
+
+    #[test]
+    fn test_factory_selects_json_renderer() {
+        let renderer = crate::renderers::ReportRendererFactory::create_renderer("out.json");
+        assert_eq!(renderer.supported_extension(), "json");
+        assert_eq!(renderer.mime_type(), "application/json");
+        assert!(crate::renderers::ReportRendererFactory::supported_formats().contains(&"json"));
+    }
+}
@@ -0,0 +1,498 @@
+//! JUnit XML report renderer for CI pipelines
+//!
+//! Maps comparison results onto the JUnit XML format understood by most CI
+//! systems: one `<testsuite>` per route and one `<testcase>` per route × user
+//! combination. Differing or failed comparisons are reported as `<failure>`
+//! elements carrying a plain-text diff.
+
+use super::{ReportMetadata, ReportRenderer};
+use crate::renderers::diff_data::{DiffOperation, DiffRow};
+use crate::renderers::diff_processor::DiffProcessor;
+use crate::types::ComparisonResult;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// JUnit XML report renderer
+pub struct JunitReportRenderer {
+    /// Name of the root `<testsuites>` element
+    pub suite_name: String,
+    /// Whether header differences are included in failure output
+    pub include_headers: bool,
+}
+
+impl JunitReportRenderer {
+    /// Create a new JUnit report renderer with default settings
+    pub fn new() -> Self {
+        Self {
+            suite_name: "http-diff".to_string(),
+            include_headers: true,
+        }
+    }
+
+    /// Set the name of the root test suite collection
+    pub fn with_suite_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.suite_name = name.into();
+        self
+    }
+
+    /// Render a single route as a `<testsuite>` element
+    fn render_suite(&self, route: &str, results: &[&ComparisonResult], xml: &mut String) {
+        let failures = results.iter().filter(|r| Self::is_failure(r)).count();
+        let time: f64 = results.iter().map(|r| Self::testcase_time(r)).sum();
+
+        let _ = writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" skipped="0" time="{:.3}">"#,
+            escape_xml(route),
+            results.len(),
+            failures,
+            time
+        );
+
+        for result in results {
+            self.render_testcase(result, xml);
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    /// Render a single route × user comparison as a `<testcase>` element
+    fn render_testcase(&self, result: &ComparisonResult, xml: &mut String) {
+        let _ = write!(
+            xml,
+            r#"    <testcase name="{}" classname="{}" time="{:.3}">"#,
+            escape_xml(&Self::testcase_name(result)),
+            escape_xml(&format!("http-diff.{}", result.route_name)),
+            Self::testcase_time(result)
+        );
+        xml.push('\n');
+
+        if Self::is_failure(result) {
+            let (failure_type, message) = Self::failure_summary(result);
+            let _ = writeln!(
+                xml,
+                r#"      <failure type="{}" message="{}">{}</failure>"#,
+                failure_type,
+                escape_xml(&message),
+                escape_xml(&self.failure_details(result))
+            );
+        }
+
+        let curl_commands = Self::curl_commands(result);
+        if !curl_commands.is_empty() {
+            let _ = writeln!(
+                xml,
+                "      <system-out>{}</system-out>",
+                escape_xml(&curl_commands)
+            );
+        }
+
+        xml.push_str("    </testcase>\n");
+    }
+
+    /// A comparison fails when responses differ, any environment returned a non-2xx status
+    /// or a route assertion failed
+    fn is_failure(result: &ComparisonResult) -> bool {
+        !result.is_identical || result.has_errors || result.has_assertion_failures()
+    }
+
+    /// Duration of a comparison in seconds: the slowest environment's total request time
+    ///
+    /// Environments are requested concurrently, so the slowest one bounds the comparison.
+    /// Responses without timing data count as zero.
+    fn testcase_time(result: &ComparisonResult) -> f64 {
+        let slowest_ms = result
+            .responses
+            .keys()
+            .filter_map(|env| result.latency_ms(env))
+            .max()
+            .unwrap_or(0);
+        slowest_ms as f64 / 1000.0
+    }
+
+    /// Build a stable test case name from the user context, e.g. `userId=1, tenant=acme`
+    fn testcase_name(result: &ComparisonResult) -> String {
+        if result.user_context.is_empty() {
+            return result.route_name.clone();
+        }
+
+        let sorted: BTreeMap<_, _> = result.user_context.iter().collect();
+        sorted
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Determine the failure type and one-line message for a failed comparison
+    fn failure_summary(result: &ComparisonResult) -> (&'static str, String) {
+        if result.has_assertion_failures() {
+            let failures = result
+                .failed_assertions()
+                .map(|a| format!("[{}] {}", a.environment, a.assertion))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return (
+                "AssertionFailed",
+                format!("Assertions failed: {}", failures),
+            );
+        }
+
+        if result.is_identical {
+            let statuses = Self::status_line(result);
+            return ("HttpError", format!("Error responses: {}", statuses));
+        }
+
+        let descriptions: Vec<&str> = result
+            .differences
+            .iter()
+            .map(|d| d.description.as_str())
+            .collect();
+        let message = if descriptions.is_empty() {
+            "Responses differ between environments".to_string()
+        } else {
+            descriptions.join("; ")
+        };
+
+        ("ResponseMismatch", message)
+    }
+
+    /// Format status codes in environment order, e.g. `dev=200, prod=500`
+    fn status_line(result: &ComparisonResult) -> String {
+        result
+            .get_environment_names_ordered()
+            .iter()
+            .filter_map(|env| {
+                result
+                    .status_codes
+                    .get(env)
+                    .map(|status| format!("{}={}", env, status))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Build the plain-text failure body with status codes and diff rows
+    fn failure_details(&self, result: &ComparisonResult) -> String {
+        let mut details = String::new();
+        let _ = writeln!(details, "Status codes: {}", Self::status_line(result));
+        if !result.ignored_fields.is_empty() {
+            let _ = writeln!(
+                details,
+                "Ignored fields: {}",
+                result.ignored_fields.join(", ")
+            );
+        }
+        for assertion in result.failed_assertions() {
+            let _ = writeln!(
+                details,
+                "[Assertion] [{}] {}: {}",
+                assertion.environment,
+                assertion.assertion,
+                assertion.message.as_deref().unwrap_or("failed")
+            );
+        }
+
+        for difference in &result.differences {
+            let _ = writeln!(
+                details,
+                "[{}] {}",
+                difference.category.name(),
+                difference.description
+            );
+            for change in difference.json_changes.iter().flatten() {
+                let _ = writeln!(details, "  {} {}", change.kind.symbol(), change);
+            }
+        }
+
+        let processor = DiffProcessor::new();
+        if let Ok(diff_data) = processor.process_comparison_result(result, self.include_headers) {
+            if let Some(headers) = diff_data.headers.filter(|h| !h.is_empty()) {
+                let _ = writeln!(details, "\nHeaders ({} -> {}):", headers.env1, headers.env2);
+                for row in &headers.rows {
+                    Self::write_row(&mut details, row);
+                }
+            }
+
+            if let Some(body) = diff_data.body.filter(|b| !b.is_empty()) {
+                let _ = writeln!(details, "\nBody ({} -> {}):", body.env1, body.env2);
+                if let Some(summary) = body.summary.as_ref().filter(|_| body.is_large_response) {
+                    let _ = writeln!(
+                        details,
+                        "Large response: {} bytes ({} lines) vs {} bytes ({} lines)",
+                        summary.size1, summary.lines1, summary.size2, summary.lines2
+                    );
+                } else {
+                    for row in &body.rows {
+                        Self::write_row(&mut details, row);
+                    }
+                }
+            }
+        }
+
+        if let Some(error_bodies) = &result.error_bodies {
+            for env in result.get_environment_names_ordered() {
+                if let Some(body) = error_bodies.get(&env) {
+                    let _ = writeln!(details, "\nError response from {}:\n{}", env, body);
+                }
+            }
+        }
+
+        details
+    }
+
+    /// Write a diff row in unified-diff notation
+    fn write_row(out: &mut String, row: &DiffRow) {
+        let prefix = row
+            .context
+            .as_ref()
+            .map(|context| format!("{}: ", context))
+            .unwrap_or_default();
+        let left = row.left_content.as_deref().unwrap_or_default();
+        let right = row.right_content.as_deref().unwrap_or_default();
+
+        match row.operation {
+            DiffOperation::Unchanged => {
+                let _ = writeln!(out, "  {}{}", prefix, left);
+            }
+            DiffOperation::Removed => {
+                let _ = writeln!(out, "- {}{}", prefix, left);
+            }
+            DiffOperation::Added => {
+                let _ = writeln!(out, "+ {}{}", prefix, right);
+            }
+            DiffOperation::Changed => {
+                let _ = writeln!(out, "- {}{}", prefix, left);
+                let _ = writeln!(out, "+ {}{}", prefix, right);
+            }
+        }
+    }
+
+    /// Collect curl commands for reproducing the requests, in environment order
+    fn curl_commands(result: &ComparisonResult) -> String {
+        result
+            .get_environment_names_ordered()
+            .iter()
+            .filter_map(|env| {
+                result
+                    .responses
+                    .get(env)
+                    .map(|response| format!("# {}\n{}\n", env, response.curl_command))
+            })
+            .collect()
+    }
+}
+
+impl Default for JunitReportRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportRenderer for JunitReportRenderer {
+    fn render_report(&self, results: &[ComparisonResult], metadata: &ReportMetadata) -> String {
+        // Group by route, keeping routes and test cases in a deterministic order
+        let mut suites: BTreeMap<&str, Vec<&ComparisonResult>> = BTreeMap::new();
+        for result in results {
+            suites.entry(&result.route_name).or_default().push(result);
+        }
+        for cases in suites.values_mut() {
+            cases.sort_by_cached_key(|r| Self::testcase_name(r));
+        }
+
+        let failures = results.iter().filter(|r| Self::is_failure(r)).count();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            r#"<testsuites name="{}" tests="{}" failures="{}" errors="0" time="{:.3}" timestamp="{}">"#,
+            escape_xml(&self.suite_name),
+            results.len(),
+            failures,
+            metadata.execution_duration.as_secs_f64(),
+            metadata.timestamp.format("%Y-%m-%dT%H:%M:%S")
+        );
+
+        for (route, cases) in &suites {
+            self.render_suite(route, cases, &mut xml);
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    fn supported_extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/xml"
+    }
+}
+
+/// Escape text for use in XML attributes and element content
+///
+/// Characters that are not allowed in XML 1.0 documents (most control
+/// characters) are dropped so binary response bodies cannot break the report.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparison::analyzer::BodyDiff;
+    use crate::types::{Difference, HttpResponse, ResponseTiming};
+    use std::collections::HashMap;
+
+    fn create_result(route: &str, user_id: &str, body2: &str, status2: u16) -> ComparisonResult {
+        let mut context = HashMap::new();
+        context.insert("userId".to_string(), user_id.to_string());
+
+        let mut result = ComparisonResult::new(route.to_string(), context);
+        for (env, status, body) in [("dev", 200, "ok"), ("prod", status2, body2)] {
+            result.add_response(
+                env.to_string(),
+                HttpResponse::new(
+                    status,
+                    HashMap::new(),
+                    body.to_string(),
+                    format!("https://{}.example.com", env),
+                    format!("curl 'https://{}.example.com/{}?a=1&b=2'", env, route),
+                ),
+            );
+        }
+        result
+    }
+
+    #[test]
+    fn test_junit_groups_by_route() {
+        let mut different = create_result("users", "2", "changed", 200);
+        different.add_difference(Difference::with_body_diff(
+            "Response bodies differ".to_string(),
+            BodyDiff {
+                normalized_body1: "ok".to_string(),
+                normalized_body2: "changed".to_string(),
+                is_large_response: false,
+                total_size: 9,
+            },
+        ));
+        let results = vec![
+            different,
+            create_result("users", "1", "ok", 200),
+            create_result("health", "1", "ok", 200),
+        ];
+        let metadata = ReportMetadata::new(vec!["dev".to_string(), "prod".to_string()], 2)
+            .with_duration(std::time::Duration::from_millis(2500));
+
+        let xml = JunitReportRenderer::new().render_report(&results, &metadata);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains(
+            r#"<testsuites name="http-diff" tests="3" failures="1" errors="0" time="2.500""#
+        ));
+        assert!(xml.contains(r#"<testsuite name="health" tests="1" failures="0""#));
+        assert!(xml.contains(r#"<testsuite name="users" tests="2" failures="1""#));
+        assert!(xml.contains(r#"<testcase name="userId=2" classname="http-diff.users""#));
+        assert!(
+            xml.contains(r#"<failure type="ResponseMismatch" message="Response bodies differ">"#)
+        );
+        assert!(xml.contains("- ok\n+ changed"));
+        assert!(xml.contains("a=1&amp;b=2"));
+
+        // Suites are sorted by route name, test cases by user context
+        assert!(xml.find("name=\"health\"").unwrap() < xml.find("name=\"users\"").unwrap());
+        assert!(xml.find("name=\"userId=1\"").unwrap() < xml.find("name=\"userId=2\"").unwrap());
+    }
+
+    #[test]
+    fn test_junit_reports_error_responses_as_failures() {
+        let result = create_result("orders", "1", "<error>boom</error>", 500);
+        let metadata = ReportMetadata::new(vec!["dev".to_string(), "prod".to_string()], 1);
+
+        let xml = JunitReportRenderer::new().render_report(&[result], &metadata);
+
+        assert!(xml.contains(
+            r#"<failure type="HttpError" message="Error responses: dev=200, prod=500">"#
+        ));
+        assert!(xml.contains("&lt;error&gt;boom&lt;/error&gt;"));
+    }
+
+    #[test]
+    fn test_junit_reports_failed_assertions() {
+        let mut result = create_result("orders", "1", "ok", 200);
+        result.assertions = vec![
+            crate::types::AssertionResult::passed("dev", "status is 200".to_string()),
+            crate::types::AssertionResult::failed(
+                "prod",
+                "header 'x-request-id' present".to_string(),
+                "header is missing".to_string(),
+            ),
+        ];
+        let metadata = ReportMetadata::new(vec!["dev".to_string(), "prod".to_string()], 1);
+
+        let xml = JunitReportRenderer::new().render_report(&[result], &metadata);
+
+        assert!(xml.contains(r#"<testsuite name="orders" tests="1" failures="1""#));
+        assert!(xml.contains(
+            r#"<failure type="AssertionFailed" message="Assertions failed: [prod] header &apos;x-request-id&apos; present">"#
+        ));
+        assert!(xml.contains(
+            "[Assertion] [prod] header &apos;x-request-id&apos; present: header is missing"
+        ));
+    }
+
+    #[test]
+    fn test_junit_reports_slowest_environment_time() {
+        let mut result = create_result("orders", "1", "ok", 200);
+        for (env, total_ms) in [("dev", 120), ("prod", 1250)] {
+            result.responses.get_mut(env).unwrap().timing = Some(ResponseTiming {
+                total_ms,
+                ..Default::default()
+            });
+        }
+        let untimed = create_result("orders", "2", "ok", 200);
+        let metadata = ReportMetadata::new(vec!["dev".to_string(), "prod".to_string()], 2);
+
+        let xml = JunitReportRenderer::new().render_report(&[result, untimed], &metadata);
+
+        assert!(xml.contains(
+            r#"<testsuite name="orders" tests="2" failures="0" errors="0" skipped="0" time="1.250">"#
+        ));
+        assert!(
+            xml.contains(r#"<testcase name="userId=1" classname="http-diff.orders" time="1.250">"#)
+        );
+        assert!(
+            xml.contains(r#"<testcase name="userId=2" classname="http-diff.orders" time="0.000">"#)
+        );
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+        assert_eq!(escape_xml("line\u{0}\u{1b}[31m\nnext"), "line[31m\nnext");
+    }
+
+    #[test]
+    fn test_factory_selects_junit_renderer() {
+        let renderer = crate::renderers::ReportRendererFactory::create_renderer("results.xml");
+        assert_eq!(renderer.supported_extension(), "xml");
+        assert!(crate::renderers::ReportRendererFactory::supported_formats().contains(&"xml"));
+    }
+}
@@ -74,9 +74,10 @@ impl ReportRendererFactory {
 
         match extension.as_str() {
             "html" | "htm" => Box::new(html::HtmlReportRenderer::new()),
+            "json" => Box::new(json::JsonReportRenderer::new()),
+            "xml" => Box::new(junit::JunitReportRenderer::new()),
             // Future formats can be added here:
             // "pdf" => Box::new(pdf::PdfReportRenderer::new()),
-            // "json" => Box::new(json::JsonReportRenderer::new()),
             _ => {
                 // Default to HTML for unknown extensions
                 eprintln!(
@@ -90,13 +91,17 @@ impl ReportRendererFactory {
 
     /// Get all supported formats
     pub fn supported_formats() -> Vec<&'static str> {
-        vec!["html", "htm"]
-        // Future: vec!["html", "htm", "pdf", "json"]
+        vec!["html", "htm", "json", "xml"]
+        // Future: vec!["html", "htm", "json", "xml", "pdf"]
     }
 }
 
 // Format-specific implementations
 pub mod html;
+pub mod json;
+pub mod junit;
 
 // Re-export main types for convenience
 pub use html::HtmlReportRenderer;
+pub use json::{JsonReportRenderer, JSON_REPORT_SCHEMA_VERSION};
+pub use junit::JunitReportRenderer;
@@ -1,3 +1,4 @@
+use crate::execution::cookies::CookieJarSnapshot;
 use crate::execution::progress::ProgressTracker;
 use crate::renderers::report::{ReportMetadata, ReportRendererFactory};
 use crate::types::{ComparisonResult, DiffViewStyle};
@@ -181,6 +182,8 @@ pub struct TuiApp {
     pub current_operation: String,
     /// Duration of the last completed execution
     pub last_execution_duration: Option<std::time::Duration>,
+    /// Cookie jars left by the last execution, when `cookie_jar` is enabled
+    pub cookie_jars: Vec<CookieJarSnapshot>,
     /// Whether execution has been requested
     pub execution_requested: bool,
     /// Whether execution is currently running
@@ -253,6 +256,7 @@ impl TuiApp {
             progress_tracker: None,
             current_operation: String::new(),
             last_execution_duration: None,
+            cookie_jars: Vec::new(),
             execution_requested: false,
             execution_running: false,
             execution_cancelled: false,
@@ -300,6 +304,7 @@ impl TuiApp {
             progress_tracker: None,
             current_operation: "Loading configuration...".to_string(),
             last_execution_duration: None,
+            cookie_jars: Vec::new(),
             execution_requested: false,
             execution_running: false,
             execution_cancelled: false,
@@ -712,6 +717,7 @@ impl TuiApp {
         self.execution_cancelled = false;
         self.current_operation = "Starting HTTP tests...".to_string();
         self.last_execution_duration = None;
+        self.cookie_jars.clear();
 
         // Create initial progress tracker to show immediate feedback
         // The total will be updated when the first real progress update arrives
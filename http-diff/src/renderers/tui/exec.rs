@@ -82,6 +82,9 @@ pub async fn execute_http_tests_async(
         }
     };
 
+    // The interactive workflow is the entry point of its run, so it installs the secret store
+    crate::secrets::install(crate::secrets::SecretStore::from_config(&config));
+
     // Load user data
     let users_path = std::path::Path::new(&users_file);
     let user_data = match load_user_data(users_path) {
@@ -167,7 +170,14 @@ pub async fn execute_http_tests_async(
         .await
     {
         Ok(execution_result) => {
-            let _ = tx.send(ExecMsg::Completed(execution_result.comparisons));
+            let cookie_jars = execution_result
+                .chain_metadata
+                .map(|metadata| metadata.cookie_jars)
+                .unwrap_or_default();
+            let _ = tx.send(ExecMsg::Completed(
+                execution_result.comparisons,
+                cookie_jars,
+            ));
         }
         Err(e) => {
             let _ = tx.send(ExecMsg::Failed(format!("Test execution failed: {}", e)));
@@ -1,3 +1,4 @@
+use crate::execution::cookies::CookieJarSnapshot;
 use crate::execution::progress::ProgressTracker;
 use crate::types::ComparisonResult;
 
@@ -74,6 +75,7 @@ pub enum ExecMsg {
         tracker: ProgressTracker,
         op: String,
     },
-    Completed(Vec<ComparisonResult>),
+    /// Comparison results and the cookie jars left at the end of the run
+    Completed(Vec<ComparisonResult>, Vec<CookieJarSnapshot>),
     Failed(String),
 }
@@ -80,7 +80,8 @@ pub fn update(app: &mut TuiApp, msg: Msg) -> Effect {
                 ExecMsg::Progress { tracker, op } => {
                     app.update_execution_progress(tracker, op);
                 }
-                ExecMsg::Completed(results) => {
+                ExecMsg::Completed(results, cookie_jars) => {
+                    app.cookie_jars = cookie_jars;
                     app.complete_execution(results);
                 }
                 ExecMsg::Failed(err) => {
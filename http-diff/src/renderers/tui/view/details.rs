@@ -3,6 +3,7 @@ use crate::renderers::tui::{
     theme::{TuiTheme, UiSymbols},
 };
 use crate::types::ComparisonResult;
+use crate::utils::response_summary::format_duration_ms;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     prelude::*,
@@ -10,6 +11,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Tabs},
 };
 
+/// Maximum number of structural JSON changes listed in the overview tab
+const MAX_OVERVIEW_JSON_CHANGES: usize = 30;
+
 pub fn draw_dashboard_details_panel(f: &mut Frame, app: &TuiApp, area: Rect) {
     let is_focused = app.is_panel_focused(&PanelFocus::Details);
     let title = app.get_panel_title(&PanelFocus::Details);
@@ -122,6 +126,60 @@ fn draw_details_overview_tab(f: &mut Frame, app: &TuiApp, result: &ComparisonRes
         }
     }
 
+    let timed: Vec<_> = result
+        .get_environment_names_ordered()
+        .into_iter()
+        .filter_map(|env| {
+            let response = result.responses.get(&env)?;
+            Some((env, response.timing.clone()?, response.attempts))
+        })
+        .collect();
+    if !timed.is_empty() {
+        lines.push("".to_string());
+        lines.push("⏱️ Timing:".to_string());
+        for (env, timing, attempts) in timed {
+            let retries = if attempts > 1 {
+                format!(" · {} attempts", attempts)
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "  {} - DNS {} · Connect {} · TTFB {} · Total {}{}",
+                env,
+                format_duration_ms(timing.dns_ms),
+                format_duration_ms(timing.connect_ms),
+                format_duration_ms(Some(timing.ttfb_ms)),
+                format_duration_ms(Some(timing.total_ms)),
+                retries
+            ));
+        }
+    }
+
+    if !result.ignored_fields.is_empty() {
+        lines.push("".to_string());
+        lines.push("🙈 Ignored fields:".to_string());
+        for field in &result.ignored_fields {
+            lines.push(format!("  • {}", field));
+        }
+    }
+
+    if !result.assertions.is_empty() {
+        lines.push("".to_string());
+        lines.push("🧪 Assertions:".to_string());
+        for assertion in &result.assertions {
+            let icon = if assertion.passed { "✅" } else { "❌" };
+            let suffix = assertion
+                .message
+                .as_ref()
+                .map(|message| format!(" ({})", message))
+                .unwrap_or_default();
+            lines.push(format!(
+                "  {} [{}] {}{}",
+                icon, assertion.environment, assertion.assertion, suffix
+            ));
+        }
+    }
+
     if !result.is_identical && !result.has_errors {
         lines.push("".to_string());
         lines.push(format!("🔍 {} differences found", result.differences.len()));
@@ -132,6 +190,26 @@ fn draw_details_overview_tab(f: &mut Frame, app: &TuiApp, result: &ComparisonRes
         for category in categories {
             lines.push(format!("  • {}", category.name()));
         }
+
+        let changes: Vec<_> = result
+            .differences
+            .iter()
+            .filter_map(|diff| diff.json_changes.as_ref())
+            .flatten()
+            .collect();
+        if !changes.is_empty() {
+            lines.push("".to_string());
+            lines.push(format!("🧬 Structural changes ({}):", changes.len()));
+            for change in changes.iter().take(MAX_OVERVIEW_JSON_CHANGES) {
+                lines.push(format!("  {} {}", change.kind.symbol(), change));
+            }
+            if changes.len() > MAX_OVERVIEW_JSON_CHANGES {
+                lines.push(format!(
+                    "  … and {} more",
+                    changes.len() - MAX_OVERVIEW_JSON_CHANGES
+                ));
+            }
+        }
     }
 
     let overview_text = lines.join("\n");
@@ -84,7 +84,18 @@ fn draw_progress_execution_view(f: &mut Frame, app: &mut TuiApp, area: Rect) {
         ("0.0s".to_string(), "starting...".to_string())
     };
 
-    let stats_text = format!("⏱ {elapsed} | 🚀 {rate}");
+    let mut stats_text = format!("⏱ {elapsed} | 🚀 {rate}");
+    if let Some(tracker) = app
+        .progress_tracker
+        .as_ref()
+        .filter(|t| t.throttled_requests > 0)
+    {
+        stats_text.push_str(&format!(
+            " | ⏳ {} throttled ({:.1}s)",
+            tracker.throttled_requests,
+            tracker.throttle_wait().as_secs_f64()
+        ));
+    }
     let stats_para = Paragraph::new(stats_text)
         .style(TuiTheme::secondary_text_style())
         .alignment(Alignment::Center);
@@ -98,12 +109,14 @@ fn draw_progress_execution_view(f: &mut Frame, app: &mut TuiApp, area: Rect) {
 }
 
 fn draw_progress_results_summary(f: &mut Frame, app: &TuiApp, area: Rect) {
+    let cookie_jar_lines = app.cookie_jars.len().min(5) as u16;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Status line
-            Constraint::Min(5),    // Bar chart
-            Constraint::Length(3), // Error summary
+            Constraint::Length(1),                // Status line
+            Constraint::Min(5),                   // Bar chart
+            Constraint::Length(3),                // Error summary
+            Constraint::Length(cookie_jar_lines), // Cookie jars
         ])
         .split(area);
 
@@ -148,6 +161,28 @@ fn draw_progress_results_summary(f: &mut Frame, app: &TuiApp, area: Rect) {
             .alignment(Alignment::Center);
         f.render_widget(success_para, chunks[2]);
     }
+
+    // Cookie values are credentials, so only their names are shown
+    let cookie_jar_text: Vec<Line> = app
+        .cookie_jars
+        .iter()
+        .map(|jar| {
+            let names = jar
+                .cookies
+                .iter()
+                .map(|cookie| cookie.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Line::from(format!(
+                "🍪 user {} @ {}: {}",
+                jar.user_data_index, jar.environment, names
+            ))
+        })
+        .collect();
+    let cookie_jar_para = Paragraph::new(cookie_jar_text)
+        .style(TuiTheme::secondary_text_style())
+        .alignment(Alignment::Center);
+    f.render_widget(cookie_jar_para, chunks[3]);
 }
 
 fn draw_progress_ready_state(f: &mut Frame, area: Rect) {
@@ -11,6 +11,10 @@ use ratatui::{
 };
 
 use super::draw_scrollbar;
+use crate::utils::response_summary::format_duration_ms;
+
+/// Maximum number of per-environment latency columns in the results table
+const MAX_LATENCY_COLUMNS: usize = 4;
 
 pub fn draw_dashboard_results_panel(f: &mut Frame, app: &mut TuiApp, area: Rect) {
     let is_focused = app.is_panel_focused(&PanelFocus::Results);
@@ -61,7 +65,19 @@ pub fn draw_dashboard_results_panel(f: &mut Frame, app: &mut TuiApp, area: Rect)
     }
     let results_count = filtered_results.len();
 
-    let header = Row::new(vec!["Route", "Status"]) // compact
+    // Per-environment latency columns, in the environment order of the results
+    let latency_environments: Vec<String> = filtered_results
+        .iter()
+        .find(|result| result.responses.values().any(|r| r.timing.is_some()))
+        .map(|result| result.get_environment_names_ordered())
+        .unwrap_or_default()
+        .into_iter()
+        .take(MAX_LATENCY_COLUMNS)
+        .collect();
+
+    let mut header_cells = vec!["Route".to_string(), "Status".to_string()]; // compact
+    header_cells.extend(latency_environments.iter().map(|env| env.to_uppercase()));
+    let header = Row::new(header_cells)
         .style(TuiTheme::primary_text_style())
         .height(1);
 
@@ -84,22 +100,37 @@ pub fn draw_dashboard_results_panel(f: &mut Frame, app: &mut TuiApp, area: Rect)
                 TuiTheme::primary_text_style()
             };
 
-            Row::new(vec![smart_truncate(&result.route_name, 50), status]).style(style)
+            let mut cells = vec![smart_truncate(&result.route_name, 50), status];
+            cells.extend(
+                latency_environments
+                    .iter()
+                    .map(|env| format_duration_ms(result.latency_ms(env))),
+            );
+            Row::new(cells).style(style)
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [Constraint::Percentage(70), Constraint::Percentage(30)],
-    )
-    .header(header)
-    .row_highlight_style(
-        Style::default()
-            .bg(TuiTheme::BACKGROUND_SELECTED)
-            .fg(TuiTheme::FOCUS)
-            .add_modifier(Modifier::BOLD),
-    )
-    .block(Block::default().borders(Borders::NONE));
+    let widths: Vec<Constraint> = if latency_environments.is_empty() {
+        vec![Constraint::Percentage(70), Constraint::Percentage(30)]
+    } else {
+        let mut widths = vec![Constraint::Min(10), Constraint::Length(8)];
+        widths.extend(
+            latency_environments
+                .iter()
+                .map(|env| Constraint::Length(env.len().max(7) as u16)),
+        );
+        widths
+    };
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(
+            Style::default()
+                .bg(TuiTheme::BACKGROUND_SELECTED)
+                .fg(TuiTheme::FOCUS)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::NONE));
 
     f.render_stateful_widget(table, chunks[1], &mut app.results_table_state);
 
@@ -0,0 +1,392 @@
+//! Secret resolution and redaction
+//!
+//! Placeholders of the form `{secret:NAME}` are resolved from the process
+//! environment, a dotenv file or an external command, configured in
+//! `[global.secrets]`. Every resolved value is remembered so it can be
+//! redacted from curl commands, reports and error messages, and the values of
+//! sensitive headers are always masked in generated curl commands.
+//!
+//! Output is produced far away from the configuration (renderers only see
+//! comparison results), so the active store is process-wide. The entry point
+//! installs it once after loading the configuration and passes it to the HTTP
+//! client, curl generator and dry-run planner.
+
+use crate::config::{HttpDiffConfig, SecretsConfig};
+use crate::error::{HttpDiffError, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Replacement written in place of secret values
+pub const REDACTED: &str = "***";
+
+/// Headers masked in output when `masked_headers` is not configured
+pub const DEFAULT_MASKED_HEADERS: &[&str] = &["Authorization", "Cookie", "X-Api-Key"];
+
+/// Values shorter than this are never redacted
+///
+/// Replacing a value such as `1` or `true` everywhere would mangle every
+/// report, while such short values are not worth hiding.
+pub const MIN_REDACTED_LEN: usize = 4;
+
+/// Dotenv file read when `env_file` is not configured
+pub const DEFAULT_ENV_FILE: &str = ".env";
+
+/// Resolves secrets and redacts their values from output
+#[derive(Debug)]
+pub struct SecretStore {
+    env_file: PathBuf,
+    command: Option<String>,
+    masked_headers: Vec<String>,
+    dotenv: OnceLock<HashMap<String, String>>,
+    /// Secrets resolved so far, by name
+    resolved: RwLock<HashMap<String, String>>,
+    /// Credentials obtained at runtime, such as access tokens
+    registered: RwLock<HashSet<String>>,
+}
+
+impl Default for SecretStore {
+    fn default() -> Self {
+        Self::from_secrets_config(&SecretsConfig::default())
+    }
+}
+
+impl SecretStore {
+    /// Create a store with the default sources and masked headers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store from a `[global.secrets]` table
+    pub fn from_secrets_config(config: &SecretsConfig) -> Self {
+        Self {
+            env_file: PathBuf::from(config.env_file.as_deref().unwrap_or(DEFAULT_ENV_FILE)),
+            command: config.command.clone(),
+            masked_headers: config
+                .masked_headers
+                .clone()
+                .unwrap_or_else(|| {
+                    DEFAULT_MASKED_HEADERS
+                        .iter()
+                        .map(|h| h.to_string())
+                        .collect()
+                })
+                .iter()
+                .map(|h| h.to_ascii_lowercase())
+                .collect(),
+            dotenv: OnceLock::new(),
+            resolved: RwLock::new(HashMap::new()),
+            registered: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Create a store from the secrets settings of a configuration
+    pub fn from_config(config: &HttpDiffConfig) -> Self {
+        match config.global.as_ref().and_then(|g| g.secrets.as_ref()) {
+            Some(secrets) => Self::from_secrets_config(secrets),
+            None => Self::default(),
+        }
+    }
+
+    /// Resolve a secret, returning `None` when no source defines it
+    ///
+    /// Sources are tried in order: environment variable, dotenv file, command.
+    pub fn resolve(&self, name: &str) -> Result<Option<String>> {
+        if let Some(value) = self.cached(name) {
+            return Ok(Some(value));
+        }
+
+        let value = match std::env::var(name) {
+            Ok(value) => Some(value),
+            Err(_) => match self.dotenv().get(name) {
+                Some(value) => Some(value.clone()),
+                None => self.run_command(name)?,
+            },
+        };
+
+        if let Some(value) = &value {
+            if let Ok(mut resolved) = self.resolved.write() {
+                resolved.insert(name.to_string(), value.clone());
+            }
+        }
+        Ok(value)
+    }
+
+    /// Remember a credential obtained at runtime so it is redacted like a secret
+    pub fn register(&self, value: &str) {
+        if let Ok(mut registered) = self.registered.write() {
+            registered.insert(value.to_string());
+        }
+    }
+
+    /// Replace every resolved secret value in `text` with [`REDACTED`]
+    ///
+    /// Percent-encoded and base64 forms of each value are replaced as well.
+    /// Values shorter than [`MIN_REDACTED_LEN`] characters are left alone.
+    pub fn redact(&self, text: &str) -> String {
+        let (Ok(resolved), Ok(registered)) = (self.resolved.read(), self.registered.read()) else {
+            return text.to_string();
+        };
+        let mut values: HashSet<String> = HashSet::new();
+        for value in resolved.values().chain(registered.iter()) {
+            if value.chars().count() >= MIN_REDACTED_LEN {
+                values.extend(encoded_forms(value));
+            }
+        }
+        // Longest values first so a secret containing another is fully hidden
+        let mut values: Vec<String> = values.into_iter().collect();
+        values.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        values.into_iter().fold(text.to_string(), |text, value| {
+            text.replace(value.as_str(), REDACTED)
+        })
+    }
+
+    /// Check whether a header's value is always masked in output
+    pub fn is_masked_header(&self, name: &str) -> bool {
+        self.masked_headers.contains(&name.to_ascii_lowercase())
+    }
+
+    /// Mask the value of a sensitive header, keeping an auth scheme such as `Bearer`
+    pub fn mask_header(&self, name: &str, value: &str) -> String {
+        if !self.is_masked_header(name) {
+            return self.redact(value);
+        }
+        match value.split_once(' ') {
+            Some((scheme, _)) if scheme.chars().all(|c| c.is_ascii_alphabetic()) => {
+                format!("{} {}", scheme, REDACTED)
+            }
+            _ => REDACTED.to_string(),
+        }
+    }
+
+    fn cached(&self, name: &str) -> Option<String> {
+        self.resolved.read().ok()?.get(name).cloned()
+    }
+
+    fn dotenv(&self) -> &HashMap<String, String> {
+        self.dotenv.get_or_init(|| {
+            std::fs::read_to_string(&self.env_file)
+                .map(|content| parse_dotenv(&content))
+                .unwrap_or_default()
+        })
+    }
+
+    fn run_command(&self, name: &str) -> Result<Option<String>> {
+        let Some(command) = &self.command else {
+            return Ok(None);
+        };
+        let command = command.replace("{name}", name);
+
+        let output = shell_command(&command).output().map_err(|e| {
+            HttpDiffError::general(format!(
+                "Failed to run secrets command for '{}': {}",
+                name, e
+            ))
+        })?;
+        if !output.status.success() {
+            return Err(HttpDiffError::general(format!(
+                "Secrets command for '{}' exited with {}",
+                name, output.status
+            )));
+        }
+
+        let value = String::from_utf8_lossy(&output.stdout)
+            .trim_end_matches(['\r', '\n'])
+            .to_string();
+        Ok(Some(value))
+    }
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+/// Parse `NAME=value` lines of a dotenv file
+///
+/// Blank lines, comments and an `export ` prefix are ignored, and values may
+/// be wrapped in single or double quotes.
+pub fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|q| value.strip_prefix(*q)?.strip_suffix(*q))
+                .unwrap_or(value);
+            Some((name.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn active_slot() -> &'static RwLock<Arc<SecretStore>> {
+    static ACTIVE: OnceLock<RwLock<Arc<SecretStore>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(Arc::new(SecretStore::default())))
+}
+
+/// Make `store` the process-wide secret store
+///
+/// Secrets resolved by the previous store stay known, so values that already
+/// appeared in responses are still redacted.
+pub fn install(store: SecretStore) -> Arc<SecretStore> {
+    let mut slot = active_slot()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let (Ok(previous), Ok(mut resolved)) = (slot.resolved.read(), store.resolved.write()) {
+        for (name, value) in previous.iter() {
+            resolved
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+    if let (Ok(previous), Ok(mut registered)) = (slot.registered.read(), store.registered.write()) {
+        registered.extend(previous.iter().cloned());
+    }
+    *slot = Arc::new(store);
+    slot.clone()
+}
+
+/// The process-wide secret store
+pub fn active() -> Arc<SecretStore> {
+    active_slot()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// A secret value along with its percent-encoded and base64 forms
+///
+/// Base64 forms are unpadded so padded and unpadded occurrences both match.
+fn encoded_forms(value: &str) -> Vec<String> {
+    use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+    use base64::Engine;
+
+    vec![
+        value.to_string(),
+        urlencoding::encode(value).into_owned(),
+        STANDARD_NO_PAD.encode(value),
+        URL_SAFE_NO_PAD.encode(value),
+    ]
+}
+
+/// Redact resolved secret values from `text` using the active store
+pub fn redact(text: &str) -> String {
+    active().redact(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let values = parse_dotenv(
+            "# comment\nTOKEN=abc\nexport QUOTED=\"with spaces\"\n\nSINGLE='x=y'\ninvalid line\n",
+        );
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(values["TOKEN"], "abc");
+        assert_eq!(values["QUOTED"], "with spaces");
+        assert_eq!(values["SINGLE"], "x=y");
+    }
+
+    #[test]
+    fn test_resolution_order_and_redaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "SECRETS_TEST_DOTENV=from-dotenv\n").unwrap();
+        std::env::set_var("SECRETS_TEST_PROCESS", "from-process");
+
+        let store = SecretStore::from_secrets_config(&SecretsConfig {
+            env_file: Some(env_file.to_string_lossy().to_string()),
+            command: Some("echo cmd-{name}".to_string()),
+            masked_headers: None,
+        });
+
+        assert_eq!(
+            store.resolve("SECRETS_TEST_PROCESS").unwrap().as_deref(),
+            Some("from-process")
+        );
+        assert_eq!(
+            store.resolve("SECRETS_TEST_DOTENV").unwrap().as_deref(),
+            Some("from-dotenv")
+        );
+        assert_eq!(
+            store.resolve("SECRETS_TEST_COMMAND").unwrap().as_deref(),
+            Some("cmd-SECRETS_TEST_COMMAND")
+        );
+
+        assert_eq!(
+            store.redact("token=from-dotenv&id=cmd-SECRETS_TEST_COMMAND"),
+            "token=***&id=***"
+        );
+    }
+
+    #[test]
+    fn test_redacts_encoded_forms() {
+        let store = SecretStore::new();
+        store.register("p@ss word/+?");
+
+        assert_eq!(
+            store.redact("password=p%40ss%20word%2F%2B%3F&raw=p@ss word/+?"),
+            "password=***&raw=***"
+        );
+        assert_eq!(store.redact("token=cEBzcyB3b3JkLys/"), "token=***");
+        assert_eq!(store.redact("token=cEBzcyB3b3JkLys_"), "token=***");
+    }
+
+    #[test]
+    fn test_short_values_are_not_redacted() {
+        let store = SecretStore::new();
+        store.register("abc");
+        store.register("true");
+
+        assert_eq!(store.redact("abc=1&enabled=true"), "abc=1&enabled=***");
+    }
+
+    #[test]
+    fn test_unknown_secret_without_command() {
+        let store = SecretStore::from_secrets_config(&SecretsConfig {
+            env_file: Some("/nonexistent/.env".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(store.resolve("SECRETS_TEST_UNKNOWN").unwrap(), None);
+    }
+
+    #[test]
+    fn test_masked_headers() {
+        let store = SecretStore::new();
+        assert_eq!(
+            store.mask_header("authorization", "Bearer abc"),
+            "Bearer ***"
+        );
+        assert_eq!(store.mask_header("Cookie", "session=abc; id=1"), "***");
+        assert_eq!(
+            store.mask_header("Accept", "application/json"),
+            "application/json"
+        );
+
+        let custom = SecretStore::from_secrets_config(&SecretsConfig {
+            masked_headers: Some(vec!["X-Session".to_string()]),
+            ..Default::default()
+        });
+        assert!(custom.is_masked_header("x-session"));
+        assert!(!custom.is_masked_header("Authorization"));
+    }
+}
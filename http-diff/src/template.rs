@@ -0,0 +1,649 @@
+//! Placeholder expression language
+//!
+//! Placeholders in paths, headers, params and bodies are written in braces.
+//! Besides bare lookups like `{userId}`, a placeholder may contain a small
+//! expression:
+//!
+//! - generators: `{uuid()}`, `{now()}`, `{now("%Y-%m-%d")}`, `{random_int(1, 100)}`
+//! - transforms: `{upper(userId)}`, `{lower(userId)}`, `{base64(token)}`,
+//!   `{urlencode(q)}`, `{sha256(body)}`
+//! - environment variables: `{$API_TOKEN}` or `{env("API_TOKEN")}`
+//! - secrets: `{secret:API_TOKEN}`, resolved by [`crate::secrets`] and redacted from output
+//! - defaults: `{siteId ?? "MLA"}`, used when the value is missing or empty
+//!
+//! Text that does not parse as an expression (JSON objects, GraphQL
+//! selections, calls to unknown functions) is left untouched.
+
+use crate::error::{HttpDiffError, Result};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// Functions available inside placeholders
+pub const FUNCTIONS: &[&str] = &[
+    "uuid",
+    "now",
+    "random_int",
+    "upper",
+    "lower",
+    "base64",
+    "urlencode",
+    "sha256",
+    "env",
+];
+
+/// A parsed placeholder expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// String or number literal
+    Literal(String),
+    /// Value looked up from user data or the extraction context
+    Variable(String),
+    /// Process environment variable (`$NAME`)
+    EnvVar(String),
+    /// Secret resolved by the active secret store (`secret:NAME`)
+    Secret(String),
+    /// Function call
+    Call { name: String, args: Vec<Expression> },
+    /// First operand that is present and not empty (`a ?? b ?? "c"`)
+    Default(Vec<Expression>),
+}
+
+/// Outcome of evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evaluated {
+    Value(String),
+    /// A variable the expression depends on has no value
+    MissingVariable(String),
+    /// An environment variable the expression depends on is not set
+    MissingEnvVar(String),
+    /// A secret the expression depends on is not defined by any source
+    MissingSecret(String),
+}
+
+impl Expression {
+    /// Parse the text between the braces of a placeholder
+    ///
+    /// Returns `None` when the text is not a placeholder expression, in which
+    /// case it must be left as is.
+    pub fn parse(source: &str) -> Option<Self> {
+        if is_valid_param_name(source) {
+            return Some(Self::Variable(source.to_string()));
+        }
+
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expression = parser.parse_default()?;
+        if parser.pos != parser.tokens.len() {
+            return None;
+        }
+        // Lone literals such as `{"key"}` and padded names such as `{ name }`
+        // are more likely data (JSON, GraphQL) than expressions
+        match expression {
+            Self::Literal(_) | Self::Variable(_) => None,
+            expression => Some(expression),
+        }
+    }
+
+    /// Evaluate the expression, resolving variables with `lookup`
+    ///
+    /// `source` is the original placeholder text, used in error messages.
+    pub fn evaluate<L>(&self, source: &str, lookup: &L) -> Result<Evaluated>
+    where
+        L: Fn(&str) -> Option<String>,
+    {
+        match self {
+            Self::Literal(value) => Ok(Evaluated::Value(value.clone())),
+            Self::Variable(name) => Ok(lookup(name)
+                .map(Evaluated::Value)
+                .unwrap_or_else(|| Evaluated::MissingVariable(name.clone()))),
+            Self::EnvVar(name) => Ok(std::env::var(name)
+                .map(Evaluated::Value)
+                .unwrap_or_else(|_| Evaluated::MissingEnvVar(name.clone()))),
+            Self::Secret(name) => Ok(crate::secrets::active()
+                .resolve(name)?
+                .map(Evaluated::Value)
+                .unwrap_or_else(|| Evaluated::MissingSecret(name.clone()))),
+            Self::Default(operands) => {
+                let mut last = Evaluated::Value(String::new());
+                for operand in operands {
+                    last = operand.evaluate(source, lookup)?;
+                    if matches!(&last, Evaluated::Value(value) if !value.is_empty()) {
+                        break;
+                    }
+                }
+                Ok(last)
+            }
+            Self::Call { name, args } => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    match arg.evaluate(source, lookup)? {
+                        Evaluated::Value(value) => values.push(value),
+                        missing => return Ok(missing),
+                    }
+                }
+                call_function(name, &values)
+                    .map(Evaluated::Value)
+                    .map_err(|message| HttpDiffError::template_error(source.to_string(), message))
+            }
+        }
+    }
+}
+
+/// Substitute every placeholder in `text`
+///
+/// `lookup` resolves variables. When a variable has no value, `on_missing`
+/// decides what happens: returning an error aborts the substitution, returning
+/// `Ok(())` leaves the placeholder unchanged. Unset environment variables are
+/// always an error unless the expression provides a `??` default. Substituted
+/// values are URL-encoded when `url_encode` is set.
+pub fn render<L, M>(text: &str, url_encode: bool, lookup: L, on_missing: M) -> Result<String>
+where
+    L: Fn(&str) -> Option<String>,
+    M: Fn(&str) -> Result<()>,
+{
+    let mut result = String::with_capacity(text.len() + 50);
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let placeholder =
+            placeholder_end(after).and_then(|end| Some((end, Expression::parse(&after[..end])?)));
+        let Some((end, expression)) = placeholder else {
+            // Not a placeholder: keep the brace and continue scanning after it
+            result.push('{');
+            rest = after;
+            continue;
+        };
+
+        let source = &after[..end];
+        match expression.evaluate(source, &lookup)? {
+            Evaluated::Value(value) if url_encode => result.push_str(&urlencoding::encode(&value)),
+            Evaluated::Value(value) => result.push_str(&value),
+            Evaluated::MissingVariable(name) => {
+                on_missing(&name)?;
+                result.push('{');
+                result.push_str(source);
+                result.push('}');
+            }
+            Evaluated::MissingEnvVar(name) => {
+                return Err(HttpDiffError::template_error(
+                    source.to_string(),
+                    format!("environment variable '{}' is not set", name),
+                ));
+            }
+            Evaluated::MissingSecret(name) => {
+                return Err(HttpDiffError::template_error(
+                    source.to_string(),
+                    format!("secret '{}' is not defined", name),
+                ));
+            }
+        }
+        rest = &after[end + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Rewrite every placeholder in `text`
+///
+/// `rewrite` receives the source text and parsed expression of each
+/// placeholder and returns its replacement, or `None` to keep it as written.
+/// Nothing is evaluated, so this is safe for converting placeholders to
+/// another syntax.
+pub fn map_placeholders<F>(text: &str, mut rewrite: F) -> String
+where
+    F: FnMut(&str, &Expression) -> Option<String>,
+{
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let placeholder =
+            placeholder_end(after).and_then(|end| Some((end, Expression::parse(&after[..end])?)));
+        let Some((end, expression)) = placeholder else {
+            result.push('{');
+            rest = after;
+            continue;
+        };
+
+        let source = &after[..end];
+        match rewrite(source, &expression) {
+            Some(replacement) => result.push_str(&replacement),
+            None => {
+                result.push('{');
+                result.push_str(source);
+                result.push('}');
+            }
+        }
+        rest = &after[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Check if a parameter name is a valid identifier (letters, numbers, underscore)
+pub fn is_valid_param_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Find the closing brace of a placeholder, skipping quoted strings
+///
+/// Returns `None` for unterminated placeholders and nested braces.
+fn placeholder_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (idx, ch) in text.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None => match ch {
+                '"' | '\'' => quote = Some(ch),
+                '{' => return None,
+                '}' => return Some(idx),
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    EnvVar(String),
+    Secret(String),
+    Coalesce,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                tokens.push(match ch {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    _ => Token::Comma,
+                });
+            }
+            '?' => {
+                chars.next();
+                if chars.next() != Some('?') {
+                    return None;
+                }
+                tokens.push(Token::Coalesce);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '\\' => value.push(chars.next()?),
+                        c if c == ch => break,
+                        c => value.push(c),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '$' => {
+                chars.next();
+                let name = take_word(&mut chars);
+                if !is_valid_param_name(&name) {
+                    return None;
+                }
+                tokens.push(Token::EnvVar(name));
+            }
+            '-' => {
+                chars.next();
+                let digits = take_word(&mut chars);
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                tokens.push(Token::Number(format!("-{}", digits)));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let word = take_word(&mut chars);
+                if word == "secret" && chars.peek() == Some(&':') {
+                    chars.next();
+                    let name = take_word(&mut chars);
+                    if name.is_empty() {
+                        return None;
+                    }
+                    tokens.push(Token::Secret(name));
+                } else if word.chars().all(|c| c.is_ascii_digit()) {
+                    tokens.push(Token::Number(word));
+                } else {
+                    tokens.push(Token::Ident(word));
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(tokens)
+}
+
+fn take_word(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if !(c.is_alphanumeric() || c == '_') {
+            break;
+        }
+        word.push(c);
+        chars.next();
+    }
+    word
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_default(&mut self) -> Option<Expression> {
+        let mut operands = vec![self.parse_operand()?];
+        while self.peek() == Some(&Token::Coalesce) {
+            self.pos += 1;
+            operands.push(self.parse_operand()?);
+        }
+        Some(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            Expression::Default(operands)
+        })
+    }
+
+    fn parse_operand(&mut self) -> Option<Expression> {
+        match self.next()? {
+            Token::Str(value) | Token::Number(value) => Some(Expression::Literal(value)),
+            Token::EnvVar(name) => Some(Expression::EnvVar(name)),
+            Token::Secret(name) => Some(Expression::Secret(name)),
+            Token::Ident(name) if self.peek() == Some(&Token::LParen) => {
+                // Only known functions make a call; anything else is not a placeholder
+                if !FUNCTIONS.contains(&name.as_str()) {
+                    return None;
+                }
+                self.pos += 1;
+                let mut args = Vec::new();
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                } else {
+                    loop {
+                        args.push(self.parse_default()?);
+                        match self.next()? {
+                            Token::Comma => continue,
+                            Token::RParen => break,
+                            _ => return None,
+                        }
+                    }
+                }
+                Some(Expression::Call { name, args })
+            }
+            Token::Ident(name) => Some(Expression::Variable(name)),
+            _ => None,
+        }
+    }
+}
+
+/// Apply a built-in function to already evaluated arguments
+fn call_function(name: &str, args: &[String]) -> std::result::Result<String, String> {
+    let expect_args = |count: usize| {
+        if args.len() == count {
+            Ok(())
+        } else {
+            Err(format!(
+                "{}() takes {} argument(s), {} given",
+                name,
+                count,
+                args.len()
+            ))
+        }
+    };
+
+    match name {
+        "uuid" => {
+            expect_args(0)?;
+            Ok(uuid::Uuid::new_v4().to_string())
+        }
+        "now" => {
+            let format = match args {
+                [] => return Ok(chrono::Utc::now().to_rfc3339()),
+                [format] => format,
+                _ => {
+                    return Err(format!(
+                        "now() takes at most 1 argument, {} given",
+                        args.len()
+                    ))
+                }
+            };
+            let mut value = String::new();
+            write!(value, "{}", chrono::Utc::now().format(format))
+                .map_err(|_| format!("invalid time format '{}'", format))?;
+            Ok(value)
+        }
+        "random_int" => {
+            expect_args(2)?;
+            let parse = |value: &String| {
+                value
+                    .trim()
+                    .parse::<i64>()
+                    .map_err(|_| format!("random_int() expects integers, got '{}'", value))
+            };
+            let (min, max) = (parse(&args[0])?, parse(&args[1])?);
+            if min > max {
+                return Err(format!("random_int() range {}..{} is empty", min, max));
+            }
+            Ok(fastrand::i64(min..=max).to_string())
+        }
+        "upper" => {
+            expect_args(1)?;
+            Ok(args[0].to_uppercase())
+        }
+        "lower" => {
+            expect_args(1)?;
+            Ok(args[0].to_lowercase())
+        }
+        "base64" => {
+            expect_args(1)?;
+            Ok(base64::engine::general_purpose::STANDARD.encode(&args[0]))
+        }
+        "urlencode" => {
+            expect_args(1)?;
+            Ok(urlencoding::encode(&args[0]).into_owned())
+        }
+        "sha256" => {
+            expect_args(1)?;
+            let digest = Sha256::digest(args[0].as_bytes());
+            Ok(digest.iter().fold(String::new(), |mut hex, byte| {
+                let _ = write!(hex, "{:02x}", byte);
+                hex
+            }))
+        }
+        "env" => {
+            expect_args(1)?;
+            std::env::var(&args[0])
+                .map_err(|_| format!("environment variable '{}' is not set", args[0]))
+        }
+        _ => Err(format!("unknown function '{}'", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn render_with(text: &str, values: &[(&str, &str)]) -> Result<String> {
+        let values: HashMap<String, String> = values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        render(
+            text,
+            false,
+            |name| values.get(name).cloned(),
+            |name| Err(HttpDiffError::general(format!("missing {}", name))),
+        )
+    }
+
+    #[test]
+    fn test_transforms_and_defaults() {
+        let values = [("userId", "abc"), ("empty", "")];
+
+        assert_eq!(
+            render_with("/users/{upper(userId)}", &values).unwrap(),
+            "/users/ABC"
+        );
+        assert_eq!(render_with("{base64(userId)}", &values).unwrap(), "YWJj");
+        assert_eq!(
+            render_with("{urlencode('a b&c')}", &values).unwrap(),
+            "a%20b%26c"
+        );
+        assert_eq!(
+            render_with("{sha256(userId)}", &values).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            render_with("{siteId ?? \"MLA\"}-{empty ?? userId}", &values).unwrap(),
+            "MLA-abc"
+        );
+        assert_eq!(
+            render_with("{upper(siteId ?? 'mla')}", &values).unwrap(),
+            "MLA"
+        );
+    }
+
+    #[test]
+    fn test_generators() {
+        let id = render_with("{uuid()}", &[]).unwrap();
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+
+        let date = render_with("{now(\"%Y-%m-%d\")}", &[]).unwrap();
+        assert!(chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_ok());
+
+        let n: i64 = render_with("{random_int(-2, 2)}", &[])
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((-2..=2).contains(&n));
+
+        let err = render_with("{random_int(5, 1)}", &[]).unwrap_err();
+        assert!(err.to_string().contains("range 5..1 is empty"));
+    }
+
+    #[test]
+    fn test_environment_variables() {
+        std::env::set_var("HTTP_DIFF_TEMPLATE_TEST", "secret");
+
+        assert_eq!(
+            render_with(
+                "{$HTTP_DIFF_TEMPLATE_TEST}/{env('HTTP_DIFF_TEMPLATE_TEST')}",
+                &[]
+            )
+            .unwrap(),
+            "secret/secret"
+        );
+        assert_eq!(
+            render_with("{$HTTP_DIFF_TEMPLATE_UNSET ?? 'fallback'}", &[]).unwrap(),
+            "fallback"
+        );
+        let err = render_with("{$HTTP_DIFF_TEMPLATE_UNSET}", &[]).unwrap_err();
+        assert!(err.to_string().contains("is not set"));
+    }
+
+    #[test]
+    fn test_secrets() {
+        std::env::set_var("HTTP_DIFF_TEMPLATE_SECRET", "s3cr3t");
+
+        assert_eq!(
+            render_with("Bearer {secret:HTTP_DIFF_TEMPLATE_SECRET}", &[]).unwrap(),
+            "Bearer s3cr3t"
+        );
+        assert_eq!(
+            crate::secrets::redact("token s3cr3t"),
+            format!("token {}", crate::secrets::REDACTED)
+        );
+        let err = render_with("{secret:HTTP_DIFF_TEMPLATE_UNDEFINED}", &[]).unwrap_err();
+        assert!(err.to_string().contains("is not defined"));
+    }
+
+    #[test]
+    fn test_non_expressions_are_left_untouched() {
+        let values = [("userId", "42")];
+
+        assert_eq!(
+            render_with(r#"{"id": "{userId}", "tags": {"a": 1}}"#, &values).unwrap(),
+            r#"{"id": "42", "tags": {"a": 1}}"#
+        );
+        assert_eq!(
+            render_with("{ viewer(first) { name } }", &values).unwrap(),
+            "{ viewer(first) { name } }"
+        );
+        assert_eq!(render_with("{\"key\"}", &values).unwrap(), "{\"key\"}");
+        assert_eq!(render_with("{ userId }", &values).unwrap(), "{ userId }");
+        assert_eq!(render_with("{{userId}}", &values).unwrap(), "{42}");
+    }
+
+    #[test]
+    fn test_missing_variables_use_callback() {
+        let err = render_with("/users/{upper(userId)}", &[]).unwrap_err();
+        assert!(err.to_string().contains("missing userId"));
+
+        let kept = render("/users/{upper(userId)}", false, |_| None, |_| Ok(())).unwrap();
+        assert_eq!(kept, "/users/{upper(userId)}");
+    }
+
+    #[test]
+    fn test_map_placeholders() {
+        let text = r#"{"id": "{userId}", "at": "{now()}", "q": {"a": 1}}"#;
+        let mapped = map_placeholders(text, |_, expression| match expression {
+            Expression::Variable(name) => Some(format!("<{}>", name)),
+            _ => None,
+        });
+        assert_eq!(
+            mapped,
+            r#"{"id": "<userId>", "at": "{now()}", "q": {"a": 1}}"#
+        );
+    }
+
+    #[test]
+    fn test_wrong_argument_count_is_an_error() {
+        let err = render_with("{upper()}", &[]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid placeholder expression '{upper()}'"));
+    }
+}
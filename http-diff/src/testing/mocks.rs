@@ -389,6 +389,14 @@ impl ChainAwareMockHttpClient {
                         // Extract status code
                         Some(response.status.to_string())
                     },
+                    ExtractorType::Body => Some(response.body.clone()),
+                    ExtractorType::XPath
+                    | ExtractorType::Cookie
+                    | ExtractorType::JmesPath
+                    | ExtractorType::Computed => {
+                        // Not simulated
+                        extraction_rule.default_value.clone()
+                    },
                 };
 
                 if let Some(value) = extracted_value {
@@ -569,6 +577,16 @@ impl ChainTestScenarioBuilder {
             ]),
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let list_route = Route {
@@ -595,6 +613,16 @@ impl ChainTestScenarioBuilder {
             ]),
             depends_on: Some(vec!["login".to_string()]),
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let detail_route = Route {
@@ -613,6 +641,16 @@ impl ChainTestScenarioBuilder {
             extract: None,
             depends_on: Some(vec!["list".to_string()]),
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         self.routes.extend(vec![login_route, list_route, detail_route]);
@@ -676,6 +714,8 @@ pub mod test_helpers {
             body: body.to_string(),
             url: "https://example.com/test".to_string(),
             curl_command: "curl 'https://example.com/test'".to_string(),
+            timing: None,
+            attempts: 1,
         }
     }
 
@@ -692,6 +732,16 @@ pub mod test_helpers {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }
 
@@ -713,6 +763,16 @@ pub mod test_helpers {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }
 
@@ -748,6 +808,16 @@ pub mod test_helpers {
             extract: if extractions.is_empty() { None } else { Some(extractions) },
             depends_on: depends_on.clone(),
             wait_for_extraction: depends_on.as_ref().map(|_| true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }
 
@@ -784,6 +854,8 @@ pub mod test_helpers {
             body: body.to_string(),
             url: "https://example.com/test".to_string(),
             curl_command: "curl 'https://example.com/test'".to_string(),
+            timing: None,
+            attempts: 1,
         }
     }
 
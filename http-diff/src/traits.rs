@@ -1,6 +1,7 @@
 use crate::config::{Route, UserData};
 use crate::conditions::{ConditionResult, ExecutionCondition};
 use crate::error::Result;
+use crate::execution::cookies::CookieJar;
 use crate::types::{ComparisonResult, HttpResponse, ExtractionRule, ExtractionResult, ValueExtractionContext};
 use std::collections::HashMap;
 use std::future::Future;
@@ -14,6 +15,20 @@ pub trait HttpClient: Send + Sync + Clone {
         environment: &str,
         user_data: &UserData,
     ) -> impl Future<Output = Result<HttpResponse>> + Send;
+
+    /// Execute a request, sending the cookies in `cookie_jar` and storing the ones set by the response
+    ///
+    /// Clients without cookie support ignore the jar.
+    fn execute_request_with_cookies(
+        &self,
+        route: &Route,
+        environment: &str,
+        user_data: &UserData,
+        cookie_jar: &CookieJar,
+    ) -> impl Future<Output = Result<HttpResponse>> + Send {
+        let _ = cookie_jar;
+        self.execute_request(route, environment, user_data)
+    }
 }
 
 /// Trait for response comparison
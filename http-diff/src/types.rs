@@ -12,6 +12,38 @@ pub struct HttpResponse {
     pub body: String,
     pub url: String,
     pub curl_command: String,
+    /// Request timing measured by the HTTP client (absent for synthetic responses)
+    #[serde(default)]
+    pub timing: Option<ResponseTiming>,
+    /// Number of attempts made, including retries
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+}
+
+fn default_attempts() -> u32 {
+    1
+}
+
+/// Request timing measured by the HTTP client
+///
+/// DNS and connect phases are only present when the request opened a new
+/// connection; requests served over a pooled connection skip them.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResponseTiming {
+    /// DNS resolution time in milliseconds
+    #[serde(default)]
+    pub dns_ms: Option<u64>,
+    /// TCP (and TLS) connection setup time in milliseconds, excluding DNS
+    #[serde(default)]
+    pub connect_ms: Option<u64>,
+    /// Time from sending the request until the response headers arrived, in milliseconds
+    #[serde(default)]
+    pub ttfb_ms: u64,
+    /// Time from sending the request until the full body was received, in milliseconds
+    pub total_ms: u64,
+    /// Size of the response body in bytes
+    #[serde(default)]
+    pub size_bytes: u64,
 }
 
 impl HttpResponse {
@@ -29,9 +61,28 @@ impl HttpResponse {
             body,
             url,
             curl_command,
+            timing: None,
+            attempts: 1,
         }
     }
 
+    /// Attach request timing to this response
+    pub fn with_timing(mut self, timing: ResponseTiming) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+
+    /// Record the number of attempts it took to get this response
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Check whether the response was only obtained after retrying
+    pub fn was_retried(&self) -> bool {
+        self.attempts > 1
+    }
+
     /// Check if the response indicates success (2xx status code)
     pub fn is_success(&self) -> bool {
         self.status >= 200 && self.status < 300
@@ -62,6 +113,45 @@ pub struct ComparisonResult {
     pub error_bodies: Option<HashMap<String, String>>, // env_name -> response_body (only for errors)
     /// Optional base environment used for orienting comparisons and diffs
     pub base_environment: Option<String>,
+    /// Ignore rules (JSONPath expressions) that excluded body fields from comparison
+    #[serde(default)]
+    pub ignored_fields: Vec<String>,
+    /// Outcomes of the route's `assert` block, per environment
+    #[serde(default)]
+    pub assertions: Vec<AssertionResult>,
+}
+
+/// Outcome of a single route assertion evaluated against one environment's response
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AssertionResult {
+    pub environment: String,
+    /// Description of the assertion, e.g. `status is 200` or `$.plan exists`
+    pub assertion: String,
+    pub passed: bool,
+    /// Why the assertion failed (absent when it passed)
+    pub message: Option<String>,
+}
+
+impl AssertionResult {
+    /// Create a passing assertion result
+    pub fn passed(environment: &str, assertion: String) -> Self {
+        Self {
+            environment: environment.to_string(),
+            assertion,
+            passed: true,
+            message: None,
+        }
+    }
+
+    /// Create a failing assertion result
+    pub fn failed(environment: &str, assertion: String, message: String) -> Self {
+        Self {
+            environment: environment.to_string(),
+            assertion,
+            passed: false,
+            message: Some(message),
+        }
+    }
 }
 
 impl ComparisonResult {
@@ -77,6 +167,8 @@ impl ComparisonResult {
             has_errors: false,
             error_bodies: None,
             base_environment: None,
+            ignored_fields: Vec::new(),
+            assertions: Vec::new(),
         }
     }
 
@@ -102,6 +194,16 @@ impl ComparisonResult {
         self.differences.push(difference);
     }
 
+    /// Get the assertions that failed
+    pub fn failed_assertions(&self) -> impl Iterator<Item = &AssertionResult> {
+        self.assertions.iter().filter(|a| !a.passed)
+    }
+
+    /// Check if any assertion failed
+    pub fn has_assertion_failures(&self) -> bool {
+        self.failed_assertions().next().is_some()
+    }
+
     /// Check if all responses have the same status code
     pub fn has_consistent_status(&self) -> bool {
         let statuses: Vec<u16> = self.status_codes.values().copied().collect();
@@ -150,6 +252,15 @@ impl ComparisonResult {
         self.get_ordered_environment_names(&resolver)
     }
 
+    /// Get the total request latency of an environment, if it was measured
+    pub fn latency_ms(&self, environment: &str) -> Option<u64> {
+        self.responses
+            .get(environment)?
+            .timing
+            .as_ref()
+            .map(|timing| timing.total_ms)
+    }
+
     /// Validate environment consistency for this result
     pub fn validate_environment_consistency(&self) -> crate::error::Result<()> {
         let resolver = self.create_environment_resolver();
@@ -167,6 +278,9 @@ pub struct Difference {
     pub header_diff: Option<Vec<crate::comparison::analyzer::HeaderDiff>>,
     /// Structured body diff data (avoids JSON serialization/deserialization)
     pub body_diff: Option<crate::comparison::analyzer::BodyDiff>,
+    /// Structural JSON changes keyed by JSON pointer (body differences between JSON documents)
+    #[serde(default)]
+    pub json_changes: Option<Vec<crate::comparison::json_diff::JsonChange>>,
 }
 
 impl Difference {
@@ -178,6 +292,7 @@ impl Difference {
             diff_output: None,
             header_diff: None,
             body_diff: None,
+            json_changes: None,
         }
     }
 
@@ -193,6 +308,7 @@ impl Difference {
             diff_output: Some(diff_output),
             header_diff: None,
             body_diff: None,
+            json_changes: None,
         }
     }
 
@@ -207,6 +323,7 @@ impl Difference {
             diff_output: None,
             header_diff: Some(header_diff),
             body_diff: None,
+            json_changes: None,
         }
     }
 
@@ -221,8 +338,18 @@ impl Difference {
             diff_output: None,
             header_diff: None,
             body_diff: Some(body_diff),
+            json_changes: None,
         }
     }
+
+    /// Attach structural JSON changes to this difference
+    pub fn with_json_changes(
+        mut self,
+        json_changes: Vec<crate::comparison::json_diff::JsonChange>,
+    ) -> Self {
+        self.json_changes = Some(json_changes);
+        self
+    }
 }
 
 /// Categories of differences that can be detected
@@ -231,6 +358,7 @@ pub enum DifferenceCategory {
     Status,
     Headers,
     Body,
+    Latency,
 }
 
 impl DifferenceCategory {
@@ -240,6 +368,7 @@ impl DifferenceCategory {
             DifferenceCategory::Status => "Status Code",
             DifferenceCategory::Headers => "Headers",
             DifferenceCategory::Body => "Response Body",
+            DifferenceCategory::Latency => "Latency",
         }
     }
 }
@@ -325,6 +454,53 @@ impl ErrorSummary {
     }
 }
 
+/// Request latency statistics of one environment across comparison results
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EnvironmentLatency {
+    pub environment: String,
+    /// Number of requests with timing data
+    pub requests: usize,
+    pub avg_ms: u64,
+    /// 95th percentile (nearest rank)
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+impl EnvironmentLatency {
+    /// Summarize request latency per environment, in environment order
+    ///
+    /// Environments without any timing data are omitted.
+    pub fn from_comparison_results(results: &[ComparisonResult]) -> Vec<Self> {
+        let mut samples: Vec<(String, Vec<u64>)> = Vec::new();
+        for result in results {
+            for env in result.get_environment_names_ordered() {
+                let Some(total_ms) = result.latency_ms(&env) else {
+                    continue;
+                };
+                match samples.iter_mut().find(|(name, _)| *name == env) {
+                    Some((_, totals)) => totals.push(total_ms),
+                    None => samples.push((env, vec![total_ms])),
+                }
+            }
+        }
+
+        samples
+            .into_iter()
+            .map(|(environment, mut totals)| {
+                totals.sort_unstable();
+                let p95_index = (totals.len() * 95).div_ceil(100) - 1;
+                Self {
+                    environment,
+                    requests: totals.len(),
+                    avg_ms: totals.iter().sum::<u64>() / totals.len() as u64,
+                    p95_ms: totals[p95_index],
+                    max_ms: totals[totals.len() - 1],
+                }
+            })
+            .collect()
+    }
+}
+
 /// Types of execution errors that can occur during test runs
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ExecutionErrorType {
@@ -534,6 +710,16 @@ pub enum ExtractionType {
     Header,
     /// Extract HTTP status code
     StatusCode,
+    /// Extract values using XPath expressions over XML or HTML bodies
+    XPath,
+    /// Extract cookie values from Set-Cookie headers
+    Cookie,
+    /// Capture the whole response body
+    Body,
+    /// Extract values using JMESPath queries
+    JmesPath,
+    /// Compute values from a template over previously extracted values
+    Computed,
 }
 
 impl ExtractionType {
@@ -544,6 +730,11 @@ impl ExtractionType {
             ExtractionType::Regex => "Regex",
             ExtractionType::Header => "Header",
             ExtractionType::StatusCode => "StatusCode",
+            ExtractionType::XPath => "XPath",
+            ExtractionType::Cookie => "Cookie",
+            ExtractionType::Body => "Body",
+            ExtractionType::JmesPath => "JmesPath",
+            ExtractionType::Computed => "Computed",
         }
     }
 }
@@ -697,6 +888,9 @@ pub struct ChainExecutionMetadata {
     pub batch_execution_times: Vec<u64>,
     /// Whether any routes had to wait for dependency completion
     pub had_dependency_waits: bool,
+    /// Cookie jar contents at the end of the run, when `cookie_jar` is enabled
+    #[serde(default)]
+    pub cookie_jars: Vec<crate::execution::cookies::CookieJarSnapshot>,
 }
 
 impl ChainExecutionMetadata {
@@ -710,6 +904,7 @@ impl ChainExecutionMetadata {
             extraction_errors: 0,
             batch_execution_times: Vec::with_capacity(total_batches),
             had_dependency_waits: false,
+            cookie_jars: Vec::new(),
         }
     }
     
@@ -797,6 +992,43 @@ mod tests {
         assert!(result.has_consistent_status());
     }
 
+    #[test]
+    fn test_environment_latency() {
+        let timed = |total_ms| {
+            HttpResponse::new(
+                200,
+                HashMap::new(),
+                "ok".to_string(),
+                "https://api.example.com".to_string(),
+                "curl command".to_string(),
+            )
+            .with_timing(ResponseTiming {
+                total_ms,
+                ..Default::default()
+            })
+        };
+        let results: Vec<ComparisonResult> = [(100, 500), (120, 700), (80, 300)]
+            .into_iter()
+            .map(|(dev, prod)| {
+                let mut result = ComparisonResult::new("route".to_string(), HashMap::new());
+                result.add_response("dev".to_string(), timed(dev));
+                result.add_response("prod".to_string(), timed(prod));
+                result
+            })
+            .collect();
+
+        let latency = EnvironmentLatency::from_comparison_results(&results);
+
+        assert_eq!(latency.len(), 2);
+        assert_eq!(latency[0].environment, "dev");
+        assert_eq!(latency[0].requests, 3);
+        assert_eq!(latency[0].avg_ms, 100);
+        assert_eq!(latency[0].p95_ms, 120);
+        assert_eq!(latency[1].environment, "prod");
+        assert_eq!(latency[1].avg_ms, 500);
+        assert_eq!(latency[1].max_ms, 700);
+    }
+
     #[test]
     fn test_error_summary() {
         let summary = ErrorSummary {
@@ -168,13 +168,17 @@ mod tests {
         environments.insert(
             "dev".to_string(),
             crate::config::Environment {
+                extends: None,
                 base_url: "https://api-dev.example.com".to_string(),
                 headers: None,
                 is_base: false,
+                rate_limit: None,
+                auth: None,
             },
         );
 
         HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: Some(crate::config::GlobalConfig {
                 timeout_seconds: None,
@@ -186,6 +190,15 @@ mod tests {
                     params.insert("api_version".to_string(), "v1".to_string());
                     params
                 }),
+                fail_on: None,
+                max_mixed_responses: None,
+                fail_on_errors: None,
+                fail_on_assertions: None,
+                ignore_fields: None,
+                latency_threshold: None,
+                retry: None,
+                secrets: None,
+                cookie_jar: None,
             }),
             routes: vec![],
         }
@@ -208,6 +221,16 @@ mod tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }
 
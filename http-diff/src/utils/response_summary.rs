@@ -46,6 +46,29 @@ pub fn count_lines_efficient(text: &str) -> usize {
     }
 }
 
+/// Format a duration in milliseconds, using `-` when it was not measured
+pub fn format_duration_ms(ms: Option<u64>) -> String {
+    match ms {
+        Some(ms) => format!("{}ms", ms),
+        None => "-".to_string(),
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 KB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
 /// Builder for large response comparison summaries
 pub struct LargeResponseSummaryBuilder {
     use_emojis: bool,
@@ -246,6 +269,15 @@ mod tests {
         assert_eq!(stats.text, text);
     }
 
+    #[test]
+    fn test_format_timing_values() {
+        assert_eq!(format_duration_ms(Some(120)), "120ms");
+        assert_eq!(format_duration_ms(None), "-");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+
     #[test]
     fn test_large_response_summary_builder() {
         let builder = LargeResponseSummaryBuilder::new();
@@ -116,9 +116,12 @@ mod chain_configuration {
     fn test_missing_dependency_route_detection() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let routes = vec![
@@ -134,10 +137,21 @@ mod chain_configuration {
                 extract: None,
                 depends_on: Some(vec!["nonexistent_route".to_string()]),
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -158,9 +172,12 @@ mod chain_configuration {
     fn test_circular_dependency_detection() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let routes = vec![
@@ -176,6 +193,16 @@ mod chain_configuration {
                 extract: None,
                 depends_on: Some(vec!["route_b".to_string()]),
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
             Route {
                 name: "route_b".to_string(),
@@ -189,6 +216,16 @@ mod chain_configuration {
                 extract: None,
                 depends_on: Some(vec!["route_c".to_string()]),
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
             Route {
                 name: "route_c".to_string(),
@@ -202,10 +239,21 @@ mod chain_configuration {
                 extract: None,
                 depends_on: Some(vec!["route_a".to_string()]),
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -236,6 +284,16 @@ mod chain_configuration {
                 extract: None,
                 depends_on: Some(vec!["self_dependent".to_string()]), // Self dependency
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
@@ -248,9 +306,12 @@ mod chain_configuration {
     fn test_extraction_rule_name_validation() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let invalid_names = vec![
@@ -282,10 +343,21 @@ mod chain_configuration {
                     }]),
                     depends_on: None,
                     wait_for_extraction: None,
+                    ignore_fields: None,
+                    compare: None,
+                    assertions: None,
+                    retry: None,
+                    body_file: None,
+                    json: None,
+                    form: None,
+                    multipart: None,
+                    group: None,
+                    base_dir: None,
                 },
             ];
 
             let config = HttpDiffConfig {
+                profiles: Default::default(),
                 environments: environments.clone(),
                 global: None,
                 routes,
@@ -311,9 +383,12 @@ mod chain_configuration {
     fn test_dependency_order_resolution() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let route_c = Route {
@@ -328,6 +403,16 @@ mod chain_configuration {
             extract: None,
             depends_on: Some(vec!["route_a".to_string(), "route_b".to_string()]),
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let route_a = Route {
@@ -342,6 +427,16 @@ mod chain_configuration {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let route_b = Route {
@@ -356,9 +451,20 @@ mod chain_configuration {
             extract: None,
             depends_on: Some(vec!["route_a".to_string()]),
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes: vec![route_c, route_a, route_b], // Intentionally out of dependency order
@@ -419,12 +525,16 @@ mod chain_configuration {
     fn test_empty_route_configuration() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes: vec![], // Empty routes
@@ -655,6 +765,16 @@ mod basic_chain_execution {
             extract: None,
             depends_on: Some(vec!["login".to_string()]),
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         // Verify the route has the expected interpolation placeholders
@@ -683,6 +803,16 @@ mod basic_chain_execution {
             extract: None,
             depends_on: Some(vec!["user_list".to_string(), "profile_list".to_string()]),
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         // Verify the route has the expected interpolation placeholders
@@ -729,9 +859,12 @@ mod complex_chain_tests {
     fn test_parallel_execution_within_batches() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let routes = vec![
@@ -760,6 +893,16 @@ mod complex_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["auth".to_string()]),
                 wait_for_extraction: Some(true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
             Route {
                 name: "profile".to_string(),
@@ -777,10 +920,21 @@ mod complex_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["auth".to_string()]),
                 wait_for_extraction: Some(true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -895,6 +1049,16 @@ mod complex_chain_tests {
             ]),
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         let mock_client = TestMockHttpClient::new();
@@ -1106,6 +1270,16 @@ mod chain_error_handling {
                 extract: None,
                 depends_on: Some(vec!["login".to_string()]),
                 wait_for_extraction: Some(true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
@@ -1268,9 +1442,12 @@ mod conditional_chain_tests {
     fn test_conditional_dependency_resolution() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let routes = vec![
@@ -1294,6 +1471,16 @@ mod conditional_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["login".to_string()]),
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
             
             // Another conditional route (different condition)
@@ -1313,10 +1500,21 @@ mod conditional_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["login".to_string()]),
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -1338,9 +1536,12 @@ mod conditional_chain_tests {
     fn test_mixed_conditional_unconditional_chain() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let routes = vec![
@@ -1377,6 +1578,16 @@ mod conditional_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["auth".to_string()]),
                 wait_for_extraction: Some(true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
             
             // Conditional route for admin users
@@ -1400,10 +1611,21 @@ mod conditional_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["auth".to_string()]),
                 wait_for_extraction: Some(true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -1474,6 +1696,16 @@ mod conditional_chain_tests {
                 extract: None,
                 depends_on: None,
                 wait_for_extraction: None,
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
@@ -1494,9 +1726,12 @@ mod conditional_chain_tests {
     fn test_conditional_chains_with_extraction_dependencies() {
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let routes = vec![
@@ -1533,10 +1768,21 @@ mod conditional_chain_tests {
                 extract: None,
                 depends_on: Some(vec!["login".to_string()]),
                 wait_for_extraction: Some(true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             },
         ];
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
@@ -1686,6 +1932,7 @@ mod chain_performance_tests {
                         ExtractorType::Regex => format!(r"value_{}=([^,\s]+)", i),
                         ExtractorType::Header => format!("X-Custom-Header-{}", i),
                         ExtractorType::StatusCode => "".to_string(),
+                        _ => unreachable!("only the types listed above are benchmarked"),
                     };
 
                     ValueExtractionRule {
@@ -1709,6 +1956,16 @@ mod chain_performance_tests {
                     extract: Some(extractions),
                     depends_on: None,
                     wait_for_extraction: None,
+                    ignore_fields: None,
+                    compare: None,
+                    assertions: None,
+                    retry: None,
+                    body_file: None,
+                    json: None,
+                    form: None,
+                    multipart: None,
+                    group: None,
+                    base_dir: None,
                 };
 
                 let response = create_mock_response(200, r#"{"data": [{"value": "test"}]}"#);
@@ -1766,18 +2023,32 @@ mod chain_performance_tests {
                 },
                 depends_on: depends_on.clone(),
                 wait_for_extraction: depends_on.as_ref().map(|_| true),
+                ignore_fields: None,
+                compare: None,
+                assertions: None,
+                retry: None,
+                body_file: None,
+                json: None,
+                form: None,
+                multipart: None,
+                group: None,
+                base_dir: None,
             }
         }).collect();
 
         // Test configuration validation
         let mut environments = HashMap::new();
         environments.insert("dev".to_string(), Environment {
+            extends: None,
             base_url: "https://dev.example.com".to_string(),
             headers: None,
             is_base: false,
+            rate_limit: None,
+            auth: None,
         });
 
         let config = HttpDiffConfig {
+            profiles: Default::default(),
             environments,
             global: None,
             routes,
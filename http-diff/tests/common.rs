@@ -21,6 +21,8 @@ pub fn create_response(status: u16, body: &str, url: Option<&str>) -> HttpRespon
         body: body.to_string(),
         url: url.unwrap_or("http://test.com").to_string(),
         curl_command: format!("curl {}", url.unwrap_or("http://test.com")),
+        timing: None,
+        attempts: 1,
     }
 }
 
@@ -59,6 +61,8 @@ pub fn create_comparison_result(
             Some(error_bodies)
         },
         base_environment: None,
+        ignored_fields: Vec::new(),
+        assertions: Vec::new(),
     }
 }
 
@@ -150,6 +154,16 @@ pub fn create_mock_route(name: &str, method: &str, path: &str) -> http_diff::con
         extract: None,
         depends_on: None,
         wait_for_extraction: None,
+        ignore_fields: None,
+        compare: None,
+        assertions: None,
+        retry: None,
+        body_file: None,
+        json: None,
+        form: None,
+        multipart: None,
+        group: None,
+        base_dir: None,
     }
 }
 
@@ -178,6 +192,8 @@ pub fn create_mock_response(status: u16, body: &str) -> http_diff::types::HttpRe
         body: body.to_string(),
         url: "https://example.com/test".to_string(),
         curl_command: "curl 'https://example.com/test'".to_string(),
+        timing: None,
+        attempts: 1,
     }
 }
 
@@ -275,6 +291,14 @@ impl TestMockHttpClient {
                     ExtractorType::StatusCode => {
                         Some(response.status.to_string())
                     },
+                    ExtractorType::Body => Some(response.body.clone()),
+                    ExtractorType::XPath
+                    | ExtractorType::Cookie
+                    | ExtractorType::JmesPath
+                    | ExtractorType::Computed => {
+                        // Not simulated
+                        extraction_rule.default_value.clone()
+                    },
                 };
 
                 if let Some(value) = extracted_value {
@@ -418,19 +442,28 @@ impl ConditionEvaluator for TestMockConditionEvaluator {
 pub fn create_test_environments() -> HashMap<String, Environment> {
     let mut environments = HashMap::new();
     environments.insert("dev".to_string(), Environment {
+        extends: None,
         base_url: "https://dev.example.com".to_string(),
         headers: None,
         is_base: false,
+        rate_limit: None,
+        auth: None,
     });
     environments.insert("staging".to_string(), Environment {
+        extends: None,
         base_url: "https://staging.example.com".to_string(),
         headers: None,
         is_base: false,
+        rate_limit: None,
+        auth: None,
     });
     environments.insert("prod".to_string(), Environment {
+        extends: None,
         base_url: "https://prod.example.com".to_string(),
         headers: None,
         is_base: true,
+        rate_limit: None,
+        auth: None,
     });
     environments
 }
@@ -455,6 +488,16 @@ pub fn create_route_with_extraction(
         extract: if extractions.is_empty() { None } else { Some(extractions) },
         depends_on: depends_on.clone(),
         wait_for_extraction: depends_on.as_ref().map(|_| true),
+        ignore_fields: None,
+        compare: None,
+        assertions: None,
+        retry: None,
+        body_file: None,
+        json: None,
+        form: None,
+        multipart: None,
+        group: None,
+        base_dir: None,
     }
 }
 
@@ -477,6 +520,16 @@ pub fn create_route_with_conditions(
         extract: None,
         depends_on: None,
         wait_for_extraction: None,
+        ignore_fields: None,
+        compare: None,
+        assertions: None,
+        retry: None,
+        body_file: None,
+        json: None,
+        form: None,
+        multipart: None,
+        group: None,
+        base_dir: None,
     }
 }
 
@@ -560,6 +613,7 @@ pub fn create_basic_chain_config() -> HttpDiffConfig {
     let environments = create_test_environments();
     
     HttpDiffConfig {
+        profiles: Default::default(),
         environments,
         global: None,
         routes: vec![
@@ -622,6 +676,7 @@ pub fn create_conditional_chain_config() -> HttpDiffConfig {
     ];
 
     HttpDiffConfig {
+        profiles: Default::default(),
         environments,
         global: None,
         routes,
@@ -724,10 +779,21 @@ pub fn create_complex_chain_config() -> HttpDiffConfig {
             extract: None,
             depends_on: Some(vec!["projects".to_string(), "teams".to_string()]),
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         },
     ];
 
     HttpDiffConfig {
+        profiles: Default::default(),
         environments,
         global: None,
         routes,
@@ -885,6 +951,16 @@ pub fn create_wide_dependency_routes(root_count: usize, dependent_count: usize)
             extract: None,
             depends_on: Some(vec![format!("root_{}", root_index)]),
             wait_for_extraction: Some(true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         });
     }
 
@@ -1037,6 +1113,7 @@ impl TestScenarioBuilder {
                 create_route("api/users", "GET", "/api/users"),
             ];
             HttpDiffConfig {
+                profiles: Default::default(),
                 environments,
                 global: None,
                 routes,
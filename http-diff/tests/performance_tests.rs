@@ -395,19 +395,28 @@ required = false
 fn create_complex_config_for_validation(route_count: usize) -> HttpDiffConfig {
     let mut environments = HashMap::new();
     environments.insert("dev".to_string(), Environment {
+        extends: None,
         base_url: "https://dev.example.com".to_string(),
         headers: None,
         is_base: false,
+        rate_limit: None,
+        auth: None,
     });
     environments.insert("staging".to_string(), Environment {
+        extends: None,
         base_url: "https://staging.example.com".to_string(),
         headers: None,
         is_base: false,
+        rate_limit: None,
+        auth: None,
     });
     environments.insert("prod".to_string(), Environment {
+        extends: None,
         base_url: "https://prod.example.com".to_string(),
         headers: None,
         is_base: true,
+        rate_limit: None,
+        auth: None,
     });
 
     let routes = (0..route_count).map(|i| {
@@ -480,10 +489,21 @@ fn create_complex_config_for_validation(route_count: usize) -> HttpDiffConfig {
             extract: Some(extractions),
             depends_on: depends_on.clone(),
             wait_for_extraction: depends_on.as_ref().map(|_| true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }).collect();
 
     HttpDiffConfig {
+        profiles: Default::default(),
         environments,
         global: None,
         routes,
@@ -495,6 +515,7 @@ fn create_memory_test_config(route_count: usize, env_count: usize, extractions_p
     let mut environments = HashMap::new();
     for i in 0..env_count {
         environments.insert(format!("env_{}", i), Environment {
+            extends: None,
             base_url: format!("https://env{}.example.com", i),
             headers: Some({
                 let mut headers = HashMap::new();
@@ -503,6 +524,8 @@ fn create_memory_test_config(route_count: usize, env_count: usize, extractions_p
                 headers
             }),
             is_base: i == 0,
+            rate_limit: None,
+            auth: None,
         });
     }
 
@@ -543,10 +566,21 @@ fn create_memory_test_config(route_count: usize, env_count: usize, extractions_p
             extract: Some(extractions),
             depends_on: depends_on.clone(),
             wait_for_extraction: depends_on.as_ref().map(|_| true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }).collect();
 
     HttpDiffConfig {
+        profiles: Default::default(),
         environments,
         global: None,
         routes,
@@ -667,6 +701,8 @@ fn create_large_json_response(size_kb: usize) -> HttpResponse {
         body: serde_json::to_string(&response_body).unwrap(),
         url: "https://example.com/performance/large".to_string(),
         curl_command: "curl 'https://example.com/performance/large'".to_string(),
+        timing: None,
+        attempts: 1,
     }
 }
 
@@ -675,6 +711,7 @@ fn create_stress_test_config(route_count: usize, env_count: usize, extractions_p
     let mut environments = HashMap::new();
     for i in 0..env_count {
         environments.insert(format!("stress_env_{}", i), Environment {
+            extends: None,
             base_url: format!("https://stress-env-{}.example.com", i),
             headers: Some({
                 let mut headers = HashMap::new();
@@ -684,6 +721,8 @@ fn create_stress_test_config(route_count: usize, env_count: usize, extractions_p
                 headers
             }),
             is_base: i == 0,
+            rate_limit: None,
+            auth: None,
         });
     }
 
@@ -771,10 +810,21 @@ fn create_stress_test_config(route_count: usize, env_count: usize, extractions_p
             extract: Some(extractions),
             depends_on: depends_on.clone(),
             wait_for_extraction: depends_on.as_ref().map(|_| true),
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         }
     }).collect();
 
     HttpDiffConfig {
+        profiles: Default::default(),
         environments,
         global: None,
         routes,
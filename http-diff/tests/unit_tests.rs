@@ -155,6 +155,16 @@ mod configuration_tests {
             extract: None,
             depends_on: None,
             wait_for_extraction: None,
+            ignore_fields: None,
+            compare: None,
+            assertions: None,
+            retry: None,
+            body_file: None,
+            json: None,
+            form: None,
+            multipart: None,
+            group: None,
+            base_dir: None,
         };
 
         assert!(route.headers.is_some());
@@ -347,6 +357,8 @@ mod core_types_tests {
             body: "test body".to_string(),
             url: "https://example.com".to_string(),
             curl_command: "curl 'https://example.com'".to_string(),
+            timing: None,
+            attempts: 1,
         };
 
         assert_eq!(response.status, 200);